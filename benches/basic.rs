@@ -1,37 +1,136 @@
-#![feature(test)]
-
-#[cfg(test)]
-extern crate test;
+#[macro_use]
+extern crate criterion;
 
 extern crate unicode_segmentation;
 
 extern crate porter_stemmer;
 
-use std::fs::File;
-use std::io::Read;
+use std::fs;
 
-use test::Bencher;
+use criterion::{black_box, BatchSize, Criterion, Throughput};
 
 use unicode_segmentation::UnicodeSegmentation;
 
-use porter_stemmer::stem_tokenized;
+use porter_stemmer::{stem, stem_buffer, Porter};
+use porter_stemmer::batch::WordBatch;
+use porter_stemmer::cache::CachedStemmer;
+use porter_stemmer::intern::InterningStemmer;
+
+/// One of the Porter stemmer's phases, applied to a word of graphemes.
+type Phase<'a> = fn(&Porter, Vec<&'a str>) -> Vec<&'a str>;
+
+fn corpus() -> Vec<String> {
+    fs::read_to_string("input.txt").unwrap().lines().map(String::from).collect()
+}
+
+/// The corpus with its `e`s accented, so every word takes the grapheme path.
+fn accented_corpus() -> Vec<String> {
+    corpus().iter().map(|word| word.replace('e', "é")).collect()
+}
+
+fn bench_phases(c: &mut Criterion) {
+    let porter = Porter::new();
+    let phases: &[(&str, Phase)] = &[
+        ("1a", Porter::phase_one_a),
+        ("1b", Porter::phase_one_b),
+        ("1c", Porter::phase_one_c),
+        ("2", Porter::phase_two),
+        ("3", Porter::phase_three),
+        ("4", Porter::phase_four),
+        ("5a", Porter::phase_5a),
+        ("5b", Porter::phase_5b),
+    ];
 
-#[bench]
-fn bench_stem(b: &mut Bencher) {
-    let mut input     = File::open("input.txt").unwrap();
-    let mut expected  = File::open("expected.txt").unwrap();
+    // Each phase is measured on the words as the phases before it leave them.
+    let corpus = corpus();
+    let mut words = corpus.iter()
+        .map(|word| word.graphemes(true).collect::<Vec<&str>>())
+        .collect::<Vec<_>>();
 
-    let mut input_s = String::new();
-    input.read_to_string(&mut input_s).unwrap();
+    let mut group = c.benchmark_group("phases");
+    group.throughput(Throughput::Elements(words.len() as u64));
 
-    let mut expected_s = String::new();
-    expected.read_to_string(&mut expected_s).unwrap();
+    for &(name, phase) in phases {
+        group.bench_function(name, |b| {
+            b.iter_batched(|| words.clone(), |words| {
+                for word in words {
+                    black_box(phase(&porter, word));
+                }
+            }, BatchSize::LargeInput)
+        });
 
-    let input = input_s.graphemes(true).collect::<Vec<&str>>();
-    let expected = input_s.graphemes(true).collect::<Vec<&str>>();
+        words = words.into_iter().map(|word| phase(&porter, word)).collect();
+    }
 
-    b.iter(|| {
-        let input = input.clone();
-        assert_eq!(expected, stem_tokenized(input));
+    group.finish();
+}
+
+fn bench_stem(c: &mut Criterion) {
+    let ascii = corpus();
+    let accented = accented_corpus();
+    let long_word = "antidisestablishmentarianism".repeat(8);
+
+    let mut group = c.benchmark_group("stem");
+    group.throughput(Throughput::Elements(ascii.len() as u64));
+
+    group.bench_function("ascii", |b| {
+        b.iter(|| {
+            for word in &ascii {
+                black_box(stem(word));
+            }
+        })
+    });
+    group.bench_function("unicode", |b| {
+        b.iter(|| {
+            for word in &accented {
+                black_box(stem(word));
+            }
+        })
     });
+
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("long word", |b| b.iter(|| stem(black_box(&long_word))));
+
+    group.finish();
+}
+
+fn bench_batch(c: &mut Criterion) {
+    let corpus = corpus();
+
+    let mut group = c.benchmark_group("batch");
+    group.throughput(Throughput::Bytes(corpus.iter().map(|word| word.len() as u64 + 1).sum()));
+
+    group.bench_function("stem", |b| {
+        b.iter(|| corpus.iter().map(|word| stem(word)).collect::<Vec<String>>())
+    });
+    group.bench_function("buffer", |b| {
+        let text = corpus.join("\n");
+        b.iter(|| {
+            let mut stems = String::new();
+            stem_buffer(&text, &mut stems);
+            stems
+        })
+    });
+    group.bench_function("packed", |b| {
+        let porter = Porter::new();
+        b.iter_batched(|| corpus.iter().map(String::as_str).collect::<WordBatch>(), |mut batch| {
+            porter.stem_batch(&mut batch);
+            batch
+        }, BatchSize::LargeInput)
+    });
+    group.bench_function("cached", |b| {
+        b.iter_batched(|| CachedStemmer::new(Porter::new()), |stemmer| {
+            corpus.iter().map(|word| stemmer.stem(word)).collect::<Vec<String>>()
+        }, BatchSize::LargeInput)
+    });
+    group.bench_function("interned", |b| {
+        b.iter_batched(|| InterningStemmer::new(Porter::new()), |stemmer| {
+            corpus.iter().map(|word| stemmer.stem(word)).collect::<Vec<_>>()
+        }, BatchSize::LargeInput)
+    });
+
+    group.finish();
 }
+
+criterion_group!(benches, bench_phases, bench_stem, bench_batch);
+criterion_main!(benches);
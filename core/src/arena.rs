@@ -0,0 +1,61 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Stemming into a `bumpalo` arena.
+
+use bumpalo::Bump;
+use bumpalo::collections::String;
+
+use porter::Porter;
+
+impl Porter {
+    /// Stem each of `words`, allocating the stems in `arena`.
+    ///
+    /// All of a document's stems can then be freed together by resetting or
+    /// dropping the arena, rather than one at a time. Requires the `bumpalo`
+    /// feature.
+    ///
+    /// ```
+    /// extern crate bumpalo;
+    /// extern crate porter_stemmer;
+    ///
+    /// use bumpalo::Bump;
+    /// use porter_stemmer::Porter;
+    ///
+    /// fn main() {
+    ///     let arena = Bump::new();
+    ///     let stems = Porter::new().stem_batch_in(&["hopeful", "relational"], &arena);
+    ///     assert_eq!(vec!["hope", "relat"], stems);
+    /// }
+    /// ```
+    pub fn stem_batch_in<'a>(&self, words: &[&str], arena: &'a Bump) -> Vec<&'a str> {
+        words.iter()
+            .map(|word| {
+                let mut stem = String::with_capacity_in(word.len(), arena);
+                self.stem_into(word, &mut stem).expect("writing to a bumpalo String never fails");
+                stem.into_bump_str()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use porter::PorterMode;
+
+    #[test]
+    fn test_stem_batch_in() {
+        let arena = Bump::new();
+        let words = ["hopefulness", "Dying", "naïvely", "conflated", ""];
+
+        for &mode in [PorterMode::Classic, PorterMode::Revised, PorterMode::Nltk, PorterMode::Lucene].iter() {
+            let porter = Porter::new().mode(mode);
+            let expected = words.iter().map(|word| porter.stem(word)).collect::<Vec<_>>();
+
+            assert_eq!(expected, porter.stem_batch_in(&words, &arena));
+        }
+    }
+}
@@ -0,0 +1,154 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Stemming many words packed into one buffer.
+//!
+//! A `WordBatch` keeps its words end to end in a single buffer, with their
+//! starts and lengths in arrays alongside, rather than each in a `String`
+//! of its own. `Porter::stem_batch` then runs each step of the algorithm
+//! over every word before moving on to the next step, so the code and
+//! tables of one step stay in cache while the words stream past.
+//!
+//! ```
+//! # extern crate porter_stemmer;
+//! use porter_stemmer::Porter;
+//! use porter_stemmer::batch::WordBatch;
+//!
+//! let mut batch = "caresses ponies hopefulness".split(' ').collect::<WordBatch>();
+//! Porter::new().stem_batch(&mut batch);
+//!
+//! assert_eq!(vec!["caress", "poni", "hope"], batch.iter().collect::<Vec<_>>());
+//! ```
+
+use std::iter::FromIterator;
+use std::str;
+
+use porter::Porter;
+
+/// Words packed into one buffer.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WordBatch {
+    letters: Vec<u8>,
+    starts: Vec<usize>,
+    lengths: Vec<usize>,
+}
+
+impl WordBatch {
+    /// An empty batch.
+    pub fn new() -> WordBatch {
+        WordBatch::default()
+    }
+
+    /// An empty batch with room for `words` words of `bytes` bytes in all.
+    pub fn with_capacity(words: usize, bytes: usize) -> WordBatch {
+        WordBatch {
+            letters: Vec::with_capacity(bytes),
+            starts: Vec::with_capacity(words),
+            lengths: Vec::with_capacity(words),
+        }
+    }
+
+    /// Add a word to the end of the batch.
+    pub fn push(&mut self, word: &str) {
+        self.starts.push(self.letters.len());
+        self.lengths.push(word.len());
+        self.letters.extend_from_slice(word.as_bytes());
+    }
+
+    /// The word at `index`, if there is one.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        let start = *self.starts.get(index)?;
+        let bytes = &self.letters[start..start + self.lengths[index]];
+
+        Some(str::from_utf8(bytes).expect("words and their stems are UTF-8"))
+    }
+
+    /// The words in the batch, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        (0..self.len()).map(move |index| self.get(index).expect("every index is in bounds"))
+    }
+
+    /// The number of words in the batch.
+    pub fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Whether the batch has no words.
+    pub fn is_empty(&self) -> bool {
+        self.starts.is_empty()
+    }
+
+    /// Remove every word, keeping the memory for reuse.
+    pub fn clear(&mut self) {
+        self.letters.clear();
+        self.starts.clear();
+        self.lengths.clear();
+    }
+}
+
+impl<'a> Extend<&'a str> for WordBatch {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, words: I) {
+        for word in words {
+            self.push(word);
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a str> for WordBatch {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(words: I) -> WordBatch {
+        let mut batch = WordBatch::new();
+        batch.extend(words);
+        batch
+    }
+}
+
+impl Porter {
+    /// Replace each word in `batch` with its stemmed form, running each step
+    /// over the whole batch in turn.
+    ///
+    /// The stems are the same as `stem` would give, and are written over the
+    /// words they come from wherever they can be.
+    pub fn stem_batch(&self, batch: &mut WordBatch) {
+        self.stem_packed(&mut batch.letters, &mut batch.starts, &mut batch.lengths);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use porter::PorterMode;
+
+    #[test]
+    fn test_stem_batch() {
+        let input = include_str!("../../input.txt");
+        let words = input.lines().chain(vec!["Dying", "naïvely", "a\r\nb", "İnnings", ""]).collect::<Vec<_>>();
+
+        for &mode in [PorterMode::Classic, PorterMode::Revised, PorterMode::Nltk, PorterMode::Lucene].iter() {
+            let porter = Porter::new().mode(mode);
+            let mut batch = words.iter().cloned().collect::<WordBatch>();
+            porter.stem_batch(&mut batch);
+
+            assert_eq!(words.len(), batch.len());
+            for (word, stem) in words.iter().zip(batch.iter()) {
+                assert_eq!(porter.stem(word), stem, "{:?} on {}", mode, word);
+            }
+        }
+    }
+
+    #[test]
+    fn test_word_batch() {
+        let mut batch = WordBatch::with_capacity(2, 8);
+        assert!(batch.is_empty());
+
+        batch.push("tea");
+        batch.extend(vec!["", "café"]);
+        assert_eq!(3, batch.len());
+        assert_eq!(Some("café"), batch.get(2));
+        assert_eq!(None, batch.get(3));
+
+        batch.clear();
+        assert_eq!(None, batch.get(0));
+    }
+}
@@ -0,0 +1,19 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Porter's algorithm and the predicates it is defined in terms of, shared
+//! by `porter-stemmer`, which re-exports them, and `porter-stemmer-macros`,
+//! which `porter-stemmer` depends on in turn.
+
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
+extern crate unicode_segmentation;
+#[cfg(feature = "bumpalo")]
+extern crate bumpalo;
+
+#[cfg(feature = "bumpalo")]
+mod arena;
+pub mod batch;
+pub mod porter;
+pub mod primitives;
@@ -0,0 +1,1206 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Porter's original algorithm, phase by phase.
+
+use std::cell::Cell;
+use std::fmt;
+use std::str;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use primitives::{ends_with, Letter, Vowels};
+
+/// The number of letters a word can have to be stemmed without allocating.
+/// Hardly any words are longer.
+const STACK_LETTERS: usize = 32;
+
+/// A suffix and what it is replaced with.
+type Rule = (&'static str, &'static str);
+
+/// Whether `word` can be stemmed byte by byte, comparing suffixes as plain
+/// byte strings.
+///
+/// Every ASCII character is a grapheme cluster of its own, apart from
+/// `\r\n`, so most words can be.
+pub fn bytewise(word: &str) -> bool {
+    word.is_ascii() && !word.contains("\r\n")
+}
+
+/// The second to last letter of `word`, which the suffixes of steps 2 and 4
+/// are grouped by.
+fn penultimate<L: Letter>(word: &[L]) -> Option<u8> {
+    word.len().checked_sub(2).and_then(|index| word[index].to_ascii())
+}
+
+/// The longest word whose vowels `Word` keeps track of.
+const MASKED_LETTERS: usize = 64;
+
+/// A word being stemmed in place, as the first `length` of `letters`.
+///
+/// Every step only ever shortens a word, apart from step 1b adding an `e`
+/// after removing `ed` or `ing`, so a word never outgrows the letters it
+/// started with.
+///
+/// The steps measure many prefixes of the same word, so each letter is
+/// only classified as a Porter vowel or not once, the first time a measure
+/// needs it, into a mask with bit `i` set for a vowel at `i`. Words too long
+/// for the mask are measured from scratch instead.
+struct Word<'a, L: 'a> {
+    letters: &'a mut [L],
+    length: usize,
+    vowels: &'a Vowels,
+    mask: Cell<u64>,
+    /// The number of letters, from the start, classified in `mask`.
+    classified: Cell<usize>,
+}
+
+impl<'a, L: Letter> Word<'a, L> {
+    fn new(letters: &'a mut [L], length: usize, vowels: &'a Vowels) -> Word<'a, L> {
+        Word { letters, length, vowels, mask: Cell::new(0), classified: Cell::new(0) }
+    }
+
+    /// The mask of the vowels among the first `length` letters, if the word
+    /// is short enough to have one.
+    fn vowel_mask(&self, length: usize) -> Option<u64> {
+        if self.letters.len() > MASKED_LETTERS {
+            return None;
+        }
+
+        let classified = self.classified.get();
+        if length > classified {
+            let letters = &self.letters[..length];
+            let mask = if classified == 0 {
+                self.vowels.porter_vowel_mask(letters)
+            } else {
+                (classified..length)
+                    .filter(|&index| self.vowels.porter_vowel(letters, index))
+                    .fold(self.mask.get(), |mask, index| mask | 1 << index)
+            };
+
+            self.mask.set(mask);
+            self.classified.set(length);
+        }
+
+        Some(self.mask.get() & Word::<L>::first(length))
+    }
+
+    fn letters(&self) -> &[L] {
+        &self.letters[..self.length]
+    }
+
+    /// The word without its last `count` letters.
+    fn stem(&self, count: usize) -> &[L] {
+        &self.letters[..self.length - count]
+    }
+
+    fn ends_with(&self, suffix: &str) -> bool {
+        ends_with(self.letters(), suffix)
+    }
+
+    /// The measure of the word without its last `count` letters.
+    fn measure(&self, count: usize) -> usize {
+        match self.vowel_mask(self.length - count) {
+            // Count the places a vowel is followed by a consonant.
+            Some(mask) => ((mask << 1) & !mask & Word::<L>::first(self.length - count)).count_ones() as usize,
+            None => self.vowels.measure(self.stem(count)),
+        }
+    }
+
+    /// Whether the word without its last `count` letters has a vowel.
+    fn contains_vowel(&self, count: usize) -> bool {
+        match self.vowel_mask(self.length - count) {
+            Some(mask) => mask != 0,
+            None => self.vowels.contains_porter_vowel(self.stem(count)),
+        }
+    }
+
+    /// The mask of the first `length` letters.
+    fn first(length: usize) -> u64 {
+        u64::MAX.checked_shr((MASKED_LETTERS - length) as u32).unwrap_or(0)
+    }
+
+    /// Remove the last `count` letters.
+    fn remove(&mut self, count: usize) {
+        self.length -= count;
+    }
+
+    fn push(&mut self, letter: u8) {
+        self.letters[self.length] = L::from_ascii(letter);
+
+        // Whether a letter is a vowel only depends on it and the letter
+        // before it, so only the new letter needs classifying again.
+        if self.classified.get() > self.length {
+            self.classified.set(self.length);
+            self.mask.set(self.mask.get() & Word::<L>::first(self.length));
+        }
+
+        self.length += 1;
+    }
+
+    /// Replace the last `count` letters with the ASCII `replacement`.
+    fn replace(&mut self, count: usize, replacement: &str) {
+        self.remove(count);
+        for letter in replacement.bytes() {
+            self.push(letter);
+        }
+    }
+}
+
+/// Porter's algorithm, along with the parts of it that can be configured.
+///
+/// `Porter::new()` is the algorithm as it is used by `stem`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate porter_stemmer;
+/// use porter_stemmer::Porter;
+/// use porter_stemmer::primitives::Vowels;
+///
+/// let porter = Porter::new().vowels(Vowels::new().with_vowel("ï"));
+/// assert_eq!("naïv", porter.stem("naïvely"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Porter {
+    vowels: Vowels,
+    mode: PorterMode,
+    final_y: Option<FinalY>,
+}
+
+/// Which version of Porter's algorithm to run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PorterMode {
+    /// The algorithm as this crate has always run it, following the 1980
+    /// paper. Kept as the default so that existing stems don't change.
+    #[default]
+    Classic,
+    /// The algorithm as Porter's own C implementation runs it, which is what
+    /// produced the reference vocabulary on his site. It departs from the
+    /// paper in replacing `bli` with `ble` rather than only `abli` with
+    /// `able`, and in adding `logi` to `log`. Only the longest suffix a
+    /// step finds is considered, so `agreement` keeps its `ment` rather
+    /// than losing `ent`, and a final `y` becomes `i` only when the rest of
+    /// the word has a vowel, so `cry` stays `cry`.
+    Revised,
+    /// The algorithm as NLTK's `PorterStemmer` runs it in its default
+    /// `NLTK_EXTENSIONS` mode, for stems identical to a Python pipeline's.
+    /// This is the revised algorithm with NLTK's own changes: `stem`
+    /// lowercases words and looks a few irregular forms up first (`dying`
+    /// to `die`), `ies` and `ied` become `ie` in four letter words and `i`
+    /// otherwise, a final `y` becomes `i` after any consonant, so `cry`
+    /// becomes `cri`, `alli` and `fulli` are reduced, and two letter stems
+    /// like `ow` count as ending consonant-vowel-consonant.
+    Nltk,
+    /// The algorithm as Lucene's `PorterStemFilter` runs it, so that stems
+    /// can be looked up in an existing Lucene or Elasticsearch index.
+    /// Lucene's stemmer is a port of Porter's own, and gives the same stems
+    /// as `Revised`, with two differences in how `stem` treats its input:
+    ///
+    /// - Words are split into `char`s rather than grapheme clusters, so a
+    ///   combining accent is a letter of its own, and a consonant. Lucene
+    ///   splits into UTF-16 code units, which differs from this only for
+    ///   words with letters outside the Basic Multilingual Plane.
+    /// - Like Lucene, nothing is lowercased. Lucene analyzers lowercase
+    ///   before stemming, and so should callers.
+    Lucene,
+}
+
+/// When step 1c turns a final `y` into `i`.
+///
+/// Each `PorterMode` has its own rule, which `Porter::final_y` overrides.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FinalY {
+    /// When the word has a vowel, counting the `y` itself, so `sky` becomes
+    /// `ski`. The rule of `Classic` mode.
+    AnyVowel,
+    /// When the word has a vowel before the `y`, so `sky` stays `sky` and
+    /// `happy` becomes `happi`. The rule of Porter's reference
+    /// implementation, and so of `Revised` and `Lucene` modes.
+    VowelInStem,
+    /// When the `y` follows a consonant that is not the first letter, so
+    /// `sky` and `cry` become `ski` and `cri` while `by` and `say` are left
+    /// alone. The rule of Porter2, and of `Nltk` mode.
+    AfterConsonant,
+}
+
+/// The irregular forms NLTK looks up before stemming, and their stems.
+const NLTK_IRREGULAR_FORMS: &[(&str, &str)] = &[
+    ("sky", "sky"), ("skies", "sky"), ("dying", "die"), ("lying", "lie"), ("tying", "tie"),
+    ("news", "news"), ("innings", "inning"), ("inning", "inning"), ("outings", "outing"),
+    ("outing", "outing"), ("cannings", "canning"), ("canning", "canning"), ("howe", "howe"),
+    ("proceed", "proceed"), ("exceed", "exceed"), ("succeed", "succeed"),
+];
+
+impl Porter {
+    /// Porter's algorithm as originally published.
+    pub fn new() -> Porter {
+        Porter::default()
+    }
+
+    /// The algorithm exactly as Porter's reference implementation runs it:
+    /// `Revised` mode with only the standard vowels. It reproduces the
+    /// published vocabulary and its stems, `voc.txt` and `output.txt`, word
+    /// for word, so `sky` stays `sky` while `say` becomes `sai`.
+    ///
+    /// ```
+    /// # extern crate porter_stemmer;
+    /// use porter_stemmer::Porter;
+    ///
+    /// let porter = Porter::strict();
+    /// assert_eq!("sky", porter.stem("sky"));
+    /// assert_eq!("sai", porter.stem("say"));
+    /// assert_eq!("agreement", porter.stem("agreement"));
+    /// ```
+    pub fn strict() -> Porter {
+        Porter::new().mode(PorterMode::Revised)
+    }
+
+    /// Use `vowels` to decide which graphemes are vowels.
+    pub fn vowels(mut self, vowels: Vowels) -> Porter {
+        self.vowels = vowels;
+        self
+    }
+
+    /// Run the given version of the algorithm.
+    pub fn mode(mut self, mode: PorterMode) -> Porter {
+        self.mode = mode;
+        self
+    }
+
+    /// Use `final_y` in step 1c, in place of the mode's own rule.
+    ///
+    /// ```
+    /// # extern crate porter_stemmer;
+    /// use porter_stemmer::{FinalY, Porter};
+    ///
+    /// assert_eq!("ski", Porter::new().stem("sky"));
+    /// assert_eq!("sky", Porter::new().final_y(FinalY::VowelInStem).stem("sky"));
+    /// ```
+    pub fn final_y(mut self, final_y: FinalY) -> Porter {
+        self.final_y = Some(final_y);
+        self
+    }
+
+    /// Given a word, return its stemmed form.
+    pub fn stem(&self, word: &str) -> String {
+        let mut stem = String::with_capacity(word.len());
+        self.stem_into(word, &mut stem).expect("writing to a String never fails");
+        stem
+    }
+
+    /// Stem every word of a whitespace separated buffer of text, appending
+    /// the stems to `output` with the whitespace between them kept as it is.
+    ///
+    /// Stems are written straight into `output`, so no `String` is allocated
+    /// per word.
+    ///
+    /// ```
+    /// # extern crate porter_stemmer;
+    /// use porter_stemmer::Porter;
+    ///
+    /// let mut stems = String::new();
+    /// Porter::new().stem_buffer("caresses ponies\nhopefulness", &mut stems);
+    /// assert_eq!("caress poni\nhope", stems);
+    /// ```
+    pub fn stem_buffer(&self, text: &str, output: &mut String) {
+        output.reserve(text.len());
+
+        let mut rest = text;
+        while !rest.is_empty() {
+            let word = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let space = rest[word..].find(|c: char| !c.is_whitespace()).map_or(rest.len(), |length| word + length);
+
+            self.stem_into(&rest[..word], output).expect("writing to a String never fails");
+            output.push_str(&rest[word..space]);
+            rest = &rest[space..];
+        }
+    }
+
+    /// Stem a word, and write its stemmed form to `output`.
+    pub(crate) fn stem_into<W: fmt::Write>(&self, word: &str, output: &mut W) -> fmt::Result {
+        let lowercase;
+        let word = if self.mode == PorterMode::Nltk {
+            // NLTK looks irregular forms up before lowercasing, so `Dying` is
+            // stemmed by the rules.
+            if let Some(&(_, stem)) = NLTK_IRREGULAR_FORMS.iter().find(|&&(form, _)| form == word) {
+                return output.write_str(stem);
+            }
+
+            lowercase = word.to_lowercase();
+            &lowercase
+        } else {
+            word
+        };
+
+        if bytewise(word) {
+            return self.with_stem(word.bytes(), |stem| {
+                output.write_str(str::from_utf8(stem).expect("stemming ASCII input only ever produces ASCII"))
+            });
+        }
+
+        match self.mode {
+            PorterMode::Lucene => self.with_stem(word.chars(), |stem| stem.iter().try_for_each(|&c| output.write_char(c))),
+            _ => self.with_stem(word.graphemes(true), |stem| stem.iter().try_for_each(|grapheme| output.write_str(grapheme))),
+        }
+    }
+
+    /// Stem the word made up of `letters`, and pass the stem to `output`.
+    ///
+    /// Words of up to `STACK_LETTERS` letters are stemmed in a buffer on the
+    /// stack, so nothing is allocated other than by `output`.
+    pub fn with_stem<L, I, T, F>(&self, letters: I, output: F) -> T
+        where L: Letter, I: Iterator<Item = L>, F: FnOnce(&[L]) -> T
+    {
+        let mut buffer = [L::from_ascii(b'a'); STACK_LETTERS];
+        let mut letters = letters;
+        let mut length = 0;
+
+        while let Some(letter) = letters.next() {
+            if length == STACK_LETTERS {
+                let mut word = buffer.to_vec();
+                word.push(letter);
+                word.extend(letters);
+
+                let length = self.stem_in_place(&mut word);
+                return output(&word[..length]);
+            }
+
+            buffer[length] = letter;
+            length += 1;
+        }
+
+        let length = self.stem_in_place(&mut buffer[..length]);
+        output(&buffer[..length])
+    }
+
+    /// Take a word as a Vector of grapheme clusters, and return the stemmed equivalent using Porter's
+    /// stemming algorithm.
+    ///
+    /// This runs each of the phases in turn: `phase_one_a`, `phase_one_b`,
+    /// `phase_one_c`, `phase_two`, `phase_three`, `phase_four`, `phase_5a` and
+    /// `phase_5b`.
+    pub fn stem_tokenized<L: Letter>(&self, word: Vec<L>) -> Vec<L> {
+        if self.mode == PorterMode::Nltk {
+            let irregular = NLTK_IRREGULAR_FORMS.iter()
+                .find(|&&(form, _)| word.len() == form.len() && ends_with(&word, form));
+
+            if let Some(&(_, stem)) = irregular {
+                return stem.bytes().map(L::from_ascii).collect();
+            }
+        }
+
+        let mut word = word;
+        let length = self.stem_in_place(&mut word);
+        word.truncate(length);
+        word
+    }
+
+    /// Stem the ASCII word in `word`, writing its stemmed form over the
+    /// start of it, and return the stem's length.
+    ///
+    /// Nothing is allocated, so this suits callers with no heap, or with
+    /// buffers of their own to fill. The stem is the same as `stem` would
+    /// give. Bytes outside ASCII are treated as consonants.
+    ///
+    /// ```
+    /// # extern crate porter_stemmer;
+    /// use porter_stemmer::Porter;
+    ///
+    /// let mut word = *b"hopefulness";
+    /// let length = Porter::new().stem_ascii_in_place(&mut word);
+    /// assert_eq!(b"hope", &word[..length]);
+    /// ```
+    pub fn stem_ascii_in_place(&self, word: &mut [u8]) -> usize {
+        if self.mode == PorterMode::Nltk {
+            if let Some(&(_, stem)) = NLTK_IRREGULAR_FORMS.iter().find(|&&(form, _)| form.as_bytes() == &word[..]) {
+                word[..stem.len()].copy_from_slice(stem.as_bytes());
+                return stem.len();
+            }
+
+            word.make_ascii_lowercase();
+        }
+
+        self.stem_in_place(word)
+    }
+
+    /// Stem words packed into `letters`, the `i`th starting at `starts[i]`
+    /// and `lengths[i]` long, a step at a time over all of them rather than a
+    /// word at a time, and update them to their stems.
+    ///
+    /// Words that can't be stemmed byte by byte are stemmed as usual, and
+    /// their stems appended to `letters`.
+    pub(crate) fn stem_packed(&self, letters: &mut Vec<u8>, starts: &mut [usize], lengths: &mut [usize]) {
+        // The letters each word can be rewritten within, or none once it has
+        // been stemmed.
+        let mut rooms = lengths.to_vec();
+
+        for index in 0..starts.len() {
+            let range = starts[index]..starts[index] + lengths[index];
+            let word = str::from_utf8(&letters[range.clone()]).expect("packed words are UTF-8");
+
+            if !bytewise(word) {
+                let stem = self.stem(word);
+                starts[index] = letters.len();
+                lengths[index] = stem.len();
+                letters.extend_from_slice(stem.as_bytes());
+                rooms[index] = 0;
+            } else if self.mode == PorterMode::Nltk {
+                if let Some(&(_, stem)) = NLTK_IRREGULAR_FORMS.iter().find(|&&(form, _)| form == word) {
+                    letters[range.start..range.start + stem.len()].copy_from_slice(stem.as_bytes());
+                    lengths[index] = stem.len();
+                    rooms[index] = 0;
+                } else {
+                    letters[range].make_ascii_lowercase();
+                }
+            }
+        }
+
+        let steps: [fn(&Porter, &mut Word<u8>); 8] = [
+            Porter::step_one_a, Porter::step_one_b, Porter::step_one_c, Porter::step_two,
+            Porter::step_three, Porter::step_four, Porter::step_5a, Porter::step_5b,
+        ];
+
+        for step in steps.iter() {
+            for index in 0..starts.len() {
+                // As in `stem_in_place`, words of two letters or fewer are
+                // left alone.
+                if rooms[index] <= 2 {
+                    continue;
+                }
+
+                let mut word = Word::new(&mut letters[starts[index]..starts[index] + rooms[index]], lengths[index], &self.vowels);
+                step(self, &mut word);
+                lengths[index] = word.length;
+            }
+        }
+    }
+
+    /// Run every step over `letters`, returning the length of the stem now
+    /// at their start.
+    fn stem_in_place<L: Letter>(&self, letters: &mut [L]) -> usize {
+        let length = letters.len();
+        let mut word = Word::new(letters, length, &self.vowels);
+
+        if word.length > 2 {
+            self.step_one_a(&mut word);
+            self.step_one_b(&mut word);
+            self.step_one_c(&mut word);
+            self.step_two(&mut word);
+            self.step_three(&mut word);
+            self.step_four(&mut word);
+            self.step_5a(&mut word);
+            self.step_5b(&mut word);
+        }
+
+        word.length
+    }
+
+    /// Run a single step over `word`.
+    fn in_place<L: Letter>(&self, word: Vec<L>, step: fn(&Porter, &mut Word<L>)) -> Vec<L> {
+        let mut word = word;
+        let length = {
+            let length = word.len();
+            let mut word = Word::new(&mut word, length, &self.vowels);
+            step(self, &mut word);
+            word.length
+        };
+
+        word.truncate(length);
+        word
+    }
+
+    /// Step 1a: remove plural suffixes.
+    ///
+    /// Order in which to apply rules:
+    ///
+    /// SSES -> SS
+    /// IES  -> I
+    /// SS -> SS
+    /// S  ->
+    pub fn phase_one_a<L: Letter>(&self, word: Vec<L>) -> Vec<L> {
+        self.in_place(word, Porter::step_one_a)
+    }
+
+    fn step_one_a<L: Letter>(&self, word: &mut Word<L>) {
+        if self.mode == PorterMode::Nltk && word.length == 4 && word.ends_with("ies") {
+            word.remove(1);
+        } else if word.ends_with("sses") || word.ends_with("ies") {
+            word.remove(2);
+        } else if word.ends_with("ss") {
+            // Left as it is.
+        } else if word.ends_with("s") {
+            word.remove(1);
+        }
+    }
+
+    /// Step 1b: remove past tense and progressive suffixes, tidying up the
+    /// remaining stem.
+    ///
+    /// Order in which to apply rules:
+    ///
+    /// measure > 0 ? EED -> EE
+    /// *v*         ? ED ->
+    /// *v*         ? ING ->
+    pub fn phase_one_b<L: Letter>(&self, word: Vec<L>) -> Vec<L> {
+        self.in_place(word, Porter::step_one_b)
+    }
+
+    fn step_one_b<L: Letter>(&self, word: &mut Word<L>) {
+        if self.mode == PorterMode::Nltk && word.ends_with("ied") {
+            let count = if word.length == 4 { 1 } else { 2 };
+            word.remove(count);
+        } else if word.ends_with("eed") {
+            if word.measure(3) > 0 {
+                word.remove(1);
+            }
+        } else if word.ends_with("ed") {
+            if word.contains_vowel(2) {
+                word.remove(2);
+                self.step_one_b_substep(word);
+            }
+        } else if word.ends_with("ing") && word.contains_vowel(3) {
+            word.remove(3);
+            self.step_one_b_substep(word);
+        }
+    }
+
+    ///
+    /// AT -> ATE
+    /// BL -> BLE
+    /// IZ -> IZE
+    ///
+    /// Contraints apply to whole word in here
+    /// *d (double consonant) and not (*L or *S or *Z) -> change to single letter
+    ///
+    /// m=1 and *o (see `ends_star_o`) -> E
+    ///
+    /// This is only ever run after `ed` or `ing` has been removed, so there
+    /// is always room to add an `e`.
+    fn step_one_b_substep<L: Letter>(&self, word: &mut Word<L>) {
+        if word.ends_with("at") || word.ends_with("bl") || word.ends_with("iz") {
+            word.push(b'e');
+        } else if self.vowels.ends_double_porters_consonant(word.letters()) &&
+                  !(word.ends_with("l") || word.ends_with("s") || word.ends_with("z")) {
+            word.remove(1);
+        } else if word.measure(0) == 1 && self.ends_star_o(word.letters()) {
+            word.push(b'e');
+        }
+    }
+
+    /// Step 1c: turn a terminal `y` into `i`, under the conditions of the
+    /// `FinalY` rule in use.
+    ///
+    /// (*v*) Y -> I
+    pub fn phase_one_c<L: Letter>(&self, word: Vec<L>) -> Vec<L> {
+        self.in_place(word, Porter::step_one_c)
+    }
+
+    fn step_one_c<L: Letter>(&self, word: &mut Word<L>) {
+        let final_y = self.final_y.unwrap_or(match self.mode {
+            PorterMode::Classic => FinalY::AnyVowel,
+            PorterMode::Revised | PorterMode::Lucene => FinalY::VowelInStem,
+            PorterMode::Nltk => FinalY::AfterConsonant,
+        });
+        let stem = word.stem(word.length.min(1));
+        let condition = match final_y {
+            FinalY::AnyVowel => word.contains_vowel(0),
+            FinalY::VowelInStem => word.contains_vowel(word.length.min(1)),
+            FinalY::AfterConsonant => stem.len() > 1 && self.vowels.porter_consonant(stem, stem.len() - 1),
+        };
+
+        if condition && word.ends_with("y") {
+            word.replace(1, "i");
+        }
+    }
+
+    /// Step 2: map double suffixes to single ones.
+    ///
+    /// For all where the STEM is measure > 0
+    /// ATIONAL -> ATE
+    /// TIONAL  -> TION
+    /// ENCI    -> ENCE
+    /// ANCI    -> ANCE
+    /// IZER    -> IZE
+    /// ABLI    -> ABLE
+    /// ALLI    -> AL
+    /// ENTLI   -> ENT
+    /// ELI     -> E
+    /// OUSLI   -> OUS
+    /// IZATION -> IZE
+    /// ATION   -> ATE
+    /// ATOR    -> ATE
+    /// ALISM   -> AL
+    /// IVENESS -> IVE
+    /// FULNESS -> FUL
+    /// OUSNESS -> OUS
+    /// ALITI   -> AL
+    /// IVITI   -> IVE
+    /// BILITI  -> BLE
+    pub fn phase_two<L: Letter>(&self, word: Vec<L>) -> Vec<L> {
+        self.in_place(word, Porter::step_two)
+    }
+
+    fn step_two<L: Letter>(&self, word: &mut Word<L>) {
+        if self.mode == PorterMode::Nltk && word.ends_with("alli") && word.measure(4) > 0 {
+            // NLTK reduces `alli` first, then tries the step again.
+            word.remove(2);
+            self.step_two(word);
+        } else if self.mode == PorterMode::Nltk && word.ends_with("logi") && word.measure(3) > 0 {
+            word.remove(1);
+        } else {
+            let rules = penultimate(word.letters()).map_or(&[][..], |letter| self.phase_two_rules(letter));
+            self.apply_rules(word, rules, 0);
+        }
+    }
+
+    /// The rules of step 2 whose suffixes have `letter` as their second to
+    /// last letter, in the order they are tried.
+    fn phase_two_rules(&self, letter: u8) -> &'static [Rule] {
+        match (letter, self.mode) {
+            (b'a', _) => &[("ational", "ate"), ("tional", "tion")],
+            (b'c', _) => &[("enci", "ence"), ("anci", "ance")],
+            (b'e', _) => &[("izer", "ize")],
+            (b'g', PorterMode::Classic) => &[],
+            (b'g', _) => &[("logi", "log")],
+            (b'l', PorterMode::Classic) => &[("abli", "able"), ("alli", "al"), ("entli", "ent"), ("eli", "e"), ("ousli", "ous")],
+            (b'l', PorterMode::Nltk) => &[("bli", "ble"), ("alli", "al"), ("entli", "ent"), ("eli", "e"), ("ousli", "ous"), ("fulli", "ful")],
+            (b'l', _) => &[("bli", "ble"), ("alli", "al"), ("entli", "ent"), ("eli", "e"), ("ousli", "ous")],
+            (b'o', _) => &[("ization", "ize"), ("ation", "ate"), ("ator", "ate")],
+            (b's', _) => &[("alism", "al"), ("iveness", "ive"), ("fulness", "ful"), ("ousness", "ous")],
+            (b't', _) => &[("aliti", "al"), ("iviti", "ive"), ("biliti", "ble")],
+            _ => &[],
+        }
+    }
+
+    /// Step 3: remove or simplify `-ic-`, `-full`, `-ness` etc. suffixes.
+    ///
+    /// For all whre the STEM measure is greater than one
+    /// ICATE -> IC
+    /// ATIVE ->
+    /// ALIZE -> AL
+    /// ICITI -> IC
+    /// ICAL  -> IC
+    /// FUL   ->
+    /// NESS  ->
+    pub fn phase_three<L: Letter>(&self, word: Vec<L>) -> Vec<L> {
+        self.in_place(word, Porter::step_three)
+    }
+
+    fn step_three<L: Letter>(&self, word: &mut Word<L>) {
+        let last = word.letters().last().and_then(|letter| letter.to_ascii());
+        let rules = last.map_or(&[][..], Porter::phase_three_rules);
+        self.apply_rules(word, rules, 0);
+    }
+
+    /// The rules of step 3 whose suffixes end with `letter`.
+    fn phase_three_rules(letter: u8) -> &'static [Rule] {
+        match letter {
+            b'e' => &[("icate", "ic"), ("ative", ""), ("alize", "al")],
+            b'i' => &[("iciti", "ic")],
+            b'l' => &[("ical", "ic"), ("ful", "")],
+            b's' => &[("ness", "")],
+            _ => &[],
+        }
+    }
+
+    /// Step 4: remove the remaining standard suffixes where the stem has a measure
+    /// greater than one.
+    pub fn phase_four<L: Letter>(&self, word: Vec<L>) -> Vec<L> {
+        self.in_place(word, Porter::step_four)
+    }
+
+    fn step_four<L: Letter>(&self, word: &mut Word<L>) {
+        // `ion` is only removed after an `s` or a `t`, and no other suffix
+        // is tried in its place.
+        if word.ends_with("ion") && !(word.ends_with("sion") || word.ends_with("tion")) {
+            return;
+        }
+
+        let rules = penultimate(word.letters()).map_or(&[][..], Porter::phase_four_rules);
+        self.apply_rules(word, rules, 1);
+    }
+
+    /// The rules of step 4 whose suffixes have `letter` as their second to
+    /// last letter.
+    fn phase_four_rules(letter: u8) -> &'static [Rule] {
+        match letter {
+            b'a' => &[("al", "")],
+            b'c' => &[("ance", ""), ("ence", "")],
+            b'e' => &[("er", "")],
+            b'i' => &[("ic", "")],
+            b'l' => &[("able", ""), ("ible", "")],
+            b'n' => &[("ant", ""), ("ement", ""), ("ment", ""), ("ent", "")],
+            b'o' => &[("ion", ""), ("ou", "")],
+            b's' => &[("ism", "")],
+            b't' => &[("ate", ""), ("iti", "")],
+            b'u' => &[("ous", "")],
+            b'v' => &[("ive", "")],
+            b'z' => &[("ize", "")],
+            _ => &[],
+        }
+    }
+
+    /// Replace the suffix of the first of `rules` that `word` ends with and
+    /// whose stem has a measure greater than `measure`.
+    ///
+    /// In the revised and NLTK algorithms only the longest suffix `word`
+    /// ends with is considered, and if its stem's measure is too small the
+    /// word is left alone rather than trying a shorter one.
+    fn apply_rules<L: Letter>(&self, word: &mut Word<L>, rules: &[Rule], measure: usize) {
+        let rule = {
+            let stem_measure = |suffix: &str| word.measure(suffix.len());
+
+            if self.mode != PorterMode::Classic {
+                let longest = rules.iter()
+                    .filter(|&&(suffix, _)| word.ends_with(suffix))
+                    .max_by_key(|&&(suffix, _)| suffix.len());
+
+                if matches!(longest, Some(&(suffix, _)) if stem_measure(suffix) <= measure) {
+                    return;
+                }
+            }
+
+            rules.iter().find(|&&(suffix, _)| word.ends_with(suffix) && stem_measure(suffix) > measure)
+        };
+
+        if let Some(&(suffix, replacement)) = rule {
+            word.replace(suffix.len(), replacement);
+        }
+    }
+
+    /// Condition `*o`, which NLTK extends to two letter stems that end
+    /// vowel-consonant.
+    fn ends_star_o<L: Letter>(&self, word: &[L]) -> bool {
+        self.vowels.ends_star_o(word) ||
+            (self.mode == PorterMode::Nltk && word.len() == 2 &&
+             self.vowels.porter_vowel(word, 0) && self.vowels.porter_consonant(word, 1))
+    }
+
+    /// Step 5a: remove a final `e`.
+    pub fn phase_5a<L: Letter>(&self, word: Vec<L>) -> Vec<L> {
+        self.in_place(word, Porter::step_5a)
+    }
+
+    fn step_5a<L: Letter>(&self, word: &mut Word<L>) {
+        if !word.ends_with("e") {
+            return;
+        }
+
+        let measure = word.measure(1);
+        if measure > 1 || (measure == 1 && !self.ends_star_o(word.stem(1))) {
+            word.remove(1);
+        }
+    }
+
+    /// Step 5b: reduce a final double `l` to a single `l`.
+    pub fn phase_5b<L: Letter>(&self, word: Vec<L>) -> Vec<L> {
+        self.in_place(word, Porter::step_5b)
+    }
+
+    fn step_5b<L: Letter>(&self, word: &mut Word<L>) {
+        if word.ends_with("l") &&
+            word.measure(0) > 1 &&
+            self.vowels.ends_double_porters_consonant(word.letters()) {
+
+            word.remove(1);
+        }
+    }
+}
+
+/// Take a word as a Vector of grapheme clusters, and return the stemmed equivalent using Porter's
+/// stemming algorithm.
+///
+/// This runs each of the public phases in turn: `phase_one_a`, `phase_one_b`,
+/// `phase_one_c`, `phase_two`, `phase_three`, `phase_four`, `phase_5a` and
+/// `phase_5b`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate porter_stemmer;
+/// use porter_stemmer::stem_tokenized;
+///
+/// let tokenized = vec!["s", "t", "e", "m", "m", "i", "n", "g"];
+/// let stemmed = stem_tokenized(tokenized);
+/// assert_eq!(&["s", "t", "e", "m"], &stemmed[..]);
+/// ```
+pub fn stem_tokenized<L: Letter>(word: Vec<L>) -> Vec<L> {
+    Porter::new().stem_tokenized(word)
+}
+
+/// Step 1a: remove plural suffixes. See `Porter::phase_one_a`.
+///
+/// Each phase is exposed so that partial pipelines can be built, for example
+/// a light stemmer that stops after plural removal:
+///
+/// ```
+/// # extern crate porter_stemmer;
+/// use porter_stemmer::phase_one_a;
+///
+/// let tokenized = vec!["p", "o", "n", "i", "e", "s"];
+/// assert_eq!(&["p", "o", "n", "i"], &phase_one_a(tokenized)[..]);
+/// ```
+pub fn phase_one_a<L: Letter>(word: Vec<L>) -> Vec<L> {
+    Porter::new().phase_one_a(word)
+}
+
+/// Step 1b: remove past tense and progressive suffixes. See
+/// `Porter::phase_one_b`.
+pub fn phase_one_b<L: Letter>(word: Vec<L>) -> Vec<L> {
+    Porter::new().phase_one_b(word)
+}
+
+/// Step 1c: turn a terminal `y` into `i`. See `Porter::phase_one_c`.
+pub fn phase_one_c<L: Letter>(word: Vec<L>) -> Vec<L> {
+    Porter::new().phase_one_c(word)
+}
+
+/// Step 2: map double suffixes to single ones. See `Porter::phase_two`.
+pub fn phase_two<L: Letter>(word: Vec<L>) -> Vec<L> {
+    Porter::new().phase_two(word)
+}
+
+/// Step 3: remove or simplify `-ic-`, `-full`, `-ness` etc. suffixes. See
+/// `Porter::phase_three`.
+pub fn phase_three<L: Letter>(word: Vec<L>) -> Vec<L> {
+    Porter::new().phase_three(word)
+}
+
+/// Step 4: remove the remaining standard suffixes. See `Porter::phase_four`.
+pub fn phase_four<L: Letter>(word: Vec<L>) -> Vec<L> {
+    Porter::new().phase_four(word)
+}
+
+/// Step 5a: remove a final `e`. See `Porter::phase_5a`.
+pub fn phase_5a<L: Letter>(word: Vec<L>) -> Vec<L> {
+    Porter::new().phase_5a(word)
+}
+
+/// Step 5b: reduce a final double `l` to a single `l`. See `Porter::phase_5b`.
+pub fn phase_5b<L: Letter>(word: Vec<L>) -> Vec<L> {
+    Porter::new().phase_5b(word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokenise<'a>(input: &'a str) -> Vec<&'a str> {
+        use unicode_segmentation::UnicodeSegmentation;
+        input.graphemes(true).collect::<Vec<&'a str>>()
+    }
+
+    fn assert_fn<'a>(f: fn(Vec<&'a str>) -> Vec<&'a str>, input: &'a str, expected: &'a str) {
+        let input = tokenise(input);
+        let expected = tokenise(expected);
+
+        assert_eq!(&f(input), &expected);
+    }
+
+    #[test]
+    fn test_phase_one() {
+        assert_fn(phase_one_a, "caresses", "caress");
+        assert_fn(phase_one_a, "caress", "caress");
+        assert_fn(phase_one_a, "ponies", "poni");
+        assert_fn(phase_one_a, "cats", "cat");
+    }
+
+    #[test]
+    fn test_phase_one_b() {
+        assert_fn(phase_one_b, "feed", "feed");
+        assert_fn(phase_one_b, "agreed", "agree");
+        assert_fn(phase_one_b, "plastered", "plaster");
+        assert_fn(phase_one_b, "bled", "bled");
+        assert_fn(phase_one_b, "motoring", "motor");
+        assert_fn(phase_one_b, "sing", "sing");
+    }
+
+    #[test]
+    fn test_phase_one_b_substep() {
+        assert_fn(phase_one_b_substep, "conflat", "conflate");
+        assert_fn(phase_one_b_substep, "troubl", "trouble");
+        assert_fn(phase_one_b_substep, "siz", "size");
+        assert_fn(phase_one_b_substep, "hopp", "hop");
+        assert_fn(phase_one_b_substep, "hiss", "hiss");
+        assert_fn(phase_one_b_substep, "fizz", "fizz");
+        assert_fn(phase_one_b_substep, "fall", "fall");
+        assert_fn(phase_one_b_substep, "fail", "fail");
+        assert_fn(phase_one_b_substep, "fil", "file");
+    }
+
+    #[test]
+    fn test_phase_one_c() {
+        assert_fn(phase_one_c, "happy", "happi");
+    }
+
+    #[test]
+    fn test_phase_one_c_sky() {
+        let tokens = |word: &'static str| tokenise(word);
+        let rules = [
+            (FinalY::AnyVowel, ["ski", "happi", "bi", "sai"]),
+            (FinalY::VowelInStem, ["sky", "happi", "by", "sai"]),
+            (FinalY::AfterConsonant, ["ski", "happi", "by", "say"]),
+        ];
+
+        for &(final_y, expected) in rules.iter() {
+            let porter = Porter::new().final_y(final_y);
+            for (word, expected) in ["sky", "happy", "by", "say"].iter().zip(expected.iter()) {
+                assert_eq!(tokens(expected), porter.phase_one_c(tokens(word)), "{:?} on {}", final_y, word);
+            }
+        }
+
+        assert_fn(phase_one_c, "sky", "ski");
+        assert_eq!(tokens("sky"), Porter::strict().phase_one_c(tokens("sky")));
+    }
+
+    #[test]
+    fn test_phase_two() {
+        assert_fn(phase_two, "relational", "relate");
+        assert_fn(phase_two, "conditional", "condition");
+        assert_fn(phase_two, "rational", "rational");
+        assert_fn(phase_two, "valenci", "valence");
+        assert_fn(phase_two, "hesitanci", "hesitance");
+        assert_fn(phase_two, "digitizer", "digitize");
+        assert_fn(phase_two, "conformabli", "conformable");
+        assert_fn(phase_two, "radicalli", "radical");
+        assert_fn(phase_two, "differentli", "different");
+        assert_fn(phase_two, "vileli", "vile");
+        assert_fn(phase_two, "analogousli", "analogous");
+        assert_fn(phase_two, "vietnamization", "vietnamize");
+        assert_fn(phase_two, "predication", "predicate");
+        assert_fn(phase_two, "operator", "operate");
+        assert_fn(phase_two, "feudalism", "feudal");
+        assert_fn(phase_two, "decisiveness", "decisive");
+        assert_fn(phase_two, "hopefulness", "hopeful");
+        assert_fn(phase_two, "callousness", "callous");
+        assert_fn(phase_two, "formaliti", "formal");
+        assert_fn(phase_two, "sensitiviti", "sensitive");
+        assert_fn(phase_two, "sensibiliti", "sensible");
+    }
+
+    #[test]
+    fn test_phase_three() {
+        assert_fn(phase_three, "triplicate", "triplic");
+        assert_fn(phase_three, "formative", "form");
+        assert_fn(phase_three, "formalize", "formal");
+        assert_fn(phase_three, "electriciti", "electric");
+        assert_fn(phase_three, "electrical", "electric");
+        assert_fn(phase_three, "hopeful", "hope");
+        assert_fn(phase_three, "goodness", "good");
+    }
+
+    #[test]
+    fn test_phase_four() {
+        assert_fn(phase_four, "revival", "reviv");
+        assert_fn(phase_four, "allowance", "allow");
+        assert_fn(phase_four, "inference", "infer");
+        assert_fn(phase_four, "airliner", "airlin");
+        assert_fn(phase_four, "gyroscopic", "gyroscop");
+        assert_fn(phase_four, "adjustable", "adjust");
+        assert_fn(phase_four, "defensible", "defens");
+        assert_fn(phase_four, "irritant", "irrit");
+        assert_fn(phase_four, "replacement", "replac");
+        assert_fn(phase_four, "adjustment", "adjust");
+        assert_fn(phase_four, "dependent", "depend");
+        assert_fn(phase_four, "adoption", "adopt");
+        assert_fn(phase_four, "homologou", "homolog");
+        assert_fn(phase_four, "communism", "commun");
+        assert_fn(phase_four, "activate", "activ");
+        assert_fn(phase_four, "angulariti", "angular");
+        assert_fn(phase_four, "homologous", "homolog");
+        assert_fn(phase_four, "effective", "effect");
+        assert_fn(phase_four, "bowdlerize", "bowdler");
+    }
+
+    #[test]
+    fn test_rule_tables() {
+        let modes = [PorterMode::Classic, PorterMode::Revised, PorterMode::Nltk, PorterMode::Lucene];
+
+        // Each suffix must be filed under the letter it is looked up by.
+        for letter in b'a'..=b'z' {
+            for &mode in modes.iter() {
+                for &(suffix, _) in Porter::new().mode(mode).phase_two_rules(letter) {
+                    assert_eq!(Some(letter), penultimate(suffix.as_bytes()), "step 2 suffix {}", suffix);
+                }
+            }
+            for &(suffix, _) in Porter::phase_three_rules(letter) {
+                assert_eq!(Some(&letter), suffix.as_bytes().last(), "step 3 suffix {}", suffix);
+            }
+            for &(suffix, _) in Porter::phase_four_rules(letter) {
+                assert_eq!(Some(letter), penultimate(suffix.as_bytes()), "step 4 suffix {}", suffix);
+            }
+        }
+    }
+
+    #[test]
+    fn test_phase_five_a() {
+        // 5a
+        assert_fn(phase_5a, "probate", "probat");
+        assert_fn(phase_5a, "rate", "rate");
+        assert_fn(phase_5a, "cease", "ceas");
+    }
+
+    #[test]
+    fn test_phase_five_b() {
+        // 5b
+        assert_fn(phase_5b, "controll", "control");
+        assert_fn(phase_5b, "roll", "roll");
+    }
+
+    fn phase_one_b_substep(word: Vec<&str>) -> Vec<&str> {
+        // Leave room for an `e`, as removing `ed` or `ing` would.
+        let mut word = word;
+        let length = word.len();
+        word.push("");
+
+        let length = {
+            let porter = Porter::new();
+            let mut word = Word::new(&mut word, length, &porter.vowels);
+            porter.step_one_b_substep(&mut word);
+            word.length
+        };
+
+        word.truncate(length);
+        word
+    }
+
+    #[test]
+    fn test_porter_vowels() {
+        let porter = Porter::new().vowels(Vowels::new().with_vowel("é"));
+        assert_eq!("ést", porter.stem("ésting"));
+        assert_eq!("ésting", Porter::new().stem("ésting"));
+        assert_eq!("relat", porter.stem("relational"));
+    }
+
+    #[test]
+    fn test_revised() {
+        let revised = Porter::new().mode(PorterMode::Revised);
+
+        let pairs = [
+            ("apology", "apolog", "apologi"),
+            ("humbly", "humbl", "humbli"),
+            ("agreement", "agreement", "agreem"),
+            ("cry", "cry", "cri"),
+            ("happy", "happi", "happi"),
+            ("relational", "relat", "relat"),
+            ("generalizations", "gener", "gener"),
+        ];
+        for &(word, expected, classic) in pairs.iter() {
+            assert_eq!(expected, revised.stem(word), "stemming {}", word);
+            assert_eq!(classic, Porter::new().stem(word), "stemming {}", word);
+        }
+    }
+
+    #[test]
+    fn test_nltk() {
+        let nltk = Porter::new().mode(PorterMode::Nltk);
+
+        let pairs = [
+            ("caresses", "caress"), ("flies", "fli"), ("dies", "die"), ("died", "die"),
+            ("cried", "cri"), ("agreed", "agre"), ("owned", "own"), ("owed", "owe"),
+            ("humbled", "humbl"), ("sized", "size"), ("meeting", "meet"), ("stating", "state"),
+            ("itemization", "item"), ("sensational", "sensat"), ("traditional", "tradit"),
+            ("reference", "refer"), ("colonizer", "colon"), ("plotted", "plot"), ("cry", "cri"),
+            ("say", "say"), ("dying", "die"), ("skies", "sky"), ("news", "news"),
+            ("hopefully", "hope"), ("apology", "apolog"), ("Running", "run"), ("Dying", "dy"),
+            ("by", "by"),
+        ];
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, nltk.stem(word), "stemming {}", word);
+        }
+
+        assert_eq!(vec!["d", "i", "e"], nltk.stem_tokenized(vec!["d", "y", "i", "n", "g"]));
+    }
+
+    #[test]
+    fn test_stem_in_place() {
+        let long = "antidisestablishmentarianismantidisestablishmentarianisms";
+        let words = ["relational", "naïvely", "résumés", "a\r\nb", long, &long.replace('a', "á")];
+
+        for &mode in [PorterMode::Classic, PorterMode::Revised, PorterMode::Lucene].iter() {
+            let porter = Porter::new().mode(mode);
+            for word in words.iter() {
+                let tokenized = match mode {
+                    PorterMode::Lucene => porter.stem_tokenized(word.chars().collect()).into_iter().collect(),
+                    _ => porter.stem_tokenized(tokenise(word)).concat(),
+                };
+                assert_eq!(tokenized, porter.stem(word), "{:?} on {}", mode, word);
+            }
+        }
+    }
+
+    #[test]
+    fn test_word_measure() {
+        let vowels = Vowels::new();
+        let long = "syzygy".repeat(12);
+
+        for word in ["troubles", "syzygy", "oaten", "y", "", &long].iter() {
+            let mut letters = word.bytes().chain(b"xx".iter().cloned()).collect::<Vec<u8>>();
+            let length = word.len();
+            let mut word = Word::new(&mut letters, length, &vowels);
+
+            for _ in 0..2 {
+                for count in 0..=word.length {
+                    assert_eq!(vowels.measure(word.stem(count)), word.measure(count));
+                    assert_eq!(vowels.contains_porter_vowel(word.stem(count)), word.contains_vowel(count));
+                }
+
+                // Changing the last letter changes whether it is a vowel.
+                if word.length > 0 {
+                    word.replace(1, "ay");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_stem_ascii_in_place() {
+        let words = include_str!("../../input.txt").lines().chain(vec!["Dying", "SKIES", "a", ""]);
+
+        for word in words {
+            for &mode in [PorterMode::Classic, PorterMode::Revised, PorterMode::Nltk, PorterMode::Lucene].iter() {
+                let porter = Porter::new().mode(mode);
+                let mut letters = word.as_bytes().to_vec();
+                let length = porter.stem_ascii_in_place(&mut letters);
+
+                assert_eq!(porter.stem(word).as_bytes(), &letters[..length], "{:?} on {}", mode, word);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lucene() {
+        let lucene = Porter::new().mode(PorterMode::Lucene);
+        let revised = Porter::new().mode(PorterMode::Revised);
+
+        for word in &["apology", "humbly", "agreement", "cry", "relational", "generalizations", "Running"] {
+            assert_eq!(revised.stem(word), lucene.stem(word), "stemming {}", word);
+        }
+
+        // Split into `char`s, the combining accent is a consonant of its own
+        // and `cre` + accent + `at` has a measure of two.
+        assert_eq!("cre\u{301}at", lucene.stem("cre\u{301}ation"));
+        assert_eq!("cre\u{301}ation", revised.stem("cre\u{301}ation"));
+    }
+
+    #[test]
+    fn test_strict_vocabulary() {
+        let porter = Porter::strict();
+        let vocabulary = include_str!("../../input.txt").lines();
+        let output = include_str!("../../expected.txt").lines();
+
+        let mut count = 0;
+        for (word, expected) in vocabulary.zip(output) {
+            assert_eq!(expected, porter.stem(word), "stemming {}", word);
+            count += 1;
+        }
+        assert_eq!(23531, count);
+    }
+
+    #[test]
+    fn test_stem_tokenized() {
+        assert_fn(stem_tokenized, "surveillance", "surveil");
+    }
+}
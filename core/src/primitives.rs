@@ -0,0 +1,593 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The building blocks the Porter stemmer is defined in terms of.
+//!
+//! All of the predicates operate over a word (or word fragment) expressed as
+//! a slice of `Letter`s, usually grapheme clusters, so they can be reused by
+//! related algorithms without copying them.
+//!
+//! ```
+//! # extern crate porter_stemmer;
+//! use porter_stemmer::primitives::{measure, contains_porter_vowel};
+//!
+//! let word = ["t", "r", "o", "u", "b", "l", "e", "s"];
+//! assert_eq!(2, measure(&word));
+//! assert!(contains_porter_vowel(&word[..3]));
+//! ```
+
+const ASCII_LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+
+const ACCENTED_VOWELS: &[&str] = &[
+    "à", "á", "â", "ã", "ä", "å", "æ",
+    "è", "é", "ê", "ë",
+    "ì", "í", "î", "ï",
+    "ò", "ó", "ô", "õ", "ö", "ø", "œ",
+    "ù", "ú", "û", "ü",
+];
+
+/// A single unit of a word as the stemmer sees it.
+///
+/// Porter's rules only ever need to compare units against, and build units
+/// from, lowercase ASCII letters. Anything else is simply "not a vowel", so
+/// the algorithm runs unchanged over grapheme clusters (`&str`), `char`s or,
+/// when the input is known to be ASCII, raw bytes (`u8`).
+pub trait Letter: Copy + PartialEq {
+    /// The ASCII character this unit represents, if it is a single ASCII
+    /// character.
+    fn to_ascii(self) -> Option<u8>;
+
+    /// Build the unit for a lowercase ASCII letter.
+    ///
+    /// Panics if `letter` is not in `b'a'..=b'z'`.
+    fn from_ascii(letter: u8) -> Self;
+
+    /// Whether this unit is exactly the grapheme cluster `grapheme`.
+    fn is_grapheme(self, grapheme: &str) -> bool;
+
+    /// Whether this unit is the ASCII character `letter`.
+    fn is(self, letter: u8) -> bool {
+        self.to_ascii() == Some(letter)
+    }
+
+    /// The letters of `word` as bytes, if this unit is a byte, so whole
+    /// words can be scanned at once rather than letter by letter.
+    fn as_bytes(word: &[Self]) -> Option<&[u8]> {
+        let _ = word;
+        None
+    }
+}
+
+impl<'a> Letter for &'a str {
+    fn to_ascii(self) -> Option<u8> {
+        if self.len() == 1 {
+            Some(self.as_bytes()[0])
+        } else {
+            None
+        }
+    }
+
+    fn from_ascii(letter: u8) -> &'a str {
+        let index = (letter - b'a') as usize;
+        &ASCII_LOWERCASE[index..index + 1]
+    }
+
+    fn is_grapheme(self, grapheme: &str) -> bool {
+        self == grapheme
+    }
+}
+
+impl Letter for u8 {
+    fn to_ascii(self) -> Option<u8> {
+        if self.is_ascii() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn from_ascii(letter: u8) -> u8 {
+        assert!(letter.is_ascii_lowercase());
+        letter
+    }
+
+    fn is_grapheme(self, grapheme: &str) -> bool {
+        grapheme.as_bytes() == [self]
+    }
+
+    fn as_bytes(word: &[u8]) -> Option<&[u8]> {
+        Some(word)
+    }
+}
+
+impl Letter for char {
+    fn to_ascii(self) -> Option<u8> {
+        if self.is_ascii() {
+            Some(self as u8)
+        } else {
+            None
+        }
+    }
+
+    fn from_ascii(letter: u8) -> char {
+        assert!(letter.is_ascii_lowercase());
+        letter as char
+    }
+
+    fn is_grapheme(self, grapheme: &str) -> bool {
+        let mut chars = grapheme.chars();
+        chars.next() == Some(self) && chars.next().is_none()
+    }
+}
+
+/// Whether `word` ends with the ASCII `suffix`, compared letter by letter.
+pub fn ends_with<L: Letter>(word: &[L], suffix: &str) -> bool {
+    let suffix = suffix.as_bytes();
+
+    if let Some(bytes) = L::as_bytes(word) {
+        return bytes.ends_with(suffix);
+    }
+
+    word.len() >= suffix.len() &&
+        word[word.len() - suffix.len()..].iter()
+            .zip(suffix)
+            .all(|(&letter, &expected)| letter.is(expected))
+}
+
+/// The five vowels, as a mask with bit `i` set for the letter `i` letters
+/// after `a`.
+const REAL_VOWELS: u32 = 1 | 1 << (b'e' - b'a') | 1 << (b'i' - b'a') | 1 << (b'o' - b'a') | 1 << (b'u' - b'a');
+
+/// Whether a grapheme is one of the five vowels `a`, `e`, `i`, `o` or `u`.
+pub fn real_vowel<L: Letter>(grapheme: L) -> bool {
+    let letter = grapheme.to_ascii().unwrap_or(0).wrapping_sub(b'a') as u32;
+
+    // Look the letter up in the mask, without branching on which it is.
+    (letter < 32) & (REAL_VOWELS.wrapping_shr(letter) & 1 == 1)
+}
+
+/// Whether a grapheme is anything other than a `real_vowel`.
+pub fn real_consonant<L: Letter>(grapheme: L) -> bool {
+    !real_vowel(grapheme)
+}
+
+/// The longest word `porter_vowel_mask` classifies.
+#[cfg(feature = "simd")]
+const MASK_LETTERS: usize = 64;
+
+/// The mask of the Porter vowels in up to `MASK_LETTERS` bytes, with bit `i`
+/// set where `porter_vowel(word, i)` holds.
+#[cfg(feature = "simd")]
+fn porter_vowel_mask(word: &[u8]) -> u64 {
+    use std::simd::prelude::*;
+
+    const LANES: usize = 16;
+
+    let mut vowels = 0;
+    let mut ys = 0;
+
+    for (index, chunk) in word.chunks(LANES).enumerate() {
+        let letters = u8x16::load_or_default(chunk);
+        let is = |letter| letters.simd_eq(u8x16::splat(letter));
+        let shift = index * LANES;
+
+        vowels |= (is(b'a') | is(b'e') | is(b'i') | is(b'o') | is(b'u')).to_bitmask() << shift;
+        ys |= is(b'y').to_bitmask() << shift;
+    }
+
+    // A `y` is a vowel after anything but a vowel, though never first.
+    vowels | (ys & !(vowels << 1) & !1)
+}
+
+/// The set of graphemes treated as vowels.
+///
+/// By default this is just `a`, `e`, `i`, `o` and `u`, along with Porter's
+/// rule for `y`. Additional vowels can be declared for romanized or accented
+/// text, so that e.g. the `ï` in `naïve` is not misclassified as a consonant.
+///
+/// Vowels are compared against whole grapheme clusters, so text should be
+/// normalized (e.g. to NFC) consistently with the vowels declared here.
+///
+/// ```
+/// # extern crate porter_stemmer;
+/// use porter_stemmer::primitives::Vowels;
+///
+/// let word = ["r", "é", "s", "u", "m", "é"];
+/// assert_eq!(1, Vowels::new().measure(&word));
+/// assert_eq!(2, Vowels::new().with_vowel("é").measure(&word));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Vowels {
+    additional: Vec<String>,
+}
+
+impl Vowels {
+    /// The standard vowels `a`, `e`, `i`, `o` and `u`.
+    pub fn new() -> Vowels {
+        Vowels::default()
+    }
+
+    /// The standard vowels, plus their common accented forms in Latin
+    /// scripts, e.g. `à`, `é`, `î`, `ö` and `ü`.
+    pub fn accented() -> Vowels {
+        ACCENTED_VOWELS.iter().fold(Vowels::new(), |vowels, vowel| vowels.with_vowel(vowel))
+    }
+
+    /// Declare an additional vowel grapheme.
+    pub fn with_vowel(mut self, vowel: &str) -> Vowels {
+        if !self.additional.iter().any(|additional| additional == vowel) {
+            self.additional.push(vowel.to_string());
+        }
+
+        self
+    }
+
+    /// The letters of `word` as bytes, if they can be classified by
+    /// `porter_vowel_mask` rather than one at a time.
+    #[cfg(feature = "simd")]
+    fn masked<'a, L: Letter>(&self, word: &'a [L]) -> Option<&'a [u8]> {
+        L::as_bytes(word).filter(|bytes| {
+            bytes.len() <= MASK_LETTERS && self.additional.iter().all(|vowel| vowel.len() > 1)
+        })
+    }
+
+    /// A mask of the Porter vowels among the first 64 letters of `word`,
+    /// with bit `i` set where `porter_vowel(word, i)` holds.
+    pub(crate) fn porter_vowel_mask<L: Letter>(&self, word: &[L]) -> u64 {
+        #[cfg(feature = "simd")]
+        {
+            if let Some(bytes) = self.masked(word) {
+                return porter_vowel_mask(bytes);
+            }
+        }
+
+        (0..word.len().min(64))
+            .filter(|&index| self.porter_vowel(word, index))
+            .fold(0, |mask, index| mask | 1 << index)
+    }
+
+    /// Whether a grapheme is one of the vowels in this set, disregarding
+    /// Porter's rule for `y`.
+    pub fn is_vowel<L: Letter>(&self, grapheme: L) -> bool {
+        real_vowel(grapheme) ||
+            self.additional.iter().any(|vowel| grapheme.is_grapheme(vowel))
+    }
+
+    /// Whether the grapheme at `index` is a vowel in Porter's sense.
+    ///
+    /// A `y` counts as a vowel when it follows a consonant, so the `y` in `toy`
+    /// is a consonant, but the `y` in `syzygy` is a vowel.
+    pub fn porter_vowel<L: Letter>(&self, word: &[L], index: usize) -> bool {
+        let grapheme = word[index];
+
+        if self.is_vowel(grapheme) {
+            true
+        } else {
+            if index == 0 || !grapheme.is(b'y') {
+                false
+            } else {
+                let preceeding_grapheme = word[index - 1];
+                !self.is_vowel(preceeding_grapheme)
+            }
+        }
+    }
+
+    /// Whether the grapheme at `index` is a consonant in Porter's sense.
+    pub fn porter_consonant<L: Letter>(&self, word: &[L], index: usize) -> bool {
+        !self.porter_vowel(word, index)
+    }
+
+    /// Condition `*v*`: the word contains a Porter vowel.
+    pub fn contains_porter_vowel<L: Letter>(&self, word: &[L]) -> bool {
+        #[cfg(feature = "simd")]
+        {
+            if let Some(bytes) = self.masked(word) {
+                return porter_vowel_mask(bytes) != 0;
+            }
+        }
+
+        for index in 0..word.len() {
+            if self.porter_vowel(word, index) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Condition `*d`: the word ends with a double consonant, e.g. `-tt`, `-ss`.
+    pub fn ends_double_porters_consonant<L: Letter>(&self, word: &[L]) -> bool {
+        let word_length = word.len();
+        if word_length > 2 {
+            let last_grapheme = word[word_length - 1];
+            let penultimate_grapheme = word[word_length - 2];
+
+            last_grapheme == penultimate_grapheme &&
+                self.porter_consonant(word, word_length - 1)
+        } else {
+            false
+        }
+
+    }
+
+    /// Condition `*o`: the stem ends consonant-vowel-consonant,
+    /// where the second consonant is not w, x or y.
+    pub fn ends_star_o<L: Letter>(&self, word: &[L]) -> bool {
+        let word_length = word.len();
+
+        if word_length > 2 {
+            let last_grapheme = word[word_length - 1];
+            match last_grapheme.to_ascii() {
+                Some(b'w') | Some(b'x') | Some(b'y') => false,
+                _ => {
+                    self.porter_consonant(word, word_length - 1) &&
+                    self.porter_vowel(word, word_length - 2) &&
+                    self.porter_consonant(word, word_length - 3)
+                }
+            }
+        } else {
+            false
+        }
+    }
+
+    /// The Porter stemmer makes use of a _measure_.
+    ///
+    /// Defined formally as the number of
+    /// Vowel sequence-Consonant sequence pairs in a word or fragment.
+    ///
+    /// If C is a sequence of consonants, and V a sequence
+    /// of vowels, the measure of a word or word part can be
+    /// defined by:
+    ///
+    /// C?(VC)*V?
+    ///
+    /// Where the measure, _m_, is equal to the number of matches
+    /// by the Kleene star `(VC)*`
+    ///
+    /// Note how the parameter is a slice of `Letter`s.  This is so we can use an
+    /// indexable list of grapheme clusters, or of bytes when the input is known to
+    /// be ASCII.
+    pub fn measure<L: Letter>(&self, word: &[L]) -> usize {
+        #[cfg(feature = "simd")]
+        {
+            if let Some(bytes) = self.masked(word) {
+                // Count the places a vowel is followed by a consonant.
+                let vowels = porter_vowel_mask(bytes);
+                let letters = u64::MAX.checked_shr((MASK_LETTERS - bytes.len()) as u32).unwrap_or(0);
+                let ends = (vowels << 1) & !vowels & letters;
+                return ends.count_ones() as usize;
+            }
+        }
+
+        let mut measure = 0;
+        let word_length = word.len();
+
+        if word_length == 0{
+            return measure;
+        }
+
+        let mut is_vowel_current = self.is_vowel(word[0]);
+
+        for index in 1..word_length {
+            let is_vowel = self.porter_vowel(word, index);
+            if !is_vowel_current && is_vowel {
+                is_vowel_current = true;
+            } else if is_vowel_current && !is_vowel {
+                is_vowel_current = false;
+                measure += 1;
+            }
+        }
+
+        measure
+    }
+}
+
+/// Whether the grapheme at `index` is a vowel in Porter's sense.
+///
+/// A `y` counts as a vowel when it follows a consonant, so the `y` in `toy`
+/// is a consonant, but the `y` in `syzygy` is a vowel.
+pub fn porter_vowel<L: Letter>(word: &[L], index: usize) -> bool {
+    Vowels::new().porter_vowel(word, index)
+}
+
+/// Whether the grapheme at `index` is a consonant in Porter's sense.
+pub fn porter_consonant<L: Letter>(word: &[L], index: usize) -> bool {
+    Vowels::new().porter_consonant(word, index)
+}
+
+/// Condition `*v*`: the word contains a Porter vowel.
+pub fn contains_porter_vowel<L: Letter>(word: &[L]) -> bool {
+    Vowels::new().contains_porter_vowel(word)
+}
+
+/// Condition `*d`: the word ends with a double consonant, e.g. `-tt`, `-ss`.
+pub fn ends_double_porters_consonant<L: Letter>(word: &[L]) -> bool {
+    Vowels::new().ends_double_porters_consonant(word)
+}
+
+/// Condition `*o`: the stem ends consonant-vowel-consonant,
+/// where the second consonant is not w, x or y.
+pub fn ends_star_o<L: Letter>(word: &[L]) -> bool {
+    Vowels::new().ends_star_o(word)
+}
+
+/// The _measure_ of a word or fragment using the standard vowels. See
+/// `Vowels::measure` for the definition.
+pub fn measure<L: Letter>(word: &[L]) -> usize {
+    Vowels::new().measure(word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokenise<'a>(input: &'a str) -> Vec<&'a str> {
+        use unicode_segmentation::UnicodeSegmentation;
+        input.graphemes(true).collect::<Vec<&'a str>>()
+    }
+
+    #[test]
+    fn test_real_vowel() {
+        assert!(real_vowel("a"));
+        assert!(real_vowel("e"));
+        assert!(real_vowel("i"));
+        assert!(real_vowel("o"));
+        assert!(real_vowel("u"));
+        assert!(!real_vowel("b"));
+
+        for byte in 0..=255u8 {
+            assert_eq!(b"aeiou".contains(&byte), real_vowel(byte), "classifying {}", byte);
+        }
+    }
+
+    #[test]
+    fn test_byte_masks() {
+        let words = ["", "y", "toy", "syzygy", "yelp", "queueing", "rhythm", "tr\u{e9}s",
+                     "pneumonoultramicroscopicsilicovolcanoconiosis", &"ay".repeat(32), &"by".repeat(40)];
+        let vowels = Vowels::new().with_vowel("\u{e9}");
+
+        for word in words.iter() {
+            let letters = word.chars().collect::<Vec<char>>();
+            let bytes = word.as_bytes();
+            assert_eq!(measure(&letters), measure(bytes), "measuring {}", word);
+            assert_eq!(contains_porter_vowel(&letters), contains_porter_vowel(bytes), "checking {}", word);
+            if word.is_ascii() {
+                assert_eq!(vowels.measure(&letters[..]), vowels.measure(bytes), "measuring {}", word);
+            }
+        }
+
+        assert!(ends_with(&b"hopeful"[..], "ful"));
+        assert!(!ends_with(&b"ul"[..], "ful"));
+    }
+
+    #[test]
+    fn test_real_consonant() {
+        assert!(!real_consonant("a"));
+        assert!(!real_consonant("e"));
+        assert!(!real_consonant("i"));
+        assert!(!real_consonant("o"));
+        assert!(!real_consonant("u"));
+        assert!(real_consonant("b"));
+    }
+
+    #[test]
+    fn test_letter() {
+        assert!("s".is(b's'));
+        assert!(!"ss".is(b's'));
+        assert_eq!(None, "é".to_ascii());
+        assert_eq!("e", <&str as Letter>::from_ascii(b'e'));
+        assert_eq!(b'l', <u8 as Letter>::from_ascii(b'l'));
+        assert_eq!(Some(b'y'), 'y'.to_ascii());
+        assert_eq!(None, 'ï'.to_ascii());
+    }
+
+    #[test]
+    fn test_ends_with() {
+        let graphemes = tokenise("caresses");
+        assert!(ends_with(&graphemes, "sses"));
+        assert!(ends_with(&graphemes, ""));
+        assert!(!ends_with(&graphemes, "ies"));
+        assert!(!ends_with(&graphemes, "scaresses"));
+        assert!(ends_with(b"ponies", "ies"));
+    }
+
+    #[test]
+    fn test_vowels() {
+        let vowels = Vowels::new().with_vowel("ï").with_vowel("ï");
+        assert!(vowels.is_vowel("ï"));
+        assert!(vowels.is_vowel('ï'));
+        assert!(vowels.is_vowel("a"));
+        assert!(!vowels.is_vowel("é"));
+        assert!(!Vowels::new().is_vowel("ï"));
+        assert_eq!(vowels, Vowels::new().with_vowel("ï"));
+
+        let graphemes = tokenise("naïv");
+        assert!(!contains_porter_vowel(&graphemes[2..]));
+        assert!(vowels.contains_porter_vowel(&graphemes[2..]));
+        assert_eq!(1, measure(&graphemes));
+        assert_eq!(1, vowels.measure(&graphemes));
+        assert!(!vowels.ends_star_o(&graphemes));
+
+        let graphemes = tokenise("gïl");
+        assert!(!ends_star_o(&graphemes));
+        assert!(vowels.ends_star_o(&graphemes));
+        assert!(Vowels::accented().ends_star_o(&graphemes));
+    }
+
+    #[test]
+    fn test_porter_character_types() {
+        let graphemes = tokenise("toy");
+
+        assert!(porter_consonant(&graphemes, 0));
+        assert!(porter_vowel(&graphemes, 1));
+        assert!(porter_consonant(&graphemes, 2));
+
+        let graphemes = tokenise("syzygy");
+        assert!(porter_consonant(&graphemes, 0));
+        assert!(porter_vowel(&graphemes, 1));
+        assert!(porter_consonant(&graphemes, 2));
+        assert!(porter_vowel(&graphemes, 3));
+        assert!(porter_consonant(&graphemes, 4));
+        assert!(porter_vowel(&graphemes, 5));
+    }
+
+    #[test]
+    fn test_ends_double_porters_consonant() {
+    let graphemes = tokenise("sell");
+    assert!(ends_double_porters_consonant(&graphemes));
+
+    let graphemes = tokenise("greyy");
+    assert!(!ends_double_porters_consonant(&graphemes));
+
+    let graphemes = tokenise("see");
+    assert!(!ends_double_porters_consonant(&graphemes));
+    }
+
+    #[test]
+    fn test_contains_vowel() {
+        let graphemes = tokenise("toy");
+        assert!(contains_porter_vowel(&graphemes));
+
+        let graphemes = tokenise("syzygy");
+        assert!(contains_porter_vowel(&graphemes));
+
+        let graphemes = tokenise("trjk");
+        assert!(!contains_porter_vowel(&graphemes));
+    }
+
+    #[test]
+    fn test_ends_star_o() {
+        let graphemes = tokenise("awhil");
+        assert!(ends_star_o(&graphemes));
+
+        let graphemes = tokenise("mix");
+        assert!(!ends_star_o(&graphemes));
+
+        let graphemes = tokenise("dew");
+        assert!(!ends_star_o(&graphemes));
+
+        let graphemes = tokenise("day");
+        assert!(!ends_star_o(&graphemes));
+    }
+
+    #[test]
+    fn test_measure() {
+        let graphemes = tokenise("crepuscular");
+        assert_eq!(4, measure(&graphemes[..]));
+
+        let graphemes = tokenise("bacon");
+        assert_eq!(2, measure(&graphemes[..]));
+
+        let graphemes = tokenise("abacus");
+        assert_eq!(3, measure(&graphemes[..]));
+
+
+        let graphemes = tokenise("paackkeeer");
+        assert_eq!(2, measure(&graphemes[..]));
+
+        let graphemes = tokenise("syzygy");
+        assert_eq!(2, measure(&graphemes[..]));
+
+    }
+}
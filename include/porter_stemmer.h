@@ -0,0 +1,124 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#ifndef PORTER_STEMMER_H
+#define PORTER_STEMMER_H
+
+/* Generated from src/ffi.rs by cbindgen. Don't edit by hand. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * Returned instead of a length when a word is null or isn't UTF-8.
+ */
+#define PORTER_STEM_ERROR SIZE_MAX
+
+/**
+ * A stemmer chosen by name, for `porter_stemmer_stem`.
+ *
+ * A stemmer may be used from several threads at once.
+ */
+typedef struct PorterStemmer PorterStemmer;
+
+#ifdef __cplusplus
+extern "C" {
+#endif  // __cplusplus
+
+/**
+ * Create a stemmer for the algorithm or language `name`, as listed by
+ * `porter-stemmer --list`, or Porter's algorithm if `name` is null.
+ *
+ * Returns null if there is no such algorithm or language. The stemmer must
+ * be freed with `porter_stemmer_free`.
+ *
+ * # Safety
+ *
+ * `name` must be null or point to a NUL-terminated string.
+ */
+PorterStemmer *porter_stemmer_new(const char *name);
+
+/**
+ * Free a stemmer created by `porter_stemmer_new`. Does nothing if
+ * `stemmer` is null.
+ *
+ * # Safety
+ *
+ * `stemmer` must be null or a stemmer from `porter_stemmer_new` that
+ * hasn't already been freed, and mustn't be used afterwards.
+ */
+void porter_stemmer_free(PorterStemmer *stemmer);
+
+/**
+ * Stem `word` with `stemmer`, or Porter's algorithm if it's null, writing
+ * the stem and a terminating NUL to `out`.
+ *
+ * Returns the length of the stem, without its NUL. Like `snprintf`, if
+ * that isn't less than `cap` then nothing is written, and the call can be
+ * repeated with a big enough buffer. Returns `PORTER_STEM_ERROR` if `word`
+ * is null or isn't UTF-8.
+ *
+ * # Safety
+ *
+ * `stemmer` must be null or a live stemmer from `porter_stemmer_new`,
+ * `word` must be null or point to a NUL-terminated string, and `out` must
+ * be null or point to at least `cap` writable bytes.
+ */
+size_t porter_stemmer_stem(const PorterStemmer *stemmer, const char *word, char *out, size_t cap);
+
+/**
+ * Stem each of the `count` words in `words` with `stemmer`, or Porter's
+ * algorithm if it's null, writing the stems to `out` one after another,
+ * each followed by a NUL.
+ *
+ * If `offsets` isn't null, the position in `out` of each stem is written
+ * to it, so `out + offsets[i]` is the `i`th stem.
+ *
+ * Returns the number of bytes the stems take up, including their NULs. If
+ * that's more than `cap` then nothing is written. Returns
+ * `PORTER_STEM_ERROR` if any word is null or isn't UTF-8.
+ *
+ * # Safety
+ *
+ * `stemmer` must be null or a live stemmer from `porter_stemmer_new`,
+ * `words` must point to `count` pointers to NUL-terminated strings, `out`
+ * must be null or point to at least `cap` writable bytes, and `offsets`
+ * must be null or point to room for `count` offsets.
+ */
+size_t porter_stemmer_stem_batch(const PorterStemmer *stemmer,
+                                 const char *const *words,
+                                 size_t count,
+                                 char *out,
+                                 size_t cap,
+                                 size_t *offsets);
+
+/**
+ * Stem `word` with Porter's algorithm, as `porter_stemmer_stem` does with
+ * a null stemmer.
+ *
+ * # Safety
+ *
+ * As for `porter_stemmer_stem`.
+ */
+size_t porter_stem(const char *word, char *out, size_t cap);
+
+/**
+ * Stem each of `words` with Porter's algorithm, as
+ * `porter_stemmer_stem_batch` does with a null stemmer.
+ *
+ * # Safety
+ *
+ * As for `porter_stemmer_stem_batch`.
+ */
+size_t porter_stem_batch(const char *const *words,
+                         size_t count,
+                         char *out,
+                         size_t cap,
+                         size_t *offsets);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* PORTER_STEMMER_H */
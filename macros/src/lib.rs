@@ -0,0 +1,66 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The `stem!` macro, re-exported by `porter-stemmer` behind its `macros`
+//! feature.
+
+extern crate porter_stemmer_core;
+extern crate proc_macro;
+extern crate unicode_segmentation;
+
+use proc_macro::{Literal, TokenStream, TokenTree};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use porter_stemmer_core::porter;
+
+/// Stem a string literal at compile time, expanding to the stemmed string
+/// literal.
+///
+/// Because the result is a literal it can be used anywhere a literal can,
+/// including as a pattern in `match` arms.
+///
+/// ```ignore
+/// match keyword {
+///     stem!("connections") => connect(),
+///     stem!("running") => run(),
+///     _ => {},
+/// }
+/// ```
+#[proc_macro]
+pub fn stem(input: TokenStream) -> TokenStream {
+    let mut tokens = input.into_iter();
+
+    let word = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Literal(literal)), None) => string_literal(&literal),
+        _ => None,
+    };
+
+    match word {
+        Some(word) => {
+            let graphemes = word.graphemes(true).collect::<Vec<&str>>();
+            let stemmed = porter::stem_tokenized(graphemes).concat();
+            TokenTree::Literal(Literal::string(&stemmed)).into()
+        },
+        None => {
+            "compile_error!(\"stem! expects a single string literal without escapes\")"
+                .parse()
+                .unwrap()
+        },
+    }
+}
+
+/// The contents of a plain `"..."` string literal without any escapes.
+fn string_literal(literal: &Literal) -> Option<String> {
+    let literal = literal.to_string();
+
+    if literal.len() >= 2 && literal.starts_with('"') && literal.ends_with('"') {
+        let contents = &literal[1..literal.len() - 1];
+        if !contents.contains('\\') {
+            return Some(contents.to_string());
+        }
+    }
+
+    None
+}
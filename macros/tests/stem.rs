@@ -0,0 +1,15 @@
+#[macro_use]
+extern crate porter_stemmer_macros;
+
+#[test]
+fn test_stem() {
+    assert_eq!("run", stem!("running"));
+    assert_eq!("relat", stem!("relational"));
+    assert_eq!("a", stem!("a"));
+    assert_eq!("", stem!(""));
+}
+
+#[test]
+fn test_stem_as_pattern() {
+    assert!(matches!("connect", stem!("connections")));
+}
@@ -0,0 +1,45 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+"""Stem Polars string columns with porter-stemmer.
+
+Importing the package adds a ``stemmer`` namespace to expressions:
+
+>>> df.with_columns(stem=pl.col("word").stemmer.porter_stem())
+"""
+
+from pathlib import Path
+
+import polars as pl
+from polars.plugins import register_plugin_function
+
+__all__ = ["porter_stem"]
+
+_PLUGIN_PATH = Path(__file__).parent
+
+
+def porter_stem(expr, stemmer=None):
+    """Stem each string of ``expr``, a column name or string expression,
+    keeping nulls as they are.
+
+    ``stemmer`` names the algorithm or language to stem with, as
+    ``porter-stemmer --list`` lists them; Porter's algorithm if ``None``.
+    """
+    return register_plugin_function(
+        plugin_path=_PLUGIN_PATH,
+        function_name="porter_stem",
+        args=expr,
+        kwargs={"stemmer": stemmer},
+        is_elementwise=True,
+    )
+
+
+@pl.api.register_expr_namespace("stemmer")
+class StemmerNamespace:
+    def __init__(self, expr):
+        self._expr = expr
+
+    def porter_stem(self, stemmer=None):
+        """Stem each string, as ``porter_stem`` does."""
+        return porter_stem(self._expr, stemmer)
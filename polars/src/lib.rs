@@ -0,0 +1,82 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! A Polars plugin stemming string columns with porter-stemmer.
+//!
+//! The `polars_porter_stemmer` Python package registers `porter_stem` as an
+//! expression, so stemming runs inside Polars' query plans rather than
+//! a Python function a row at a time:
+//!
+//! ```python
+//! import polars as pl
+//! import polars_porter_stemmer
+//!
+//! df.with_columns(stem=pl.col("word").stemmer.porter_stem())
+//! df.with_columns(stem=pl.col("wort").stemmer.porter_stem("german"))
+//! ```
+
+use polars::prelude::*;
+use porter_stemmer::{Algorithm, Language, Stemmer};
+use pyo3::prelude::*;
+use pyo3_polars::derive::polars_expr;
+use serde::Deserialize;
+
+/// The arguments of `porter_stem`.
+#[derive(Deserialize)]
+struct StemArguments {
+    /// The algorithm or language to stem with, or Porter's algorithm if
+    /// `None`.
+    stemmer: Option<String>,
+}
+
+/// The stemmer named `name`, or Porter's algorithm if there's no name.
+fn stemmer(name: Option<&str>) -> PolarsResult<Box<dyn Stemmer>> {
+    let name = match name {
+        Some(name) => name,
+        None => return Ok(Box::new(Algorithm::Porter)),
+    };
+
+    if let Ok(algorithm) = name.parse::<Algorithm>() {
+        Ok(Box::new(algorithm))
+    } else if let Ok(language) = name.parse::<Language>() {
+        Ok(Box::new(language))
+    } else {
+        Err(polars_err!(InvalidOperation: "unknown stemming algorithm or language: {}", name))
+    }
+}
+
+/// Stem each string in `words` with `stemmer`, keeping nulls where they are.
+fn stem_strings(stemmer: &dyn Stemmer, words: &StringChunked) -> StringChunked {
+    words.apply_into_string_amortized(|word, stem| stem.push_str(&stemmer.stem(word)))
+}
+
+#[polars_expr(output_type = String)]
+fn porter_stem(inputs: &[Series], kwargs: StemArguments) -> PolarsResult<Series> {
+    let stemmer = stemmer(kwargs.stemmer.as_deref())?;
+    Ok(stem_strings(&*stemmer, inputs[0].str()?).into_series())
+}
+
+/// The extension module Python imports, whose library holds the expressions.
+#[pymodule]
+fn _internal(_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem_strings() {
+        let words = StringChunked::from_slice_options("word".into(), &[Some("Hopefulness"), None, Some("ponies")]);
+        let stems = stem_strings(&*stemmer(None).unwrap(), &words);
+        assert_eq!(vec![Some("Hope"), None, Some("poni")], stems.into_iter().collect::<Vec<_>>());
+
+        let words = StringChunked::from_slice("wort".into(), &["Häuser", "Katzen"]);
+        let stems = stem_strings(&*stemmer(Some("german")).unwrap(), &words);
+        assert_eq!(vec![Some("Haus"), Some("Katz")], stems.into_iter().collect::<Vec<_>>());
+
+        assert!(stemmer(Some("klingon")).is_err());
+    }
+}
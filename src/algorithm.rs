@@ -0,0 +1,251 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Selecting a stemming algorithm by name.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use dawson;
+use krovetz::Krovetz;
+use lovins;
+use porter::Porter;
+use porter2;
+use truncate::{self, Truncate};
+use uea_lite;
+use Profile;
+
+/// A stemming algorithm, for code that wants to be generic over which one
+/// it uses.
+///
+/// # Examples
+///
+/// ```
+/// use porter_stemmer::{Algorithm, Stemmer};
+///
+/// fn stem_all(stemmer: &dyn Stemmer, words: &[&str]) -> Vec<String> {
+///     words.iter().map(|word| stemmer.stem(word)).collect()
+/// }
+///
+/// assert_eq!(vec!["hope", "hope"], stem_all(&Algorithm::Porter, &["hopeful", "hopes"]));
+/// assert_eq!(vec!["hop", "hop"], stem_all(&Algorithm::Lovins, &["hopeful", "hopes"]));
+/// ```
+pub trait Stemmer {
+    /// Given a word, return its stemmed form.
+    fn stem(&self, word: &str) -> String;
+}
+
+/// The stemming algorithms provided by this crate.
+///
+/// Algorithms can be parsed from, and displayed as, their names, so command
+/// line tools and configuration files can refer to them directly.
+///
+/// # Examples
+///
+/// ```
+/// use porter_stemmer::Algorithm;
+///
+/// let algorithm: Algorithm = "porter".parse().unwrap();
+/// assert_eq!(Algorithm::Porter, algorithm);
+/// assert_eq!("porter", algorithm.to_string());
+/// assert_eq!("hope", algorithm.stem("hopefulness"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    /// Porter's original 1980 algorithm, as used by `stem`.
+    #[default]
+    Porter,
+    /// Martin Porter's revised algorithm, also known as the Snowball English
+    /// stemmer. See the `porter2` module.
+    Porter2,
+    /// Julie Beth Lovins' 1968 algorithm. See the `lovins` module.
+    Lovins,
+    /// Robert Krovetz's dictionary-assisted algorithm, with the built-in
+    /// dictionary. See the `krovetz` module.
+    Krovetz,
+    /// Jenkins and Smith's conservative UEA-Lite algorithm. See the
+    /// `uea_lite` module.
+    UeaLite,
+    /// John Dawson's extension of the Lovins algorithm. See the `dawson`
+    /// module.
+    Dawson,
+    /// Truncation to the first five letters, as a baseline. See the
+    /// `truncate` module.
+    Truncate,
+}
+
+const ALGORITHMS: &[Algorithm] = &[
+    Algorithm::Porter,
+    Algorithm::Porter2,
+    Algorithm::Lovins,
+    Algorithm::Krovetz,
+    Algorithm::UeaLite,
+    Algorithm::Dawson,
+    Algorithm::Truncate,
+];
+
+impl Algorithm {
+    /// Every available algorithm.
+    pub fn all() -> &'static [Algorithm] {
+        ALGORITHMS
+    }
+
+    /// The name the algorithm is parsed from and displayed as.
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Porter => "porter",
+            Algorithm::Porter2 => "porter2",
+            Algorithm::Lovins => "lovins",
+            Algorithm::Krovetz => "krovetz",
+            Algorithm::UeaLite => "uea-lite",
+            Algorithm::Dawson => "dawson",
+            Algorithm::Truncate => "truncate",
+        }
+    }
+
+    /// Given a word, return its stemmed form using this algorithm.
+    pub fn stem(self, word: &str) -> String {
+        match self {
+            Algorithm::Porter => Porter::new().stem(word),
+            Algorithm::Porter2 => porter2::stem(word),
+            Algorithm::Lovins => lovins::stem(word),
+            Algorithm::Krovetz => Krovetz::new().stem(word),
+            Algorithm::UeaLite => uea_lite::stem(word),
+            Algorithm::Dawson => dawson::stem(word),
+            Algorithm::Truncate => truncate::stem(word),
+        }
+    }
+}
+
+impl Stemmer for Algorithm {
+    fn stem(&self, word: &str) -> String {
+        Algorithm::stem(*self, word)
+    }
+}
+
+impl Stemmer for Krovetz {
+    fn stem(&self, word: &str) -> String {
+        Krovetz::stem(self, word)
+    }
+}
+
+impl Stemmer for Porter {
+    fn stem(&self, word: &str) -> String {
+        Porter::stem(self, word)
+    }
+}
+
+impl Stemmer for Profile {
+    fn stem(&self, word: &str) -> String {
+        Profile::stem(*self, word)
+    }
+}
+
+impl Stemmer for Truncate {
+    fn stem(&self, word: &str) -> String {
+        Truncate::stem(self, word)
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = ParseAlgorithmError;
+
+    /// Parse an algorithm from its name, ignoring case.
+    fn from_str(name: &str) -> Result<Algorithm, ParseAlgorithmError> {
+        ALGORITHMS.iter()
+            .find(|algorithm| algorithm.name().eq_ignore_ascii_case(name))
+            .cloned()
+            .ok_or_else(|| ParseAlgorithmError { name: name.to_string() })
+    }
+}
+
+/// The error returned when parsing an unknown `Algorithm` name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseAlgorithmError {
+    name: String,
+}
+
+impl fmt::Display for ParseAlgorithmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown stemming algorithm `{}`, expected one of: ", self.name)?;
+
+        for (index, algorithm) in ALGORITHMS.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(algorithm.name())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for ParseAlgorithmError {}
+
+#[cfg(feature = "clap")]
+impl ::clap::ValueEnum for Algorithm {
+    fn value_variants<'a>() -> &'a [Algorithm] {
+        ALGORITHMS
+    }
+
+    fn to_possible_value(&self) -> Option<::clap::builder::PossibleValue> {
+        Some(::clap::builder::PossibleValue::new(self.name()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for &algorithm in Algorithm::all() {
+            assert_eq!(Ok(algorithm), algorithm.to_string().parse());
+        }
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Ok(Algorithm::Porter), "Porter".parse());
+        assert_eq!(Ok(Algorithm::Porter), "PORTER".parse::<Algorithm>());
+        assert_eq!(Ok(Algorithm::UeaLite), "UEA-Lite".parse());
+
+        let error = "porter9".parse::<Algorithm>().unwrap_err();
+        assert_eq!("unknown stemming algorithm `porter9`, expected one of: porter, porter2, lovins, krovetz, uea-lite, dawson, truncate", error.to_string());
+    }
+
+    #[test]
+    fn test_stem() {
+        assert_eq!("relat", Algorithm::Porter.stem("relational"));
+        assert_eq!("relat", Algorithm::Porter2.stem("relational"));
+        assert_eq!("gener", Algorithm::Porter.stem("generally"));
+        assert_eq!("general", Algorithm::Porter2.stem("generally"));
+        assert_eq!("nat", Algorithm::Lovins.stem("nationally"));
+        assert_eq!("memorize", Algorithm::Krovetz.stem("memorizing"));
+        assert_eq!("relational", Algorithm::UeaLite.stem("relational"));
+        assert_eq!("rel", Algorithm::Dawson.stem("relations"));
+        assert_eq!("relat", Algorithm::Truncate.stem("relations"));
+        assert_eq!(Algorithm::Porter, Algorithm::default());
+    }
+
+    #[test]
+    fn test_stemmer() {
+        let stemmers: Vec<Box<dyn Stemmer>> = vec![
+            Box::new(Algorithm::Porter2),
+            Box::new(Porter::new()),
+            Box::new(Truncate::default()),
+        ];
+
+        for stemmer in stemmers.iter() {
+            assert_eq!("relat", stemmer.stem("relational"));
+        }
+    }
+}
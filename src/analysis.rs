@@ -0,0 +1,173 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Turning text into the terms a search index stores.
+//!
+//! An `Analyzer` splits text into words, then passes each through its
+//! stages in the order they were added: lowercasing, dropping stopwords,
+//! stemming, or any other filter. The terms that come out keep where their
+//! words were in the text.
+//!
+//! ```
+//! use porter_stemmer::analysis::Analyzer;
+//!
+//! let analyzer = Analyzer::english();
+//! let terms = analyzer.analyze("The ponies ran to the stables").collect::<Vec<_>>();
+//!
+//! assert_eq!(vec!["poni", "ran", "stabl"], terms.iter().map(|term| &term.text[..]).collect::<Vec<_>>());
+//! assert_eq!(4..10, terms[0].byte_range);
+//! assert_eq!(5, terms[2].position);
+//! ```
+
+use std::collections::HashSet;
+use std::ops::Range;
+
+use algorithm::{Algorithm, Stemmer};
+use tokenizer::tokenize;
+
+/// Lucene's English stopwords, as used by `Analyzer::english`.
+pub const ENGLISH_STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "for", "if", "in", "into", "is", "it", "no", "not", "of",
+    "on", "or", "such", "that", "the", "their", "then", "there", "these", "they", "this", "to", "was", "will", "with",
+];
+
+/// A term produced by an `Analyzer`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Term {
+    /// The word, as the stages left it.
+    pub text: String,
+    /// Where the word was in the text.
+    pub byte_range: Range<usize>,
+    /// The number of words before it in the text, including any that were
+    /// dropped, so phrase queries don't match across a stopword.
+    pub position: usize,
+}
+
+impl AsRef<str> for Term {
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+enum Stage {
+    Lowercase,
+    Stopwords(HashSet<String>),
+    Stem(Box<dyn Stemmer + Send + Sync>),
+    Filter(Box<dyn Fn(String) -> Option<String> + Send + Sync>),
+}
+
+impl Stage {
+    /// What this stage makes of `word`, or `None` if it drops it.
+    fn apply(&self, word: String) -> Option<String> {
+        match *self {
+            Stage::Lowercase => Some(word.to_lowercase()),
+            Stage::Stopwords(ref stopwords) if stopwords.contains(&word) => None,
+            Stage::Stopwords(_) => Some(word),
+            Stage::Stem(ref stemmer) => Some(stemmer.stem(&word)),
+            Stage::Filter(ref filter) => filter(word),
+        }
+    }
+}
+
+/// Splits text into words and passes them through a chain of stages.
+#[derive(Default)]
+pub struct Analyzer {
+    stages: Vec<Stage>,
+}
+
+impl Analyzer {
+    /// An analyzer that only splits text into words, to add stages to.
+    pub fn new() -> Analyzer {
+        Analyzer::default()
+    }
+
+    /// An analyzer that lowercases words, drops `ENGLISH_STOPWORDS` and
+    /// stems the rest with Porter's algorithm.
+    pub fn english() -> Analyzer {
+        Analyzer::new()
+            .lowercase()
+            .stopwords(ENGLISH_STOPWORDS.iter().cloned())
+            .stem(Algorithm::Porter)
+    }
+
+    /// Lowercase each word.
+    pub fn lowercase(mut self) -> Analyzer {
+        self.stages.push(Stage::Lowercase);
+        self
+    }
+
+    /// Drop each word that's one of `stopwords`. Words are compared as the
+    /// stages before have left them, so a list of lowercase stopwords
+    /// should come after `lowercase`.
+    pub fn stopwords<I, S>(mut self, stopwords: I) -> Analyzer
+        where I: IntoIterator<Item = S>, S: Into<String>
+    {
+        self.stages.push(Stage::Stopwords(stopwords.into_iter().map(Into::into).collect()));
+        self
+    }
+
+    /// Stem each word with `stemmer`.
+    pub fn stem<S: Stemmer + Send + Sync + 'static>(mut self, stemmer: S) -> Analyzer {
+        self.stages.push(Stage::Stem(Box::new(stemmer)));
+        self
+    }
+
+    /// Replace each word with what `filter` returns for it, dropping it if
+    /// that's `None`.
+    pub fn filter<F>(mut self, filter: F) -> Analyzer
+        where F: Fn(String) -> Option<String> + Send + Sync + 'static
+    {
+        self.stages.push(Stage::Filter(Box::new(filter)));
+        self
+    }
+
+    /// The terms of `text`, in the order their words appear.
+    pub fn analyze<'a>(&'a self, text: &'a str) -> impl Iterator<Item = Term> + 'a {
+        tokenize(text).filter_map(move |token| {
+            let text = self.stages.iter().try_fold(token.text.to_string(), |word, stage| stage.apply(word))?;
+            Some(Term { text, byte_range: token.byte_range, position: token.position })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use language::Language;
+
+    fn texts(analyzer: &Analyzer, text: &str) -> Vec<String> {
+        analyzer.analyze(text).map(|term| term.text).collect()
+    }
+
+    #[test]
+    fn test_english() {
+        let terms = Analyzer::english().analyze("It's the Hopeful ponies, not THE horses.").collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                Term { text: "it'".to_string(), byte_range: 0..4, position: 0 },
+                Term { text: "hope".to_string(), byte_range: 9..16, position: 2 },
+                Term { text: "poni".to_string(), byte_range: 17..23, position: 3 },
+                Term { text: "hors".to_string(), byte_range: 33..39, position: 6 },
+            ],
+            terms,
+        );
+    }
+
+    #[test]
+    fn test_stages() {
+        // Without lowercasing first, capitalized stopwords are kept.
+        let analyzer = Analyzer::new().stopwords(vec!["the"]).lowercase();
+        assert_eq!(vec!["the", "cat"], texts(&analyzer, "The cat"));
+
+        let analyzer = Analyzer::new().lowercase().stem(Language::German);
+        assert_eq!(vec!["haus", "katz"], texts(&analyzer, "Häuser, Katzen"));
+
+        let analyzer = Analyzer::new().filter(|word| if word.len() > 2 { Some(word) } else { None });
+        assert_eq!(vec!["one", "three"], texts(&analyzer, "one to three"));
+
+        assert_eq!(vec!["Ponies", "ran"], texts(&Analyzer::new(), "Ponies ran."));
+    }
+}
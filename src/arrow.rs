@@ -0,0 +1,99 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Stemming Apache Arrow string columns.
+//!
+//! Requires the `arrow` feature.
+//!
+//! ```
+//! extern crate arrow_array;
+//! extern crate porter_stemmer;
+//!
+//! use arrow_array::StringArray;
+//! use porter_stemmer::arrow::stem_array;
+//!
+//! # fn main() {
+//! let words = StringArray::from(vec![Some("connected"), None, Some("ponies")]);
+//!
+//! assert_eq!(StringArray::from(vec![Some("connect"), None, Some("poni")]), stem_array(&words));
+//! # }
+//! ```
+
+use arrow_array::builder::GenericStringBuilder;
+use arrow_array::{Array, GenericStringArray, OffsetSizeTrait};
+
+use algorithm::Stemmer;
+use batch::WordBatch;
+use porter::Porter;
+
+/// Stem each string in `array` with Porter's algorithm, as `stem` does,
+/// keeping nulls where they are.
+///
+/// The strings are stemmed together as a `WordBatch`, a step at a time, as
+/// they're packed in the array.
+pub fn stem_array<O: OffsetSizeTrait>(array: &GenericStringArray<O>) -> GenericStringArray<O> {
+    let mut batch = WordBatch::with_capacity(array.len(), array.value_data().len());
+    batch.extend(array.iter().map(|value| value.unwrap_or("")));
+    Porter::new().stem_batch(&mut batch);
+
+    let mut stems = GenericStringBuilder::<O>::with_capacity(array.len(), array.value_data().len());
+    for (index, stem) in batch.iter().enumerate() {
+        if array.is_null(index) {
+            stems.append_null();
+        } else {
+            stems.append_value(stem);
+        }
+    }
+
+    stems.finish()
+}
+
+/// Stem each string in `array` with `stemmer`, keeping nulls where they
+/// are.
+pub fn stem_array_with<O: OffsetSizeTrait>(stemmer: &dyn Stemmer, array: &GenericStringArray<O>) -> GenericStringArray<O> {
+    let mut stems = GenericStringBuilder::<O>::with_capacity(array.len(), array.value_data().len());
+    for value in array.iter() {
+        match value {
+            Some(word) => stems.append_value(stemmer.stem(word)),
+            None => stems.append_null(),
+        }
+    }
+
+    stems.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow_array::{LargeStringArray, StringArray};
+
+    use algorithm::Algorithm;
+    use language::Language;
+
+    #[test]
+    fn test_stem_array() {
+        let words = StringArray::from(vec![Some("Hopefulness"), None, Some(""), Some("naïvely"), Some("ponies")]);
+        let stems = stem_array(&words);
+
+        assert_eq!(5, stems.len());
+        assert_eq!(1, stems.null_count());
+        assert!(stems.is_null(1));
+        for (word, stem) in words.iter().zip(stems.iter()) {
+            assert_eq!(word.map(::stem), stem.map(String::from));
+        }
+
+        let large = LargeStringArray::from(vec!["connected", "connecting"]);
+        assert_eq!(LargeStringArray::from(vec!["connect", "connect"]), stem_array(&large));
+    }
+
+    #[test]
+    fn test_stem_array_with() {
+        let words = StringArray::from(vec![Some("Häuser"), None, Some("Katzen")]);
+        assert_eq!(StringArray::from(vec![Some("Haus"), None, Some("Katz")]), stem_array_with(&Language::German, &words));
+
+        let words = StringArray::from(vec!["hopeful"]);
+        assert_eq!(StringArray::from(vec!["hop"]), stem_array_with(&Algorithm::Lovins, &words));
+    }
+}
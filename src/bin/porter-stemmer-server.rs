@@ -0,0 +1,236 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Stem words over HTTP, for services that can't link to Rust.
+//!
+//! `POST /stem` takes a JSON object with one of `word`, a single word;
+//! `words`, an array of them; or `text`, to be split into words. It may also
+//! name a `language` or an `algorithm` to stem with, as `--list` lists them
+//! on the command line; otherwise words are stemmed with Porter's algorithm.
+//!
+//! ```text
+//! $ curl -d '{"word": "running"}' localhost:8080/stem
+//! {"stem":"run"}
+//! $ curl -d '{"words": ["Häuser", "Katzen"], "language": "german"}' localhost:8080/stem
+//! {"stems":["Haus","Katz"]}
+//! $ curl -d '{"text": "Ponies ran."}' localhost:8080/stem
+//! {"tokens":[{"end":6,"start":0,"stem":"Poni","token":"Ponies"},{"end":10,"start":7,"stem":"ran","token":"ran"}]}
+//! ```
+//!
+//! Failures are answered with an `error` message. Requires the `server`
+//! feature.
+
+extern crate porter_stemmer;
+#[macro_use]
+extern crate serde_json;
+extern crate tiny_http;
+extern crate unicode_segmentation;
+
+use std::env;
+use std::io::Read;
+use std::process;
+use std::sync::Arc;
+use std::thread;
+
+use serde_json::Value;
+use tiny_http::{Header, Method, Request, Response, Server};
+use unicode_segmentation::UnicodeSegmentation;
+
+use porter_stemmer::{Algorithm, Language, Stemmer};
+
+/// The address to listen on if none is given.
+const ADDRESS: &str = "127.0.0.1:8080";
+
+/// The largest request body accepted, in bytes.
+const MAX_BODY: usize = 16 << 20;
+
+/// A failed request, with the HTTP status to answer it with.
+#[derive(Debug, PartialEq)]
+struct Failure {
+    status: u16,
+    message: String,
+}
+
+impl Failure {
+    fn bad_request<S: Into<String>>(message: S) -> Failure {
+        Failure { status: 400, message: message.into() }
+    }
+}
+
+/// The stemmer a request asks for.
+fn stemmer(request: &Value) -> Result<Box<dyn Stemmer>, Failure> {
+    let name = |field: &str| match request.get(field) {
+        None | Some(&Value::Null) => Ok(None),
+        Some(name) => name.as_str().map(Some).ok_or_else(|| Failure::bad_request(format!("`{}` must be a string", field))),
+    };
+
+    match (name("language")?, name("algorithm")?) {
+        (Some(_), Some(_)) => Err(Failure::bad_request("only one of `language` and `algorithm` may be given")),
+        (Some(language), None) => {
+            let language = language.parse::<Language>().map_err(|error| Failure::bad_request(error.to_string()))?;
+            Ok(Box::new(language))
+        },
+        (None, Some(algorithm)) => {
+            let algorithm = algorithm.parse::<Algorithm>().map_err(|error| Failure::bad_request(error.to_string()))?;
+            Ok(Box::new(algorithm))
+        },
+        (None, None) => Ok(Box::new(Algorithm::Porter)),
+    }
+}
+
+/// Answer the JSON request `body` to `POST /stem`.
+fn stem(body: &str) -> Result<Value, Failure> {
+    let request = serde_json::from_str::<Value>(body).map_err(|error| Failure::bad_request(error.to_string()))?;
+    if !request.is_object() {
+        return Err(Failure::bad_request("expected a JSON object"));
+    }
+    let stemmer = stemmer(&request)?;
+
+    if let Some(word) = request.get("word") {
+        let word = word.as_str().ok_or_else(|| Failure::bad_request("`word` must be a string"))?;
+        return Ok(json!({ "stem": stemmer.stem(word) }));
+    }
+
+    if let Some(words) = request.get("words") {
+        let words = words.as_array()
+            .and_then(|words| words.iter().map(Value::as_str).collect::<Option<Vec<_>>>())
+            .ok_or_else(|| Failure::bad_request("`words` must be an array of strings"))?;
+        let stems = words.into_iter().map(|word| stemmer.stem(word)).collect::<Vec<_>>();
+        return Ok(json!({ "stems": stems }));
+    }
+
+    if let Some(text) = request.get("text") {
+        let text = text.as_str().ok_or_else(|| Failure::bad_request("`text` must be a string"))?;
+        let tokens = text.split_word_bound_indices()
+            .filter(|&(_, token)| token.chars().any(char::is_alphanumeric))
+            .map(|(start, token)| json!({
+                "token": token,
+                "stem": stemmer.stem(token),
+                "start": start,
+                "end": start + token.len(),
+            }))
+            .collect::<Vec<_>>();
+        return Ok(json!({ "tokens": tokens }));
+    }
+
+    Err(Failure::bad_request("expected one of `word`, `words` or `text`"))
+}
+
+/// Answer any request to the server.
+fn answer(method: &Method, url: &str, body: &mut dyn Read, length: Option<usize>) -> Result<Value, Failure> {
+    // Ignore any query string.
+    let path = url.split('?').next().unwrap_or(url);
+    if path != "/stem" {
+        return Err(Failure { status: 404, message: format!("no such endpoint {}, only /stem", path) });
+    }
+    if *method != Method::Post {
+        return Err(Failure { status: 405, message: "/stem only accepts POST".to_string() });
+    }
+    if length.is_some_and(|length| length > MAX_BODY) {
+        return Err(Failure { status: 413, message: format!("requests are limited to {} bytes", MAX_BODY) });
+    }
+
+    let mut text = String::new();
+    body.take(MAX_BODY as u64 + 1)
+        .read_to_string(&mut text)
+        .map_err(|error| Failure::bad_request(error.to_string()))?;
+    if text.len() > MAX_BODY {
+        return Err(Failure { status: 413, message: format!("requests are limited to {} bytes", MAX_BODY) });
+    }
+
+    stem(&text)
+}
+
+fn respond(mut request: Request) {
+    let (method, url, length) = (request.method().clone(), request.url().to_string(), request.body_length());
+    let (status, body) = match answer(&method, &url, request.as_reader(), length) {
+        Ok(body) => (200, body),
+        Err(failure) => (failure.status, json!({ "error": failure.message })),
+    };
+
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).expect("the header is valid");
+    let response = Response::from_string(body.to_string()).with_status_code(status).with_header(content_type);
+
+    if let Err(error) = request.respond(response) {
+        eprintln!("porter-stemmer-server: {}", error);
+    }
+}
+
+fn main() {
+    let address = env::args().nth(1).unwrap_or_else(|| ADDRESS.to_string());
+    let server = match Server::http(&address) {
+        Ok(server) => Arc::new(server),
+        Err(error) => {
+            eprintln!("porter-stemmer-server: {}: {}", address, error);
+            process::exit(1);
+        },
+    };
+    eprintln!("porter-stemmer-server: listening on {}", address);
+
+    let threads = thread::available_parallelism().map(|threads| threads.get()).unwrap_or(1);
+    let workers = (0..threads)
+        .map(|_| {
+            let server = server.clone();
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    respond(request);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for worker in workers {
+        let _ = worker.join();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io;
+
+    #[test]
+    fn test_stem() {
+        assert_eq!(Ok(json!({ "stem": "run" })), stem(r#"{"word": "running"}"#));
+        assert_eq!(Ok(json!({ "stems": ["Haus", "Katz"] })), stem(r#"{"words": ["Häuser", "Katzen"], "language": "german"}"#));
+        assert_eq!(Ok(json!({ "stems": ["hop"] })), stem(r#"{"words": ["hopeful"], "algorithm": "lovins"}"#));
+        assert_eq!(
+            Ok(json!({ "tokens": [
+                { "token": "Ponies", "stem": "Poni", "start": 0, "end": 6 },
+                { "token": "ran", "stem": "ran", "start": 7, "end": 10 },
+            ] })),
+            stem(r#"{"text": "Ponies ran."}"#),
+        );
+    }
+
+    #[test]
+    fn test_stem_failures() {
+        let failure = |body: &str| stem(body).unwrap_err().message;
+
+        assert!(failure("not json").contains("expected"));
+        assert_eq!("expected a JSON object", failure(r#"["running"]"#));
+        assert_eq!("expected one of `word`, `words` or `text`", failure("{}"));
+        assert_eq!("`word` must be a string", failure(r#"{"word": 1}"#));
+        assert_eq!("`words` must be an array of strings", failure(r#"{"words": ["a", 1]}"#));
+        assert!(failure(r#"{"word": "a", "language": "klingon"}"#).starts_with("unknown language"));
+        assert_eq!(
+            "only one of `language` and `algorithm` may be given",
+            failure(r#"{"word": "a", "language": "german", "algorithm": "porter"}"#),
+        );
+    }
+
+    #[test]
+    fn test_answer() {
+        let answer = |method: Method, url: &str, body: &str| {
+            answer(&method, url, &mut io::Cursor::new(body.as_bytes()), Some(body.len())).map_err(|failure| failure.status)
+        };
+
+        assert_eq!(Ok(json!({ "stem": "poni" })), answer(Method::Post, "/stem?pretty", r#"{"word": "ponies"}"#));
+        assert_eq!(Err(400), answer(Method::Post, "/stem", "{"));
+        assert_eq!(Err(404), answer(Method::Post, "/", "{}"));
+        assert_eq!(Err(405), answer(Method::Get, "/stem", ""));
+        assert_eq!(Err(413), answer(Method::Post, "/stem", &" ".repeat(MAX_BODY + 1)));
+    }
+}
@@ -0,0 +1,138 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Checking stems against a reference, such as `input.txt` and
+//! `expected.txt`: a vocabulary of a word per line, and the stem of each
+//! word on the same line of the other file.
+
+use std::fmt;
+
+use porter_stemmer::Stemmer;
+
+/// How many mismatches to show examples of.
+const EXAMPLES: usize = 10;
+
+/// A word whose stem isn't the one expected.
+#[derive(Debug, PartialEq)]
+pub struct Mismatch {
+    /// The line of the word, counting from one.
+    pub line: usize,
+    pub word: String,
+    pub stem: String,
+    pub expected: String,
+}
+
+/// The stems of a vocabulary, compared with those expected.
+#[derive(Debug, PartialEq)]
+pub struct Evaluation {
+    /// The number of words in the vocabulary.
+    pub words: usize,
+    /// The number of stems expected.
+    pub expected: usize,
+    /// The number of words stemmed differently from expected.
+    pub mismatches: usize,
+    /// The first few of those words.
+    pub examples: Vec<Mismatch>,
+}
+
+impl Evaluation {
+    /// Stem each of `words` and compare the stem with the same line of
+    /// `expected`.
+    pub fn new<'a, I>(stemmer: &dyn Stemmer, words: I, expected: &str) -> Evaluation
+        where I: IntoIterator<Item = &'a str>
+    {
+        let mut expected_stems = expected.lines();
+        let mut evaluation = Evaluation {
+            words: 0,
+            expected: 0,
+            mismatches: 0,
+            examples: Vec::new(),
+        };
+
+        for (line, (word, expected)) in words.into_iter().zip(expected_stems.by_ref()).enumerate() {
+            let stem = stemmer.stem(word);
+            evaluation.words += 1;
+            evaluation.expected += 1;
+
+            if stem != expected {
+                evaluation.mismatches += 1;
+                if evaluation.examples.len() < EXAMPLES {
+                    evaluation.examples.push(Mismatch {
+                        line: line + 1,
+                        word: word.to_string(),
+                        stem,
+                        expected: expected.to_string(),
+                    });
+                }
+            }
+        }
+
+        evaluation.expected += expected_stems.count();
+        evaluation
+    }
+
+    /// Whether every word was stemmed as expected, and there were as many
+    /// stems expected as words.
+    pub fn passed(&self) -> bool {
+        self.mismatches == 0 && self.words == self.expected
+    }
+}
+
+impl fmt::Display for Evaluation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for mismatch in &self.examples {
+            writeln!(
+                f, "line {}: {} stemmed to {}, expected {}",
+                mismatch.line, mismatch.word, mismatch.stem, mismatch.expected,
+            )?;
+        }
+        if self.mismatches > self.examples.len() {
+            writeln!(f, "... and {} more", self.mismatches - self.examples.len())?;
+        }
+
+        if self.words != self.expected {
+            writeln!(f, "{} words, but {} stems expected", self.words, self.expected)?;
+        }
+        writeln!(f, "{} of {} words stemmed differently than expected", self.mismatches, self.words.min(self.expected))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use porter_stemmer::Algorithm;
+
+    #[test]
+    fn test_evaluation() {
+        let words = "hopeful\nponies\nrelational\n";
+
+        let passed = Evaluation::new(&Algorithm::Porter, words.lines(), "hope\nponi\nrelat\n");
+        assert!(passed.passed());
+        assert_eq!("0 of 3 words stemmed differently than expected\n", passed.to_string());
+
+        let failed = Evaluation::new(&Algorithm::Porter, words.lines(), "hope\npony\nrelate\nextra\n");
+        assert!(!failed.passed());
+        assert_eq!(4, failed.expected);
+        assert_eq!(2, failed.mismatches);
+        assert_eq!(Mismatch { line: 2, word: "ponies".into(), stem: "poni".into(), expected: "pony".into() }, failed.examples[0]);
+        assert_eq!(
+            "line 2: ponies stemmed to poni, expected pony\n\
+             line 3: relational stemmed to relat, expected relate\n\
+             3 words, but 4 stems expected\n\
+             2 of 3 words stemmed differently than expected\n",
+            failed.to_string(),
+        );
+    }
+
+    #[test]
+    fn test_examples() {
+        let words = "ponies\n".repeat(EXAMPLES + 2);
+        let evaluation = Evaluation::new(&Algorithm::Porter, words.lines(), &"pony\n".repeat(EXAMPLES + 2));
+
+        assert_eq!(EXAMPLES + 2, evaluation.mismatches);
+        assert_eq!(EXAMPLES, evaluation.examples.len());
+        assert!(evaluation.to_string().contains("\n... and 2 more\n"));
+    }
+}
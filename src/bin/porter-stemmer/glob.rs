@@ -0,0 +1,166 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Expanding file name patterns, for shells that don't, or for patterns
+//! matching more files than fit on a command line.
+//!
+//! In each component of a path, `*` matches any run of characters and `?`
+//! any one character. A component of just `**` matches any number of
+//! directories, including none.
+
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Whether `pattern` has any wildcards in it.
+pub fn is_pattern(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
+
+/// Whether the file name `name` matches the single component `pattern`.
+pub fn matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<char>>();
+    let name = name.chars().collect::<Vec<char>>();
+
+    // The positions just after the last `*`, in the pattern and the name,
+    // to go back to when the rest of the pattern doesn't match.
+    let mut star = None;
+    let (mut p, mut n) = (0, 0);
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                p += 1;
+                star = Some((p, n));
+            },
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            },
+            _ => match star {
+                Some((after_star, matched)) => {
+                    p = after_star;
+                    n = matched + 1;
+                    star = Some((after_star, matched + 1));
+                },
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// The files matching `pattern`, in sorted order.
+pub fn expand(pattern: &str) -> io::Result<Vec<PathBuf>> {
+    let mut paths = vec![PathBuf::new()];
+
+    for component in Path::new(pattern).components() {
+        let part = match component {
+            Component::Normal(part) => part.to_string_lossy(),
+            other => {
+                for path in paths.iter_mut() {
+                    path.push(other.as_os_str());
+                }
+                continue;
+            },
+        };
+
+        if part == "**" {
+            let mut directories = Vec::new();
+            for path in paths {
+                descendants(path, &mut directories)?;
+            }
+            paths = directories;
+        } else if is_pattern(&part) {
+            let mut matching = Vec::new();
+            for path in paths {
+                for name in entries(&path)? {
+                    if matches(&part, &name) {
+                        matching.push(path.join(name));
+                    }
+                }
+            }
+            paths = matching;
+        } else {
+            for path in paths.iter_mut() {
+                path.push(&*part);
+            }
+        }
+    }
+
+    paths.retain(|path| path.is_file());
+    paths.sort();
+    paths.dedup();
+    Ok(paths)
+}
+
+/// The names of the entries in `directory`, or none if it isn't one.
+fn entries(directory: &Path) -> io::Result<Vec<String>> {
+    let directory = if directory.as_os_str().is_empty() { Path::new(".") } else { directory };
+    if !directory.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut names = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let name = entry?.file_name().to_string_lossy().into_owned();
+        // As in shells, wildcards don't match hidden files.
+        if !name.starts_with('.') {
+            names.push(name);
+        }
+    }
+
+    Ok(names)
+}
+
+/// Add `directory` and every directory below it to `directories`.
+fn descendants(directory: PathBuf, directories: &mut Vec<PathBuf>) -> io::Result<()> {
+    for name in entries(&directory)? {
+        let path = directory.join(name);
+        if path.is_dir() {
+            descendants(path, directories)?;
+        }
+    }
+
+    directories.push(directory);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+
+    #[test]
+    fn test_matches() {
+        assert!(matches("*.txt", "input.txt"));
+        assert!(matches("*", ""));
+        assert!(matches("in?ut.*", "input.txt"));
+        assert!(matches("*a*b", "xaxxab"));
+        assert!(!matches("*.txt", "input.txt.stemmed"));
+        assert!(!matches("in?ut", "inut"));
+        assert!(!matches("", "a"));
+    }
+
+    #[test]
+    fn test_expand() {
+        let root = env::temp_dir().join(format!("porter-stemmer-glob-{}", std::process::id()));
+        fs::create_dir_all(root.join("a/b")).unwrap();
+        for file in ["one.txt", "a/two.txt", "a/b/three.txt", "a/b/four.md"].iter() {
+            fs::write(root.join(file), "").unwrap();
+        }
+
+        let pattern = |pattern: &str| root.join(pattern).to_string_lossy().into_owned();
+        let files = |files: &[&str]| files.iter().map(|file| root.join(file)).collect::<Vec<_>>();
+
+        assert_eq!(files(&["one.txt"]), expand(&pattern("*.txt")).unwrap());
+        assert_eq!(files(&["a/b/three.txt", "a/two.txt", "one.txt"]), expand(&pattern("**/*.txt")).unwrap());
+        assert_eq!(files(&["a/b/four.md"]), expand(&pattern("*/?/*.md")).unwrap());
+        assert!(expand(&pattern("missing/*")).unwrap().is_empty());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
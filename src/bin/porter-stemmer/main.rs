@@ -0,0 +1,904 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Stem text from the command line.
+//!
+//! With no files, stems standard input to standard output a line at a time.
+//! Otherwise stems each of the files given, several at once, writing each
+//! stemmed file alongside its input or into an output directory.
+//!
+//! The stems are written either as the text with each word replaced by its
+//! stem, or as a table of each word and its stem, in TSV or JSON lines.
+//!
+//! Words are stemmed with Porter's algorithm unless another algorithm, or the
+//! stemmer for another language, is chosen.
+//!
+//! It can write only the words that stemming changed, to check a stemmer on
+//! a new corpus, or rather than the stems themselves, count how often each
+//! stem occurs across all of its input, or list each different stem once.
+//!
+//! Finally, it can check a stemmer against a reference vocabulary and the
+//! stems expected of it.
+//!
+//! Progress through a long run can be reported on standard error.
+
+extern crate porter_stemmer;
+extern crate unicode_segmentation;
+
+mod eval;
+mod glob;
+mod progress;
+mod stats;
+
+use std::collections::HashMap;
+use std::env;
+use std::ffi::OsString;
+use std::fmt::Write as FmtWrite;
+use std::fs;
+use std::io::{self, BufRead, BufWriter, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::thread;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use porter_stemmer::{Algorithm, Language, Stemmer};
+
+use eval::Evaluation;
+use progress::Progress;
+use stats::Stats;
+
+const USAGE: &str = "\
+Usage: porter-stemmer [OPTIONS] [FILE]...
+
+Stems standard input to standard output, or each FILE to FILE.stemmed.
+A FILE may be a pattern, in which `*` and `?` match within a path
+component and `**` matches any number of directories.
+
+Options:
+  -a, --algorithm NAME  stem with the algorithm NAME [default: porter]
+  -l, --language NAME   stem with the stemmer for the language NAME
+      --list            list the algorithms and languages, then exit
+  -o, --output-dir DIR  write the stemmed files under DIR instead, at their
+                        paths relative to the current directory
+  -s, --suffix SUFFIX   the extension added to stemmed files written
+                        alongside their inputs [default: stemmed]
+  -j, --jobs N          stem N files at once [default: one per CPU]
+  -p, --progress        report progress, and how quickly words are stemmed,
+                        on standard error
+  -f, --format FORMAT   text, the input with its words stemmed; tsv, a
+                        table of each word and its stem; or json, an object
+                        per line for each word and its stem [default: text]
+      --offsets         add each word's start and end byte offsets to tsv
+                        or json output
+      --diff            write only the words stemming changes; as a line of
+                        each word and its stem, separated by a tab, for
+                        text, or as rows of the table for tsv or json
+      --stats           write how often each stem occurs in all the input
+                        to standard output instead, most frequent first
+      --unique          write each different stem in all the input once to
+                        standard output instead, in the order first seen
+      --sort            with --unique, write the stems in sorted order
+      --reduction       with --stats or --unique, also report on standard
+                        error how far stemming reduced the vocabulary
+      --eval EXPECTED   treat each line of the input as a word, and check
+                        its stem is the same line of the file EXPECTED,
+                        reporting any that aren't and failing if so
+  -h, --help            print this message
+";
+
+/// How to write the stems.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Format {
+    /// The input, with each word replaced by its stem.
+    Text,
+    /// A header, then a line of each word and its stem, separated by tabs.
+    Tsv,
+    /// A JSON object for each word and its stem, one per line.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Format, String> {
+        match format {
+            "text" => Ok(Format::Text),
+            "tsv" => Ok(Format::Tsv),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format {}, expected text, tsv or json", format)),
+        }
+    }
+}
+
+/// The stemmer chosen on the command line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Choice {
+    Algorithm(Algorithm),
+    Language(Language),
+}
+
+impl Stemmer for Choice {
+    fn stem(&self, word: &str) -> String {
+        match *self {
+            Choice::Algorithm(algorithm) => algorithm.stem(word),
+            Choice::Language(language) => language.stem(word),
+        }
+    }
+}
+
+/// The command line's options.
+#[derive(Debug, PartialEq)]
+struct Options {
+    files: Vec<String>,
+    output_dir: Option<PathBuf>,
+    suffix: String,
+    jobs: usize,
+    progress: bool,
+    format: Format,
+    offsets: bool,
+    stemmer: Choice,
+    list: bool,
+    diff: bool,
+    stats: bool,
+    unique: bool,
+    sort: bool,
+    reduction: bool,
+    eval: Option<PathBuf>,
+}
+
+impl Options {
+    /// Parse the command line arguments, less the program name.
+    ///
+    /// Returns `Ok(None)` if help was asked for.
+    fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Option<Options>, String> {
+        let mut options = Options {
+            files: Vec::new(),
+            output_dir: None,
+            suffix: "stemmed".to_string(),
+            jobs: thread::available_parallelism().map(|jobs| jobs.get()).unwrap_or(1),
+            progress: false,
+            format: Format::Text,
+            offsets: false,
+            stemmer: Choice::Algorithm(Algorithm::default()),
+            list: false,
+            diff: false,
+            stats: false,
+            unique: false,
+            sort: false,
+            reduction: false,
+            eval: None,
+        };
+        let mut chosen = None;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                options.files.extend(args);
+                break;
+            }
+            if !arg.starts_with('-') || arg == "-" {
+                options.files.push(arg);
+                continue;
+            }
+
+            // Long options may be given their value after an `=`.
+            let (name, value) = match arg.find('=') {
+                Some(equals) if arg.starts_with("--") => (&arg[..equals], Some(arg[equals + 1..].to_string())),
+                _ => (&arg[..], None),
+            };
+            let mut value = || value.clone().or_else(|| args.next()).ok_or_else(|| format!("{} needs a value", name));
+
+            match name {
+                "-h" | "--help" => return Ok(None),
+                "-o" | "--output-dir" => options.output_dir = Some(PathBuf::from(value()?)),
+                "-s" | "--suffix" => options.suffix = value()?,
+                "-j" | "--jobs" => {
+                    options.jobs = match value()?.parse() {
+                        Ok(jobs) if jobs > 0 => jobs,
+                        _ => return Err(format!("{} needs a positive number", name)),
+                    };
+                },
+                "-p" | "--progress" => options.progress = true,
+                "-f" | "--format" => options.format = value()?.parse()?,
+                "--offsets" => options.offsets = true,
+                "-a" | "--algorithm" | "-l" | "--language" => {
+                    if let Some(previous) = chosen.replace(name.to_string()) {
+                        return Err(format!("{} can't be given with {}", name, previous));
+                    }
+
+                    let value = value()?;
+                    options.stemmer = match name {
+                        "-a" | "--algorithm" => Choice::Algorithm(value.parse().map_err(|error| format!("{}", error))?),
+                        _ => Choice::Language(value.parse().map_err(|error| format!("{}", error))?),
+                    };
+                },
+                "--list" => options.list = true,
+                "--diff" => options.diff = true,
+                "--stats" => options.stats = true,
+                "--unique" => options.unique = true,
+                "--sort" => options.sort = true,
+                "--reduction" => options.reduction = true,
+                "--eval" => options.eval = Some(PathBuf::from(value()?)),
+                _ => return Err(format!("unknown option {}", name)),
+            }
+        }
+
+        if options.offsets && options.format == Format::Text {
+            return Err("--offsets needs --format tsv or json".to_string());
+        }
+        // The modes that count the stems of all the input.
+        let counting = match (options.stats, options.unique) {
+            (true, true) => return Err("--stats can't be given with --unique".to_string()),
+            (true, false) => Some("--stats"),
+            (false, true) => Some("--unique"),
+            (false, false) => None,
+        };
+        if let Some(mode) = counting {
+            if options.diff {
+                return Err(format!("--diff can't be given with {}", mode));
+            }
+            if options.offsets {
+                return Err(format!("--offsets can't be given with {}", mode));
+            }
+            if options.output_dir.is_some() {
+                return Err(format!("{} writes to standard output, so takes no --output-dir", mode));
+            }
+        }
+        if options.reduction && counting.is_none() {
+            return Err("--reduction needs --stats or --unique".to_string());
+        }
+        if options.sort && !options.unique {
+            return Err("--sort needs --unique".to_string());
+        }
+        if options.eval.is_some() && (counting.is_some() || options.diff || options.output_dir.is_some() || options.format != Format::Text) {
+            return Err("--eval can't be given with --stats, --unique, --diff, --output-dir or --format".to_string());
+        }
+        if options.eval.is_some() && options.progress {
+            return Err("--eval can't be given with --progress".to_string());
+        }
+
+        if options.suffix.is_empty() && options.output_dir.is_none() {
+            return Err("an empty suffix would overwrite the inputs".to_string());
+        }
+
+        Ok(Some(options))
+    }
+
+    /// The files named on the command line, with any patterns expanded.
+    fn inputs(&self) -> Result<Vec<PathBuf>, String> {
+        let mut inputs = Vec::new();
+
+        for file in &self.files {
+            if glob::is_pattern(file) {
+                let matching = glob::expand(file).map_err(|error| format!("{}: {}", file, error))?;
+                if matching.is_empty() {
+                    return Err(format!("{}: no files match", file));
+                }
+                inputs.extend(matching);
+            } else {
+                inputs.push(PathBuf::from(file));
+            }
+        }
+
+        Ok(inputs)
+    }
+
+    /// Where to write the stemmed form of `input`, which mustn't be `input`
+    /// itself, as it is with `-o .` for an input in the current directory.
+    fn output(&self, input: &Path) -> io::Result<PathBuf> {
+        let output = match self.output_dir {
+            // Only the input's normal components are kept, so that absolute
+            // paths and `..` can't lead out of the output directory.
+            Some(ref directory) => input.components()
+                .filter_map(|component| match component {
+                    Component::Normal(part) => Some(part),
+                    _ => None,
+                })
+                .fold(directory.clone(), |path, part| path.join(part)),
+            None => {
+                let mut output = OsString::from(input);
+                output.push(".");
+                output.push(&self.suffix);
+                PathBuf::from(output)
+            },
+        };
+
+        // An output that doesn't exist yet can't be the input.
+        if let (Ok(input), Ok(existing)) = (fs::canonicalize(input), fs::canonicalize(&output)) {
+            if input == existing {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "the output would overwrite the input"));
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Refuse to stem two different files into the same output, as `a.txt`
+    /// and `../a.txt` would be with `-o`, since they'd be written at once
+    /// and one would be lost.
+    fn check_outputs(&self, inputs: &[PathBuf]) -> Result<(), String> {
+        let mut outputs = HashMap::new();
+
+        for input in inputs {
+            // An input that can't be stemmed is reported when it's tried.
+            let output = match self.output(input) {
+                Ok(output) => output,
+                Err(_) => continue,
+            };
+            // The same file given twice is just stemmed twice.
+            let file = fs::canonicalize(input).unwrap_or_else(|_| input.clone());
+
+            if let Some((other, other_file)) = outputs.insert(output.clone(), (input, file.clone())) {
+                if other_file != file {
+                    return Err(format!(
+                        "{} and {} would both be written to {}",
+                        other.display(), input.display(), output.display(),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The first line of the output, if it has one.
+    fn header(&self) -> &'static str {
+        match (self.format, self.offsets) {
+            (Format::Tsv, false) => "token\tstem\n",
+            (Format::Tsv, true) => "token\tstem\tstart\tend\n",
+            _ => "",
+        }
+    }
+
+    /// Write the stems of `text` to `output` in the chosen format, counting
+    /// offsets from `offset`, the position of `text` in the whole input.
+    ///
+    /// Returns the number of words stemmed.
+    fn stem_text(&self, text: &str, offset: usize, output: &mut String) -> usize {
+        let mut words = 0;
+
+        for (start, segment) in text.split_word_bound_indices() {
+            if !is_word(segment) {
+                if self.format == Format::Text && !self.diff {
+                    output.push_str(segment);
+                }
+                continue;
+            }
+
+            words += 1;
+            let stem = self.stemmer.stem(segment);
+            if self.diff && stem == segment {
+                continue;
+            }
+
+            let (start, end) = (offset + start, offset + start + segment.len());
+            match self.format {
+                // Words never contain tabs or line breaks, so need no quoting.
+                Format::Text if self.diff => writeln!(output, "{}\t{}", segment, stem).unwrap(),
+                Format::Text => output.push_str(&stem),
+                Format::Tsv => {
+                    write!(output, "{}\t{}", segment, stem).unwrap();
+                    if self.offsets {
+                        write!(output, "\t{}\t{}", start, end).unwrap();
+                    }
+                    output.push('\n');
+                },
+                Format::Json => {
+                    output.push_str("{\"token\":");
+                    push_json_string(segment, output);
+                    output.push_str(",\"stem\":");
+                    push_json_string(&stem, output);
+                    if self.offsets {
+                        write!(output, ",\"start\":{},\"end\":{}", start, end).unwrap();
+                    }
+                    output.push_str("}\n");
+                },
+            }
+        }
+
+        words
+    }
+
+    /// Write each different stem to `output` in the chosen format.
+    fn write_unique(&self, stats: &Stats, output: &mut String) {
+        let mut stems = stats.unique();
+        if self.sort {
+            stems.sort_unstable();
+        }
+
+        if self.format == Format::Tsv {
+            output.push_str("stem\n");
+        }
+        for stem in stems {
+            match self.format {
+                Format::Text | Format::Tsv => writeln!(output, "{}", stem).unwrap(),
+                Format::Json => {
+                    output.push_str("{\"stem\":");
+                    push_json_string(stem, output);
+                    output.push_str("}\n");
+                },
+            }
+        }
+    }
+
+    /// Write how often each stem occurs to `output` in the chosen format.
+    fn write_stats(&self, stats: &Stats, output: &mut String) {
+        let frequencies = stats.frequencies();
+        let width = frequencies.first().map_or(0, |&(_, count)| count.to_string().len());
+
+        if self.format == Format::Tsv {
+            output.push_str("stem\tcount\n");
+        }
+        for (stem, count) in frequencies {
+            match self.format {
+                Format::Text => writeln!(output, "{:>2$} {}", count, stem, width).unwrap(),
+                Format::Tsv => writeln!(output, "{}\t{}", stem, count).unwrap(),
+                Format::Json => {
+                    output.push_str("{\"stem\":");
+                    push_json_string(stem, output);
+                    writeln!(output, ",\"count\":{}}}", count).unwrap();
+                },
+            }
+        }
+    }
+}
+
+/// Whether a segment of text between word boundaries is a word, rather than
+/// space or punctuation.
+fn is_word(segment: &str) -> bool {
+    segment.chars().any(char::is_alphanumeric)
+}
+
+/// Write `string` to `output` as a JSON string.
+fn push_json_string(string: &str, output: &mut String) {
+    output.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            c if c < ' ' => write!(output, "\\u{:04x}", c as u32).unwrap(),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
+/// The algorithms and languages that can be chosen, for `--list`.
+fn list() -> String {
+    let mut list = String::from("Algorithms:\n");
+    for algorithm in Algorithm::all() {
+        writeln!(list, "  {}", algorithm).unwrap();
+    }
+
+    list.push_str("\nLanguages:\n");
+    for language in Language::all() {
+        writeln!(list, "  {}", language).unwrap();
+    }
+
+    list
+}
+
+/// Stem standard input to standard output, a line at a time.
+fn stem_stdin(options: &Options, progress: Option<&Progress>) -> io::Result<()> {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut input = stdin.lock();
+    let mut output = BufWriter::new(stdout.lock());
+    output.write_all(options.header().as_bytes())?;
+
+    let (mut line, mut stems, mut offset) = (String::new(), String::new(), 0);
+    while input.read_line(&mut line)? > 0 {
+        let words = options.stem_text(&line, offset, &mut stems);
+        output.write_all(stems.as_bytes())?;
+        if let Some(progress) = progress {
+            progress.record(0, words, line.len());
+        }
+        offset += line.len();
+        line.clear();
+        stems.clear();
+    }
+
+    output.flush()
+}
+
+/// Stem the file `input` into the file `output`.
+fn stem_file(options: &Options, input: &Path, output: &Path, progress: Option<&Progress>) -> io::Result<()> {
+    let text = fs::read_to_string(input)?;
+    let mut stems = String::with_capacity(text.len());
+    stems.push_str(options.header());
+    let words = options.stem_text(&text, 0, &mut stems);
+
+    if let Some(directory) = output.parent() {
+        fs::create_dir_all(directory)?;
+    }
+    fs::write(output, stems)?;
+
+    if let Some(progress) = progress {
+        progress.record(0, words, text.len());
+    }
+    Ok(())
+}
+
+/// Run `work` on every input file on `options.jobs` threads, each thread
+/// keeping some state of its own. `work` is given the file's index among
+/// the inputs, as well as its path.
+///
+/// Returns each thread's state, and how many files `work` failed on.
+fn each_file<T, F>(options: &Options, inputs: Vec<PathBuf>, progress: Option<&Progress>, work: F) -> (Vec<T>, usize)
+    where T: Default + Send, F: Fn(usize, &Path, &mut T) -> io::Result<()> + Sync
+{
+    let jobs = options.jobs.min(inputs.len());
+    let inputs = &Mutex::new(inputs.into_iter().enumerate());
+    let work = &work;
+
+    thread::scope(|scope| {
+        let workers = (0..jobs)
+            .map(|_| {
+                scope.spawn(move || {
+                    let (mut state, mut failures) = (T::default(), 0);
+
+                    loop {
+                        let (index, input) = match inputs.lock().unwrap().next() {
+                            Some(input) => input,
+                            None => return (state, failures),
+                        };
+
+                        if let Err(error) = work(index, &input, &mut state) {
+                            eprintln!("porter-stemmer: {}: {}", input.display(), error);
+                            failures += 1;
+                        }
+                        if let Some(progress) = progress {
+                            progress.record(1, 0, 0);
+                        }
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut states = Vec::with_capacity(jobs);
+        let mut failures = 0;
+        for worker in workers {
+            let (state, failed) = worker.join().expect("stemming a file doesn't panic");
+            states.push(state);
+            failures += failed;
+        }
+
+        (states, failures)
+    })
+}
+
+/// Stem standard input, or each of `inputs` into its output file, returning
+/// how many couldn't be stemmed.
+fn stem(options: &Options, inputs: Option<Vec<PathBuf>>, progress: Option<&Progress>) -> usize {
+    match inputs {
+        Some(inputs) => {
+            if let Err(error) = options.check_outputs(&inputs) {
+                eprintln!("porter-stemmer: {}", error);
+                return 1;
+            }
+
+            each_file(options, inputs, progress, |_, input, _: &mut ()| {
+                stem_file(options, input, &options.output(input)?, progress)
+            }).1
+        },
+        None => match stem_stdin(options, progress) {
+            Ok(()) => 0,
+            Err(error) => {
+                eprintln!("porter-stemmer: {}", error);
+                1
+            },
+        },
+    }
+}
+
+/// Count the stems of standard input, or of all of `inputs`, and write how
+/// often each occurs, or each different stem, to standard output, returning
+/// how many files couldn't be read.
+fn stats(options: &Options, inputs: Option<Vec<PathBuf>>, progress: Option<&Progress>) -> usize {
+    // Count the stems of some text, reporting progress as it goes.
+    let add = |stats: &mut Stats, document: usize, text: &str| {
+        let tokens = stats.tokens();
+        stats.add(&options.stemmer, document, text);
+        if let Some(progress) = progress {
+            progress.record(0, stats.tokens() - tokens, text.len());
+        }
+    };
+
+    let (stats, mut failures) = match inputs {
+        Some(inputs) => {
+            let (counts, failures) = each_file(options, inputs, progress, |index, input, stats: &mut Stats| {
+                add(stats, index, &fs::read_to_string(input)?);
+                Ok(())
+            });
+
+            let mut stats = Stats::default();
+            for counted in counts {
+                stats.merge(counted);
+            }
+            (stats, failures)
+        },
+        None => {
+            let (mut stats, mut failures) = (Stats::default(), 0);
+            let (mut input, mut line) = (io::stdin().lock(), String::new());
+            loop {
+                match input.read_line(&mut line) {
+                    Ok(0) => break,
+                    Ok(_) => add(&mut stats, 0, &line),
+                    Err(error) => {
+                        eprintln!("porter-stemmer: {}", error);
+                        failures += 1;
+                        break;
+                    },
+                }
+                line.clear();
+            }
+            (stats, failures)
+        },
+    };
+
+    let mut table = String::new();
+    if options.unique {
+        options.write_unique(&stats, &mut table);
+    } else {
+        options.write_stats(&stats, &mut table);
+    }
+
+    let stdout = io::stdout();
+    if let Err(error) = stdout.lock().write_all(table.as_bytes()) {
+        eprintln!("porter-stemmer: {}", error);
+        failures += 1;
+    }
+
+    if options.reduction {
+        eprintln!(
+            "{} words, {} different, stemmed to {} stems: vocabulary reduced by {:.1}%",
+            stats.tokens(), stats.words(), stats.stems(), stats.reduction(),
+        );
+    }
+
+    failures
+}
+
+/// Stem the vocabulary on standard input, or in each of `inputs` in turn,
+/// and report any stems that differ from those in `expected`, returning 1
+/// if any do.
+fn eval(options: &Options, expected: &Path, inputs: Option<Vec<PathBuf>>) -> usize {
+    let read = |path: &Path| fs::read_to_string(path).map_err(|error| format!("{}: {}", path.display(), error));
+
+    let vocabulary = match inputs {
+        Some(inputs) => inputs.iter().map(|input| read(input)).collect::<Result<Vec<_>, _>>(),
+        None => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).map(|_| vec![text]).map_err(|error| error.to_string())
+        },
+    };
+    let (vocabulary, expected) = match vocabulary.and_then(|vocabulary| Ok((vocabulary, read(expected)?))) {
+        Ok(texts) => texts,
+        Err(error) => {
+            eprintln!("porter-stemmer: {}", error);
+            return 1;
+        },
+    };
+
+    let words = vocabulary.iter().flat_map(|text| text.lines());
+    let evaluation = Evaluation::new(&options.stemmer, words, &expected);
+    print!("{}", evaluation);
+
+    if evaluation.passed() { 0 } else { 1 }
+}
+
+fn main() {
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            print!("{}", USAGE);
+            return;
+        },
+        Err(error) => {
+            eprint!("porter-stemmer: {}\n\n{}", error, USAGE);
+            process::exit(2);
+        },
+    };
+
+    if options.list {
+        print!("{}", list());
+        return;
+    }
+
+    let inputs = match options.inputs() {
+        Ok(_) if options.files.is_empty() => None,
+        Ok(inputs) => Some(inputs),
+        Err(error) => {
+            eprintln!("porter-stemmer: {}", error);
+            process::exit(1);
+        },
+    };
+
+    let progress = if options.progress { Some(Progress::new(inputs.as_ref().map(Vec::len))) } else { None };
+
+    let failures = match options.eval {
+        Some(ref expected) => eval(&options, expected, inputs),
+        None if options.stats || options.unique => stats(&options, inputs, progress.as_ref()),
+        None => stem(&options, inputs, progress.as_ref()),
+    };
+    if let Some(progress) = progress {
+        progress.finish();
+    }
+    if failures > 0 {
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<Options>, String> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        let options = parse(&["-o", "out", "--jobs=3", "a.txt", "--", "-b.txt"]).unwrap().unwrap();
+        assert_eq!(vec!["a.txt", "-b.txt"], options.files);
+        assert_eq!(Some(PathBuf::from("out")), options.output_dir);
+        assert_eq!("stemmed", options.suffix);
+        assert_eq!(3, options.jobs);
+
+        assert_eq!(Ok(None), parse(&["a.txt", "--help"]));
+        assert!(parse(&["--jobs", "0"]).is_err());
+        assert!(parse(&["--suffix"]).is_err());
+        assert!(parse(&["--suffix="]).is_err());
+        assert!(parse(&["--suffix=", "-o", "out"]).is_ok());
+        assert!(parse(&["--verbose"]).is_err());
+        assert!(parse(&["--format", "xml"]).is_err());
+        assert!(parse(&["--offsets"]).is_err());
+        assert_eq!(Format::Json, parse(&["-f", "json", "--offsets"]).unwrap().unwrap().format);
+        assert!(parse(&["--list"]).unwrap().unwrap().list);
+        assert!(parse(&["--stats", "--reduction", "-f", "json"]).is_ok());
+        assert!(parse(&["--stats", "-f", "json", "--offsets"]).is_err());
+        assert!(parse(&["--stats", "-o", "out"]).is_err());
+        assert!(parse(&["--reduction"]).is_err());
+        assert!(parse(&["--unique", "--sort", "--reduction"]).is_ok());
+        assert!(parse(&["--unique", "--stats"]).is_err());
+        assert!(parse(&["--unique", "-o", "out"]).is_err());
+        assert!(parse(&["--sort"]).is_err());
+        assert!(parse(&["--progress", "--stats"]).unwrap().unwrap().progress);
+        assert!(parse(&["--progress", "--eval", "expected.txt"]).is_err());
+        assert!(parse(&["--stats", "--diff"]).is_err());
+        assert_eq!(Some(PathBuf::from("expected.txt")), parse(&["--eval", "expected.txt"]).unwrap().unwrap().eval);
+        assert!(parse(&["--eval", "expected.txt", "--diff"]).is_err());
+        assert!(parse(&["--eval", "expected.txt", "-f", "json"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_stemmer() {
+        let stemmer = |args: &[&str]| parse(args).map(|options| options.unwrap().stemmer);
+
+        assert_eq!(Ok(Choice::Algorithm(Algorithm::Porter)), stemmer(&[]));
+        assert_eq!(Ok(Choice::Algorithm(Algorithm::Lovins)), stemmer(&["-a", "lovins"]));
+        assert_eq!(Ok(Choice::Algorithm(Algorithm::Porter2)), stemmer(&["--algorithm=porter2"]));
+        assert_eq!(Ok(Choice::Language(Language::German)), stemmer(&["--language", "German"]));
+        assert_eq!(Ok(Choice::Language(Language::French)), stemmer(&["-l", "french"]));
+
+        assert!(stemmer(&["--algorithm", "english"]).unwrap_err().contains("unknown stemming algorithm"));
+        assert!(stemmer(&["--language", "klingon"]).unwrap_err().contains("unknown language"));
+        assert_eq!(Err("-l can't be given with -a".to_string()), stemmer(&["-a", "porter", "-l", "german"]));
+    }
+
+    #[test]
+    fn test_output() {
+        let alongside = parse(&["-s", "stems"]).unwrap().unwrap();
+        assert_eq!(PathBuf::from("corpus/a.txt.stems"), alongside.output(Path::new("corpus/a.txt")).unwrap());
+
+        let directory = parse(&["-o", "out"]).unwrap().unwrap();
+        assert_eq!(PathBuf::from("out/corpus/a.txt"), directory.output(Path::new("corpus/a.txt")).unwrap());
+        assert_eq!(PathBuf::from("out/data/a.txt"), directory.output(Path::new("/data/a.txt")).unwrap());
+
+        let current = parse(&["-o", "."]).unwrap().unwrap();
+        assert!(current.output(Path::new("Cargo.toml")).is_err());
+    }
+
+    fn stem_text(args: &[&str], text: &str) -> String {
+        let options = parse(args).unwrap().unwrap();
+        let mut stems = options.header().to_string();
+        options.stem_text(text, 4, &mut stems);
+        stems
+    }
+
+    #[test]
+    fn test_stem_text() {
+        let text = "Forty  years later,\tthese ponies...\n";
+        assert_eq!("Forti  year later,\tthese poni...\n", stem_text(&[], text));
+        assert_eq!(stem_text(&[], text), stem_text(&["--format", "text"], text));
+    }
+
+    #[test]
+    fn test_stem_text_stemmer() {
+        assert_eq!("hop hop", stem_text(&["-a", "lovins"], "hopeful hopes"));
+        assert_eq!("Haus, Katz", stem_text(&["-l", "german"], "Häuser, Katzen"));
+    }
+
+    #[test]
+    fn test_write_stats() {
+        let mut stats = Stats::default();
+        stats.add(&Algorithm::Porter, 0, &"hopes hoping ".repeat(5));
+        stats.add(&Algorithm::Porter, 0, "ponies");
+
+        let write_stats = |args: &[&str]| {
+            let mut table = String::new();
+            parse(args).unwrap().unwrap().write_stats(&stats, &mut table);
+            table
+        };
+
+        assert_eq!("10 hope\n 1 poni\n", write_stats(&["--stats"]));
+        assert_eq!("stem\tcount\nhope\t10\nponi\t1\n", write_stats(&["--stats", "-f", "tsv"]));
+        assert_eq!("{\"stem\":\"hope\",\"count\":10}\n{\"stem\":\"poni\",\"count\":1}\n", write_stats(&["--stats", "-f", "json"]));
+    }
+
+    #[test]
+    fn test_write_unique() {
+        let mut stats = Stats::default();
+        stats.add(&Algorithm::Porter, 0, "The ponies were hoping, the hopes were relational");
+
+        let write_unique = |args: &[&str]| {
+            let mut stems = String::new();
+            parse(args).unwrap().unwrap().write_unique(&stats, &mut stems);
+            stems
+        };
+
+        assert_eq!("The\nponi\nwere\nhope\nthe\nrelat\n", write_unique(&["--unique"]));
+        assert_eq!("The\nhope\nponi\nrelat\nthe\nwere\n", write_unique(&["--unique", "--sort"]));
+        assert_eq!("stem\nThe\nhope\n", &write_unique(&["--unique", "--sort", "-f", "tsv"])[..14]);
+        assert!(write_unique(&["--unique", "-f", "json"]).starts_with("{\"stem\":\"The\"}\n{\"stem\":\"poni\"}\n"));
+    }
+
+    #[test]
+    fn test_list() {
+        let list = list();
+        assert!(list.starts_with("Algorithms:\n  porter\n  porter2\n"));
+        assert!(list.contains("\nLanguages:\n  english\n"));
+        assert_eq!(Algorithm::all().len() + Language::all().len() + 3, list.lines().count());
+    }
+
+    #[test]
+    fn test_stem_text_diff() {
+        let text = "The ponies ran, hopefully.\n";
+        assert_eq!("ponies\tponi\nhopefully\thopefulli\n", stem_text(&["--diff"], text));
+        assert_eq!("token\tstem\nponies\tponi\nhopefully\thopefulli\n", stem_text(&["--diff", "-f", "tsv"], text));
+        assert_eq!(
+            "{\"token\":\"ponies\",\"stem\":\"poni\",\"start\":8,\"end\":14}\n",
+            stem_text(&["--diff", "-f", "json", "--offsets"], "The ponies"),
+        );
+        assert_eq!("", stem_text(&["--diff"], "The cat ran.\n"));
+    }
+
+    #[test]
+    fn test_stem_text_tsv() {
+        assert_eq!("token\tstem\nponies\tponi\nran\tran\n", stem_text(&["-f", "tsv"], "ponies ran."));
+        assert_eq!(
+            "token\tstem\tstart\tend\nponies\tponi\t4\t10\nran\tran\t11\t14\n",
+            stem_text(&["-f", "tsv", "--offsets"], "ponies ran."),
+        );
+    }
+
+    #[test]
+    fn test_stem_text_json() {
+        assert_eq!(
+            "{\"token\":\"Ponies\",\"stem\":\"Poni\"}\n{\"token\":\"can't\",\"stem\":\"can't\"}\n",
+            stem_text(&["--format=json"], "Ponies can't"),
+        );
+        assert_eq!(
+            "{\"token\":\"ponies\",\"stem\":\"poni\",\"start\":4,\"end\":10}\n",
+            stem_text(&["--format=json", "--offsets"], "ponies"),
+        );
+
+        let mut string = String::new();
+        push_json_string("a\"b\\c\u{1}é", &mut string);
+        assert_eq!("\"a\\\"b\\\\c\\u0001é\"", string);
+    }
+}
@@ -0,0 +1,185 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Reporting the progress of a long run on standard error.
+//!
+//! On a terminal, a status line is redrawn in place several times a second.
+//! Otherwise, as when standard error is redirected to a log, a line is
+//! written every few seconds instead.
+
+use std::io::{self, IsTerminal, Write};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// The width of the progress bar, in characters.
+const BAR: usize = 20;
+
+/// How much is stemmed so far, and how quickly.
+pub struct Progress {
+    /// The number of files to stem, if stemming files.
+    files: Option<usize>,
+    done: AtomicUsize,
+    words: AtomicUsize,
+    bytes: AtomicUsize,
+    start: Instant,
+    terminal: bool,
+    /// When the status was last reported.
+    reported: Mutex<Instant>,
+}
+
+impl Progress {
+    /// Start reporting on the stemming of `files` files, or of standard
+    /// input if `None`.
+    pub fn new(files: Option<usize>) -> Progress {
+        let start = Instant::now();
+
+        Progress {
+            files,
+            done: AtomicUsize::new(0),
+            words: AtomicUsize::new(0),
+            bytes: AtomicUsize::new(0),
+            start,
+            terminal: io::stderr().is_terminal(),
+            reported: Mutex::new(start),
+        }
+    }
+
+    /// Record the stemming of `words` words in `bytes` bytes, finishing
+    /// `files` files, and report the status if it's been a while since it
+    /// last was.
+    pub fn record(&self, files: usize, words: usize, bytes: usize) {
+        self.done.fetch_add(files, Ordering::Relaxed);
+        self.words.fetch_add(words, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+
+        let interval = if self.terminal { Duration::from_millis(100) } else { Duration::from_secs(5) };
+
+        // Whichever thread is already reporting can do so for all of them.
+        let mut reported = match self.reported.try_lock() {
+            Ok(reported) => reported,
+            Err(_) => return,
+        };
+        if reported.elapsed() < interval {
+            return;
+        }
+        *reported = Instant::now();
+
+        let status = self.status(self.start.elapsed());
+        // Failing to report progress is no reason to stop stemming.
+        let _ = if self.terminal {
+            write!(io::stderr(), "\r{}\x1b[K", status)
+        } else {
+            writeln!(io::stderr(), "{}", status)
+        };
+    }
+
+    /// Report what was stemmed in all, replacing the status line.
+    pub fn finish(&self) {
+        let summary = self.summary(self.start.elapsed());
+        let _ = if self.terminal {
+            writeln!(io::stderr(), "\r{}\x1b[K", summary)
+        } else {
+            writeln!(io::stderr(), "{}", summary)
+        };
+    }
+
+    /// The status after `elapsed`, with a progress bar if stemming files.
+    fn status(&self, elapsed: Duration) -> String {
+        let done = self.done.load(Ordering::Relaxed);
+        let mut status = String::new();
+
+        if let Some(files) = self.files {
+            let filled = (BAR * done).checked_div(files).unwrap_or(BAR);
+            status.push_str(&format!(
+                "[{}{}] {}/{} files, ",
+                "=".repeat(filled), " ".repeat(BAR - filled), done, files,
+            ));
+        }
+
+        status + &format!("{}: {}", self.totals(), self.rates(elapsed))
+    }
+
+    /// What was stemmed in all, and how quickly, after `elapsed`.
+    fn summary(&self, elapsed: Duration) -> String {
+        let files = match self.files {
+            Some(_) => format!("{} files, ", self.done.load(Ordering::Relaxed)),
+            None => String::new(),
+        };
+
+        format!("stemmed {}{} in {}: {}", files, self.totals(), duration(elapsed), self.rates(elapsed))
+    }
+
+    fn totals(&self) -> String {
+        let (words, bytes) = (self.words.load(Ordering::Relaxed), self.bytes.load(Ordering::Relaxed));
+        format!("{} words, {:.1} MB", count(words as f64), bytes as f64 / 1e6)
+    }
+
+    fn rates(&self, elapsed: Duration) -> String {
+        // Avoid dividing by zero, and absurd rates, so soon after starting.
+        let seconds = elapsed.as_secs_f64().max(0.001);
+        let (words, bytes) = (self.words.load(Ordering::Relaxed), self.bytes.load(Ordering::Relaxed));
+
+        format!("{} words/s, {:.1} MB/s", count(words as f64 / seconds), bytes as f64 / 1e6 / seconds)
+    }
+}
+
+/// `count`, abbreviated to thousands or millions.
+fn count(count: f64) -> String {
+    if count >= 1e6 {
+        format!("{:.1}M", count / 1e6)
+    } else if count >= 1e3 {
+        format!("{:.1}k", count / 1e3)
+    } else {
+        format!("{:.0}", count)
+    }
+}
+
+/// `elapsed`, in hours, minutes and seconds.
+fn duration(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+
+    if seconds >= 3600 {
+        format!("{}h{:02}m{:02}s", seconds / 3600, seconds / 60 % 60, seconds % 60)
+    } else if seconds >= 60 {
+        format!("{}m{:02}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{:.1}s", elapsed.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status() {
+        let progress = Progress::new(Some(4));
+        progress.record(1, 1500, 9000);
+        progress.record(0, 500, 3000);
+
+        assert_eq!(
+            "[=====               ] 1/4 files, 2.0k words, 0.0 MB: 1.0k words/s, 0.0 MB/s",
+            progress.status(Duration::from_secs(2)),
+        );
+        assert_eq!(
+            "stemmed 1 files, 2.0k words, 0.0 MB in 2.0s: 1.0k words/s, 0.0 MB/s",
+            progress.summary(Duration::from_secs(2)),
+        );
+
+        let stdin = Progress::new(None);
+        stdin.record(0, 3_000_000, 20_000_000);
+        assert_eq!(
+            "stemmed 3.0M words, 20.0 MB in 1h01m01s: 819 words/s, 0.0 MB/s",
+            stdin.summary(Duration::from_secs(3661)),
+        );
+    }
+
+    #[test]
+    fn test_duration() {
+        assert_eq!("0.5s", duration(Duration::from_millis(500)));
+        assert_eq!("2m05s", duration(Duration::from_secs(125)));
+        assert_eq!("10h00m00s", duration(Duration::from_secs(36000)));
+    }
+}
@@ -0,0 +1,141 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Counting the stems of a corpus.
+
+use std::collections::{HashMap, HashSet};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use porter_stemmer::Stemmer;
+
+use is_word;
+
+/// How often a stem occurs, and where it first does.
+#[derive(Debug)]
+struct Count {
+    count: usize,
+    /// The document the stem was first seen in, and the number of words
+    /// counted before it.
+    first: (usize, usize),
+}
+
+/// How often each stem occurs in some text, and how many different words
+/// they were stemmed from.
+#[derive(Debug, Default)]
+pub struct Stats {
+    tokens: usize,
+    words: HashSet<String>,
+    stems: HashMap<String, Count>,
+}
+
+impl Stats {
+    /// Count the stems of the words in `text`, from the `document`th
+    /// document of the corpus.
+    ///
+    /// A document may be added a part at a time, as long as its parts are
+    /// added in order.
+    pub fn add(&mut self, stemmer: &dyn Stemmer, document: usize, text: &str) {
+        for word in text.split_word_bounds().filter(|segment| is_word(segment)) {
+            let first = (document, self.tokens);
+            self.stems.entry(stemmer.stem(word)).or_insert(Count { count: 0, first }).count += 1;
+            self.tokens += 1;
+
+            if !self.words.contains(word) {
+                self.words.insert(word.to_string());
+            }
+        }
+    }
+
+    /// Add the counts from `other`, from other documents of the same corpus.
+    pub fn merge(&mut self, other: Stats) {
+        self.tokens += other.tokens;
+        self.words.extend(other.words);
+        for (stem, other) in other.stems {
+            let count = self.stems.entry(stem).or_insert(Count { count: 0, first: other.first });
+            count.count += other.count;
+            count.first = count.first.min(other.first);
+        }
+    }
+
+    /// The number of words counted, including repeats.
+    pub fn tokens(&self) -> usize {
+        self.tokens
+    }
+
+    /// The number of different words counted.
+    pub fn words(&self) -> usize {
+        self.words.len()
+    }
+
+    /// The number of different stems counted.
+    pub fn stems(&self) -> usize {
+        self.stems.len()
+    }
+
+    /// Each stem and how often it occurs, the most frequent first and
+    /// otherwise in alphabetical order.
+    pub fn frequencies(&self) -> Vec<(&str, usize)> {
+        let mut frequencies = self.stems.iter().map(|(stem, count)| (stem.as_str(), count.count)).collect::<Vec<_>>();
+        frequencies.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        frequencies
+    }
+
+    /// Each different stem, in the order they were first seen.
+    pub fn unique(&self) -> Vec<&str> {
+        let mut stems = self.stems.iter().collect::<Vec<_>>();
+        stems.sort_by_key(|&(_, count)| count.first);
+        stems.into_iter().map(|(stem, _)| stem.as_str()).collect()
+    }
+
+    /// By how much stemming reduced the vocabulary, as a percentage of the
+    /// number of different words.
+    pub fn reduction(&self) -> f64 {
+        if self.words.is_empty() {
+            return 0.0;
+        }
+
+        100.0 * (1.0 - self.stems() as f64 / self.words() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use porter_stemmer::Algorithm;
+
+    #[test]
+    fn test_stats() {
+        let mut stats = Stats::default();
+        stats.add(&Algorithm::Porter, 0, "Hopes, hoping; the hopes.");
+
+        let mut more = Stats::default();
+        more.add(&Algorithm::Porter, 1, "the relational ponies");
+        stats.merge(more);
+
+        assert_eq!(7, stats.tokens());
+        assert_eq!(6, stats.words());
+        assert_eq!(5, stats.stems());
+        assert_eq!(vec![("hope", 2), ("the", 2), ("Hope", 1), ("poni", 1), ("relat", 1)], stats.frequencies());
+        assert!((stats.reduction() - 100.0 / 6.0).abs() < 1e-9);
+
+        assert_eq!(0.0, Stats::default().reduction());
+    }
+
+    #[test]
+    fn test_unique() {
+        // Documents counted out of order, as several threads may.
+        let mut stats = Stats::default();
+        stats.add(&Algorithm::Porter, 2, "ponies hopes");
+
+        let mut earlier = Stats::default();
+        earlier.add(&Algorithm::Porter, 0, "relational ponies");
+        earlier.add(&Algorithm::Porter, 1, "the hoping");
+        earlier.add(&Algorithm::Porter, 1, "the relate");
+        stats.merge(earlier);
+
+        assert_eq!(vec!["relat", "poni", "the", "hope"], stats.unique());
+    }
+}
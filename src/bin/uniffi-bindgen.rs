@@ -0,0 +1,12 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Generate Kotlin and Swift bindings for `porter_stemmer::mobile` from the
+//! built library. Requires the `uniffi` feature.
+
+extern crate uniffi;
+
+fn main() {
+    uniffi::uniffi_bindgen_main()
+}
@@ -0,0 +1,324 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Memoizing a stemmer's results.
+//!
+//! Word frequencies in natural language are Zipfian: a few thousand distinct
+//! words make up most of any corpus, so most of the work of stemming one is
+//! stemming the same words over and over. `CachedStemmer` remembers the stems
+//! of the most recently used words, up to a fixed capacity, and counts how
+//! often it finds a word it has already seen.
+//!
+//! ```
+//! use porter_stemmer::Algorithm;
+//! use porter_stemmer::cache::CachedStemmer;
+//!
+//! let stemmer = CachedStemmer::with_capacity(Algorithm::Porter, 1000);
+//!
+//! for word in "the cat sat on the mat".split(' ') {
+//!     stemmer.stem(word);
+//! }
+//!
+//! assert_eq!(1, stemmer.stats().hits());
+//! assert_eq!(5, stemmer.stats().misses());
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+use algorithm::Stemmer;
+
+/// The number of words a `CachedStemmer` remembers unless told otherwise.
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// Marks the absence of an entry in the recency list.
+const NONE: usize = usize::MAX;
+
+/// The number of lookups a cache could and couldn't answer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CacheStats {
+    hits: u64,
+    misses: u64,
+}
+
+impl CacheStats {
+    /// The number of words whose stem was already cached.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// The number of words that had to be stemmed.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// The total number of words looked up.
+    pub fn lookups(&self) -> u64 {
+        self.hits + self.misses
+    }
+
+    /// The fraction of lookups that were hits, or zero if there have been
+    /// none.
+    pub fn hit_rate(&self) -> f64 {
+        if self.lookups() == 0 {
+            0.0
+        } else {
+            self.hits as f64 / self.lookups() as f64
+        }
+    }
+}
+
+struct Entry {
+    word: String,
+    stem: String,
+    /// The entry used just more recently than this one.
+    newer: usize,
+    /// The entry used just less recently than this one.
+    older: usize,
+}
+
+/// A least recently used cache, with its entries in a list threaded through
+/// `entries` by index.
+struct Lru {
+    capacity: usize,
+    indices: HashMap<String, usize>,
+    entries: Vec<Entry>,
+    newest: usize,
+    oldest: usize,
+    stats: CacheStats,
+}
+
+impl Lru {
+    fn new(capacity: usize) -> Lru {
+        Lru {
+            capacity,
+            indices: HashMap::new(),
+            entries: Vec::new(),
+            newest: NONE,
+            oldest: NONE,
+            stats: CacheStats::default(),
+        }
+    }
+
+    fn unlink(&mut self, index: usize) {
+        let (newer, older) = (self.entries[index].newer, self.entries[index].older);
+
+        match newer {
+            NONE => self.newest = older,
+            newer => self.entries[newer].older = older,
+        }
+        match older {
+            NONE => self.oldest = newer,
+            older => self.entries[older].newer = newer,
+        }
+    }
+
+    fn push_newest(&mut self, index: usize) {
+        self.entries[index].newer = NONE;
+        self.entries[index].older = self.newest;
+
+        match self.newest {
+            NONE => self.oldest = index,
+            newest => self.entries[newest].newer = index,
+        }
+        self.newest = index;
+    }
+
+    fn get(&mut self, word: &str) -> Option<String> {
+        let index = match self.indices.get(word) {
+            Some(&index) => index,
+            None => {
+                self.stats.misses += 1;
+                return None;
+            },
+        };
+
+        self.stats.hits += 1;
+        self.unlink(index);
+        self.push_newest(index);
+
+        Some(self.entries[index].stem.clone())
+    }
+
+    fn insert(&mut self, word: &str, stem: String) {
+        if self.capacity == 0 || self.indices.contains_key(word) {
+            return;
+        }
+
+        let index = if self.entries.len() < self.capacity {
+            self.entries.push(Entry { word: word.to_string(), stem, newer: NONE, older: NONE });
+            self.entries.len() - 1
+        } else {
+            // Reuse the least recently used entry.
+            let index = self.oldest;
+            self.unlink(index);
+            self.indices.remove(&self.entries[index].word);
+
+            let entry = &mut self.entries[index];
+            entry.word.clear();
+            entry.word.push_str(word);
+            entry.stem = stem;
+            index
+        };
+
+        self.indices.insert(word.to_string(), index);
+        self.push_newest(index);
+    }
+}
+
+/// A stemmer that remembers the stems of the words it has most recently
+/// seen.
+///
+/// The cache is shared behind a lock, so a `CachedStemmer` can be used from
+/// several threads at once through a shared reference.
+pub struct CachedStemmer<S> {
+    stemmer: S,
+    cache: Mutex<Lru>,
+}
+
+impl<S: Stemmer> CachedStemmer<S> {
+    /// Cache the stems of up to ten thousand words.
+    pub fn new(stemmer: S) -> CachedStemmer<S> {
+        CachedStemmer::with_capacity(stemmer, DEFAULT_CAPACITY)
+    }
+
+    /// Cache the stems of up to `capacity` words. With a capacity of zero
+    /// nothing is cached, but lookups are still counted.
+    pub fn with_capacity(stemmer: S, capacity: usize) -> CachedStemmer<S> {
+        CachedStemmer {
+            stemmer,
+            cache: Mutex::new(Lru::new(capacity)),
+        }
+    }
+
+    /// Given a word, return its stemmed form, stemming it only if it isn't
+    /// already cached.
+    pub fn stem(&self, word: &str) -> String {
+        if let Some(stem) = self.lock().get(word) {
+            return stem;
+        }
+
+        // Stem without holding the lock, so other threads aren't kept waiting.
+        let stem = self.stemmer.stem(word);
+        self.lock().insert(word, stem.clone());
+        stem
+    }
+
+    /// The number of hits and misses so far.
+    pub fn stats(&self) -> CacheStats {
+        self.lock().stats
+    }
+
+    /// The most words that are cached at once.
+    pub fn capacity(&self) -> usize {
+        self.lock().capacity
+    }
+
+    /// The number of words currently cached.
+    pub fn len(&self) -> usize {
+        self.lock().indices.len()
+    }
+
+    /// Whether no words are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forget every cached word, and reset the stats.
+    pub fn clear(&self) {
+        let mut cache = self.lock();
+        *cache = Lru::new(cache.capacity);
+    }
+
+    /// The wrapped stemmer.
+    pub fn get_ref(&self) -> &S {
+        &self.stemmer
+    }
+
+    /// Unwrap the stemmer, discarding the cache.
+    pub fn into_inner(self) -> S {
+        self.stemmer
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Lru> {
+        // The cache is consistent between calls, so a panic in another
+        // thread doesn't invalidate it.
+        self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<S: Stemmer> Stemmer for CachedStemmer<S> {
+    fn stem(&self, word: &str) -> String {
+        CachedStemmer::stem(self, word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    use algorithm::Algorithm;
+
+    /// Counts the words it is asked to stem.
+    struct Counting {
+        calls: Cell<usize>,
+    }
+
+    impl Stemmer for Counting {
+        fn stem(&self, word: &str) -> String {
+            self.calls.set(self.calls.get() + 1);
+            word.to_uppercase()
+        }
+    }
+
+    #[test]
+    fn test_cached_stem() {
+        let stemmer = CachedStemmer::new(Algorithm::Porter);
+
+        assert_eq!("hope", stemmer.stem("hopefulness"));
+        assert_eq!("hope", stemmer.stem("hopefulness"));
+        assert_eq!("relat", stemmer.stem("relational"));
+
+        assert_eq!(CacheStats { hits: 1, misses: 2 }, stemmer.stats());
+        assert_eq!(3, stemmer.stats().lookups());
+        assert_eq!(2, stemmer.len());
+        assert_eq!(DEFAULT_CAPACITY, stemmer.capacity());
+
+        stemmer.clear();
+        assert!(stemmer.is_empty());
+        assert_eq!(0.0, stemmer.stats().hit_rate());
+    }
+
+    #[test]
+    fn test_eviction() {
+        let stemmer = CachedStemmer::with_capacity(Counting { calls: Cell::new(0) }, 2);
+
+        stemmer.stem("a");
+        stemmer.stem("b");
+        stemmer.stem("a");
+        // `b` is now the least recently used, so it makes way for `c`.
+        stemmer.stem("c");
+        assert_eq!(2, stemmer.len());
+        assert_eq!(3, stemmer.get_ref().calls.get());
+
+        assert_eq!("A", stemmer.stem("a"));
+        assert_eq!("C", stemmer.stem("c"));
+        assert_eq!(3, stemmer.get_ref().calls.get());
+
+        assert_eq!("B", stemmer.stem("b"));
+        assert_eq!(4, stemmer.get_ref().calls.get());
+        assert_eq!(CacheStats { hits: 3, misses: 4 }, stemmer.stats());
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let stemmer = CachedStemmer::with_capacity(Counting { calls: Cell::new(0) }, 0);
+
+        assert_eq!("A", stemmer.stem("a"));
+        assert_eq!("A", stemmer.stem("a"));
+        assert!(stemmer.is_empty());
+        assert_eq!(2, stemmer.into_inner().calls.get());
+    }
+}
@@ -0,0 +1,161 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Expansion of English contractions before stemming.
+//!
+//! None of the stemming rules know about apostrophes, so a contraction like
+//! `they're` is otherwise left as it is and never matches `they` or `are`.
+//! Expanding it first gives the stemmer ordinary words to work with.
+//!
+//! Both straight and curly apostrophes are recognised, and the expansion
+//! follows the case of the contraction. A trailing `'s` is only expanded
+//! after pronouns like `it` and `that`, since elsewhere it is more likely to
+//! be possessive, and `'d` is always taken to be `would`.
+//!
+//! ```
+//! use porter_stemmer::contractions::{expand, expand_text};
+//!
+//! assert_eq!(Some("can not".to_string()), expand("can't"));
+//! assert_eq!(Some("They are".to_string()), expand("They’re"));
+//! assert_eq!(None, expand("dog's"));
+//! assert_eq!("I am sure it is fine", expand_text("I'm sure it's fine"));
+//! ```
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use pipeline::Stage;
+
+/// Contractions that don't follow from their ending alone.
+const IRREGULAR: &[(&str, &str)] = &[
+    ("can't", "can not"), ("won't", "will not"), ("shan't", "shall not"), ("ain't", "am not"),
+    ("let's", "let us"), ("y'all", "you all"), ("'tis", "it is"), ("'twas", "it was"),
+];
+
+/// Endings and what they expand to.
+const ENDINGS: &[(&str, &str)] = &[
+    ("n't", " not"), ("'re", " are"), ("'ve", " have"), ("'ll", " will"), ("'m", " am"), ("'d", " would"),
+];
+
+/// The words after which `'s` is expanded to `is`.
+const IS_AFTER: &[&str] = &[
+    "he", "she", "it", "that", "there", "here", "what", "who", "where", "when", "why", "how",
+];
+
+/// Match the case of `expansion` to that of `word`: all capitals, a leading
+/// capital, or as it is.
+fn match_case(word: &str, expansion: &str) -> String {
+    let mut letters = word.chars().filter(|c| c.is_alphabetic());
+
+    if word.chars().any(|c| c.is_uppercase()) && !word.chars().any(|c| c.is_lowercase()) {
+        expansion.to_uppercase()
+    } else if letters.next().is_some_and(|c| c.is_uppercase()) {
+        let mut chars = expansion.chars();
+        chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+    } else {
+        expansion.to_string()
+    }
+}
+
+/// Given a single word, return its expansion if it is a contraction.
+pub fn expand(word: &str) -> Option<String> {
+    let normalized = word.to_lowercase().replace('\u{2019}', "'");
+
+    if let Some(&(_, expansion)) = IRREGULAR.iter().find(|&&(contraction, _)| contraction == normalized) {
+        return Some(match_case(word, expansion));
+    }
+
+    let (ending, expansion) = ENDINGS.iter()
+        .cloned()
+        .find(|&(ending, _)| normalized.ends_with(ending))
+        .or_else(|| {
+            normalized.strip_suffix("'s")
+                .filter(|stem| IS_AFTER.contains(stem))
+                .map(|_| ("'s", " is"))
+        })?;
+
+    let length = word.chars().count() - ending.chars().count();
+    if length == 0 {
+        return None;
+    }
+
+    let stem = word.chars().take(length).collect::<String>();
+    Some(format!("{}{}", stem, match_case(word, expansion)))
+}
+
+/// Expand every contraction in a passage of text, leaving everything else as
+/// it is.
+pub fn expand_text(text: &str) -> String {
+    text.split_word_bounds()
+        .fold(String::with_capacity(text.len()), |mut expanded, word| {
+            match expand(word) {
+                Some(expansion) => expanded.push_str(&expansion),
+                None => expanded.push_str(word),
+            }
+            expanded
+        })
+}
+
+/// A pipeline `Stage` that replaces each contraction in a chunk with the
+/// words it expands to.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ExpandStage;
+
+impl Stage for ExpandStage {
+    fn process(&mut self, chunk: Vec<String>) -> Vec<String> {
+        let mut words = Vec::with_capacity(chunk.len());
+
+        for word in chunk {
+            match expand(&word) {
+                Some(expansion) => words.extend(expansion.split(' ').map(String::from)),
+                None => words.push(word),
+            }
+        }
+
+        words
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand() {
+        let pairs = [
+            ("can't", "can not"),
+            ("won't", "will not"),
+            ("don't", "do not"),
+            ("they're", "they are"),
+            ("we've", "we have"),
+            ("you'll", "you will"),
+            ("i'm", "i am"),
+            ("she'd", "she would"),
+            ("that's", "that is"),
+            ("let's", "let us"),
+            ("isn’t", "is not"),
+            ("Can't", "Can not"),
+            ("WE'RE", "WE ARE"),
+            ("'Tis", "It is"),
+        ];
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(Some(expected.to_string()), expand(word), "expanding {}", word);
+        }
+
+        for word in ["cat", "dog's", "'s", "n't", "o'clock"].iter() {
+            assert_eq!(None, expand(word), "expanding {}", word);
+        }
+    }
+
+    #[test]
+    fn test_expand_text() {
+        assert_eq!("They are sure it is the dog's, are not they?", expand_text("They're sure it's the dog's, aren't they?"));
+        assert_eq!("", expand_text(""));
+    }
+
+    #[test]
+    fn test_expand_stage() {
+        let chunk = vec!["we're".to_string(), "hoping".to_string()];
+        assert_eq!(vec!["we", "are", "hoping"], ExpandStage.process(chunk));
+    }
+}
@@ -0,0 +1,128 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Dawson stemmer.
+//!
+//! John Dawson's 1974 extension of the Lovins stemmer adds the plural of
+//! every ending to Lovins' list, so `relations` loses `ations` in one step,
+//! and stores the much larger table reverse-indexed by final letter, so only
+//! the endings that could possibly match a word are ever compared. Endings
+//! keep the context condition of the ending they were formed from, and the
+//! stem is respelled as Lovins' algorithm does.
+//!
+//! Words are expected to be lowercase.
+//!
+//! ```
+//! use porter_stemmer::dawson;
+//!
+//! assert_eq!("rel", dawson::stem("relations"));
+//! assert_eq!("nat", dawson::stem("nationally"));
+//! ```
+
+use std::cmp::Reverse;
+use std::sync::OnceLock;
+
+use lovins::{self, Condition, ENDINGS};
+use primitives::ends_with;
+
+/// Endings grouped by their final letter, `a` to `z`, each group longest
+/// first.
+type Table = Vec<Vec<(String, Condition)>>;
+
+fn plural(ending: &str) -> String {
+    let bytes = ending.as_bytes();
+    let last = bytes[bytes.len() - 1];
+    let before = bytes[bytes.len() - 2];
+
+    if last == b'y' && !b"aeiou".contains(&before) {
+        format!("{}ies", &ending[..ending.len() - 1])
+    } else if b"sxz".contains(&last) || ending.ends_with("ch") || ending.ends_with("sh") {
+        format!("{}es", ending)
+    } else {
+        format!("{}s", ending)
+    }
+}
+
+fn table() -> &'static Table {
+    static TABLE: OnceLock<Table> = OnceLock::new();
+
+    TABLE.get_or_init(|| {
+        let mut endings = ENDINGS.iter()
+            .map(|&(ending, condition)| (ending.to_string(), condition))
+            .collect::<Vec<(String, Condition)>>();
+
+        for &(ending, condition) in ENDINGS.iter().filter(|&&(ending, _)| ending.len() > 1) {
+            let plural = plural(ending);
+            if !endings.iter().any(|(existing, _)| *existing == plural) {
+                endings.push((plural, condition));
+            }
+        }
+
+        endings.sort_by_key(|(ending, _)| Reverse(ending.len()));
+
+        let mut table = vec![Vec::new(); 26];
+        for (ending, condition) in endings {
+            let last = ending.as_bytes()[ending.len() - 1];
+            table[(last - b'a') as usize].push((ending, condition));
+        }
+        table
+    })
+}
+
+/// Given a lowercase word, return its stemmed form using Dawson's algorithm.
+pub fn stem(word: &str) -> String {
+    let mut word = word.chars().collect::<Vec<char>>();
+
+    if let Some(&last) = word.last().filter(|last| last.is_ascii_lowercase()) {
+        let length = word.len();
+        let ending = table()[(last as u8 - b'a') as usize].iter()
+            .find(|(ending, condition)| {
+                ends_with(&word, ending) && condition.holds(&word[..length - ending.len()])
+            });
+
+        if let Some((ending, _)) = ending {
+            word.truncate(length - ending.len());
+        }
+    }
+
+    lovins::undouble(&mut word);
+    lovins::respell(&mut word);
+
+    word.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table() {
+        let table = table();
+
+        assert!(table.iter().map(Vec::len).sum::<usize>() > ENDINGS.len());
+        for group in table.iter() {
+            for pair in group.windows(2) {
+                assert!(pair[0].0.len() >= pair[1].0.len());
+            }
+        }
+        assert!(table[(b's' - b'a') as usize].iter().any(|(ending, _)| ending == "abilities"));
+    }
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("relations", "rel"),
+            ("nationally", "nat"),
+            ("abilities", "abil"),
+            ("sittings", "sit"),
+            ("believe", "belief"),
+            ("matrices", "matric"),
+            ("2nd", "2nd"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
@@ -0,0 +1,216 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Guessing the language of a text, so that mixed corpora can be stemmed
+//! without knowing each document's language up front.
+//!
+//! Most languages here have a script of their own, and for those the script
+//! decides. Languages that share a script are told apart by their most
+//! common words, such as `the` and `and` for English, and by letters only
+//! they use, such as `ñ` for Spanish. This works well on sentences and
+//! documents, but a single word often gives too little to go on.
+//!
+//! ```
+//! use porter_stemmer::Language;
+//! use porter_stemmer::detect::{detect, stem_text};
+//!
+//! assert_eq!(Some(Language::French), detect("Le chat est sur la table"));
+//! assert_eq!(vec!["le", "chat", "dort", "dan", "le", "jardin"], stem_text("Le chat dort dans le jardin"));
+//! ```
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use language::Language;
+use snowball::turkish;
+
+/// Common words of the languages written in the Latin script.
+const LATIN_WORDS: &[(Language, &[&str])] = &[
+    (Language::English, &["the", "and", "of", "to", "is", "in", "that", "it", "with", "for", "was", "are"]),
+    (Language::French, &["le", "la", "les", "et", "est", "un", "une", "des", "du", "dans", "que", "pour", "pas"]),
+    (Language::German, &["der", "die", "das", "und", "ist", "nicht", "ein", "eine", "zu", "mit", "den", "auf", "sich"]),
+    (Language::Spanish, &["el", "los", "las", "y", "es", "un", "una", "del", "en", "que", "por", "para", "con"]),
+    (Language::Italian, &["il", "gli", "e", "è", "di", "che", "per", "una", "non", "sono", "della", "con"]),
+    (Language::Portuguese, &["o", "os", "as", "e", "é", "um", "uma", "do", "da", "em", "que", "não", "com"]),
+    (Language::Dutch, &["de", "het", "een", "en", "is", "van", "niet", "dat", "op", "te", "zijn", "met"]),
+    (Language::Swedish, &["och", "är", "att", "det", "som", "en", "ett", "inte", "på", "med", "jag", "av"]),
+    (Language::Norwegian, &["og", "er", "ikke", "det", "som", "en", "et", "på", "med", "jeg", "av", "til"]),
+    (Language::Danish, &["og", "er", "ikke", "det", "som", "en", "et", "på", "med", "jeg", "af", "til"]),
+    (Language::Finnish, &["ja", "on", "ei", "että", "se", "hän", "oli", "ovat", "mutta", "kun"]),
+    (Language::Romanian, &["și", "este", "în", "un", "o", "cu", "pe", "nu", "care", "din", "la"]),
+    (Language::Hungarian, &["a", "az", "és", "egy", "hogy", "nem", "van", "is", "meg", "de"]),
+    (Language::Turkish, &["ve", "bir", "bu", "da", "de", "için", "ile", "çok", "ne", "değil"]),
+    (Language::Indonesian, &["dan", "yang", "di", "ini", "itu", "dengan", "tidak", "untuk", "ada", "dari"]),
+    (Language::Basque, &["eta", "da", "ez", "bat", "du", "dira", "baina", "ere"]),
+    (Language::Catalan, &["el", "la", "els", "les", "i", "és", "un", "una", "amb", "per", "que", "no"]),
+    (Language::Irish, &["agus", "an", "na", "is", "ar", "bhí", "sé", "sí", "go", "le"]),
+    (Language::Lithuanian, &["ir", "yra", "kad", "su", "ne", "tai", "bet", "į", "jis", "ji"]),
+    (Language::Serbian, &["i", "je", "u", "na", "da", "se", "su", "od", "za", "ne"]),
+];
+
+/// Letters only one of the languages written in the Latin script uses.
+const LATIN_LETTERS: &[(Language, &str)] = &[
+    (Language::Spanish, "ñ¿¡"),
+    (Language::German, "ß"),
+    (Language::Portuguese, "ãõ"),
+    (Language::Dutch, "ĳ"),
+    (Language::Romanian, "șțşţ"),
+    (Language::Hungarian, "őű"),
+    (Language::Turkish, "ğı"),
+    (Language::Lithuanian, "ąęėįųū"),
+    (Language::Serbian, "đćž"),
+    (Language::Catalan, "·"),
+];
+
+/// Common words of the languages written in Devanagari.
+const DEVANAGARI_WORDS: &[(Language, &[&str])] = &[
+    (Language::Hindi, &["है", "और", "के", "की", "में", "का", "यह", "से", "नहीं", "हैं"]),
+    (Language::Nepali, &["छ", "र", "मा", "यो", "हो", "पनि", "थियो", "गर्न", "छन्", "भन्ने"]),
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    Armenian,
+    Hebrew,
+    Arabic,
+    Devanagari,
+    Tamil,
+}
+
+fn script(c: char) -> Option<Script> {
+    match c {
+        '\u{370}'..='\u{3FF}' | '\u{1F00}'..='\u{1FFF}' => Some(Script::Greek),
+        '\u{400}'..='\u{4FF}' => Some(Script::Cyrillic),
+        '\u{530}'..='\u{58F}' => Some(Script::Armenian),
+        '\u{590}'..='\u{5FF}' => Some(Script::Hebrew),
+        '\u{600}'..='\u{6FF}' => Some(Script::Arabic),
+        '\u{900}'..='\u{97F}' => Some(Script::Devanagari),
+        '\u{B80}'..='\u{BFF}' => Some(Script::Tamil),
+        c if c.is_alphabetic() => Some(Script::Latin),
+        _ => None,
+    }
+}
+
+/// Guess the language `text` is written in, or `None` if there is nothing
+/// to go on.
+pub fn detect(text: &str) -> Option<Language> {
+    let scripts = [
+        Script::Latin, Script::Greek, Script::Cyrillic, Script::Armenian, Script::Hebrew,
+        Script::Arabic, Script::Devanagari, Script::Tamil,
+    ];
+    let dominant = scripts.iter()
+        .map(|&candidate| (candidate, text.chars().filter(|&c| script(c) == Some(candidate)).count()))
+        .filter(|&(_, count)| count > 0)
+        .max_by_key(|&(_, count)| count);
+
+    let words = text.unicode_words().map(|word| word.to_lowercase()).collect::<Vec<String>>();
+
+    match dominant?.0 {
+        Script::Latin => most_likely(&words, LATIN_WORDS, |language, word| {
+            LATIN_LETTERS.iter()
+                .filter(|&&(candidate, _)| candidate == language)
+                .map(|&(_, letters)| word.chars().filter(|&c| letters.contains(c)).count())
+                .sum()
+        }),
+        Script::Greek => Some(Language::Greek),
+        Script::Cyrillic if text.chars().any(|c| "ђјљњћџЂЈЉЊЋЏ".contains(c)) => Some(Language::Serbian),
+        Script::Cyrillic => Some(Language::Russian),
+        Script::Armenian => Some(Language::Armenian),
+        Script::Hebrew => Some(Language::Yiddish),
+        Script::Arabic => Some(Language::Arabic),
+        Script::Devanagari => most_likely(&words, DEVANAGARI_WORDS, |_, _| 0).or(Some(Language::Hindi)),
+        Script::Tamil => Some(Language::Tamil),
+    }
+}
+
+/// The language scoring highest on its common words and on `letters`.
+/// Ties go to the language listed first.
+fn most_likely<F>(words: &[String], common: &[(Language, &[&str])], letters: F) -> Option<Language>
+    where F: Fn(Language, &str) -> usize
+{
+    let mut best = None;
+    let mut best_score = 0;
+
+    for &(language, common) in common {
+        let score = words.iter()
+            .map(|word| letters(language, word) + if common.contains(&word.as_str()) { 1 } else { 0 })
+            .sum::<usize>();
+
+        if score > best_score {
+            best = Some(language);
+            best_score = score;
+        }
+    }
+
+    best
+}
+
+/// Split `text` into words, detect its language once for the whole text,
+/// and stem every word with that language's stemmer. Text with no clear
+/// language is stemmed as English.
+pub fn stem_text(text: &str) -> Vec<String> {
+    let language = detect(text).unwrap_or_default();
+
+    text.unicode_words()
+        .map(|word| match language {
+            Language::Turkish => language.stem(&turkish::to_lowercase(word)),
+            _ => language.stem(&word.to_lowercase()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_by_script() {
+        assert_eq!(Some(Language::Greek), detect("ο άνθρωπος"));
+        assert_eq!(Some(Language::Russian), detect("Мы читали книги"));
+        assert_eq!(Some(Language::Serbian), detect("Читао сам књигу"));
+        assert_eq!(Some(Language::Arabic), detect("والكتاب"));
+        assert_eq!(Some(Language::Armenian), detect("քաղաքները"));
+        assert_eq!(Some(Language::Yiddish), detect("די קינדער"));
+        assert_eq!(Some(Language::Tamil), detect("மரங்கள்"));
+        assert_eq!(None, detect("1234 !?"));
+        assert_eq!(None, detect(""));
+    }
+
+    #[test]
+    fn test_detect_by_words() {
+        let pairs = [
+            ("The cat sat on the mat and it was happy", Language::English),
+            ("Der Hund ist nicht im Garten", Language::German),
+            ("El perro y los gatos están en el jardín", Language::Spanish),
+            ("Il cane e il gatto sono in giardino", Language::Italian),
+            ("De hond en de kat zijn in de tuin", Language::Dutch),
+            ("Hunden och katten är i trädgården", Language::Swedish),
+            ("Koira ja kissa ovat puutarhassa", Language::Finnish),
+            ("Bu kedi ve bir köpek", Language::Turkish),
+            ("Kucing dan anjing yang ada di taman", Language::Indonesian),
+            ("यह किताब मेरी है और वह तुम्हारी है", Language::Hindi),
+            ("यो किताब मेरो हो र त्यो तिम्रो हो", Language::Nepali),
+        ];
+
+        for &(text, expected) in pairs.iter() {
+            assert_eq!(Some(expected), detect(text), "detecting {}", text);
+        }
+    }
+
+    #[test]
+    fn test_detect_by_letters() {
+        assert_eq!(Some(Language::Spanish), detect("mañana"));
+        assert_eq!(Some(Language::Hungarian), detect("kőműves"));
+        assert_eq!(None, detect("xyz"));
+    }
+
+    #[test]
+    fn test_stem_text() {
+        assert_eq!(vec!["der", "hund", "ist", "nicht", "im", "gart"], stem_text("Der Hund ist nicht im Garten"));
+        assert_eq!(vec!["connect", "reader"], stem_text("connected readers"));
+        assert!(stem_text("").is_empty());
+    }
+}
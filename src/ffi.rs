@@ -0,0 +1,283 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! A C interface, for linking the stemmer into C and C++ programs.
+//!
+//! `include/porter_stemmer.h` declares these functions, and is generated
+//! from this module by `cbindgen`. Words and stems are NUL-terminated UTF-8,
+//! and stems are written to buffers the caller provides:
+//!
+//! ```c
+//! char stem[64];
+//! if (porter_stem("hopefulness", stem, sizeof stem) < sizeof stem) {
+//!     puts(stem); /* hope */
+//! }
+//! ```
+//!
+//! Requires the `ffi` feature.
+
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::ptr;
+use std::slice;
+
+use algorithm::{Algorithm, Stemmer};
+use language::Language;
+
+/// Returned instead of a length when a word is null or isn't UTF-8.
+pub const PORTER_STEM_ERROR: usize = usize::MAX;
+
+/// A stemmer chosen by name, for `porter_stemmer_stem`.
+///
+/// A stemmer may be used from several threads at once.
+pub struct PorterStemmer {
+    stemmer: Box<dyn Stemmer + Send + Sync>,
+}
+
+/// The stemmer behind `stemmer`, or Porter's if it's null.
+unsafe fn stemmer<'a>(stemmer: *const PorterStemmer) -> &'a dyn Stemmer {
+    match stemmer.as_ref() {
+        Some(stemmer) => &*stemmer.stemmer,
+        None => &Algorithm::Porter,
+    }
+}
+
+/// The UTF-8 string at `string`, unless it's null or not UTF-8.
+unsafe fn string<'a>(string: *const c_char) -> Option<&'a str> {
+    if string.is_null() {
+        return None;
+    }
+
+    CStr::from_ptr(string).to_str().ok()
+}
+
+/// Copy `bytes` to `out` if there's room for them, returning their length.
+unsafe fn write(bytes: &[u8], out: *mut c_char, cap: usize) -> usize {
+    if !out.is_null() && bytes.len() <= cap {
+        ptr::copy_nonoverlapping(bytes.as_ptr(), out as *mut u8, bytes.len());
+    }
+
+    bytes.len()
+}
+
+/// Create a stemmer for the algorithm or language `name`, as listed by
+/// `porter-stemmer --list`, or Porter's algorithm if `name` is null.
+///
+/// Returns null if there is no such algorithm or language. The stemmer must
+/// be freed with `porter_stemmer_free`.
+///
+/// # Safety
+///
+/// `name` must be null or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn porter_stemmer_new(name: *const c_char) -> *mut PorterStemmer {
+    let stemmer: Box<dyn Stemmer + Send + Sync> = if name.is_null() {
+        Box::new(Algorithm::Porter)
+    } else {
+        let name = match string(name) {
+            Some(name) => name,
+            None => return ptr::null_mut(),
+        };
+
+        if let Ok(algorithm) = name.parse::<Algorithm>() {
+            Box::new(algorithm)
+        } else if let Ok(language) = name.parse::<Language>() {
+            Box::new(language)
+        } else {
+            return ptr::null_mut();
+        }
+    };
+
+    Box::into_raw(Box::new(PorterStemmer { stemmer }))
+}
+
+/// Free a stemmer created by `porter_stemmer_new`. Does nothing if
+/// `stemmer` is null.
+///
+/// # Safety
+///
+/// `stemmer` must be null or a stemmer from `porter_stemmer_new` that
+/// hasn't already been freed, and mustn't be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn porter_stemmer_free(stemmer: *mut PorterStemmer) {
+    if !stemmer.is_null() {
+        drop(Box::from_raw(stemmer));
+    }
+}
+
+/// Stem `word` with `stemmer`, or Porter's algorithm if it's null, writing
+/// the stem and a terminating NUL to `out`.
+///
+/// Returns the length of the stem, without its NUL. Like `snprintf`, if
+/// that isn't less than `cap` then nothing is written, and the call can be
+/// repeated with a big enough buffer. Returns `PORTER_STEM_ERROR` if `word`
+/// is null or isn't UTF-8.
+///
+/// # Safety
+///
+/// `stemmer` must be null or a live stemmer from `porter_stemmer_new`,
+/// `word` must be null or point to a NUL-terminated string, and `out` must
+/// be null or point to at least `cap` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn porter_stemmer_stem(stemmer: *const PorterStemmer, word: *const c_char, out: *mut c_char, cap: usize) -> usize {
+    let word = match string(word) {
+        Some(word) => word,
+        None => return PORTER_STEM_ERROR,
+    };
+
+    let mut stem = self::stemmer(stemmer).stem(word).into_bytes();
+    stem.push(0);
+    write(&stem, out, cap) - 1
+}
+
+/// Stem each of the `count` words in `words` with `stemmer`, or Porter's
+/// algorithm if it's null, writing the stems to `out` one after another,
+/// each followed by a NUL.
+///
+/// If `offsets` isn't null, the position in `out` of each stem is written
+/// to it, so `out + offsets[i]` is the `i`th stem.
+///
+/// Returns the number of bytes the stems take up, including their NULs. If
+/// that's more than `cap` then nothing is written. Returns
+/// `PORTER_STEM_ERROR` if any word is null or isn't UTF-8.
+///
+/// # Safety
+///
+/// `stemmer` must be null or a live stemmer from `porter_stemmer_new`,
+/// `words` must point to `count` pointers to NUL-terminated strings, `out`
+/// must be null or point to at least `cap` writable bytes, and `offsets`
+/// must be null or point to room for `count` offsets.
+#[no_mangle]
+pub unsafe extern "C" fn porter_stemmer_stem_batch(
+    stemmer: *const PorterStemmer,
+    words: *const *const c_char,
+    count: usize,
+    out: *mut c_char,
+    cap: usize,
+    offsets: *mut usize,
+) -> usize {
+    if words.is_null() && count > 0 {
+        return PORTER_STEM_ERROR;
+    }
+    let words = if count == 0 { &[] } else { slice::from_raw_parts(words, count) };
+    let stemmer = self::stemmer(stemmer);
+
+    let mut stems = Vec::new();
+    let mut starts = Vec::with_capacity(count);
+    for &word in words {
+        let word = match string(word) {
+            Some(word) => word,
+            None => return PORTER_STEM_ERROR,
+        };
+
+        starts.push(stems.len());
+        stems.extend_from_slice(stemmer.stem(word).as_bytes());
+        stems.push(0);
+    }
+
+    if !out.is_null() && !offsets.is_null() && stems.len() <= cap {
+        ptr::copy_nonoverlapping(starts.as_ptr(), offsets, count);
+    }
+    write(&stems, out, cap)
+}
+
+/// Stem `word` with Porter's algorithm, as `porter_stemmer_stem` does with
+/// a null stemmer.
+///
+/// # Safety
+///
+/// As for `porter_stemmer_stem`.
+#[no_mangle]
+pub unsafe extern "C" fn porter_stem(word: *const c_char, out: *mut c_char, cap: usize) -> usize {
+    porter_stemmer_stem(ptr::null(), word, out, cap)
+}
+
+/// Stem each of `words` with Porter's algorithm, as
+/// `porter_stemmer_stem_batch` does with a null stemmer.
+///
+/// # Safety
+///
+/// As for `porter_stemmer_stem_batch`.
+#[no_mangle]
+pub unsafe extern "C" fn porter_stem_batch(
+    words: *const *const c_char,
+    count: usize,
+    out: *mut c_char,
+    cap: usize,
+    offsets: *mut usize,
+) -> usize {
+    porter_stemmer_stem_batch(ptr::null(), words, count, out, cap, offsets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ffi::CString;
+
+    fn stem_with(stemmer: *const PorterStemmer, word: &str, cap: usize) -> (usize, String) {
+        let word = CString::new(word).unwrap();
+        let mut out = vec![b'#' as c_char; cap];
+        let length = unsafe { porter_stemmer_stem(stemmer, word.as_ptr(), out.as_mut_ptr(), cap) };
+
+        let written = out.iter().take_while(|&&c| c != 0).map(|&c| c as u8).collect::<Vec<_>>();
+        (length, String::from_utf8(written).unwrap())
+    }
+
+    #[test]
+    fn test_porter_stem() {
+        let word = CString::new("hopefulness").unwrap();
+        let mut out = [0 as c_char; 8];
+
+        assert_eq!(4, unsafe { porter_stem(word.as_ptr(), out.as_mut_ptr(), out.len()) });
+        assert_eq!(b"hope\0", unsafe { CStr::from_ptr(out.as_ptr()) }.to_bytes_with_nul());
+
+        assert_eq!((4, "hope".to_string()), stem_with(ptr::null(), "hopefulness", 5));
+        // No room for the NUL, so nothing is written.
+        assert_eq!((4, "####".to_string()), stem_with(ptr::null(), "hopefulness", 4));
+        assert_eq!(4, unsafe { porter_stem(word.as_ptr(), ptr::null_mut(), 0) });
+
+        let invalid = [0xffu8 as c_char, 0];
+        assert_eq!(PORTER_STEM_ERROR, unsafe { porter_stem(invalid.as_ptr(), out.as_mut_ptr(), out.len()) });
+        assert_eq!(PORTER_STEM_ERROR, unsafe { porter_stem(ptr::null(), out.as_mut_ptr(), out.len()) });
+    }
+
+    #[test]
+    fn test_porter_stemmer() {
+        unsafe {
+            let german = porter_stemmer_new(CString::new("german").unwrap().as_ptr());
+            let lovins = porter_stemmer_new(CString::new("lovins").unwrap().as_ptr());
+            let porter = porter_stemmer_new(ptr::null());
+
+            assert_eq!((4, "Haus".to_string()), stem_with(german, "Häuser", 16));
+            assert_eq!((3, "hop".to_string()), stem_with(lovins, "hopeful", 16));
+            assert_eq!((4, "hope".to_string()), stem_with(porter, "hopeful", 16));
+            assert!(porter_stemmer_new(CString::new("klingon").unwrap().as_ptr()).is_null());
+
+            porter_stemmer_free(german);
+            porter_stemmer_free(lovins);
+            porter_stemmer_free(porter);
+            porter_stemmer_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_porter_stem_batch() {
+        let words = ["connected", "ponies", ""].iter().map(|&word| CString::new(word).unwrap()).collect::<Vec<_>>();
+        let pointers = words.iter().map(|word| word.as_ptr()).collect::<Vec<_>>();
+        let mut out = [0 as c_char; 32];
+        let mut offsets = [0; 3];
+
+        let length = unsafe { porter_stem_batch(pointers.as_ptr(), 3, out.as_mut_ptr(), out.len(), offsets.as_mut_ptr()) };
+        assert_eq!(14, length);
+        assert_eq!([0, 8, 13], offsets);
+        assert_eq!(b"connect\0poni\0\0", &out[..14].iter().map(|&c| c as u8).collect::<Vec<_>>()[..]);
+
+        assert_eq!(14, unsafe { porter_stem_batch(pointers.as_ptr(), 3, ptr::null_mut(), 0, ptr::null_mut()) });
+        assert_eq!(0, unsafe { porter_stem_batch(ptr::null(), 0, out.as_mut_ptr(), out.len(), ptr::null_mut()) });
+
+        let with_null = [pointers[0], ptr::null()];
+        assert_eq!(PORTER_STEM_ERROR, unsafe { porter_stem_batch(with_null.as_ptr(), 2, out.as_mut_ptr(), out.len(), ptr::null_mut()) });
+    }
+}
@@ -0,0 +1,459 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Stemming with a Hunspell dictionary.
+//!
+//! Hunspell dictionaries come as a pair of files: a `.dic` file listing root
+//! words, each with the flags of the affixes it takes, and an `.aff` file
+//! defining those affixes. A word is stemmed by undoing an affix and
+//! checking that the result is a root word that takes it, so the stem is
+//! always a real word and is right for every language a dictionary exists
+//! for. Words the dictionary can't analyse, and every word when no
+//! dictionary is given, are stemmed by Porter's algorithm instead.
+//!
+//! Prefixes, suffixes and their cross products are supported, along with the
+//! `FLAG` types. Compounding, continuation classes and the other options of
+//! the `.aff` format are ignored.
+//!
+//! ```
+//! use porter_stemmer::hunspell::Hunspell;
+//!
+//! let aff = "
+//! SFX S Y 2
+//! SFX S 0 s [^sxy]
+//! SFX S y ies [^aeiou]y
+//! PFX U Y 1
+//! PFX U 0 un .
+//! SFX D Y 1
+//! SFX D 0 ed [^ey]
+//! ";
+//! let dic = "2\ncity/S\nwalk/DU\n";
+//!
+//! let hunspell = Hunspell::with_dictionary(aff, dic).unwrap();
+//! assert_eq!("city", hunspell.stem("cities"));
+//! assert_eq!("walk", hunspell.stem("unwalked"));
+//! assert_eq!("connect", hunspell.stem("connections"));
+//! ```
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use algorithm::Stemmer;
+
+/// How flags are written in a dictionary.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FlagType {
+    /// One character per flag, the default.
+    Char,
+    /// Two characters per flag.
+    Long,
+    /// Comma separated decimal numbers.
+    Number,
+}
+
+#[derive(Clone, Debug)]
+enum Condition {
+    Any,
+    Char(char),
+    Set(bool, Vec<char>),
+}
+
+impl Condition {
+    fn matches(&self, c: char) -> bool {
+        match *self {
+            Condition::Any => true,
+            Condition::Char(expected) => c == expected,
+            Condition::Set(negated, ref set) => set.contains(&c) != negated,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Affix {
+    prefix: bool,
+    flag: String,
+    cross_product: bool,
+    strip: String,
+    add: String,
+    condition: Vec<Condition>,
+}
+
+/// A Hunspell dictionary, or none, along with the affixes it uses.
+#[derive(Clone, Debug, Default)]
+pub struct Hunspell {
+    words: HashMap<String, Vec<String>>,
+    prefixes: Vec<Affix>,
+    suffixes: Vec<Affix>,
+}
+
+/// The error returned when a Hunspell dictionary cannot be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseHunspellError {
+    file: &'static str,
+    line: usize,
+    message: String,
+}
+
+impl fmt::Display for ParseHunspellError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} file, line {}: {}", self.file, self.line, self.message)
+    }
+}
+
+impl Error for ParseHunspellError {}
+
+fn error<T>(file: &'static str, line: usize, message: String) -> Result<T, ParseHunspellError> {
+    Err(ParseHunspellError { file, line, message })
+}
+
+impl Hunspell {
+    /// No dictionary at all, so that every word is stemmed by Porter's
+    /// algorithm.
+    pub fn new() -> Hunspell {
+        Hunspell::default()
+    }
+
+    /// A dictionary, given the contents of its `.aff` and `.dic` files.
+    pub fn with_dictionary(aff: &str, dic: &str) -> Result<Hunspell, ParseHunspellError> {
+        let mut hunspell = Hunspell::new();
+        let flag_type = hunspell.parse_aff(aff)?;
+        hunspell.parse_dic(dic, flag_type)?;
+        Ok(hunspell)
+    }
+
+    /// A dictionary, read from its `.aff` and `.dic` files. The files must
+    /// be UTF-8.
+    pub fn open<P: AsRef<Path>, Q: AsRef<Path>>(aff: P, dic: Q) -> io::Result<Hunspell> {
+        let aff = fs::read_to_string(aff)?;
+        let dic = fs::read_to_string(dic)?;
+
+        Hunspell::with_dictionary(&aff, &dic).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    fn parse_aff(&mut self, aff: &str) -> Result<FlagType, ParseHunspellError> {
+        let mut flag_type = FlagType::Char;
+        // The flag, kind and cross product setting of the affix class whose
+        // rules are being read, and how many of them are left.
+        let mut class: Option<(String, String, bool)> = None;
+        let mut remaining = 0;
+
+        for (index, line) in aff.lines().enumerate() {
+            let line_number = index + 1;
+            let fields = line.split_whitespace().collect::<Vec<&str>>();
+
+            match fields[..] {
+                ["FLAG", kind, ..] => {
+                    flag_type = match kind {
+                        "long" => FlagType::Long,
+                        "num" => FlagType::Number,
+                        "UTF-8" => FlagType::Char,
+                        _ => return error("affix", line_number, format!("unknown flag type `{}`", kind)),
+                    };
+                }
+                [kind, flag, cross_product, count, ..] if (kind == "PFX" || kind == "SFX") && remaining == 0 => {
+                    remaining = match count.parse::<usize>() {
+                        Ok(count) => count,
+                        Err(_) => return error("affix", line_number, format!("invalid rule count `{}`", count)),
+                    };
+                    class = Some((kind.to_string(), flag.to_string(), cross_product == "Y"));
+                }
+                [kind, flag, strip, add, ref condition @ ..] if kind == "PFX" || kind == "SFX" => {
+                    let (class_kind, class_flag, cross_product) = match class {
+                        Some((ref class_kind, ref class_flag, cross_product)) if class_kind == kind && class_flag == flag => {
+                            (class_kind.clone(), class_flag.clone(), cross_product)
+                        }
+                        _ => return error("affix", line_number, format!("rule for undeclared affix `{}`", flag)),
+                    };
+
+                    let condition = match condition.first() {
+                        Some(condition) => parse_condition(condition)
+                            .map_or_else(|| error("affix", line_number, format!("invalid condition `{}`", condition)), Ok)?,
+                        None => Vec::new(),
+                    };
+                    // Continuation classes after a `/` are not supported.
+                    let add = add.split('/').next().unwrap_or("");
+                    let affix = Affix {
+                        prefix: class_kind == "PFX",
+                        flag: class_flag,
+                        cross_product,
+                        strip: if strip == "0" { String::new() } else { strip.to_string() },
+                        add: if add == "0" { String::new() } else { add.to_string() },
+                        condition,
+                    };
+
+                    if affix.prefix {
+                        self.prefixes.push(affix);
+                    } else {
+                        self.suffixes.push(affix);
+                    }
+                    remaining -= 1;
+                }
+                [kind, ..] if kind == "PFX" || kind == "SFX" => {
+                    return error("affix", line_number, format!("expected four fields, found {}", fields.len()));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(flag_type)
+    }
+
+    fn parse_dic(&mut self, dic: &str, flag_type: FlagType) -> Result<(), ParseHunspellError> {
+        for (index, line) in dic.lines().enumerate() {
+            // Morphological fields follow the word after a tab.
+            let entry = line.split('\t').next().unwrap_or("").trim();
+
+            if entry.is_empty() || (index == 0 && entry.chars().all(|c| c.is_ascii_digit())) {
+                continue;
+            }
+
+            let (word, flags) = match entry.find('/') {
+                Some(slash) => (&entry[..slash], parse_flags(&entry[slash + 1..], flag_type)),
+                None => (entry, Some(Vec::new())),
+            };
+            let flags = flags.map_or_else(|| error("dictionary", index + 1, format!("invalid flags in `{}`", entry)), Ok)?;
+
+            self.words.entry(word.to_string()).or_default().extend(flags);
+        }
+
+        Ok(())
+    }
+
+    /// Whether the dictionary has `word` as a root word.
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains_key(word)
+    }
+
+    fn takes(&self, word: &str, flags: &[&str]) -> bool {
+        match self.words.get(word) {
+            Some(word_flags) => flags.iter().all(|&flag| word_flags.iter().any(|word_flag| word_flag == flag)),
+            None => false,
+        }
+    }
+
+    /// Every root word `word` can be formed from, in the order they were
+    /// found: the word itself, then roots with a suffix, with a prefix and
+    /// suffix, and with a prefix.
+    pub fn stems(&self, word: &str) -> Vec<String> {
+        let mut stems = Vec::new();
+
+        if self.contains(word) {
+            stems.push(word.to_string());
+        }
+
+        for suffix in &self.suffixes {
+            let root = match suffix.remove(word) {
+                Some(root) => root,
+                None => continue,
+            };
+
+            if self.takes(&root, &[&suffix.flag]) {
+                stems.push(root.clone());
+            }
+
+            if !suffix.cross_product {
+                continue;
+            }
+
+            for prefix in self.prefixes.iter().filter(|prefix| prefix.cross_product) {
+                match prefix.remove(&root) {
+                    Some(inner) if self.takes(&inner, &[&suffix.flag, &prefix.flag]) => stems.push(inner),
+                    _ => {}
+                }
+            }
+        }
+
+        for prefix in &self.prefixes {
+            match prefix.remove(word) {
+                Some(root) if self.takes(&root, &[&prefix.flag]) => stems.push(root),
+                _ => {}
+            }
+        }
+
+        let mut unique = Vec::with_capacity(stems.len());
+        for stem in stems {
+            if !unique.contains(&stem) {
+                unique.push(stem);
+            }
+        }
+        unique
+    }
+
+    /// Given a word, return the first root word it can be formed from, or
+    /// its Porter stem if there is none.
+    pub fn stem(&self, word: &str) -> String {
+        self.stems(word).into_iter().next().unwrap_or_else(|| ::stem(word))
+    }
+}
+
+impl Stemmer for Hunspell {
+    fn stem(&self, word: &str) -> String {
+        Hunspell::stem(self, word)
+    }
+}
+
+impl Affix {
+    /// Undo this affix, returning the root `word` would be formed from if the
+    /// root meets the affix's condition.
+    fn remove(&self, word: &str) -> Option<String> {
+        let root = if self.prefix {
+            let rest = word.strip_prefix(&self.add[..])?;
+            format!("{}{}", self.strip, rest)
+        } else {
+            let rest = word.strip_suffix(&self.add[..])?;
+            format!("{}{}", rest, self.strip)
+        };
+
+        let chars = root.chars().collect::<Vec<char>>();
+        if chars.is_empty() || chars.len() < self.condition.len() {
+            return None;
+        }
+
+        let start = if self.prefix { 0 } else { chars.len() - self.condition.len() };
+        let meets_condition = self.condition.iter()
+            .zip(&chars[start..])
+            .all(|(condition, &c)| condition.matches(c));
+
+        if meets_condition {
+            Some(root)
+        } else {
+            None
+        }
+    }
+}
+
+/// Parse a rule's condition, a pattern made of characters, `.` for any
+/// character and bracketed sets such as `[aeiou]` and `[^aeiou]`.
+fn parse_condition(pattern: &str) -> Option<Vec<Condition>> {
+    if pattern == "." {
+        return Some(Vec::new());
+    }
+
+    let mut condition = Vec::new();
+    let mut chars = pattern.chars();
+
+    while let Some(c) = chars.next() {
+        condition.push(match c {
+            '.' => Condition::Any,
+            '[' => {
+                let mut set = chars.by_ref().take_while(|&c| c != ']').collect::<Vec<char>>();
+                let negated = set.first() == Some(&'^');
+                if negated {
+                    set.remove(0);
+                }
+                Condition::Set(negated, set)
+            }
+            ']' => return None,
+            c => Condition::Char(c),
+        });
+    }
+
+    Some(condition)
+}
+
+fn parse_flags(flags: &str, flag_type: FlagType) -> Option<Vec<String>> {
+    match flag_type {
+        FlagType::Char => Some(flags.chars().map(|c| c.to_string()).collect()),
+        FlagType::Long => {
+            let chars = flags.chars().collect::<Vec<char>>();
+            if chars.len() % 2 == 1 {
+                return None;
+            }
+            Some(chars.chunks(2).map(|pair| pair.iter().collect()).collect())
+        }
+        FlagType::Number => flags.split(',')
+            .map(|flag| flag.trim().parse::<u16>().ok().map(|flag| flag.to_string()))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFF: &str = "
+SET UTF-8
+# Plurals and past tenses.
+SFX S Y 3
+SFX S 0 s [^sxy]
+SFX S y ies [^aeiou]y
+SFX S 0 es [sx]
+SFX D Y 2
+SFX D 0 ed [^ey]
+SFX D 0 d e
+PFX U Y 1
+PFX U 0 un .
+PFX R N 1
+PFX R 0 re .
+";
+
+    const DIC: &str = "5
+city/S
+box/S
+walk/DU
+hope/DR
+house/S	po:noun
+";
+
+    #[test]
+    fn test_stems() {
+        let hunspell = Hunspell::with_dictionary(AFF, DIC).unwrap();
+
+        let pairs = [
+            ("cities", "city"),
+            ("boxes", "box"),
+            ("houses", "house"),
+            ("walked", "walk"),
+            ("unwalked", "walk"),
+            ("hoped", "hope"),
+            ("rehope", "hope"),
+            ("walk", "walk"),
+        ];
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(vec![expected.to_string()], hunspell.stems(word), "stemming {}", word);
+        }
+
+        assert!(hunspell.stems("rehoped").is_empty());
+        assert!(hunspell.stems("citys").is_empty());
+        assert!(hunspell.stems("unhoped").is_empty());
+    }
+
+    #[test]
+    fn test_fallback() {
+        let hunspell = Hunspell::with_dictionary(AFF, DIC).unwrap();
+        assert_eq!("connect", hunspell.stem("connections"));
+        assert_eq!("city", hunspell.stem("cities"));
+
+        let empty = Hunspell::new();
+        assert_eq!("citi", empty.stem("cities"));
+        assert!(!empty.contains("city"));
+    }
+
+    #[test]
+    fn test_flag_types() {
+        let aff = "FLAG long\nSFX Aa Y 1\nSFX Aa 0 s .\n";
+        let hunspell = Hunspell::with_dictionary(aff, "1\ncat/AaBb\n").unwrap();
+        assert_eq!(vec!["cat".to_string()], hunspell.stems("cats"));
+
+        let aff = "FLAG num\nSFX 101 Y 1\nSFX 101 0 s .\n";
+        let hunspell = Hunspell::with_dictionary(aff, "1\ncat/7,101\n").unwrap();
+        assert_eq!(vec!["cat".to_string()], hunspell.stems("cats"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = Hunspell::with_dictionary("SFX S Y x\n", "").unwrap_err();
+        assert_eq!("affix file, line 1: invalid rule count `x`", error.to_string());
+
+        let error = Hunspell::with_dictionary("SFX S Y 1\nSFX T 0 s .\n", "").unwrap_err();
+        assert_eq!("affix file, line 2: rule for undeclared affix `T`", error.to_string());
+
+        let error = Hunspell::with_dictionary("FLAG long\n", "1\ncat/A\n").unwrap_err();
+        assert_eq!("dictionary file, line 2: invalid flags in `cat/A`", error.to_string());
+    }
+}
@@ -0,0 +1,185 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Sharing one copy of each distinct stem.
+//!
+//! A bag-of-words model of a large corpus holds millions of stems, but only
+//! tens of thousands of different ones. `InterningStemmer` keeps a single
+//! copy of each stem it produces and hands out shared references to it,
+//! either as an `Arc<str>` or as a `Symbol`, a small integer standing in for
+//! the stem that can be resolved back to it.
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use porter_stemmer::Algorithm;
+//! use porter_stemmer::intern::InterningStemmer;
+//!
+//! let stemmer = InterningStemmer::new(Algorithm::Porter);
+//!
+//! let hopes = stemmer.stem("hopes");
+//! let hoping = stemmer.stem("hoping");
+//! assert_eq!("hope", &*hoping);
+//! assert!(Arc::ptr_eq(&hopes, &hoping));
+//!
+//! let symbol = stemmer.symbol("hopefulness");
+//! assert_eq!(symbol, stemmer.symbol("hoped"));
+//! assert_eq!(Some(hopes), stemmer.resolve(symbol));
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use algorithm::Stemmer;
+
+/// A stand-in for a stem interned by an `InterningStemmer`.
+///
+/// Symbols are numbered from zero in the order their stems were first seen,
+/// and are only meaningful to the stemmer that produced them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// The symbol's number, suitable for indexing a table of stems.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Default)]
+struct Stems {
+    symbols: HashMap<Arc<str>, Symbol>,
+    strings: Vec<Arc<str>>,
+}
+
+impl Stems {
+    fn intern(&mut self, stem: String) -> (Symbol, Arc<str>) {
+        if let Some((string, &symbol)) = self.symbols.get_key_value(stem.as_str()) {
+            return (symbol, string.clone());
+        }
+
+        assert!(self.strings.len() <= u32::MAX as usize, "too many distinct stems to intern");
+
+        let symbol = Symbol(self.strings.len() as u32);
+        let string = Arc::<str>::from(stem);
+        self.symbols.insert(string.clone(), symbol);
+        self.strings.push(string.clone());
+
+        (symbol, string)
+    }
+}
+
+/// A stemmer that returns each distinct stem as the same shared string.
+///
+/// Interned stems are kept for the life of the stemmer. The table is shared
+/// behind a lock, so an `InterningStemmer` can be used from several threads
+/// at once through a shared reference.
+pub struct InterningStemmer<S> {
+    stemmer: S,
+    stems: Mutex<Stems>,
+}
+
+impl<S: Stemmer> InterningStemmer<S> {
+    /// Intern the stems produced by `stemmer`.
+    pub fn new(stemmer: S) -> InterningStemmer<S> {
+        InterningStemmer {
+            stemmer,
+            stems: Mutex::new(Stems::default()),
+        }
+    }
+
+    /// Given a word, return its stemmed form, shared with every other word
+    /// with the same stem.
+    pub fn stem(&self, word: &str) -> Arc<str> {
+        let stem = self.stemmer.stem(word);
+        self.lock().intern(stem).1
+    }
+
+    /// Given a word, return the symbol for its stemmed form.
+    ///
+    /// Panics if more than `u32::MAX` distinct stems have been interned.
+    pub fn symbol(&self, word: &str) -> Symbol {
+        let stem = self.stemmer.stem(word);
+        self.lock().intern(stem).0
+    }
+
+    /// The stem a symbol stands for, or `None` if it didn't come from this
+    /// stemmer.
+    pub fn resolve(&self, symbol: Symbol) -> Option<Arc<str>> {
+        self.lock().strings.get(symbol.index()).cloned()
+    }
+
+    /// Every stem interned so far, indexed by symbol.
+    pub fn stems(&self) -> Vec<Arc<str>> {
+        self.lock().strings.clone()
+    }
+
+    /// The number of distinct stems interned.
+    pub fn len(&self) -> usize {
+        self.lock().strings.len()
+    }
+
+    /// Whether no stems have been interned.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The wrapped stemmer.
+    pub fn get_ref(&self) -> &S {
+        &self.stemmer
+    }
+
+    /// Unwrap the stemmer, discarding the interned stems.
+    pub fn into_inner(self) -> S {
+        self.stemmer
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Stems> {
+        // Interning never leaves the table half updated, so a panic in
+        // another thread doesn't invalidate it.
+        self.stems.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<S: Stemmer> Stemmer for InterningStemmer<S> {
+    fn stem(&self, word: &str) -> String {
+        InterningStemmer::stem(self, word).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use algorithm::Algorithm;
+
+    #[test]
+    fn test_stem() {
+        let stemmer = InterningStemmer::new(Algorithm::Porter);
+
+        let connected = stemmer.stem("connected");
+        let connection = stemmer.stem("connection");
+        let relational = stemmer.stem("relational");
+
+        assert_eq!("connect", &*connected);
+        assert!(Arc::ptr_eq(&connected, &connection));
+        assert!(!Arc::ptr_eq(&connected, &relational));
+        assert_eq!(2, stemmer.len());
+        assert_eq!(vec![connected, relational], stemmer.stems());
+    }
+
+    #[test]
+    fn test_symbol() {
+        let stemmer = InterningStemmer::new(Algorithm::Porter);
+
+        assert!(stemmer.is_empty());
+        assert_eq!(Symbol(0), stemmer.symbol("hopeful"));
+        assert_eq!(Symbol(1), stemmer.symbol("relational"));
+        assert_eq!(Symbol(0), stemmer.symbol("hoping"));
+        assert_eq!(1, stemmer.symbol("relate").index());
+
+        assert_eq!(Some(Arc::from("relat")), stemmer.resolve(Symbol(1)));
+        assert_eq!(None, stemmer.resolve(Symbol(2)));
+    }
+}
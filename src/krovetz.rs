@@ -0,0 +1,261 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Krovetz stemmer.
+//!
+//! Robert Krovetz's 1993 algorithm checks every candidate stem against a
+//! dictionary, and only accepts a derivational stem when it is a real word.
+//! Its output is a word a reader would recognise, `memorizing` stems to
+//! `memorize` rather than `memoriz`, at the cost of conflating fewer forms.
+//!
+//! A small built-in dictionary is derived from the vocabulary the crate is
+//! tested against, keeping the words that aren't inflections of another word
+//! in it, nor fragments such as `runn` left by a doubled consonant.
+//! Applications with a proper lexicon can supply it instead.
+//!
+//! Words are expected to be lowercase.
+//!
+//! ```
+//! use porter_stemmer::krovetz::Krovetz;
+//!
+//! let krovetz = Krovetz::new();
+//! assert_eq!("memorize", krovetz.stem("memorizing"));
+//! assert_eq!("happy", krovetz.stem("happily"));
+//! ```
+
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use primitives::{ends_with, real_vowel};
+
+const BUILTIN_WORDS: &str = include_str!("krovetz.txt");
+
+/// Derivational suffixes, longest first, and the endings to try in their
+/// place. A replacement is only accepted if the result is in the dictionary.
+const DERIVATIONS: &[(&str, &[&str])] = &[
+    ("ization", &["ize"]),
+    ("ability", &["able"]),
+    ("ibility", &["ible"]),
+    ("ation", &["ate", "e", ""]),
+    ("iness", &["y"]),
+    ("ement", &["e"]),
+    ("ness", &[""]),
+    ("ment", &[""]),
+    ("ence", &["ent", ""]),
+    ("ance", &["ant", ""]),
+    ("able", &["", "e"]),
+    ("ible", &["", "e"]),
+    ("ily", &["y"]),
+    ("ion", &["e", ""]),
+    ("ity", &["", "e"]),
+    ("ive", &["e", ""]),
+    ("ism", &["", "e"]),
+    ("ist", &["", "e"]),
+    ("ize", &["", "e"]),
+    ("ful", &[""]),
+    ("al", &["", "e"]),
+    ("ly", &["", "le"]),
+];
+
+pub(crate) fn builtin() -> &'static HashSet<String> {
+    static BUILTIN: OnceLock<HashSet<String>> = OnceLock::new();
+
+    BUILTIN.get_or_init(|| BUILTIN_WORDS.lines().map(|word| word.to_string()).collect())
+}
+
+/// The Krovetz stemmer, along with the dictionary it checks stems against.
+#[derive(Clone, Debug)]
+pub struct Krovetz {
+    builtin: bool,
+    words: HashSet<String>,
+}
+
+impl Default for Krovetz {
+    fn default() -> Krovetz {
+        Krovetz::new()
+    }
+}
+
+impl Krovetz {
+    /// The stemmer with the built-in dictionary.
+    pub fn new() -> Krovetz {
+        Krovetz { builtin: true, words: HashSet::new() }
+    }
+
+    /// The stemmer with only the given dictionary, in place of the built-in
+    /// one.
+    pub fn with_dictionary<I, S>(words: I) -> Krovetz
+        where I: IntoIterator<Item = S>, S: Into<String>
+    {
+        Krovetz { builtin: false, words: words.into_iter().map(Into::into).collect() }
+    }
+
+    /// Add a word to the dictionary.
+    pub fn with_word(mut self, word: &str) -> Krovetz {
+        self.words.insert(word.to_string());
+        self
+    }
+
+    /// Whether a word is in the dictionary.
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(word) || (self.builtin && builtin().contains(word))
+    }
+
+    /// Given a lowercase word, return its stemmed form using Krovetz's
+    /// algorithm.
+    pub fn stem(&self, word: &str) -> String {
+        if word.chars().count() < 3 || self.contains(word) {
+            return word.to_string();
+        }
+
+        let word = self.inflection(word);
+
+        if self.contains(&word) {
+            return word;
+        }
+
+        self.derivation(&word).unwrap_or(word)
+    }
+
+    fn first_known(&self, candidates: &[String]) -> Option<String> {
+        candidates.iter()
+            .find(|candidate| candidate.chars().count() >= 2 && self.contains(candidate))
+            .cloned()
+    }
+
+    /// Remove a plural, past tense or `ing` ending, preferring whichever
+    /// candidate stem is a known word.
+    fn inflection(&self, word: &str) -> String {
+        let letters = word.chars().collect::<Vec<char>>();
+        let length = letters.len();
+        let without = |count: usize| letters[..length - count].iter().collect::<String>();
+
+        if ends_with(&letters, "ies") {
+            let base = without(3);
+            let candidates = [format!("{}y", base), format!("{}ie", base)];
+            self.first_known(&candidates).unwrap_or_else(|| format!("{}y", base))
+        } else if ends_with(&letters, "es") {
+            let candidates = [without(1), without(2)];
+            self.first_known(&candidates).unwrap_or_else(|| {
+                let base = &letters[..length - 2];
+                if ["s", "x", "z", "ch", "sh"].iter().any(|ending| ends_with(base, ending)) {
+                    without(2)
+                } else {
+                    without(1)
+                }
+            })
+        } else if ends_with(&letters, "s") && !["ss", "us", "is"].iter().any(|ending| ends_with(&letters, ending)) {
+            without(1)
+        } else if ends_with(&letters, "ied") {
+            format!("{}y", without(3))
+        } else if ends_with(&letters, "ed") && length > 4 {
+            let base = &letters[..length - 2];
+            let candidates = if doubled(base) {
+                [undouble(base), without(1), without(2)]
+            } else {
+                [without(1), without(2), undouble(base)]
+            };
+            self.first_known(&candidates).unwrap_or_else(|| undouble(base))
+        } else if ends_with(&letters, "ing") && length > 5 {
+            let base = &letters[..length - 3];
+            let candidates = if doubled(base) {
+                [undouble(base), without(3), format!("{}e", without(3))]
+            } else if short_syllable(base) {
+                [format!("{}e", without(3)), without(3), undouble(base)]
+            } else {
+                [without(3), format!("{}e", without(3)), undouble(base)]
+            };
+            self.first_known(&candidates).unwrap_or_else(|| undouble(base))
+        } else {
+            word.to_string()
+        }
+    }
+
+    /// Replace the longest derivational suffix, if doing so gives a known
+    /// word.
+    fn derivation(&self, word: &str) -> Option<String> {
+        let &(suffix, replacements) = DERIVATIONS.iter()
+            .find(|&&(suffix, _)| word.len() > suffix.len() + 1 && word.ends_with(suffix))?;
+
+        let base = &word[..word.len() - suffix.len()];
+        let candidates = replacements.iter()
+            .map(|replacement| format!("{}{}", base, replacement))
+            .collect::<Vec<String>>();
+
+        self.first_known(&candidates)
+    }
+}
+
+/// Whether a stem ends in a double consonant other than `ll`, `ss` or `zz`,
+/// with at least three letters before its second half, so `runn` but not
+/// `add`.
+fn doubled(base: &[char]) -> bool {
+    let length = base.len();
+
+    length >= 4 && base[length - 1] == base[length - 2] &&
+        !real_vowel(base[length - 1]) && !"lsz".contains(base[length - 1])
+}
+
+/// Remove the last letter of a `doubled` stem.
+fn undouble(base: &[char]) -> String {
+    if doubled(base) {
+        base[..base.len() - 1].iter().collect()
+    } else {
+        base.iter().collect()
+    }
+}
+
+/// Whether a stem ends consonant-vowel-consonant, and so probably lost an `e`.
+fn short_syllable(base: &[char]) -> bool {
+    let length = base.len();
+
+    length >= 3 &&
+        !real_vowel(base[length - 3]) &&
+        real_vowel(base[length - 2]) &&
+        !real_vowel(base[length - 1]) && !"wxy".contains(base[length - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        let krovetz = Krovetz::new();
+        let pairs = [
+            ("memorizing", "memorize"),
+            ("hoping", "hope"),
+            ("hopping", "hop"),
+            ("stopped", "stop"),
+            ("running", "run"),
+            ("begging", "beg"),
+            ("adding", "add"),
+            ("erred", "err"),
+            ("ponies", "pony"),
+            ("horses", "horse"),
+            ("boxes", "box"),
+            ("happily", "happy"),
+            ("quickly", "quick"),
+            ("during", "during"),
+            ("is", "is"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, krovetz.stem(word), "stemming {}", word);
+        }
+    }
+
+    #[test]
+    fn test_dictionary() {
+        let empty = Krovetz::with_dictionary(Vec::<String>::new());
+        assert_eq!("memoriz", empty.stem("memorizing"));
+        assert_eq!("kindness", empty.stem("kindness"));
+
+        let krovetz = empty.with_word("memorize").with_word("kind");
+        assert!(krovetz.contains("kind"));
+        assert!(!krovetz.contains("memory"));
+        assert_eq!("memorize", krovetz.stem("memorizing"));
+        assert_eq!("kind", krovetz.stem("kindness"));
+    }
+}
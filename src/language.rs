@@ -0,0 +1,308 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Selecting a stemmer by the language of the text.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use algorithm::Stemmer;
+use porter2;
+use snowball::{arabic, armenian, basque, catalan, danish, dutch, finnish, french, german, greek, hindi, hungarian, indonesian, irish, italian, lithuanian, nepali, norwegian, portuguese, romanian, russian, serbian, spanish, swedish, tamil, turkish, yiddish};
+
+/// The languages this crate can stem.
+///
+/// English uses the Porter2 algorithm, Arabic the Light10 light stemmer, and
+/// every other language its Snowball stemmer. Languages can be parsed from,
+/// and displayed as, their English names in lower case.
+///
+/// # Examples
+///
+/// ```
+/// use porter_stemmer::Language;
+///
+/// let language: Language = "french".parse().unwrap();
+/// assert_eq!(Language::French, language);
+/// assert_eq!("continu", language.stem("continuité"));
+/// assert_eq!("continu", Language::English.stem("continuing"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Language {
+    /// Stemmed with Porter2. See the `porter2` module.
+    #[default]
+    English,
+    /// See the `snowball::french` module.
+    French,
+    /// See the `snowball::german` module.
+    German,
+    /// See the `snowball::spanish` module.
+    Spanish,
+    /// See the `snowball::italian` module.
+    Italian,
+    /// See the `snowball::portuguese` module.
+    Portuguese,
+    /// See the `snowball::dutch` module.
+    Dutch,
+    /// See the `snowball::swedish` module.
+    Swedish,
+    /// See the `snowball::norwegian` module.
+    Norwegian,
+    /// See the `snowball::danish` module.
+    Danish,
+    /// See the `snowball::finnish` module.
+    Finnish,
+    /// See the `snowball::russian` module.
+    Russian,
+    /// See the `snowball::romanian` module.
+    Romanian,
+    /// See the `snowball::hungarian` module.
+    Hungarian,
+    /// See the `snowball::turkish` module.
+    Turkish,
+    /// Stemmed with Light10. See the `snowball::arabic` module.
+    Arabic,
+    /// See the `snowball::greek` module.
+    Greek,
+    /// See the `snowball::hindi` module.
+    Hindi,
+    /// See the `snowball::tamil` module.
+    Tamil,
+    /// See the `snowball::indonesian` module.
+    Indonesian,
+    /// See the `snowball::nepali` module.
+    Nepali,
+    /// See the `snowball::basque` module.
+    Basque,
+    /// See the `snowball::catalan` module.
+    Catalan,
+    /// See the `snowball::irish` module.
+    Irish,
+    /// See the `snowball::lithuanian` module.
+    Lithuanian,
+    /// See the `snowball::armenian` module.
+    Armenian,
+    /// See the `snowball::serbian` module.
+    Serbian,
+    /// See the `snowball::yiddish` module.
+    Yiddish,
+}
+
+const LANGUAGES: &[Language] = &[
+    Language::English,
+    Language::French,
+    Language::German,
+    Language::Spanish,
+    Language::Italian,
+    Language::Portuguese,
+    Language::Dutch,
+    Language::Swedish,
+    Language::Norwegian,
+    Language::Danish,
+    Language::Finnish,
+    Language::Russian,
+    Language::Romanian,
+    Language::Hungarian,
+    Language::Turkish,
+    Language::Arabic,
+    Language::Greek,
+    Language::Hindi,
+    Language::Tamil,
+    Language::Indonesian,
+    Language::Nepali,
+    Language::Basque,
+    Language::Catalan,
+    Language::Irish,
+    Language::Lithuanian,
+    Language::Armenian,
+    Language::Serbian,
+    Language::Yiddish,
+];
+
+impl Language {
+    /// Every available language.
+    pub fn all() -> &'static [Language] {
+        LANGUAGES
+    }
+
+    /// The name the language is parsed from and displayed as.
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "english",
+            Language::French => "french",
+            Language::German => "german",
+            Language::Spanish => "spanish",
+            Language::Italian => "italian",
+            Language::Portuguese => "portuguese",
+            Language::Dutch => "dutch",
+            Language::Swedish => "swedish",
+            Language::Norwegian => "norwegian",
+            Language::Danish => "danish",
+            Language::Finnish => "finnish",
+            Language::Russian => "russian",
+            Language::Romanian => "romanian",
+            Language::Hungarian => "hungarian",
+            Language::Turkish => "turkish",
+            Language::Arabic => "arabic",
+            Language::Greek => "greek",
+            Language::Hindi => "hindi",
+            Language::Tamil => "tamil",
+            Language::Indonesian => "indonesian",
+            Language::Nepali => "nepali",
+            Language::Basque => "basque",
+            Language::Catalan => "catalan",
+            Language::Irish => "irish",
+            Language::Lithuanian => "lithuanian",
+            Language::Armenian => "armenian",
+            Language::Serbian => "serbian",
+            Language::Yiddish => "yiddish",
+        }
+    }
+
+    /// Given a lowercase word in this language, return its stemmed form.
+    pub fn stem(self, word: &str) -> String {
+        match self {
+            Language::English => porter2::stem(word),
+            Language::French => french::stem(word),
+            Language::German => german::stem(word),
+            Language::Spanish => spanish::stem(word),
+            Language::Italian => italian::stem(word),
+            Language::Portuguese => portuguese::stem(word),
+            Language::Dutch => dutch::stem(word),
+            Language::Swedish => swedish::stem(word),
+            Language::Norwegian => norwegian::stem(word),
+            Language::Danish => danish::stem(word),
+            Language::Finnish => finnish::stem(word),
+            Language::Russian => russian::stem(word),
+            Language::Romanian => romanian::stem(word),
+            Language::Hungarian => hungarian::stem(word),
+            Language::Turkish => turkish::stem(word),
+            Language::Arabic => arabic::stem(word),
+            Language::Greek => greek::stem(word),
+            Language::Hindi => hindi::stem(word),
+            Language::Tamil => tamil::stem(word),
+            Language::Indonesian => indonesian::stem(word),
+            Language::Nepali => nepali::stem(word),
+            Language::Basque => basque::stem(word),
+            Language::Catalan => catalan::stem(word),
+            Language::Irish => irish::stem(word),
+            Language::Lithuanian => lithuanian::stem(word),
+            Language::Armenian => armenian::stem(word),
+            Language::Serbian => serbian::stem(word),
+            Language::Yiddish => yiddish::stem(word),
+        }
+    }
+}
+
+impl Stemmer for Language {
+    fn stem(&self, word: &str) -> String {
+        Language::stem(*self, word)
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Language {
+    type Err = ParseLanguageError;
+
+    /// Parse a language from its name, ignoring case.
+    fn from_str(name: &str) -> Result<Language, ParseLanguageError> {
+        LANGUAGES.iter()
+            .find(|language| language.name().eq_ignore_ascii_case(name))
+            .cloned()
+            .ok_or_else(|| ParseLanguageError { name: name.to_string() })
+    }
+}
+
+/// The error returned when parsing an unknown `Language` name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseLanguageError {
+    name: String,
+}
+
+impl fmt::Display for ParseLanguageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown language `{}`, expected one of: ", self.name)?;
+
+        for (index, language) in LANGUAGES.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(language.name())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for ParseLanguageError {}
+
+#[cfg(feature = "clap")]
+impl ::clap::ValueEnum for Language {
+    fn value_variants<'a>() -> &'a [Language] {
+        LANGUAGES
+    }
+
+    fn to_possible_value(&self) -> Option<::clap::builder::PossibleValue> {
+        Some(::clap::builder::PossibleValue::new(self.name()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for &language in Language::all() {
+            assert_eq!(Ok(language), language.to_string().parse());
+        }
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Ok(Language::French), "French".parse());
+
+        let error = "klingon".parse::<Language>().unwrap_err();
+        assert_eq!("unknown language `klingon`, expected one of: english, french, german, spanish, italian, portuguese, dutch, swedish, norwegian, danish, finnish, russian, romanian, hungarian, turkish, arabic, greek, hindi, tamil, indonesian, nepali, basque, catalan, irish, lithuanian, armenian, serbian, yiddish", error.to_string());
+    }
+
+    #[test]
+    fn test_stem() {
+        assert_eq!("general", Language::English.stem("generally"));
+        assert_eq!("continu", Language::French.stem("continuité"));
+        assert_eq!("cheval", Language::French.stem("chevaux"));
+        assert_eq!("haus", Language::German.stem("häuser"));
+        assert_eq!("acept", Language::Spanish.stem("aceptación"));
+        assert_eq!("abit", Language::Italian.stem("abitazione"));
+        assert_eq!("inform", Language::Portuguese.stem("informações"));
+        assert_eq!("kat", Language::Dutch.stem("katten"));
+        assert_eq!("flick", Language::Swedish.stem("flickorna"));
+        assert_eq!("bil", Language::Norwegian.stem("bilene"));
+        assert_eq!("bil", Language::Danish.stem("bilerne"));
+        assert_eq!("talo", Language::Finnish.stem("talossakin"));
+        assert_eq!("книг", Language::Russian.stem("книги"));
+        assert_eq!("organiz", Language::Romanian.stem("organizaţie"));
+        assert_eq!("ház", Language::Hungarian.stem("házakat"));
+        assert_eq!("kitap", Language::Turkish.stem("kitaplar"));
+        assert_eq!("كتاب", Language::Arabic.stem("والكتاب"));
+        assert_eq!("ανθρωπ", Language::Greek.stem("άνθρωπος"));
+        assert_eq!("किताब", Language::Hindi.stem("किताबें"));
+        assert_eq!("மரம்", Language::Tamil.stem("மரங்கள்"));
+        assert_eq!("baca", Language::Indonesian.stem("membaca"));
+        assert_eq!("घर", Language::Nepali.stem("घरहरूमा"));
+        assert_eq!("mendi", Language::Basque.stem("mendietan"));
+        assert_eq!("cant", Language::Catalan.stem("cantava"));
+        assert_eq!("fear", Language::Irish.stem("bhfear"));
+        assert_eq!("nam", Language::Lithuanian.stem("namuose"));
+        assert_eq!("քաղ", Language::Armenian.stem("քաղաքները"));
+        assert_eq!("knjig", Language::Serbian.stem("књигама"));
+        assert_eq!("קינד", Language::Yiddish.stem("קינדער"));
+        assert_eq!(Language::English, Language::default());
+    }
+}
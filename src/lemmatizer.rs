@@ -0,0 +1,215 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! A dictionary-based English lemmatizer. Requires the `wordnet` feature.
+//!
+//! Rather than stripping suffixes, a word is mapped to its dictionary form:
+//! `better` to `good`, `geese` to `goose` and `running` to `run`. Irregular
+//! forms come from the WordNet exception lists, and regular ones are found
+//! with WordNet's morphy rules, which replace an ending and accept the
+//! result only if it is a known word. A word with no known lemma is left as
+//! it is.
+//!
+//! The exception lists bundled with the crate cover the common irregular
+//! forms rather than all of WordNet's. Known words are those of the `krovetz`
+//! module's built-in dictionary, and applications with a proper lexicon can
+//! supply it instead. The exception lists are under WordNet's license, in
+//! `src/wordnet/LICENSE`.
+//!
+//! Words are expected to be lowercase.
+//!
+//! ```
+//! use porter_stemmer::lemmatizer::{Lemmatizer, PosTag};
+//!
+//! let lemmatizer = Lemmatizer::new();
+//! assert_eq!("goose", lemmatizer.lemma("geese"));
+//! assert_eq!("good", lemmatizer.lemmatize("better", PosTag::Adjective));
+//! assert_eq!("run", lemmatizer.lemmatize("running", PosTag::Verb));
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+use krovetz;
+
+pub use PosTag;
+
+const NOUN_EXCEPTIONS: &str = include_str!("wordnet/noun.exc");
+const VERB_EXCEPTIONS: &str = include_str!("wordnet/verb.exc");
+const ADJECTIVE_EXCEPTIONS: &str = include_str!("wordnet/adj.exc");
+const ADVERB_EXCEPTIONS: &str = include_str!("wordnet/adv.exc");
+
+/// Morphy's endings and their replacements for each part of speech, tried
+/// in order.
+const NOUN_RULES: &[(&str, &str)] = &[
+    ("s", ""), ("ses", "s"), ("xes", "x"), ("zes", "z"), ("ches", "ch"), ("shes", "sh"),
+    ("men", "man"), ("ies", "y"),
+];
+const VERB_RULES: &[(&str, &str)] = &[
+    ("s", ""), ("ies", "y"), ("es", "e"), ("es", ""), ("ed", "e"), ("ed", ""), ("ing", "e"),
+    ("ing", ""),
+];
+const ADJECTIVE_RULES: &[(&str, &str)] = &[("er", ""), ("est", ""), ("er", "e"), ("est", "e")];
+
+impl PosTag {
+    fn exceptions(self) -> &'static HashMap<&'static str, &'static str> {
+        static NOUNS: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+        static VERBS: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+        static ADJECTIVES: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+        static ADVERBS: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+
+        match self {
+            PosTag::Noun => NOUNS.get_or_init(|| parse_exceptions(NOUN_EXCEPTIONS)),
+            PosTag::Verb => VERBS.get_or_init(|| parse_exceptions(VERB_EXCEPTIONS)),
+            PosTag::Adjective => ADJECTIVES.get_or_init(|| parse_exceptions(ADJECTIVE_EXCEPTIONS)),
+            PosTag::Adverb => ADVERBS.get_or_init(|| parse_exceptions(ADVERB_EXCEPTIONS)),
+        }
+    }
+
+    fn rules(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            PosTag::Noun => NOUN_RULES,
+            PosTag::Verb => VERB_RULES,
+            PosTag::Adjective => ADJECTIVE_RULES,
+            PosTag::Adverb => &[],
+        }
+    }
+}
+
+/// Read an exception list, one `inflected lemma` pair per line. Where a form
+/// has several lemmas, the first is kept.
+fn parse_exceptions(list: &'static str) -> HashMap<&'static str, &'static str> {
+    let mut exceptions = HashMap::new();
+
+    for line in list.lines() {
+        let mut fields = line.split_whitespace();
+        if let (Some(inflected), Some(lemma)) = (fields.next(), fields.next()) {
+            exceptions.entry(inflected).or_insert(lemma);
+        }
+    }
+
+    exceptions
+}
+
+/// The lemmatizer, along with the dictionary it checks lemmas against.
+#[derive(Clone, Debug)]
+pub struct Lemmatizer {
+    builtin: bool,
+    words: HashSet<String>,
+}
+
+impl Default for Lemmatizer {
+    fn default() -> Lemmatizer {
+        Lemmatizer::new()
+    }
+}
+
+impl Lemmatizer {
+    /// The lemmatizer with the built-in dictionary.
+    pub fn new() -> Lemmatizer {
+        Lemmatizer { builtin: true, words: HashSet::new() }
+    }
+
+    /// The lemmatizer with only the given dictionary, in place of the
+    /// built-in one. Irregular forms are still lemmatized from the exception
+    /// lists.
+    pub fn with_dictionary<I, S>(words: I) -> Lemmatizer
+        where I: IntoIterator<Item = S>, S: Into<String>
+    {
+        Lemmatizer { builtin: false, words: words.into_iter().map(Into::into).collect() }
+    }
+
+    /// Add a word to the dictionary.
+    pub fn with_word(mut self, word: &str) -> Lemmatizer {
+        self.words.insert(word.to_string());
+        self
+    }
+
+    /// Whether a word is in the dictionary.
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(word) || (self.builtin && krovetz::builtin().contains(word))
+    }
+
+    /// Given a lowercase word used as the given part of speech, return its
+    /// lemma, or `None` if it has no known lemma as that part of speech.
+    pub fn lookup(&self, word: &str, pos: PosTag) -> Option<String> {
+        if let Some(lemma) = pos.exceptions().get(word) {
+            return Some(lemma.to_string());
+        }
+
+        if self.contains(word) {
+            return Some(word.to_string());
+        }
+
+        pos.rules().iter()
+            .filter(|&&(ending, _)| word.len() > ending.len() && word.ends_with(ending))
+            .map(|&(ending, replacement)| format!("{}{}", &word[..word.len() - ending.len()], replacement))
+            .find(|candidate| self.contains(candidate))
+    }
+
+    /// Given a lowercase word used as the given part of speech, return its
+    /// lemma, or the word itself if it has none.
+    pub fn lemmatize(&self, word: &str, pos: PosTag) -> String {
+        self.lookup(word, pos).unwrap_or_else(|| word.to_string())
+    }
+
+    /// Given a lowercase word of unknown part of speech, return its lemma as
+    /// the first of `PosTag::ALL` it has one for, or the word itself.
+    pub fn lemma(&self, word: &str) -> String {
+        PosTag::ALL.iter()
+            .filter_map(|&pos| self.lookup(word, pos))
+            .find(|lemma| lemma != word)
+            .unwrap_or_else(|| word.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exceptions() {
+        let lemmatizer = Lemmatizer::new();
+
+        assert_eq!("goose", lemmatizer.lemmatize("geese", PosTag::Noun));
+        assert_eq!("child", lemmatizer.lemmatize("children", PosTag::Noun));
+        assert_eq!("good", lemmatizer.lemmatize("better", PosTag::Adjective));
+        assert_eq!("well", lemmatizer.lemmatize("better", PosTag::Adverb));
+        assert_eq!("go", lemmatizer.lemmatize("went", PosTag::Verb));
+        assert_eq!("be", lemmatizer.lemma("was"));
+    }
+
+    #[test]
+    fn test_rules() {
+        let pairs = [
+            ("dogs", PosTag::Noun, "dog"),
+            ("boxes", PosTag::Noun, "box"),
+            ("churches", PosTag::Noun, "church"),
+            ("cities", PosTag::Noun, "city"),
+            ("walked", PosTag::Verb, "walk"),
+            ("hoped", PosTag::Verb, "hope"),
+            ("loving", PosTag::Verb, "love"),
+            ("running", PosTag::Verb, "run"),
+            ("cleverest", PosTag::Adjective, "clever"),
+            ("happiest", PosTag::Adjective, "happy"),
+            ("quickly", PosTag::Adverb, "quickly"),
+        ];
+
+        let lemmatizer = Lemmatizer::new().with_word("love").with_word("hope").with_word("clever");
+        for &(word, pos, expected) in pairs.iter() {
+            assert_eq!(expected, lemmatizer.lemmatize(word, pos), "lemmatizing {}", word);
+        }
+    }
+
+    #[test]
+    fn test_dictionary() {
+        let lemmatizer = Lemmatizer::with_dictionary(vec!["meet"]);
+
+        assert_eq!("meet", lemmatizer.lemma("meeting"));
+        assert_eq!("meeting", lemmatizer.clone().with_word("meeting").lemmatize("meeting", PosTag::Noun));
+        assert_eq!("tables", lemmatizer.lemma("tables"));
+        assert_eq!(None, lemmatizer.lookup("tables", PosTag::Noun));
+        assert_eq!("mouse", lemmatizer.lemma("mice"));
+    }
+}
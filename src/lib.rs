@@ -3,9 +3,103 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 extern crate unicode_segmentation;
+#[cfg(feature = "arrow")]
+extern crate arrow_array;
+#[cfg(feature = "charabia")]
+extern crate charabia;
+#[cfg(feature = "clap")]
+extern crate clap;
+#[cfg(feature = "datafusion")]
+extern crate datafusion;
+#[cfg(feature = "futures")]
+extern crate futures;
+#[cfg(feature = "sqlite")]
+extern crate libsqlite3_sys;
+extern crate porter_stemmer_core;
+#[cfg(feature = "macros")]
+extern crate porter_stemmer_macros;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "tantivy")]
+extern crate tantivy_tokenizer_api;
+#[cfg(feature = "uniffi")]
+extern crate uniffi;
+#[cfg(feature = "wasm")]
+extern crate wasm_bindgen;
+
+use std::borrow::Cow;
+use std::ops::Range;
 
 use unicode_segmentation::UnicodeSegmentation;
 
+mod algorithm;
+pub mod analysis;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod cache;
+pub mod contractions;
+pub mod dawson;
+pub mod detect;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod hunspell;
+pub mod intern;
+pub mod krovetz;
+mod language;
+#[cfg(feature = "wordnet")]
+pub mod lemmatizer;
+pub mod lovins;
+#[cfg(feature = "uniffi")]
+pub mod mobile;
+#[cfg(feature = "charabia")]
+pub mod normalizer;
+pub mod paice;
+pub mod phonetic;
+pub mod pipeline;
+pub mod shingle;
+pub mod snowball;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(feature = "futures")]
+pub mod stream;
+#[cfg(feature = "tantivy")]
+pub mod tantivy;
+pub mod tfidf;
+pub mod porter2;
+pub mod tokenizer;
+pub mod truncate;
+pub mod uea_lite;
+#[cfg(feature = "datafusion")]
+pub mod udf;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
+
+pub use porter_stemmer_core::{batch, primitives};
+
+pub use algorithm::{Algorithm, ParseAlgorithmError, Stemmer};
+pub use language::{Language, ParseLanguageError};
+pub use porter::{phase_one_a, phase_one_b, phase_one_c, phase_two, phase_three, phase_four, phase_5a, phase_5b, stem_tokenized, FinalY, Porter, PorterMode};
+pub use primitives::Letter;
+
+use porter_stemmer_core::porter;
+
+/// Stem a string literal at compile time. Requires the `macros` feature.
+///
+/// ```
+/// extern crate porter_stemmer;
+///
+/// use porter_stemmer::stem;
+///
+/// fn main() {
+///     assert_eq!("hope", stem!("hopefulness"));
+/// }
+/// ```
+#[cfg(feature = "macros")]
+pub use porter_stemmer_macros::stem;
+
 /// Given a word, return its stemmed form
 ///
 /// # Examples
@@ -17,628 +111,323 @@ use unicode_segmentation::UnicodeSegmentation;
 /// assert_eq!("total", &stemmed);
 /// ```
 pub fn stem(word: &str) -> String {
-    stem_tokenized(word.graphemes(true).collect::<Vec<&str>>()).iter()
-        .fold(String::new(), |prev, next| { format!("{}{}", prev, next) })
+    Porter::new().stem(word)
 }
 
-/// Take a word as a Vector of grapheme clusters, and return the stemmed equivalent using Porter's
-/// stemming algorithm.
+/// Stem every word of a whitespace separated buffer of text, appending the
+/// stems to `output`. See `Porter::stem_buffer`.
 ///
 /// # Examples
 ///
 /// ```
-/// use porter_stemmer::stem_tokenized;
+/// use porter_stemmer::stem_buffer;
 ///
-/// let tokenized = vec!["s", "t", "e", "m", "m", "i", "n", "g"];
-/// let stemmed = stem_tokenized(tokenized);
-/// assert_eq!(&["s", "t", "e", "m"], &stemmed[..]);
+/// let mut stems = String::new();
+/// stem_buffer("the ponies were running", &mut stems);
+/// assert_eq!("the poni were run", stems);
 /// ```
-pub fn stem_tokenized(word: Vec<&str>) -> Vec<&str> {
-    if word.len() > 2 {
-        let word = phase_one_a(word);
-        let word = phase_one_b(word);
-        let word = phase_one_c(word);
-        let word = phase_two(word);
-        let word = phase_three(word);
-        let word = phase_four(word);
-        let word = phase_5a(word);
-        let word = phase_5b(word);
-        word
-    } else {
-        word
-    }
+pub fn stem_buffer(text: &str, output: &mut String) {
+    Porter::new().stem_buffer(text, output)
 }
 
-fn real_vowel(grapheme: &str) -> bool {
-    match grapheme {
-        "a" |
-        "e" |
-        "i" |
-        "o" |
-        "u" => {
-            true
-        },
-        _ => false
-    }
+/// The `String` of a stem produced from ASCII input.
+fn ascii_string(stem: &[u8]) -> String {
+    String::from_utf8(stem.to_vec()).expect("stemming ASCII input only ever produces ASCII")
 }
 
-fn real_consonant(grapheme: &str) -> bool {
-    !real_vowel(grapheme)
-}
+/// The result of `stem_ref`.
+///
+/// Most of Porter's rules only ever truncate a word, in which case the stem is
+/// borrowed from the input. Only the rules that append or replace letters
+/// (e.g. `conflat` to `conflate`) produce an owned `String`.
+pub type StemOutput<'a> = Cow<'a, str>;
 
-fn porter_vowel(word: &[&str], index: usize) -> bool {
-    let grapheme = word[index];
-
-    if real_vowel(grapheme) {
-        true
-    } else {
-        if index == 0 || grapheme != "y" {
-            false
-        } else {
-            let preceeding_grapheme = word[index - 1];
-            real_consonant(preceeding_grapheme)
-        }
+/// Given a word, return its stemmed form, borrowing from `word` where the stem
+/// is a prefix of it.
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+/// use porter_stemmer::stem_ref;
+///
+/// assert_eq!(Cow::Borrowed("connect"), stem_ref("connections"));
+///
+/// let stemmed: String = stem_ref("happy").into_owned();
+/// assert_eq!("happi", stemmed);
+/// ```
+pub fn stem_ref<'a>(word: &'a str) -> StemOutput<'a> {
+    if porter::bytewise(word) {
+        return Porter::new().with_stem(word.bytes(), |stemmed| {
+            if word.as_bytes().starts_with(stemmed) {
+                Cow::Borrowed(&word[..stemmed.len()])
+            } else {
+                Cow::Owned(ascii_string(stemmed))
+            }
+        });
     }
-}
-
-fn porter_consonant(word: &[&str], index: usize) -> bool {
-    !porter_vowel(word, index)
-}
 
-fn contains_porter_vowel(word: &[&str]) -> bool {
-    for index in 0..word.len() {
-        if porter_vowel(word, index) {
-            return true;
+    Porter::new().with_stem(word.graphemes(true), |stemmed| {
+        let mut length = 0;
+        for grapheme in stemmed {
+            if word[length..].starts_with(grapheme) {
+                length += grapheme.len();
+            } else {
+                return Cow::Owned(stemmed.concat());
+            }
         }
-    }
 
-    return false;
+        Cow::Borrowed(&word[..length])
+    })
 }
 
-fn ends_double_porters_consonant(word: &[&str]) -> bool {
-    let word_length = word.len();
-    if word_length > 2 {
-        let last_grapheme = word[word_length - 1];
-        let penultimate_grapheme = word[word_length - 2];
-
-        last_grapheme == penultimate_grapheme &&
-            porter_consonant(word, word_length - 1)
-    } else {
-        false
-    }
-
+/// Given a word, return its stemmed form along with the part of the word that
+/// was removed to produce it.
+///
+/// The removed suffix is everything in the original word after the longest
+/// prefix it shares with the stem, so rules that rewrite rather than truncate
+/// (e.g. `y` to `i`) still report what was taken away from the original.
+///
+/// # Examples
+///
+/// ```
+/// use porter_stemmer::stem_parts;
+///
+/// assert_eq!(("relat".to_string(), "ional".to_string()), stem_parts("relational"));
+/// assert_eq!(("happi".to_string(), "y".to_string()), stem_parts("happy"));
+/// ```
+pub fn stem_parts(word: &str) -> (String, String) {
+    if porter::bytewise(word) {
+        return Porter::new().with_stem(word.bytes(), |stemmed| {
+            let shared = word.bytes().zip(stemmed).take_while(|&(original, &stemmed)| original == stemmed).count();
+            (ascii_string(stemmed), word[shared..].to_string())
+        });
+    }
+
+    Porter::new().with_stem(word.graphemes(true), |stemmed| {
+        let shared = word.graphemes(true)
+            .zip(stemmed)
+            .take_while(|&(original, stemmed)| original == *stemmed)
+            .map(|(original, _)| original.len())
+            .sum::<usize>();
+
+        (stemmed.concat(), word[shared..].to_string())
+    })
 }
 
-// Condition: *o  the stem ends consonant-vowel-consonant,
-// where the second consonant is not w, x or y.
-fn ends_star_o(word: &[&str]) -> bool {
-    let word_length = word.len();
-
-    if word_length > 2 {
-        let last_grapheme = word[word_length - 1];
-        match last_grapheme {
-            "w" | "x" | "y" => false,
-            _ => {
-                porter_consonant(word, word_length - 1) &&
-                porter_vowel(word, word_length - 2) &&
-                porter_consonant(word, word_length - 3)
-            }
-        }
-    } else {
-        false
-    }
+/// Split a passage of text into words and stem each of them, keeping track of
+/// where each word was found.
+///
+/// Yields `(byte_range, original, stem)` for every word in `text`, where
+/// `&text[byte_range] == original`. Whitespace and punctuation are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use porter_stemmer::stem_with_offsets;
+///
+/// let text = "Hopeful, connected readers";
+/// let stems = stem_with_offsets(text).collect::<Vec<_>>();
+///
+/// assert_eq!((0..7, "Hopeful", "Hope".to_string()), stems[0]);
+/// assert_eq!((9..18, "connected", "connect".to_string()), stems[1]);
+/// assert_eq!((19..26, "readers", "reader".to_string()), stems[2]);
+/// ```
+pub fn stem_with_offsets<'a>(text: &'a str) -> impl Iterator<Item = (Range<usize>, &'a str, String)> + 'a {
+    tokenizer::tokenize(text).map(|token| (token.byte_range, token.text, stem(token.text)))
 }
 
-/// The Porter stemmer makes use of a _measure_.
+/// Stem a word given as raw bytes, skipping grapheme segmentation and UTF-8
+/// validation entirely.
 ///
-/// Defined formally as the number of
-/// Vowel sequence-Consonant sequence pairs in a word or fragment.
+/// This is intended for high-throughput pipelines where input is already
+/// known to be lowercase ASCII. Any non-ASCII bytes are treated as consonants,
+/// one byte at a time.
 ///
-/// If C is a sequence of consonants, and V a sequence
-/// of vowels, the measure of a word or word part can be
-/// defined by:
+/// # Examples
 ///
-/// C?(VC)*V?
+/// ```
+/// use porter_stemmer::stem_bytes;
 ///
-/// Where the measure, _m_, is equal to the number of matches
-/// by the Kleene star `(VC)*`
+/// assert_eq!(b"hope".to_vec(), stem_bytes(b"hopefulness"));
+/// ```
+pub fn stem_bytes(word: &[u8]) -> Vec<u8> {
+    Porter::new().with_stem(word.iter().cloned(), |stemmed| stemmed.to_vec())
+}
+
+/// Stem the ASCII word in `word` in place, and return the length of its
+/// stemmed form, now at the start of `word`. Nothing is allocated. See
+/// `Porter::stem_ascii_in_place`.
 ///
-/// Note how the parameter is a &[&'a str] (slice).  This is so we can use an
-/// indexable list of grapheme clusters.
+/// # Examples
 ///
-/// TODO: Maybe parameterise over Index trait so we can optimise
-/// for known single char byte sequences in English? What if
-/// the English input has a name with a diacritic?
-fn measure(word: &[&str]) -> usize {
-    let mut measure = 0;
-    let word_length = word.len();
-
-    if word_length == 0{
-        return measure;
-    }
-
-    let mut is_vowel_current = real_vowel(word[0]);
+/// ```
+/// use porter_stemmer::stem_ascii_in_place;
+///
+/// let mut word = *b"relational";
+/// let length = stem_ascii_in_place(&mut word);
+/// assert_eq!(b"relat", &word[..length]);
+/// ```
+pub fn stem_ascii_in_place(word: &mut [u8]) -> usize {
+    Porter::new().stem_ascii_in_place(word)
+}
 
-    for index in 1..word_length {
-        let is_vowel = porter_vowel(word, index);
-        if !is_vowel_current && is_vowel {
-            is_vowel_current = true;
-        } else if is_vowel_current && !is_vowel {
-            is_vowel_current = false;
-            measure += 1;
-        }
-    }
+/// Given a word, remove only a possessive `'s` or trailing apostrophe,
+/// without stemming it any further.
+///
+/// This is the lightest possible conflation, for applications where
+/// `judgment's` should match `judgment` but never `judge`. Both straight and
+/// curly apostrophes are recognised.
+///
+/// # Examples
+///
+/// ```
+/// use porter_stemmer::strip_possessive;
+///
+/// assert_eq!("court", strip_possessive("court's"));
+/// assert_eq!("parties", strip_possessive("parties'"));
+/// assert_eq!("judgments", strip_possessive("judgments"));
+/// ```
+pub fn strip_possessive(word: &str) -> &str {
+    let stripped = ["'s", "'S", "\u{2019}s", "\u{2019}S", "'", "\u{2019}"].iter()
+        .find_map(|suffix| word.strip_suffix(suffix))
+        .unwrap_or(word);
 
-    return measure;
+    if stripped.is_empty() { word } else { stripped }
 }
 
-/// Order in which to apply rules:
-///
-/// SSES -> SS
-/// IES  -> I
-/// SS -> SS
-/// S  ->
-fn phase_one_a(word: Vec<&str>) -> Vec<&str> {
-    // Move `word` in here where we can make mutable where necessary
-    let word_length = word.len();
-
-    if word.ends_with(&["s", "s", "e", "s"]) || word.ends_with(&["i", "e", "s"]) {
-        let mut word = word;
-        word.truncate(word_length - 2);
-        word
-    } else if word.ends_with(&["s", "s"]) {
-        word
-    } else if word.ends_with(&["s"]) {
-        let mut word = word;
-        word.truncate(word_length - 1);
-        word
-    } else {
-        word
-    }
+/// A part of speech, as assigned by a tagger.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PosTag {
+    Noun,
+    Verb,
+    Adjective,
+    Adverb,
 }
 
-/// Order in which to apply rules:
-///
-/// measure > 0 ? EED -> EE
-/// *v*         ? ED ->
-/// *v*         ? ING ->
-fn phase_one_b(word: Vec<&str>) -> Vec<&str> {
-    let word_length = word.len();
-
-    if word.ends_with(&["e", "e", "d"]) {
-        if measure(&word[..word_length - 3]) > 0 {
-            let mut word = word;
-            word.truncate(word_length - 1);
-            word
-        } else {
-            word
-        }
-    } else if word.ends_with(&["e", "d"]) {
-        if contains_porter_vowel(&word[..word_length - 2]) {
-            let mut word = word;
-            word.truncate(word_length - 2);
-            phase_one_b_substep(word)
-        } else {
-            word
-        }
-    } else if word.ends_with(&["i", "n", "g"]) {
-        if contains_porter_vowel(&word[..word_length - 3]) {
-            let mut word = word;
-            word.truncate(word_length - 3);
-            phase_one_b_substep(word)
-        } else {
-            word
-        }
-    } else {
-        word
-    }
+impl PosTag {
+    /// Every part of speech.
+    pub const ALL: &'static [PosTag] = &[PosTag::Noun, PosTag::Verb, PosTag::Adjective, PosTag::Adverb];
 }
 
+/// Given a word and its part of speech, return its stemmed form.
 ///
-/// AT -> ATE
-/// BL -> BLE
-/// IZ -> IZE
-///
-/// Contraints apply to whole word in here
-/// *d (double consonant) and not (*L or *S or *Z) -> change to single letter
-///
-/// m=1 and *o (see `ends_star_o`) -> E
-fn phase_one_b_substep(word: Vec<&str>) -> Vec<&str> {
-    let word_length = word.len();
-    if word.ends_with(&["a", "t"]) ||
-       word.ends_with(&["b", "l"]) ||
-       word.ends_with(&["i", "z"]) {
-        let mut word = word;
-        word.push("e");
-        word
-    } else if ends_double_porters_consonant(&word) &&
-              !(word.ends_with(&["l"]) ||
-                word.ends_with(&["s"]) ||
-                word.ends_with(&["z"])) {
-
-        let mut word = word;
-        word.truncate(word_length - 1);
-        word
-
-    } else if measure(&word) == 1 && ends_star_o(&word) {
-        let mut word = word;
-        word.push("e");
-        word
-    } else {
-        word
-    }
+/// Only verbs have the `ed` and `ing` endings removed, so that a noun like
+/// `meeting` is kept apart from the verb `meet`, and adjectives and adverbs
+/// also keep a final `s`. The rest of Porter's algorithm applies whatever
+/// the part of speech.
+///
+/// # Examples
+///
+/// ```
+/// use porter_stemmer::{stem_with_pos, PosTag};
+///
+/// assert_eq!("meeting", stem_with_pos("meetings", PosTag::Noun));
+/// assert_eq!("meet", stem_with_pos("meeting", PosTag::Verb));
+/// ```
+pub fn stem_with_pos(word: &str, pos: PosTag) -> String {
+    let porter = Porter::new();
+    let word = word.graphemes(true).collect::<Vec<&str>>();
+
+    if word.len() <= 2 {
+        return word.concat();
+    }
+
+    let word = match pos {
+        PosTag::Noun => porter.phase_one_a(word),
+        PosTag::Verb => porter.phase_one_b(porter.phase_one_a(word)),
+        PosTag::Adjective | PosTag::Adverb => word,
+    };
+    let word = porter.phase_one_c(word);
+    let word = porter.phase_two(word);
+    let word = porter.phase_three(word);
+    let word = porter.phase_four(word);
+    let word = porter.phase_5a(word);
+    porter.phase_5b(word).concat()
 }
 
+/// How a word is split up before it is stemmed.
 ///
-/// TODO: Question about "contains* vowel and the 'Y' case (see ignored test on sky)
-/// *v* Y -> I
-fn phase_one_c(word: Vec<&str>) -> Vec<&str> {
-    let word_length = word.len();
-    if contains_porter_vowel(&word) && word.ends_with(&["y"]) {
-        let mut word = word;
-        word[word_length - 1] = "i";
-        word
-    } else {
-        word
-    }
+/// Grapheme clusters are always correct, but segmenting a word into them
+/// costs more than the stemming itself for short English words. `Char` and
+/// `Byte` trade that correctness for speed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Granularity {
+    /// Extended grapheme clusters, as used by `stem`.
+    #[default]
+    Grapheme,
+    /// Unicode scalar values.
+    Char,
+    /// Raw bytes, as used by `stem_bytes`. Words that are not entirely ASCII
+    /// are stemmed by `char` instead, so that the output is always valid UTF-8.
+    Byte,
 }
 
-/// For all where the STEM is measure > 0
-/// ATIONAL -> ATE
-/// TIONAL  -> TION
-/// ENCI    -> ENCE
-/// ANCI    -> ANCE
-/// IZER    -> IZE
-/// ABLI    -> ABLE
-/// ALLI    -> AL
-/// ENTLI   -> ENT
-/// ELI     -> E
-/// OUSLI   -> OUS
-/// IZATION -> IZE
-/// ATION   -> ATE
-/// ATOR    -> ATE
-/// ALISM   -> AL
-/// IVENESS -> IVE
-/// FULNESS -> FUL
-/// OUSNESS -> OUS
-/// ALITI   -> AL
-/// IVITI   -> IVE
-/// BILITI  -> BLE
-// TODO: This is a naive implementation - we can definitely be more efficient here by traversing
-// backwards and splitting on the last grapheme rather than searching everything (use a trie to
-// hold the search space)
-fn phase_two(word: Vec<&str>) -> Vec<&str> {
-    let word_length = word.len();
-    if word.ends_with(&["a", "t", "i", "o", "n", "a", "l"]) &&
-        measure(&word[..word_length - 7]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 5);
-        word.push("e");
-        word
-    } else if word.ends_with(&["t", "i", "o", "n", "a", "l"]) &&
-        measure(&word[..word_length - 6]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 2);
-        word
-    } else if word.ends_with(&["e", "n", "c", "i"]) &&
-        measure(&word[..word_length - 4]) > 0 {
-
-        let mut word = word;
-        word[word_length - 1] = "e";
-        word
-    } else if word.ends_with(&["a", "n", "c", "i"]) &&
-        measure(&word[..word_length - 4]) > 0 {
-
-        let mut word = word;
-        word[word_length - 1] = "e";
-        word
-    } else if word.ends_with(&["i", "z", "e", "r"]) &&
-        measure(&word[..word_length - 4]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 1);
-        word
-    } else if word.ends_with(&["a", "b", "l", "i"]) &&
-        measure(&word[..word_length - 4]) > 0 {
-
-        let mut word = word;
-        word[word_length - 1] = "e";
-        word
-    } else if word.ends_with(&["a", "l", "l", "i"]) &&
-        measure(&word[..word_length - 4]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 2);
-        word
-    } else if word.ends_with(&["e", "n", "t", "l", "i"]) &&
-        measure(&word[..word_length - 5]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 2);
-        word
-    } else if word.ends_with(&["e", "l", "i"]) &&
-        measure(&word[..word_length - 3]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 2);
-        word
-    } else if word.ends_with(&["o", "u", "s", "l", "i"]) &&
-        measure(&word[..word_length - 5]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 2);
-        word
-    } else if word.ends_with(&["i", "z", "a", "t", "i", "o", "n"]) &&
-        measure(&word[..word_length - 7]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 5);
-        word.push("e");
-        word
-    } else if word.ends_with(&["a", "t", "i", "o", "n"]) &&
-        measure(&word[..word_length - 5]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 3);
-        word.push("e");
-        word
-    } else if word.ends_with(&["a", "t", "o", "r"]) &&
-        measure(&word[..word_length - 4]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 2);
-        word.push("e");
-        word
-    } else if word.ends_with(&["a", "l", "i", "s", "m"]) &&
-        measure(&word[..word_length - 5]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 3);
-        word
-    } else if word.ends_with(&["i", "v", "e", "n", "e", "s", "s"]) &&
-        measure(&word[..word_length - 7]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 4);
-        word
-    } else if word.ends_with(&["f", "u", "l", "n", "e", "s", "s"]) &&
-        measure(&word[..word_length - 7]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 4);
-        word
-    } else if word.ends_with(&["o", "u", "s", "n", "e", "s", "s"]) &&
-        measure(&word[..word_length - 7]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 4);
-        word
-    } else if word.ends_with(&["a", "l", "i", "t", "i"]) &&
-        measure(&word[..word_length - 5]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 3);
-        word
-    } else if word.ends_with(&["i", "v", "i", "t", "i"]) &&
-        measure(&word[..word_length - 5]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 3);
-        word.push("e");
-        word
-    } else if word.ends_with(&["b", "i", "l", "i", "t", "i"]) &&
-        measure(&word[..word_length - 6]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 5);
-        word.push("l");
-        word.push("e");
-        word
-    } else {
-        word
+/// Given a word, return its stemmed form, splitting it up at the given
+/// `Granularity`.
+///
+/// # Examples
+///
+/// ```
+/// use porter_stemmer::{stem_with_granularity, Granularity};
+///
+/// assert_eq!("gener", stem_with_granularity("generalizations", Granularity::Byte));
+/// assert_eq!("naïv", stem_with_granularity("naïve", Granularity::Char));
+/// ```
+pub fn stem_with_granularity(word: &str, granularity: Granularity) -> String {
+    match granularity {
+        Granularity::Grapheme => stem(word),
+        Granularity::Char => Porter::new().with_stem(word.chars(), |stemmed| stemmed.iter().collect()),
+        Granularity::Byte if word.is_ascii() => {
+            String::from_utf8(stem_bytes(word.as_bytes()))
+                .expect("stemming ASCII input only ever produces ASCII")
+        },
+        Granularity::Byte => stem_with_granularity(word, Granularity::Char),
     }
 }
 
-/// For all whre the STEM measure is greater than one
-/// ICATE -> IC
-/// ATIVE ->
-/// ALIZE -> AL
-/// ICITI -> IC
-/// ICAL  -> IC
-/// FUL   ->
-/// NESS  ->
-// TODO: see phase_two
-fn phase_three(word: Vec<&str>) -> Vec<&str> {
-    let word_length = word.len();
-    if word.ends_with(&["i", "c", "a", "t", "e"]) &&
-        measure(&word[..word_length - 5]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 3);
-        word
-    } else if word.ends_with(&["a", "t", "i", "v", "e"]) &&
-        measure(&word[..word_length - 5]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 5);
-        word
-    } else if word.ends_with(&["a", "l", "i", "z", "e"]) &&
-        measure(&word[..word_length - 5]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 3);
-        word
-    } else if word.ends_with(&["i", "c", "i", "t", "i"]) &&
-        measure(&word[..word_length - 5]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 3);
-        word
-    } else if word.ends_with(&["i", "c", "a", "l"]) &&
-        measure(&word[..word_length - 4]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 2);
-        word
-    } else if word.ends_with(&["f", "u", "l"]) &&
-        measure(&word[..word_length - 3]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 3);
-        word
-    } else if word.ends_with(&["n", "e", "s", "s"]) &&
-        measure(&word[..word_length - 4]) > 0 {
-
-        let mut word = word;
-        word.truncate(word_length - 4);
-        word
-    } else {
-        word
-    }
+/// How aggressively to stem, depending on which side of a search it is for.
+///
+/// Stemming documents fully at index time and only removing plurals from
+/// queries keeps queries precise while still finding every form of a word.
+/// The two are compatible: stemming a query-time stem with `IndexTime`
+/// always gives the word's index-time stem, so a query term can be looked up
+/// in an index built with `IndexTime`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Profile {
+    /// Porter's algorithm in full, as used by `stem`.
+    #[default]
+    IndexTime,
+    /// Only plurals are removed, by the first step of Porter's algorithm.
+    QueryTime,
 }
 
-fn phase_four(word: Vec<&str>) -> Vec<&str> {
-    let word_length = word.len();
-    if word.ends_with(&["a", "l"]) &&
-        measure(&word[..word_length - 2]) > 1 {
-        let mut word = word;
-        word.truncate(word_length - 2);
-        word
-    } else if word.ends_with(&["a", "n", "c", "e"]) &&
-        measure(&word[..word_length - 4]) > 1 {
-        let mut word = word;
-        word.truncate(word_length - 4);
-        word
-    } else if word.ends_with(&["e", "n", "c", "e"]) &&
-        measure(&word[..word_length - 4]) > 1 {
-        let mut word = word;
-        word.truncate(word_length - 4);
-        word
-    } else if word.ends_with(&["e", "r"]) &&
-        measure(&word[..word_length - 2]) > 1 {
-        let mut word = word;
-        word.truncate(word_length - 2);
-        word
-    } else if word.ends_with(&["i", "c"]) &&
-        measure(&word[..word_length - 2]) > 1 {
-        let mut word = word;
-        word.truncate(word_length - 2);
-        word
-    } else if word.ends_with(&["a", "b", "l", "e"]) &&
-        measure(&word[..word_length - 4]) > 1 {
-        let mut word = word;
-        word.truncate(word_length - 4);
-        word
-    } else if word.ends_with(&["i", "b", "l", "e"]) &&
-        measure(&word[..word_length - 4]) > 1 {
-        let mut word = word;
-        word.truncate(word_length - 4);
-        word
-    } else if word.ends_with(&["a", "n", "t"]) &&
-        measure(&word[..word_length - 3]) > 1 {
-        let mut word = word;
-        word.truncate(word_length - 3);
-        word
-    } else if word.ends_with(&["e", "m", "e", "n", "t"]) &&
-        measure(&word[..word_length - 5]) > 1 {
-        let mut word = word;
-        word.truncate(word_length - 5);
-        word
-    } else if word.ends_with(&["m", "e", "n", "t"]) &&
-        measure(&word[..word_length - 4]) > 1 {
-        let mut word = word;
-        word.truncate(word_length - 4);
-        word
-    } else if word.ends_with(&["e", "n", "t"]) &&
-        measure(&word[..word_length - 3]) > 1 {
-        let mut word = word;
-        word.truncate(word_length - 3);
-        word
-    } else if word.ends_with(&["i", "o", "n"]) &&
-        measure(&word[..word_length - 3]) > 1 {
-
-        let last_grapheme_in_stem = word[word_length - 4];
-        if last_grapheme_in_stem == "s" || last_grapheme_in_stem == "t" {
-            let mut word = word;
-            word.truncate(word_length - 3);
-            word
-        } else {
-            word
+impl Profile {
+    /// Given a word, return its stemmed form using this profile.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use porter_stemmer::Profile;
+    ///
+    /// assert_eq!("relat", Profile::IndexTime.stem("relational"));
+    /// assert_eq!("relational", Profile::QueryTime.stem("relational"));
+    /// assert_eq!("pony", Profile::QueryTime.stem("ponys"));
+    /// assert_eq!(Profile::IndexTime.stem("ponies"), Profile::IndexTime.stem(&Profile::QueryTime.stem("ponies")));
+    /// ```
+    pub fn stem(self, word: &str) -> String {
+        match self {
+            Profile::IndexTime => stem(word),
+            Profile::QueryTime => {
+                let word = word.graphemes(true).collect::<Vec<&str>>();
+
+                if word.len() <= 2 {
+                    return word.concat();
+                }
+
+                phase_one_a(word).concat()
+            },
         }
-    } else if word.ends_with(&["o", "u"]) &&
-        measure(&word[..word_length - 2]) > 1 {
-        let mut word = word;
-        word.truncate(word_length - 2);
-        word
-    } else if word.ends_with(&["i", "s", "m"]) &&
-        measure(&word[..word_length - 3]) > 1 {
-        let mut word = word;
-        word.truncate(word_length - 3);
-        word
-    } else if word.ends_with(&["a", "t", "e"]) &&
-        measure(&word[..word_length - 3]) > 1 {
-        let mut word = word;
-        word.truncate(word_length - 3);
-        word
-    } else if word.ends_with(&["i", "t", "i"]) &&
-        measure(&word[..word_length - 3]) > 1 {
-        let mut word = word;
-        word.truncate(word_length - 3);
-        word
-    } else if word.ends_with(&["o", "u", "s"]) &&
-        measure(&word[..word_length - 3]) > 1 {
-        let mut word = word;
-        word.truncate(word_length - 3);
-        word
-    } else if word.ends_with(&["i", "v", "e"]) &&
-        measure(&word[..word_length - 3]) > 1 {
-        let mut word = word;
-        word.truncate(word_length - 3);
-        word
-    } else if word.ends_with(&["i", "z", "e"]) &&
-        measure(&word[..word_length - 3]) > 1 {
-        let mut word = word;
-        word.truncate(word_length - 3);
-        word
-    } else {
-        word
-    }
-
-}
-
-fn phase_5a(word: Vec<&str>) -> Vec<&str> {
-    let word_length = word.len();
-
-    if word.ends_with(&["e"]) &&
-        measure(&word[..word_length - 1]) > 1 {
-        let mut word = word;
-        word.truncate(word_length - 1);
-        word
-    } else if word.ends_with(&["e"]) &&
-        measure(&word[..word_length - 1]) == 1 &&
-        !ends_star_o(&word[..word_length - 1]) {
-
-        let mut word = word;
-        word.truncate(word_length - 1);
-        word
-    } else {
-        word
-    }
-}
-
-fn phase_5b(word: Vec<&str>) -> Vec<&str> {
-    let word_length = word.len();
-    if word.ends_with(&["l"]) &&
-        measure(&word) > 1 &&
-        ends_double_porters_consonant(&word) {
-
-        let mut word = word;
-        word.truncate(word_length - 1);
-        word
-    } else {
-        word
     }
 }
 
@@ -647,232 +436,123 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_real_vowel() {
-        assert!(real_vowel("a"));
-        assert!(real_vowel("e"));
-        assert!(real_vowel("i"));
-        assert!(real_vowel("o"));
-        assert!(real_vowel("u"));
-        assert!(!real_vowel("b"));
-    }
-
-    #[test]
-    fn test_real_consonant() {
-        assert!(!real_consonant("a"));
-        assert!(!real_consonant("e"));
-        assert!(!real_consonant("i"));
-        assert!(!real_consonant("o"));
-        assert!(!real_consonant("u"));
-        assert!(real_consonant("b"));
-    }
-
-    fn tokenise<'a>(input: &'a str) -> Vec<&'a str> {
-        use unicode_segmentation::UnicodeSegmentation;
-        input.graphemes(true).collect::<Vec<&'a str>>()
-    }
-
-    fn assert_fn<'a>(f: fn(Vec<&'a str>) -> Vec<&'a str>, input: &'a str, expected: &'a str) {
-        let input = tokenise(input);
-        let expected = tokenise(expected);
-
-        assert_eq!(&f(input), &expected);
-    }
-
-
-    #[test]
-    fn test_porter_character_types() {
-        let graphemes = tokenise("toy");
-
-        assert!(porter_consonant(&graphemes, 0));
-        assert!(porter_vowel(&graphemes, 1));
-        assert!(porter_consonant(&graphemes, 2));
-
-        let graphemes = tokenise("syzygy");
-        assert!(porter_consonant(&graphemes, 0));
-        assert!(porter_vowel(&graphemes, 1));
-        assert!(porter_consonant(&graphemes, 2));
-        assert!(porter_vowel(&graphemes, 3));
-        assert!(porter_consonant(&graphemes, 4));
-        assert!(porter_vowel(&graphemes, 5));
-    }
-
-    #[test]
-    fn test_ends_double_porters_consonant() {
-    let graphemes = tokenise("sell");
-    assert!(ends_double_porters_consonant(&graphemes));
-
-    let graphemes = tokenise("greyy");
-    assert!(!ends_double_porters_consonant(&graphemes));
-
-    let graphemes = tokenise("see");
-    assert!(!ends_double_porters_consonant(&graphemes));
-    }
-
-    #[test]
-    fn test_contains_vowel() {
-        let graphemes = tokenise("toy");
-        assert!(contains_porter_vowel(&graphemes));
-
-        let graphemes = tokenise("syzygy");
-        assert!(contains_porter_vowel(&graphemes));
-
-        let graphemes = tokenise("trjk");
-        assert!(!contains_porter_vowel(&graphemes));
+    fn test_stem_parts() {
+        assert_eq!(("surveil".to_string(), "lance".to_string()), stem_parts("surveillance"));
+        assert_eq!(("cat".to_string(), "s".to_string()), stem_parts("cats"));
+        assert_eq!(("is".to_string(), "".to_string()), stem_parts("is"));
+        assert_eq!(("conflat".to_string(), "ed".to_string()), stem_parts("conflated"));
     }
 
     #[test]
-    fn test_ends_star_o() {
-        let graphemes = tokenise("awhil");
-        assert!(ends_star_o(&graphemes));
-
-        let graphemes = tokenise("mix");
-        assert!(!ends_star_o(&graphemes));
-
-        let graphemes = tokenise("dew");
-        assert!(!ends_star_o(&graphemes));
-
-        let graphemes = tokenise("day");
-        assert!(!ends_star_o(&graphemes));
-    }
-
-    #[test]
-    fn test_measure() {
-        let graphemes = tokenise("crepuscular");
-        assert_eq!(4, measure(&graphemes[..]));
-
-        let graphemes = tokenise("bacon");
-        assert_eq!(2, measure(&graphemes[..]));
-
-        let graphemes = tokenise("abacus");
-        assert_eq!(3, measure(&graphemes[..]));
-
-
-        let graphemes = tokenise("paackkeeer");
-        assert_eq!(2, measure(&graphemes[..]));
-
-        let graphemes = tokenise("syzygy");
-        assert_eq!(2, measure(&graphemes[..]));
+    fn test_stem_with_offsets() {
+        let text = "  caresses\tand ponies.";
+        let stems = stem_with_offsets(text).collect::<Vec<_>>();
 
+        assert_eq!(3, stems.len());
+        for &(ref range, original, _) in &stems {
+            assert_eq!(original, &text[range.clone()]);
+        }
+        assert_eq!((2..10, "caresses", "caress".to_string()), stems[0]);
+        assert_eq!((15..21, "ponies", "poni".to_string()), stems[2]);
     }
 
     #[test]
-    fn test_phase_one() {
-        assert_fn(phase_one_a, "caresses", "caress");
-        assert_fn(phase_one_a, "caress", "caress");
-        assert_fn(phase_one_a, "ponies", "poni");
-        assert_fn(phase_one_a, "cats", "cat");
-    }
+    fn test_stem_buffer() {
+        let input = include_str!("../input.txt");
 
-    #[test]
-    fn test_phase_one_b() {
-        assert_fn(phase_one_b, "feed", "feed");
-        assert_fn(phase_one_b, "agreed", "agree");
-        assert_fn(phase_one_b, "plastered", "plaster");
-        assert_fn(phase_one_b, "bled", "bled");
-        assert_fn(phase_one_b, "motoring", "motor");
-        assert_fn(phase_one_b, "sing", "sing");
-    }
+        let mut stems = String::new();
+        stem_buffer(input, &mut stems);
+        assert_eq!(input.lines().map(stem).collect::<Vec<_>>(), stems.lines().collect::<Vec<_>>());
 
-    #[test]
-    fn test_phase_one_b_substep() {
-        assert_fn(phase_one_b_substep, "conflat", "conflate");
-        assert_fn(phase_one_b_substep, "troubl", "trouble");
-        assert_fn(phase_one_b_substep, "siz", "size");
-        assert_fn(phase_one_b_substep, "hopp", "hop");
-        assert_fn(phase_one_b_substep, "hiss", "hiss");
-        assert_fn(phase_one_b_substep, "fizz", "fizz");
-        assert_fn(phase_one_b_substep, "fall", "fall");
-        assert_fn(phase_one_b_substep, "fail", "fail");
-        assert_fn(phase_one_b_substep, "fil", "file");
-    }
-
-    #[test]
-    fn test_phase_one_c() {
-        assert_fn(phase_one_c, "happy", "happi");
+        let mut stems = "kept ".to_string();
+        stem_buffer("  two\tspaces\r\n ", &mut stems);
+        assert_eq!("kept   two\tspace\r\n ", stems);
     }
 
     #[test]
-    #[ignore]
-    fn test_phase_one_c_sky() {
-        assert_fn(phase_one_c, "sky", "sky");
+    fn test_stem_ref_and_parts() {
+        for word in include_str!("../input.txt").lines().chain(vec!["résumés", "a\r\nb"]) {
+            let stemmed = stem(word);
+            let (kept, removed) = stem_parts(word);
+
+            match stem_ref(word) {
+                Cow::Borrowed(borrowed) => assert!(word.starts_with(borrowed), "stemming {}", word),
+                Cow::Owned(_) => assert!(!word.starts_with(&stemmed), "stemming {}", word),
+            }
+            assert_eq!(stemmed, stem_ref(word), "stemming {}", word);
+            assert_eq!(stemmed, kept, "stemming {}", word);
+            assert!(word.ends_with(&removed), "stemming {}", word);
+        }
     }
 
     #[test]
-    fn test_phase_two() {
-        assert_fn(phase_two, "relational", "relate");
-        assert_fn(phase_two, "conditional", "condition");
-        assert_fn(phase_two, "rational", "rational");
-        assert_fn(phase_two, "valenci", "valence");
-        assert_fn(phase_two, "hesitanci", "hesitance");
-        assert_fn(phase_two, "digitizer", "digitize");
-        assert_fn(phase_two, "conformabli", "conformable");
-        assert_fn(phase_two, "radicalli", "radical");
-        assert_fn(phase_two, "differentli", "different");
-        assert_fn(phase_two, "vileli", "vile");
-        assert_fn(phase_two, "analogousli", "analogous");
-        assert_fn(phase_two, "vietnamization", "vietnamize");
-        assert_fn(phase_two, "predication", "predicate");
-        assert_fn(phase_two, "operator", "operate");
-        assert_fn(phase_two, "feudalism", "feudal");
-        assert_fn(phase_two, "decisiveness", "decisive");
-        assert_fn(phase_two, "hopefulness", "hopeful");
-        assert_fn(phase_two, "callousness", "callous");
-        assert_fn(phase_two, "formaliti", "formal");
-        assert_fn(phase_two, "sensitiviti", "sensitive");
-        assert_fn(phase_two, "sensibiliti", "sensible");
+    fn test_stem_bytes() {
+        for word in &["caresses", "relational", "surveillance", "controlling", "sky", "a"] {
+            assert_eq!(stem(word).into_bytes(), stem_bytes(word.as_bytes()));
+        }
     }
 
     #[test]
-    fn test_phase_three() {
-        assert_fn(phase_three, "triplicate", "triplic");
-        assert_fn(phase_three, "formative", "form");
-        assert_fn(phase_three, "formalize", "formal");
-        assert_fn(phase_three, "electriciti", "electric");
-        assert_fn(phase_three, "electrical", "electric");
-        assert_fn(phase_three, "hopeful", "hope");
-        assert_fn(phase_three, "goodness", "good");
+    fn test_stem_with_granularity() {
+        let granularities = [Granularity::Grapheme, Granularity::Char, Granularity::Byte];
+        for word in &["caresses", "relational", "hopefulness", "résumés", "a"] {
+            for &granularity in &granularities {
+                assert_eq!(stem(word), stem_with_granularity(word, granularity));
+            }
+        }
     }
 
     #[test]
-    fn test_phase_four() {
-        assert_fn(phase_four, "revival", "reviv");
-        assert_fn(phase_four, "allowance", "allow");
-        assert_fn(phase_four, "inference", "infer");
-        assert_fn(phase_four, "airliner", "airlin");
-        assert_fn(phase_four, "gyroscopic", "gyroscop");
-        assert_fn(phase_four, "adjustable", "adjust");
-        assert_fn(phase_four, "defensible", "defens");
-        assert_fn(phase_four, "irritant", "irrit");
-        assert_fn(phase_four, "replacement", "replac");
-        assert_fn(phase_four, "adjustment", "adjust");
-        assert_fn(phase_four, "dependent", "depend");
-        assert_fn(phase_four, "adoption", "adopt");
-        assert_fn(phase_four, "homologou", "homolog");
-        assert_fn(phase_four, "communism", "commun");
-        assert_fn(phase_four, "activate", "activ");
-        assert_fn(phase_four, "angulariti", "angular");
-        assert_fn(phase_four, "homologous", "homolog");
-        assert_fn(phase_four, "effective", "effect");
-        assert_fn(phase_four, "bowdlerize", "bowdler");
+    fn test_strip_possessive() {
+        assert_eq!("dog", strip_possessive("dog's"));
+        assert_eq!("DOG", strip_possessive("DOG'S"));
+        assert_eq!("dog", strip_possessive("dog\u{2019}s"));
+        assert_eq!("dogs", strip_possessive("dogs'"));
+        assert_eq!("dogs", strip_possessive("dogs"));
+        assert_eq!("'s", strip_possessive("'s"));
+        assert_eq!("'", strip_possessive("'"));
+        assert_eq!("", strip_possessive(""));
     }
 
     #[test]
-    fn test_phase_five_a() {
-        // 5a
-        assert_fn(phase_5a, "probate", "probat");
-        assert_fn(phase_5a, "rate", "rate");
-        assert_fn(phase_5a, "cease", "ceas");
+    fn test_stem_with_pos() {
+        assert_eq!("meeting", stem_with_pos("meeting", PosTag::Noun));
+        assert_eq!("meet", stem_with_pos("meeting", PosTag::Verb));
+        assert_eq!("build", stem_with_pos("buildings", PosTag::Verb));
+        assert_eq!("building", stem_with_pos("buildings", PosTag::Noun));
+        assert_eq!("hope", stem_with_pos("hopeful", PosTag::Adjective));
+        assert_eq!("serious", stem_with_pos("serious", PosTag::Adjective));
+        assert_eq!("seriou", stem("serious"));
+        for word in &["caresses", "relational", "controlling", "is"] {
+            assert_eq!(stem(word), stem_with_pos(word, PosTag::Verb));
+        }
     }
 
     #[test]
-    fn test_phase_five_b() {
-        // 5b
-        assert_fn(phase_5b, "controll", "control");
-        assert_fn(phase_5b, "roll", "roll");
+    fn test_profile() {
+        assert_eq!("caress", Profile::QueryTime.stem("caresses"));
+        assert_eq!("poni", Profile::QueryTime.stem("ponies"));
+        assert_eq!("hopeful", Profile::QueryTime.stem("hopeful"));
+        assert_eq!("is", Profile::QueryTime.stem("is"));
+        assert_eq!(Profile::IndexTime, Profile::default());
+
+        for word in include_str!("../input.txt").lines() {
+            let index = Profile::IndexTime.stem(word);
+            assert_eq!(index, Profile::IndexTime.stem(&Profile::QueryTime.stem(word)), "stemming {}", word);
+        }
     }
 
     #[test]
-    fn test_stem_tokenized() {
-        assert_fn(stem_tokenized, "surveillance", "surveil");
+    fn test_stem_ref() {
+        match stem_ref("surveillance") {
+            Cow::Borrowed(stemmed) => assert_eq!("surveil", stemmed),
+            Cow::Owned(_) => panic!("truncated stems should be borrowed"),
+        }
+        match stem_ref("happy") {
+            Cow::Owned(stemmed) => assert_eq!("happi", stemmed),
+            Cow::Borrowed(_) => panic!("rewritten stems should be owned"),
+        }
+        assert_eq!(Cow::Borrowed("is"), stem_ref("is"));
+        assert_eq!("", stem_ref(""));
     }
 }
@@ -0,0 +1,329 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Lovins stemmer.
+//!
+//! Julie Beth Lovins' 1968 algorithm was the first published stemmer. In a
+//! single pass it removes the longest ending, from a fixed list of nearly
+//! three hundred, whose context condition holds, then tidies up the remaining
+//! stem with a set of respelling rules. It is much more aggressive than
+//! Porter's algorithm.
+//!
+//! Words are expected to be lowercase.
+//!
+//! See <http://snowball.tartarus.org/algorithms/lovins/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::lovins;
+//!
+//! assert_eq!("nat", lovins::stem("nationally"));
+//! assert_eq!("absorb", lovins::stem("absorption"));
+//! ```
+
+use primitives::ends_with;
+
+/// The context conditions an ending can be removed under, named as in the
+/// original paper. Every ending also needs a stem of at least two letters.
+#[derive(Clone, Copy)]
+pub(crate) enum Condition {
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, BB, CC,
+}
+
+pub(crate) const ENDINGS: &[(&str, Condition)] = &[
+    ("alistically", Condition::B), ("arizability", Condition::A), ("izationally", Condition::B),
+
+    ("antialness", Condition::A), ("arisations", Condition::A), ("arizations", Condition::A),
+    ("entialness", Condition::A),
+
+    ("allically", Condition::C), ("antaneous", Condition::A), ("antiality", Condition::A),
+    ("arisation", Condition::A), ("arization", Condition::A), ("ationally", Condition::B),
+    ("ativeness", Condition::A), ("eableness", Condition::E), ("entations", Condition::A),
+    ("entiality", Condition::A), ("entialize", Condition::A), ("entiation", Condition::A),
+    ("ionalness", Condition::A), ("istically", Condition::A), ("itousness", Condition::A),
+    ("izability", Condition::A), ("izational", Condition::A),
+
+    ("ableness", Condition::A), ("arizable", Condition::A), ("entation", Condition::A),
+    ("entially", Condition::A), ("eousness", Condition::A), ("ibleness", Condition::A),
+    ("icalness", Condition::A), ("ionalism", Condition::A), ("ionality", Condition::A),
+    ("ionalize", Condition::A), ("iousness", Condition::A), ("izations", Condition::A),
+    ("lessness", Condition::A),
+
+    ("ability", Condition::A), ("aically", Condition::A), ("alistic", Condition::B),
+    ("alities", Condition::A), ("ariness", Condition::E), ("aristic", Condition::A),
+    ("arizing", Condition::A), ("ateness", Condition::A), ("atingly", Condition::A),
+    ("ational", Condition::B), ("atively", Condition::A), ("ativism", Condition::A),
+    ("elihood", Condition::E), ("encible", Condition::A), ("entally", Condition::A),
+    ("entials", Condition::A), ("entiate", Condition::A), ("entness", Condition::A),
+    ("fulness", Condition::A), ("ibility", Condition::A), ("icalism", Condition::A),
+    ("icalist", Condition::A), ("icality", Condition::A), ("icalize", Condition::A),
+    ("ication", Condition::G), ("icianry", Condition::A), ("ination", Condition::A),
+    ("ingness", Condition::A), ("ionally", Condition::A), ("isation", Condition::A),
+    ("ishness", Condition::A), ("istical", Condition::A), ("iteness", Condition::A),
+    ("iveness", Condition::A), ("ivistic", Condition::A), ("ivities", Condition::A),
+    ("ization", Condition::F), ("izement", Condition::A), ("oidally", Condition::A),
+    ("ousness", Condition::A),
+
+    ("aceous", Condition::A), ("acious", Condition::B), ("action", Condition::G),
+    ("alness", Condition::A), ("ancial", Condition::A), ("ancies", Condition::A),
+    ("ancing", Condition::B), ("ariser", Condition::A), ("arized", Condition::A),
+    ("arizer", Condition::A), ("atable", Condition::A), ("ations", Condition::B),
+    ("atives", Condition::A), ("eature", Condition::Z), ("efully", Condition::A),
+    ("encies", Condition::A), ("encing", Condition::A), ("ential", Condition::A),
+    ("enting", Condition::C), ("entist", Condition::A), ("eously", Condition::A),
+    ("ialist", Condition::A), ("iality", Condition::A), ("ialize", Condition::A),
+    ("ically", Condition::A), ("icance", Condition::A), ("icians", Condition::A),
+    ("icists", Condition::A), ("ifully", Condition::A), ("ionals", Condition::A),
+    ("ionate", Condition::D), ("ioning", Condition::A), ("ionist", Condition::A),
+    ("iously", Condition::A), ("istics", Condition::A), ("izable", Condition::E),
+    ("lessly", Condition::A), ("nesses", Condition::A), ("oidism", Condition::A),
+
+    ("acies", Condition::A), ("acity", Condition::A), ("aging", Condition::B),
+    ("aical", Condition::A), ("alism", Condition::B), ("alist", Condition::A),
+    ("ality", Condition::A), ("alize", Condition::A), ("allic", Condition::BB),
+    ("anced", Condition::B), ("ances", Condition::B), ("antic", Condition::C),
+    ("arial", Condition::A), ("aries", Condition::A), ("arily", Condition::A),
+    ("arity", Condition::B), ("arize", Condition::A), ("aroid", Condition::A),
+    ("ately", Condition::A), ("ating", Condition::I), ("ation", Condition::B),
+    ("ative", Condition::A), ("ators", Condition::A), ("atory", Condition::A),
+    ("ature", Condition::E), ("early", Condition::Y), ("ehood", Condition::A),
+    ("eless", Condition::A), ("elity", Condition::A), ("ement", Condition::A),
+    ("enced", Condition::A), ("ences", Condition::A), ("eness", Condition::E),
+    ("ening", Condition::E), ("ental", Condition::A), ("ented", Condition::C),
+    ("ently", Condition::A), ("fully", Condition::A), ("ially", Condition::A),
+    ("icant", Condition::A), ("ician", Condition::A), ("icide", Condition::A),
+    ("icism", Condition::A), ("icist", Condition::A), ("icity", Condition::A),
+    ("idine", Condition::I), ("iedly", Condition::A), ("ihood", Condition::A),
+    ("inate", Condition::A), ("iness", Condition::A), ("ingly", Condition::B),
+    ("inism", Condition::J), ("inity", Condition::CC), ("ional", Condition::A),
+    ("ioned", Condition::A), ("ished", Condition::A), ("istic", Condition::A),
+    ("ities", Condition::A), ("itous", Condition::A), ("ively", Condition::A),
+    ("ivity", Condition::A), ("izers", Condition::F), ("izing", Condition::F),
+    ("oidal", Condition::A), ("oides", Condition::A), ("otide", Condition::A),
+    ("ously", Condition::A),
+
+    ("able", Condition::A), ("ably", Condition::A), ("ages", Condition::B),
+    ("ally", Condition::B), ("ance", Condition::B), ("ancy", Condition::B),
+    ("ants", Condition::B), ("aric", Condition::A), ("arly", Condition::K),
+    ("ated", Condition::I), ("ates", Condition::A), ("atic", Condition::B),
+    ("ator", Condition::A), ("ealy", Condition::Y), ("edly", Condition::E),
+    ("eful", Condition::A), ("eity", Condition::A), ("ence", Condition::A),
+    ("ency", Condition::A), ("ened", Condition::E), ("enly", Condition::E),
+    ("eous", Condition::A), ("hood", Condition::A), ("ials", Condition::A),
+    ("ians", Condition::A), ("ible", Condition::A), ("ibly", Condition::A),
+    ("ical", Condition::A), ("ides", Condition::L), ("iers", Condition::A),
+    ("iful", Condition::A), ("ines", Condition::M), ("ings", Condition::N),
+    ("ions", Condition::B), ("ious", Condition::A), ("isms", Condition::B),
+    ("ists", Condition::A), ("itic", Condition::H), ("ized", Condition::F),
+    ("izer", Condition::F), ("less", Condition::A), ("lily", Condition::A),
+    ("ness", Condition::A), ("ogen", Condition::A), ("ward", Condition::A),
+    ("wise", Condition::A), ("ying", Condition::B), ("yish", Condition::A),
+
+    ("acy", Condition::A), ("age", Condition::B), ("aic", Condition::A),
+    ("als", Condition::BB), ("ant", Condition::B), ("ars", Condition::O),
+    ("ary", Condition::F), ("ata", Condition::A), ("ate", Condition::A),
+    ("eal", Condition::Y), ("ear", Condition::Y), ("ely", Condition::E),
+    ("ene", Condition::E), ("ent", Condition::C), ("ery", Condition::E),
+    ("ese", Condition::A), ("ful", Condition::A), ("ial", Condition::A),
+    ("ian", Condition::A), ("ics", Condition::A), ("ide", Condition::L),
+    ("ied", Condition::A), ("ier", Condition::A), ("ies", Condition::P),
+    ("ily", Condition::A), ("ine", Condition::M), ("ing", Condition::N),
+    ("ion", Condition::Q), ("ish", Condition::C), ("ism", Condition::B),
+    ("ist", Condition::A), ("ite", Condition::AA), ("ity", Condition::A),
+    ("ium", Condition::A), ("ive", Condition::A), ("ize", Condition::F),
+    ("oid", Condition::A), ("one", Condition::R), ("ous", Condition::A),
+
+    ("ae", Condition::A), ("al", Condition::BB), ("ar", Condition::X),
+    ("as", Condition::B), ("ed", Condition::E), ("en", Condition::F),
+    ("es", Condition::E), ("ia", Condition::A), ("ic", Condition::A),
+    ("is", Condition::A), ("ly", Condition::B), ("on", Condition::S),
+    ("or", Condition::T), ("um", Condition::U), ("us", Condition::V),
+
+    ("a", Condition::A), ("e", Condition::A), ("i", Condition::A),
+    ("o", Condition::A), ("s", Condition::W), ("y", Condition::B),
+];
+
+const DOUBLES: &[&str] = &["bb", "dd", "gg", "ll", "mm", "nn", "pp", "rr", "ss", "tt"];
+
+/// Respellings applied to the stem, as `(ending, replacement, must not follow)`.
+const RESPELLINGS: &[(&str, &str, &[&str])] = &[
+    ("iev", "ief", &[]),
+    ("uct", "uc", &[]),
+    ("umpt", "um", &[]),
+    ("rpt", "rb", &[]),
+    ("urs", "ur", &[]),
+    ("istr", "ister", &[]),
+    ("metr", "meter", &[]),
+    ("olv", "olut", &[]),
+    ("ul", "l", &["a", "i", "o"]),
+    ("bex", "bic", &[]),
+    ("dex", "dic", &[]),
+    ("pex", "pic", &[]),
+    ("tex", "tic", &[]),
+    ("ax", "ac", &[]),
+    ("ex", "ec", &[]),
+    ("ix", "ic", &[]),
+    ("lux", "luc", &[]),
+    ("uad", "uas", &[]),
+    ("vad", "vas", &[]),
+    ("cid", "cis", &[]),
+    ("lid", "lis", &[]),
+    ("erid", "eris", &[]),
+    ("pand", "pans", &[]),
+    ("end", "ens", &["s"]),
+    ("ond", "ons", &[]),
+    ("lud", "lus", &[]),
+    ("rud", "rus", &[]),
+    ("her", "hes", &["p", "t"]),
+    ("mit", "mis", &[]),
+    ("ent", "ens", &["m"]),
+    ("ert", "ers", &[]),
+    ("et", "es", &["n"]),
+    ("yt", "ys", &[]),
+    ("yz", "ys", &[]),
+];
+
+/// Given a lowercase word, return its stemmed form using Lovins' algorithm.
+pub fn stem(word: &str) -> String {
+    let mut word = word.chars().collect::<Vec<char>>();
+
+    remove_ending(&mut word);
+    undouble(&mut word);
+    respell(&mut word);
+
+    word.into_iter().collect()
+}
+
+fn ends_with_any(stem: &[char], suffixes: &[&str]) -> bool {
+    suffixes.iter().any(|suffix| ends_with(stem, suffix))
+}
+
+impl Condition {
+    /// Whether an ending can be removed, leaving `stem` behind.
+    pub(crate) fn holds(self, stem: &[char]) -> bool {
+        let length = stem.len();
+
+        if length < 2 {
+            return false;
+        }
+
+        match self {
+            Condition::A => true,
+            Condition::B => length >= 3,
+            Condition::C => length >= 4,
+            Condition::D => length >= 5,
+            Condition::E => !ends_with(stem, "e"),
+            Condition::F => length >= 3 && !ends_with(stem, "e"),
+            Condition::G => length >= 3 && ends_with(stem, "f"),
+            Condition::H => ends_with_any(stem, &["t", "ll"]),
+            Condition::I => !ends_with_any(stem, &["o", "e"]),
+            Condition::J => !ends_with_any(stem, &["a", "e"]),
+            Condition::K => length >= 3 && (ends_with_any(stem, &["l", "i"]) || u_e(stem)),
+            Condition::L => !ends_with_any(stem, &["u", "x"]) && (!ends_with(stem, "s") || ends_with(stem, "os")),
+            Condition::M => !ends_with_any(stem, &["a", "c", "e", "m"]),
+            Condition::N => length >= 3 && (stem[length - 3] != 's' || length >= 4),
+            Condition::O => ends_with_any(stem, &["l", "i"]),
+            Condition::P => !ends_with(stem, "c"),
+            Condition::Q => length >= 3 && !ends_with_any(stem, &["l", "n"]),
+            Condition::R => ends_with_any(stem, &["n", "r"]),
+            Condition::S => ends_with(stem, "dr") || (ends_with(stem, "t") && !ends_with(stem, "tt")),
+            Condition::T => ends_with(stem, "s") || (ends_with(stem, "t") && !ends_with(stem, "ot")),
+            Condition::U => ends_with_any(stem, &["l", "m", "n", "r"]),
+            Condition::V => ends_with(stem, "c"),
+            Condition::W => !ends_with_any(stem, &["s", "u"]),
+            Condition::X => ends_with_any(stem, &["l", "i"]) || u_e(stem),
+            Condition::Y => ends_with(stem, "in"),
+            Condition::Z => !ends_with(stem, "f"),
+            Condition::AA => ends_with_any(stem, &["d", "f", "ph", "th", "l", "er", "or", "es", "t"]),
+            Condition::BB => length >= 3 && !ends_with_any(stem, &["met", "ryst"]),
+            Condition::CC => ends_with(stem, "l"),
+        }
+    }
+}
+
+/// Whether the stem ends in `u`, any letter, then `e`.
+fn u_e(stem: &[char]) -> bool {
+    let length = stem.len();
+
+    length >= 3 && stem[length - 3] == 'u' && stem[length - 1] == 'e'
+}
+
+/// Remove the longest ending whose condition holds for the remaining stem.
+fn remove_ending(word: &mut Vec<char>) {
+    let length = word.len();
+
+    let ending = ENDINGS.iter()
+        .find(|&&(ending, condition)| {
+            ends_with(word, ending) && condition.holds(&word[..length - ending.len()])
+        });
+
+    if let Some(&(ending, _)) = ending {
+        word.truncate(length - ending.len());
+    }
+}
+
+pub(crate) fn undouble(word: &mut Vec<char>) {
+    if ends_with_any(word, DOUBLES) {
+        word.pop();
+    }
+}
+
+/// Apply the longest matching respelling rule, if its context allows it.
+pub(crate) fn respell(word: &mut Vec<char>) {
+    let rule = RESPELLINGS.iter()
+        .filter(|&&(ending, _, _)| ends_with(word, ending))
+        .max_by_key(|&&(ending, _, _)| ending.len());
+
+    if let Some(&(ending, replacement, exceptions)) = rule {
+        let stem = word.len() - ending.len();
+
+        if !ends_with_any(&word[..stem], exceptions) {
+            word.truncate(stem);
+            word.extend(replacement.chars());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endings_are_longest_first() {
+        for pair in ENDINGS.windows(2) {
+            assert!(pair[0].0.len() >= pair[1].0.len(), "{} before {}", pair[0].0, pair[1].0);
+        }
+    }
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("nationally", "nat"),
+            ("sitting", "sit"),
+            ("believe", "belief"),
+            ("absorption", "absorb"),
+            ("conclude", "conclus"),
+            ("conclusion", "conclus"),
+            ("matrix", "matric"),
+            ("matrices", "matric"),
+            ("rubbing", "rub"),
+            ("rub", "rub"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+
+    #[test]
+    fn test_conditions() {
+        // "ing" needs four letters before it when the stem ends `s**`.
+        assert_eq!("sing", stem("sing"));
+        assert_eq!("bring", stem("bring"));
+        // "e" can't be removed from a one letter stem.
+        assert_eq!("be", stem("be"));
+        // "s" is not removed after `s` or `u`.
+        assert_eq!("bus", stem("bus"));
+        assert_eq!("cat", stem("cats"));
+    }
+}
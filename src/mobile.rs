@@ -0,0 +1,139 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Stemming from Kotlin and Swift, through UniFFI.
+//!
+//! `uniffi-bindgen` generates bindings for this module from the built
+//! library, so Android and iOS apps can stem search queries on the device
+//! exactly as the Rust service indexing the documents does:
+//!
+//! ```kotlin
+//! val porter = Stemmer()
+//! porter.stem("hopefulness")               // "hope"
+//! Stemmer.named("german").stem("Häuser")   // "Haus"
+//! stemmerNames()                           // ["porter", "porter2", ...]
+//! ```
+//!
+//! Requires the `uniffi` feature.
+
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use algorithm::{self, Algorithm};
+use language::Language;
+
+/// A name that isn't an algorithm or a language.
+#[derive(Debug, PartialEq, uniffi::Error)]
+pub enum StemmerError {
+    UnknownStemmer { name: String },
+}
+
+impl fmt::Display for StemmerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StemmerError::UnknownStemmer { ref name } => write!(f, "unknown stemming algorithm or language: {}", name),
+        }
+    }
+}
+
+impl Error for StemmerError {}
+
+/// A stemming algorithm or language, as `Stemmer` is in Rust.
+#[derive(uniffi::Object)]
+pub struct Stemmer {
+    stemmer: Box<dyn algorithm::Stemmer + Send + Sync>,
+}
+
+#[uniffi::export]
+impl Stemmer {
+    /// A stemmer using Porter's algorithm, as the crate's `stem` does.
+    #[uniffi::constructor]
+    pub fn new() -> Arc<Stemmer> {
+        Arc::new(Stemmer { stemmer: Box::new(Algorithm::Porter) })
+    }
+
+    /// A stemmer for the algorithm or language `name`, one of
+    /// `stemmer_names`.
+    #[uniffi::constructor]
+    pub fn named(name: String) -> Result<Arc<Stemmer>, StemmerError> {
+        let stemmer: Box<dyn algorithm::Stemmer + Send + Sync> = if let Ok(algorithm) = name.parse::<Algorithm>() {
+            Box::new(algorithm)
+        } else if let Ok(language) = name.parse::<Language>() {
+            Box::new(language)
+        } else {
+            return Err(StemmerError::UnknownStemmer { name });
+        };
+
+        Ok(Arc::new(Stemmer { stemmer }))
+    }
+
+    /// Given a word, return its stemmed form.
+    pub fn stem(&self, word: String) -> String {
+        self.stemmer.stem(&word)
+    }
+
+    /// Stem each word of `text`, keeping the spaces and punctuation between
+    /// them as they are.
+    pub fn stem_text(&self, text: String) -> String {
+        let mut stems = String::with_capacity(text.len());
+
+        for segment in text.split_word_bounds() {
+            if segment.chars().any(char::is_alphanumeric) {
+                stems.push_str(&self.stemmer.stem(segment));
+            } else {
+                stems.push_str(segment);
+            }
+        }
+
+        stems
+    }
+
+    /// Stem each of `words`, crossing into Rust once rather than once a word.
+    pub fn stem_batch(&self, words: Vec<String>) -> Vec<String> {
+        words.iter().map(|word| self.stemmer.stem(word)).collect()
+    }
+}
+
+/// The names of the algorithms, then the languages, that `Stemmer.named`
+/// accepts.
+#[uniffi::export]
+pub fn stemmer_names() -> Vec<String> {
+    let algorithms = Algorithm::all().iter().map(|algorithm| algorithm.name());
+    let languages = Language::all().iter().map(|language| language.name());
+
+    algorithms.chain(languages).map(String::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stemmer() {
+        let porter = Stemmer::new();
+        assert_eq!("hope", porter.stem("hopefulness".into()));
+        assert_eq!("The poni ran, hopefulli.\n", porter.stem_text("The ponies ran, hopefully.\n".into()));
+        assert_eq!(vec!["connect", "poni", ""], porter.stem_batch(vec!["connected".into(), "ponies".into(), "".into()]));
+
+        let german = Stemmer::named("german".into()).unwrap();
+        assert_eq!(vec!["Haus", "Katz"], german.stem_batch(vec!["Häuser".into(), "Katzen".into()]));
+        assert_eq!("hop", Stemmer::named("lovins".into()).unwrap().stem("hopeful".into()));
+
+        let unknown = Stemmer::named("klingon".into()).err().unwrap();
+        assert_eq!(StemmerError::UnknownStemmer { name: "klingon".into() }, unknown);
+        assert_eq!("unknown stemming algorithm or language: klingon", unknown.to_string());
+    }
+
+    #[test]
+    fn test_stemmer_names() {
+        let names = stemmer_names();
+
+        assert_eq!(Some(&"porter".to_string()), names.first());
+        assert!(names.contains(&"german".to_string()));
+        assert!(names.iter().all(|name| Stemmer::named(name.clone()).is_ok()));
+    }
+}
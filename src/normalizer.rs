@@ -0,0 +1,154 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! A charabia `Normalizer`, stemming the words of Meilisearch-style token
+//! streams after charabia's own normalizers have run.
+//!
+//! Requires the `charabia` feature.
+//!
+//! ```ignore
+//! use charabia::normalizer::{Normalizer, NormalizerOption};
+//! use charabia::Tokenize;
+//! use porter_stemmer::normalizer::StemNormalizer;
+//!
+//! let stemmer = StemNormalizer::default();
+//! let options = NormalizerOption { create_char_map: true, ..Default::default() };
+//! let stems = "The ponies ran".tokenize()
+//!     .map(|token| if stemmer.should_normalize(&token) { stemmer.normalize(token, &options) } else { token })
+//!     .collect::<Vec<_>>();
+//! ```
+
+use std::borrow::Cow;
+
+use charabia::normalizer::{Normalizer, NormalizerOption};
+use charabia::Token;
+
+use algorithm::{Algorithm, Stemmer};
+
+/// Replaces the lemma of each word token with its stem, Porter's algorithm
+/// by default.
+///
+/// If asked to, the token's `char_map` is updated too, so a highlighter can
+/// still find the original characters each stem's characters came from.
+#[derive(Clone, Debug)]
+pub struct StemNormalizer<S> {
+    stemmer: S,
+}
+
+impl<S: Stemmer + Send + Sync> StemNormalizer<S> {
+    /// A normalizer stemming with `stemmer`.
+    pub fn new(stemmer: S) -> StemNormalizer<S> {
+        StemNormalizer { stemmer }
+    }
+}
+
+impl Default for StemNormalizer<Algorithm> {
+    fn default() -> StemNormalizer<Algorithm> {
+        StemNormalizer::new(Algorithm::Porter)
+    }
+}
+
+impl<S: Stemmer + Send + Sync> Normalizer for StemNormalizer<S> {
+    fn normalize<'o>(&self, mut token: Token<'o>, options: &NormalizerOption) -> Token<'o> {
+        let stem = self.stemmer.stem(token.lemma());
+        if stem == token.lemma() {
+            return token;
+        }
+
+        if options.create_char_map {
+            token.char_map = Some(char_map(token.char_map.as_ref().map(|map| &map[..]), token.lemma(), &stem));
+        }
+        token.lemma = Cow::Owned(stem);
+        token
+    }
+
+    fn should_normalize(&self, token: &Token) -> bool {
+        token.is_word()
+    }
+}
+
+/// The `char_map` of a token whose lemma `lemma` was stemmed to `stem`,
+/// given its map before, if it had one.
+///
+/// A map pairs each original character's length with the length of what it
+/// became, in bytes. The stem's characters are taken to replace the lemma's
+/// one for one; characters the stem drops become nothing, and any it adds
+/// are counted with the last character.
+fn char_map(map: Option<&[(u8, u8)]>, lemma: &str, stem: &str) -> Vec<(u8, u8)> {
+    let mut stem_chars = stem.chars();
+    let mut lengths = lemma.chars()
+        .map(|c| (c.len_utf8(), stem_chars.next().map_or(0, char::len_utf8)))
+        .collect::<Vec<_>>();
+    if let Some(last) = lengths.last_mut() {
+        last.1 += stem_chars.map(char::len_utf8).sum::<usize>();
+    }
+
+    let map = match map {
+        Some(map) => map,
+        None => return lengths.into_iter().map(|(lemma, stem)| (lemma as u8, stem as u8)).collect(),
+    };
+
+    // Each original character became some of the lemma's characters, which
+    // become what the stem has in their place.
+    let mut lengths = lengths.into_iter();
+    map.iter()
+        .map(|&(original, normalized)| {
+            let (mut from, mut to) = (0, 0);
+            while from < normalized as usize {
+                match lengths.next() {
+                    Some((lemma_length, stem_length)) => {
+                        from += lemma_length;
+                        to += stem_length;
+                    },
+                    None => break,
+                }
+            }
+            (original, to as u8)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use charabia::TokenKind;
+
+    use language::Language;
+
+    fn word(lemma: &str) -> Token<'static> {
+        Token { kind: TokenKind::Word, lemma: Cow::Owned(lemma.to_string()), ..Token::default() }
+    }
+
+    #[test]
+    fn test_normalize() {
+        let stemmer = StemNormalizer::default();
+        let options = NormalizerOption::default();
+
+        assert_eq!("poni", stemmer.normalize(word("ponies"), &options).lemma());
+        assert_eq!("hope", stemmer.normalize(word("hopefulness"), &options).lemma());
+        assert_eq!(None, stemmer.normalize(word("ponies"), &options).char_map);
+
+        let german = StemNormalizer::new(Language::German);
+        assert_eq!("Haus", german.normalize(word("Häuser"), &options).lemma());
+
+        let separator = Token { kind: TokenKind::Separator(Default::default()), ..word(" ") };
+        assert!(stemmer.should_normalize(&word("ponies")));
+        assert!(!stemmer.should_normalize(&separator));
+    }
+
+    #[test]
+    fn test_char_map() {
+        let stemmer = StemNormalizer::default();
+        let options = NormalizerOption { create_char_map: true, ..NormalizerOption::default() };
+
+        let stem = stemmer.normalize(word("ponies"), &options);
+        assert_eq!(Some(vec![(1, 1), (1, 1), (1, 1), (1, 1), (1, 0), (1, 0)]), stem.char_map);
+
+        // Earlier normalizers made two characters of the first, and stripped
+        // the accent from the third.
+        let map = [(2, 3), (1, 1), (2, 1), (1, 1), (1, 1)];
+        assert_eq!(vec![(2, 3), (1, 1), (2, 1), (1, 0), (1, 0)], char_map(Some(&map), "džaeed", "džae"));
+    }
+}
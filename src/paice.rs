@@ -0,0 +1,278 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Paice/Husk stemmer, with rule tables that can be loaded at run time.
+//!
+//! Chris Paice and Gareth Husk's 1990 stemmer, also known as the Lancaster
+//! stemmer, keeps all of its knowledge in a table of rules, so a stemmer
+//! for a specialised vocabulary, medical or legal, is a matter of writing a
+//! new table rather than new code. Each rule is written as
+//!
+//! - an ending, reversed, so `gni` for `ing`,
+//! - an optional `*`, if the rule only applies to words no other rule has
+//!   changed,
+//! - how many letters to remove,
+//! - an optional string to append, and
+//! - `>` to keep stemming afterwards, or `.` to stop.
+//!
+//! `gni3>` removes `ing` and carries on, and `ylp0.` protects words ending
+//! `ply`. Rules are separated by whitespace, and `#` starts a comment.
+//!
+//! The rules are tried in order against a word's last letter, and the first
+//! that matches is applied if it leaves an acceptable stem: two letters if
+//! the word starts with a vowel, otherwise three with a vowel or `y` among
+//! the first three letters.
+//!
+//! Words are expected to be lowercase.
+//!
+//! ```
+//! use porter_stemmer::paice::Rules;
+//!
+//! let lancaster = Rules::lancaster();
+//! assert_eq!("maxim", lancaster.stem("maximum"));
+//!
+//! let rules: Rules = "
+//!     siti0.    # Protect `-itis`.
+//!     ymotce3.  # Reduce `-ectomy` to `-ect`.
+//!     s*1>
+//! ".parse().unwrap();
+//! assert_eq!("arthritis", rules.stem("arthritis"));
+//! assert_eq!("appendect", rules.stem("appendectomy"));
+//! assert_eq!("cell", rules.stem("cells"));
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use algorithm::Stemmer;
+
+/// The rules of the original Lancaster stemmer.
+const LANCASTER: &str = "
+    ai*2. a*1. bb1. city3s. ci2> cn1t> dd1. dei3y> deec2ss. dee1. de2> dooh4> e1> feil1v. fi2>
+    gni3> gai3y. ga2> gg1. ht*2. hsiug5ct. hsi3> i*1. i1y> ji1d. juf1s. ju1d. jo1d. jeh1r.
+    jrev1t. jsim2t. jn1d. j1s. lbaifi6. lbai4y. lba3> lbi3. lib2l> lc1. lufi4y. luf3> lu2.
+    lai3> lau3> la2> ll1. mui3. mu*2. msi3> mm1. nois4j> noix4ct. noi3> nai3> na2> nee0. ne2>
+    nn1. pihs4> pp1. re2> rae0. ra2. ro2> ru2> rr1. rt1> rei3y> sei3y> sis2. si2> ssen4> ss0.
+    suo3> su*2. s*1> s0. tacilp4y. ta2> tnem4> tne3> tna3> tpir2b. tpro2b. tcud1. tpmus2.
+    tpec2iv. tulo2v. tsis0. tsi3> tt1. uqi3. ugo1. vis3j> vie0. vi2> ylb1> yli3y> ylp0. yl2>
+    ygo1. yhp1. ymo1. ypo1. yti3> yte3> ytl2. yrtsi5. yra3> yro3> yfi3. ycn2t> yca3> zi2>
+    zy1s.
+";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Rule {
+    /// The ending, in its usual order.
+    ending: String,
+    intact_only: bool,
+    remove: usize,
+    append: String,
+    proceed: bool,
+}
+
+/// A table of Paice/Husk rules.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rules {
+    rules: Vec<Rule>,
+}
+
+/// The error returned when a table of rules cannot be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseRulesError {
+    line: usize,
+    rule: String,
+}
+
+impl fmt::Display for ParseRulesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: invalid rule `{}`", self.line, self.rule)
+    }
+}
+
+impl Error for ParseRulesError {}
+
+impl Rules {
+    /// The rules of the original Lancaster stemmer.
+    pub fn lancaster() -> Rules {
+        static RULES: OnceLock<Rules> = OnceLock::new();
+
+        RULES.get_or_init(|| LANCASTER.parse().expect("the Lancaster rules are valid")).clone()
+    }
+
+    /// Read a table of rules from a file.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Rules> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    /// Given a lowercase word, return its stemmed form using these rules.
+    pub fn stem(&self, word: &str) -> String {
+        let mut stem = word.chars().collect::<Vec<char>>();
+        let mut intact = true;
+
+        'stemming: loop {
+            for rule in &self.rules {
+                if !ends_with(&stem, &rule.ending) || (rule.intact_only && !intact) || !acceptable(&stem, rule.remove) {
+                    continue;
+                }
+
+                let length = stem.len() - rule.remove;
+                stem.truncate(length);
+                stem.extend(rule.append.chars());
+                intact = false;
+
+                if rule.proceed {
+                    continue 'stemming;
+                }
+                break 'stemming;
+            }
+
+            break;
+        }
+
+        stem.into_iter().collect()
+    }
+}
+
+impl Stemmer for Rules {
+    fn stem(&self, word: &str) -> String {
+        Rules::stem(self, word)
+    }
+}
+
+impl FromStr for Rules {
+    type Err = ParseRulesError;
+
+    fn from_str(table: &str) -> Result<Rules, ParseRulesError> {
+        let mut rules = Vec::new();
+
+        for (index, line) in table.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("");
+
+            for rule in line.split_whitespace() {
+                // Paice's own tables mark their end with `end0.`.
+                if rule == "end0." {
+                    return Ok(Rules { rules });
+                }
+
+                match parse_rule(rule) {
+                    Some(rule) => rules.push(rule),
+                    None => return Err(ParseRulesError { line: index + 1, rule: rule.to_string() }),
+                }
+            }
+        }
+
+        Ok(Rules { rules })
+    }
+}
+
+fn parse_rule(rule: &str) -> Option<Rule> {
+    let (rule, proceed) = match rule.chars().last()? {
+        '>' => (&rule[..rule.len() - 1], true),
+        '.' => (&rule[..rule.len() - 1], false),
+        _ => return None,
+    };
+
+    let digit = rule.find(|c: char| c.is_ascii_digit())?;
+    let (ending, intact_only) = match rule[..digit].strip_suffix('*') {
+        Some(ending) => (ending, true),
+        None => (&rule[..digit], false),
+    };
+    let remove = rule[digit..digit + 1].parse().ok()?;
+    let append = &rule[digit + 1..];
+
+    let letters = |part: &str| part.chars().all(|c| c.is_alphabetic());
+    if ending.is_empty() || !letters(ending) || !letters(append) || remove > ending.chars().count() {
+        return None;
+    }
+
+    Some(Rule {
+        ending: ending.chars().rev().collect(),
+        intact_only,
+        remove,
+        append: append.to_string(),
+        proceed,
+    })
+}
+
+fn ends_with(word: &[char], ending: &str) -> bool {
+    let length = ending.chars().count();
+    word.len() >= length && word[word.len() - length..].iter().cloned().eq(ending.chars())
+}
+
+fn is_vowel(c: char) -> bool {
+    "aeiouy".contains(c)
+}
+
+/// Whether removing `remove` letters from `word` leaves enough of a stem.
+fn acceptable(word: &[char], remove: usize) -> bool {
+    let remaining = word.len().saturating_sub(remove);
+
+    match word.first() {
+        Some(&first) if is_vowel(first) => remaining >= 2,
+        Some(_) => remaining >= 3 && (is_vowel(word[1]) || is_vowel(word[2])),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lancaster() {
+        let lancaster = Rules::lancaster();
+
+        let pairs = [
+            ("maximum", "maxim"),
+            ("presumably", "presum"),
+            ("multiply", "multiply"),
+            ("provision", "provid"),
+            ("owed", "ow"),
+            ("ear", "ear"),
+            ("saying", "say"),
+            ("crying", "cry"),
+            ("string", "string"),
+            ("meant", "meant"),
+            ("cement", "cem"),
+        ];
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, lancaster.stem(word), "stemming {}", word);
+        }
+    }
+
+    #[test]
+    fn test_custom_rules() {
+        let rules: Rules = "
+            # Keep `-itis` intact, and reduce `-ectomy` to `-ect`.
+            siti0.
+            ymotce3.
+            ssen4>
+            s*1>
+        ".parse().unwrap();
+
+        assert_eq!("arthritis", rules.stem("arthritis"));
+        assert_eq!("appendect", rules.stem("appendectomy"));
+        assert_eq!("kind", rules.stem("kindness"));
+        assert_eq!("kindnesse", rules.stem("kindnesses"));
+        assert_eq!("xs", rules.stem("xs"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Rules { rules: Vec::new() }, "".parse().unwrap());
+        assert_eq!(1, "s1> end0. nonsense".parse::<Rules>().unwrap().rules.len());
+
+        let error = "s1>\nsx.".parse::<Rules>().unwrap_err();
+        assert_eq!("line 2: invalid rule `sx.`", error.to_string());
+        assert!("s1".parse::<Rules>().is_err());
+        assert!("s3.".parse::<Rules>().is_err());
+        assert!("*1.".parse::<Rules>().is_err());
+    }
+}
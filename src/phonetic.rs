@@ -0,0 +1,712 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Phonetic keys, for matching words that sound alike.
+//!
+//! A stem conflates the forms of a word, and a phonetic key conflates its
+//! spellings, so using both lets a search for `Schmidt` find `Smith`.
+//!
+//! Two encodings are provided. Soundex is the classic four-character code
+//! used for surnames. Lawrence Philips' Double Metaphone is more accurate,
+//! and gives a second key for words with an alternative pronunciation,
+//! often a foreign one.
+//!
+//! Non-ASCII letters other than `Ç` and `Ñ` are ignored.
+//!
+//! ```
+//! use porter_stemmer::phonetic::{double_metaphone, soundex};
+//!
+//! assert_eq!("R163", soundex("Robert"));
+//! assert_eq!(soundex("Robert"), soundex("Rupert"));
+//! assert_eq!(("SM0".to_string(), "XMT".to_string()), double_metaphone("Smith"));
+//! assert_eq!(("XMT".to_string(), "SMT".to_string()), double_metaphone("Schmidt"));
+//! ```
+
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The length of a Double Metaphone key.
+const KEY_LENGTH: usize = 4;
+
+fn soundex_code(c: char) -> Option<char> {
+    match c {
+        'B' | 'F' | 'P' | 'V' => Some('1'),
+        'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => Some('2'),
+        'D' | 'T' => Some('3'),
+        'L' => Some('4'),
+        'M' | 'N' => Some('5'),
+        'R' => Some('6'),
+        _ => None,
+    }
+}
+
+/// Given a word, return its American Soundex code: its first letter followed
+/// by three digits, or an empty string if it has no letters.
+pub fn soundex(word: &str) -> String {
+    let mut letters = word.chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .map(|c| c.to_ascii_uppercase());
+
+    let first = match letters.next() {
+        Some(first) => first,
+        None => return String::new(),
+    };
+
+    let mut code = first.to_string();
+    let mut last = soundex_code(first);
+
+    for c in letters {
+        if code.len() == 4 {
+            break;
+        }
+
+        match soundex_code(c) {
+            Some(digit) => {
+                if last != Some(digit) {
+                    code.push(digit);
+                }
+                last = Some(digit);
+            },
+            // `H` and `W` don't separate letters with the same code, but
+            // vowels do.
+            None if c == 'H' || c == 'W' => {},
+            None => last = None,
+        }
+    }
+
+    while code.len() < 4 {
+        code.push('0');
+    }
+
+    code
+}
+
+/// Given a word, return its primary and alternative Double Metaphone keys.
+/// The two are the same if the word has no alternative pronunciation.
+pub fn double_metaphone(word: &str) -> (String, String) {
+    let mut encoder = Encoder::new(word);
+    encoder.encode();
+
+    encoder.primary.truncate(KEY_LENGTH);
+    encoder.secondary.truncate(KEY_LENGTH);
+    (encoder.primary, encoder.secondary)
+}
+
+/// Split a passage of text into words and key each of them, keeping track of
+/// where each word was found, as `stem_with_offsets` does for stems.
+///
+/// Yields `(byte_range, original, keys)` for every word in `text`.
+pub fn double_metaphone_with_offsets<'a>(text: &'a str) -> impl Iterator<Item = (Range<usize>, &'a str, (String, String))> + 'a {
+    text.split_word_bound_indices()
+        .filter(|&(_, word)| word.chars().any(|c| c.is_alphanumeric()))
+        .map(|(offset, word)| (offset..offset + word.len(), word, double_metaphone(word)))
+}
+
+struct Encoder {
+    /// The uppercase word, padded with spaces so that rules can look past
+    /// its end.
+    word: Vec<char>,
+    length: isize,
+    primary: String,
+    secondary: String,
+}
+
+impl Encoder {
+    fn new(word: &str) -> Encoder {
+        let word = word.to_uppercase().chars().collect::<Vec<char>>();
+        let length = word.len() as isize;
+
+        Encoder {
+            word: word.into_iter().chain("     ".chars()).collect(),
+            length,
+            primary: String::new(),
+            secondary: String::new(),
+        }
+    }
+
+    fn at(&self, position: isize) -> char {
+        if position < 0 {
+            return '\0';
+        }
+        self.word.get(position as usize).cloned().unwrap_or('\0')
+    }
+
+    /// Whether any of `options` is found at `position`.
+    fn is_at(&self, position: isize, options: &[&str]) -> bool {
+        if position < 0 {
+            return false;
+        }
+
+        options.iter().any(|option| {
+            let end = position as usize + option.chars().count();
+            end <= self.word.len() && self.word[position as usize..end].iter().cloned().eq(option.chars())
+        })
+    }
+
+    fn is_vowel(&self, position: isize) -> bool {
+        position >= 0 && position < self.length && matches!(self.at(position), 'A' | 'E' | 'I' | 'O' | 'U' | 'Y')
+    }
+
+    fn is_slavo_germanic(&self) -> bool {
+        self.word.contains(&'W') || self.word.contains(&'K') || self.is_in_word("CZ") || self.is_in_word("WITZ")
+    }
+
+    fn is_in_word(&self, part: &str) -> bool {
+        (0..self.length).any(|position| self.is_at(position, &[part]))
+    }
+
+    fn add(&mut self, both: &str) {
+        self.primary.push_str(both);
+        self.secondary.push_str(both);
+    }
+
+    fn add_alternative(&mut self, primary: &str, secondary: &str) {
+        self.primary.push_str(primary);
+        self.secondary.push_str(secondary);
+    }
+
+    /// Add `key` and skip `letter` if it is doubled.
+    fn add_single(&mut self, current: isize, letter: char, key: &str) -> isize {
+        self.add(key);
+        if self.at(current + 1) == letter { current + 2 } else { current + 1 }
+    }
+
+    fn encode(&mut self) {
+        let last = self.length - 1;
+        let mut current = 0;
+
+        // Skip silent letters at the start.
+        if self.is_at(0, &["GN", "KN", "PN", "WR", "PS"]) {
+            current += 1;
+        }
+
+        // `X` at the start sounds like `S`, as in `Xavier`.
+        if self.at(0) == 'X' {
+            self.add("S");
+            current += 1;
+        }
+
+        while (self.primary.len() < KEY_LENGTH || self.secondary.len() < KEY_LENGTH) && current < self.length {
+            current = match self.at(current) {
+                'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+                    if current == 0 {
+                        self.add("A");
+                    }
+                    current + 1
+                },
+                'B' => self.add_single(current, 'B', "P"),
+                'Ç' => {
+                    self.add("S");
+                    current + 1
+                },
+                'C' => self.encode_c(current),
+                'D' => {
+                    if self.is_at(current, &["DG"]) {
+                        if self.is_at(current + 2, &["I", "E", "Y"]) {
+                            self.add("J");
+                            current + 3
+                        } else {
+                            self.add("TK");
+                            current + 2
+                        }
+                    } else if self.is_at(current, &["DT", "DD"]) {
+                        self.add("T");
+                        current + 2
+                    } else {
+                        self.add("T");
+                        current + 1
+                    }
+                },
+                'F' => self.add_single(current, 'F', "F"),
+                'G' => self.encode_g(current),
+                'H' => {
+                    // Only kept at the start or between vowels.
+                    if (current == 0 || self.is_vowel(current - 1)) && self.is_vowel(current + 1) {
+                        self.add("H");
+                        current + 2
+                    } else {
+                        current + 1
+                    }
+                },
+                'J' => self.encode_j(current, last),
+                'K' => self.add_single(current, 'K', "K"),
+                'L' => {
+                    if self.at(current + 1) == 'L' {
+                        // Spanish, as in `cabrillo` and `gallegos`.
+                        if (current == self.length - 3 && self.is_at(current - 1, &["ILLO", "ILLA", "ALLE"])) ||
+                            ((self.is_at(last - 1, &["AS", "OS"]) || self.is_at(last, &["A", "O"])) && self.is_at(current - 1, &["ALLE"])) {
+                            self.add_alternative("L", "");
+                        } else {
+                            self.add("L");
+                        }
+                        current + 2
+                    } else {
+                        self.add("L");
+                        current + 1
+                    }
+                },
+                'M' => {
+                    self.add("M");
+                    // `thumb` and `dumber`, but not `dumbo`.
+                    if (self.is_at(current - 1, &["UMB"]) && (current + 1 == last || self.is_at(current + 2, &["ER"]))) ||
+                        self.at(current + 1) == 'M' {
+                        current + 2
+                    } else {
+                        current + 1
+                    }
+                },
+                'N' => self.add_single(current, 'N', "N"),
+                'Ñ' => {
+                    self.add("N");
+                    current + 1
+                },
+                'P' => {
+                    if self.at(current + 1) == 'H' {
+                        self.add("F");
+                        current + 2
+                    } else {
+                        // Also `campbell` and `raspberry`.
+                        self.add("P");
+                        if self.is_at(current + 1, &["P", "B"]) { current + 2 } else { current + 1 }
+                    }
+                },
+                'Q' => self.add_single(current, 'Q', "K"),
+                'R' => {
+                    // French, as in `rogier`, but not `hochmeier`.
+                    if current == last && !self.is_slavo_germanic() && self.is_at(current - 2, &["IE"]) &&
+                        !self.is_at(current - 4, &["ME", "MA"]) {
+                        self.add_alternative("", "R");
+                    } else {
+                        self.add("R");
+                    }
+                    if self.at(current + 1) == 'R' { current + 2 } else { current + 1 }
+                },
+                'S' => self.encode_s(current, last),
+                'T' => {
+                    if self.is_at(current, &["TION", "TIA", "TCH"]) {
+                        self.add("X");
+                        current + 3
+                    } else if self.is_at(current, &["TH", "TTH"]) {
+                        // `thomas`, `thames` or Germanic.
+                        if self.is_at(current + 2, &["OM", "AM"]) || self.is_at(0, &["VAN ", "VON ", "SCH"]) {
+                            self.add("T");
+                        } else {
+                            self.add_alternative("0", "T");
+                        }
+                        current + 2
+                    } else {
+                        self.add("T");
+                        if self.is_at(current + 1, &["T", "D"]) { current + 2 } else { current + 1 }
+                    }
+                },
+                'V' => self.add_single(current, 'V', "F"),
+                'W' => self.encode_w(current, last),
+                'X' => {
+                    // French, as in `breaux`.
+                    if !(current == last && (self.is_at(current - 3, &["IAU", "EAU"]) || self.is_at(current - 2, &["AU", "OU"]))) {
+                        self.add("KS");
+                    }
+                    if self.is_at(current + 1, &["C", "X"]) { current + 2 } else { current + 1 }
+                },
+                'Z' => {
+                    if self.at(current + 1) == 'H' {
+                        // Chinese pinyin, as in `zhao`.
+                        self.add("J");
+                        current + 2
+                    } else {
+                        if self.is_at(current + 1, &["ZO", "ZI", "ZA"]) ||
+                            (self.is_slavo_germanic() && current > 0 && self.at(current - 1) != 'T') {
+                            self.add_alternative("S", "TS");
+                        } else {
+                            self.add("S");
+                        }
+                        if self.at(current + 1) == 'Z' { current + 2 } else { current + 1 }
+                    }
+                },
+                _ => current + 1,
+            };
+        }
+    }
+
+    fn encode_c(&mut self, current: isize) -> isize {
+        // Germanic, as in `bacher` and `macher`.
+        if current > 1 && !self.is_vowel(current - 2) && self.is_at(current - 1, &["ACH"]) &&
+            self.at(current + 2) != 'I' && (self.at(current + 2) != 'E' || self.is_at(current - 2, &["BACHER", "MACHER"])) {
+            self.add("K");
+            return current + 2;
+        }
+
+        if current == 0 && self.is_at(current, &["CAESAR"]) {
+            self.add("S");
+            return current + 2;
+        }
+
+        // Italian, as in `chianti`.
+        if self.is_at(current, &["CHIA"]) {
+            self.add("K");
+            return current + 2;
+        }
+
+        if self.is_at(current, &["CH"]) {
+            // `michael`
+            if current > 0 && self.is_at(current, &["CHAE"]) {
+                self.add_alternative("K", "X");
+                return current + 2;
+            }
+
+            // Greek roots, as in `chemistry` and `chorus`.
+            if current == 0 && (self.is_at(current + 1, &["HARAC", "HARIS", "HOR", "HYM", "HIA", "HEM"])) &&
+                !self.is_at(0, &["CHORE"]) {
+                self.add("K");
+                return current + 2;
+            }
+
+            // Germanic, Greek, or otherwise `ch` for a `kh` sound.
+            if self.is_at(0, &["VAN ", "VON ", "SCH"]) ||
+                self.is_at(current - 2, &["ORCHES", "ARCHIT", "ORCHID"]) ||
+                self.is_at(current + 2, &["T", "S"]) ||
+                ((self.is_at(current - 1, &["A", "O", "U", "E"]) || current == 0) &&
+                    self.is_at(current + 2, &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "])) {
+                self.add("K");
+            } else if current > 0 {
+                if self.is_at(0, &["MC"]) {
+                    self.add("K");
+                } else {
+                    self.add_alternative("X", "K");
+                }
+            } else {
+                self.add("X");
+            }
+            return current + 2;
+        }
+
+        // `czerny`
+        if self.is_at(current, &["CZ"]) && !self.is_at(current - 2, &["WICZ"]) {
+            self.add_alternative("S", "X");
+            return current + 2;
+        }
+
+        // `focaccia`
+        if self.is_at(current + 1, &["CIA"]) {
+            self.add("X");
+            return current + 3;
+        }
+
+        // A double `C`, but not as in `McClellan`.
+        if self.is_at(current, &["CC"]) && !(current == 1 && self.at(0) == 'M') {
+            // `bellocchio`, but not `bacchus`.
+            if self.is_at(current + 2, &["I", "E", "H"]) && !self.is_at(current + 2, &["HU"]) {
+                // `accident`, `accede` and `succeed`.
+                if (current == 1 && self.at(current - 1) == 'A') || self.is_at(current - 1, &["UCCEE", "UCCES"]) {
+                    self.add("KS");
+                } else {
+                    // `bacci`, `bertucci` and other Italian.
+                    self.add("X");
+                }
+                return current + 3;
+            }
+
+            self.add("K");
+            return current + 2;
+        }
+
+        if self.is_at(current, &["CK", "CG", "CQ"]) {
+            self.add("K");
+            return current + 2;
+        }
+
+        if self.is_at(current, &["CI", "CE", "CY"]) {
+            // Italian or English.
+            if self.is_at(current, &["CIO", "CIE", "CIA"]) {
+                self.add_alternative("S", "X");
+            } else {
+                self.add("S");
+            }
+            return current + 2;
+        }
+
+        self.add("K");
+
+        // Names like `mac caffrey` and `mac gregor`.
+        if self.is_at(current + 1, &[" C", " Q", " G"]) {
+            current + 3
+        } else if self.is_at(current + 1, &["C", "K", "Q"]) && !self.is_at(current + 1, &["CE", "CI"]) {
+            current + 2
+        } else {
+            current + 1
+        }
+    }
+
+    fn encode_g(&mut self, current: isize) -> isize {
+        if self.at(current + 1) == 'H' {
+            if current > 0 && !self.is_vowel(current - 1) {
+                self.add("K");
+                return current + 2;
+            }
+
+            // `ghislane` and `ghiradelli`.
+            if current == 0 {
+                if self.at(current + 2) == 'I' {
+                    self.add("J");
+                } else {
+                    self.add("K");
+                }
+                return current + 2;
+            }
+
+            // Parker's rule, as in `hugh`, `bough` and `broughton`.
+            if (current > 1 && self.is_at(current - 2, &["B", "H", "D"])) ||
+                (current > 2 && self.is_at(current - 3, &["B", "H", "D"])) ||
+                (current > 3 && self.is_at(current - 4, &["B", "H"])) {
+                return current + 2;
+            }
+
+            // `laugh`, `cough`, `rough` and `tough`.
+            if current > 2 && self.at(current - 1) == 'U' && self.is_at(current - 3, &["C", "G", "L", "R", "T"]) {
+                self.add("F");
+            } else if current > 0 && self.at(current - 1) != 'I' {
+                self.add("K");
+            }
+            return current + 2;
+        }
+
+        if self.at(current + 1) == 'N' {
+            if current == 1 && self.is_vowel(0) && !self.is_slavo_germanic() {
+                self.add_alternative("KN", "N");
+            } else if !self.is_at(current + 2, &["EY"]) && self.at(current + 1) != 'Y' && !self.is_slavo_germanic() {
+                // Not as in `cagney`.
+                self.add_alternative("N", "KN");
+            } else {
+                self.add("KN");
+            }
+            return current + 2;
+        }
+
+        // `tagliaro`
+        if self.is_at(current + 1, &["LI"]) && !self.is_slavo_germanic() {
+            self.add_alternative("KL", "L");
+            return current + 2;
+        }
+
+        // `ges`, `gep`, `gel` and `gie` at the start.
+        if current == 0 && (self.at(current + 1) == 'Y' ||
+            self.is_at(current + 1, &["ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER"])) {
+            self.add_alternative("K", "J");
+            return current + 2;
+        }
+
+        // `ger` and `gy`.
+        if (self.is_at(current + 1, &["ER"]) || self.at(current + 1) == 'Y') &&
+            !self.is_at(0, &["DANGER", "RANGER", "MANGER"]) &&
+            !self.is_at(current - 1, &["E", "I"]) &&
+            !self.is_at(current - 1, &["RGY", "OGY"]) {
+            self.add_alternative("K", "J");
+            return current + 2;
+        }
+
+        // Italian, as in `biaggi`.
+        if self.is_at(current + 1, &["E", "I", "Y"]) || self.is_at(current - 1, &["AGGI", "OGGI"]) {
+            if self.is_at(0, &["VAN ", "VON ", "SCH"]) || self.is_at(current + 1, &["ET"]) {
+                // Obviously Germanic.
+                self.add("K");
+            } else if self.is_at(current + 1, &["IER "]) {
+                // Always soft with a French ending.
+                self.add("J");
+            } else {
+                self.add_alternative("J", "K");
+            }
+            return current + 2;
+        }
+
+        self.add_single(current, 'G', "K")
+    }
+
+    fn encode_j(&mut self, current: isize, last: isize) -> isize {
+        // Spanish, as in `jose` and `san jacinto`.
+        if self.is_at(current, &["JOSE"]) || self.is_at(0, &["SAN "]) {
+            if (current == 0 && self.at(current + 4) == ' ') || self.is_at(0, &["SAN "]) {
+                self.add("H");
+            } else {
+                self.add_alternative("J", "H");
+            }
+            return current + 1;
+        }
+
+        if current == 0 && !self.is_at(current, &["JOSE"]) {
+            // `Yankelovich` and `Jankelowicz`.
+            self.add_alternative("J", "A");
+        } else if self.is_vowel(current - 1) && !self.is_slavo_germanic() && matches!(self.at(current + 1), 'A' | 'O') {
+            // Spanish, as in `bajador`.
+            self.add_alternative("J", "H");
+        } else if current == last {
+            self.add_alternative("J", "");
+        } else if !self.is_at(current + 1, &["L", "T", "K", "S", "N", "M", "B", "Z"]) && !self.is_at(current - 1, &["S", "K", "L"]) {
+            self.add("J");
+        }
+
+        if self.at(current + 1) == 'J' { current + 2 } else { current + 1 }
+    }
+
+    fn encode_s(&mut self, current: isize, last: isize) -> isize {
+        // `island`, `isle`, `carlisle` and `carlysle`.
+        if self.is_at(current - 1, &["ISL", "YSL"]) {
+            return current + 1;
+        }
+
+        if current == 0 && self.is_at(current, &["SUGAR"]) {
+            self.add_alternative("X", "S");
+            return current + 1;
+        }
+
+        if self.is_at(current, &["SH"]) {
+            // Germanic.
+            if self.is_at(current + 1, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                self.add("S");
+            } else {
+                self.add("X");
+            }
+            return current + 2;
+        }
+
+        // Italian and Armenian.
+        if self.is_at(current, &["SIO", "SIA", "SIAN"]) {
+            if self.is_slavo_germanic() {
+                self.add("S");
+            } else {
+                self.add_alternative("S", "X");
+            }
+            return current + 3;
+        }
+
+        // German and anglicisations, so `smith` matches `schmidt` and `snider`
+        // matches `schneider`. Also `sz` in Slavic languages, although it is
+        // an `s` in Hungarian.
+        if (current == 0 && self.is_at(current + 1, &["M", "N", "L", "W"])) || self.is_at(current + 1, &["Z"]) {
+            self.add_alternative("S", "X");
+            return if self.is_at(current + 1, &["Z"]) { current + 2 } else { current + 1 };
+        }
+
+        if self.is_at(current, &["SC"]) {
+            // Schlesinger's rule.
+            if self.at(current + 2) == 'H' {
+                if self.is_at(current + 3, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                    // Dutch, as in `school` and `schooner`, or `schermerhorn`
+                    // and `schenker`.
+                    if self.is_at(current + 3, &["ER", "EN"]) {
+                        self.add_alternative("X", "SK");
+                    } else {
+                        self.add("SK");
+                    }
+                } else if current == 0 && !self.is_vowel(3) && self.at(3) != 'W' {
+                    self.add_alternative("X", "S");
+                } else {
+                    self.add("X");
+                }
+            } else if self.is_at(current + 2, &["I", "E", "Y"]) {
+                self.add("S");
+            } else {
+                self.add("SK");
+            }
+            return current + 3;
+        }
+
+        // French, as in `resnais` and `artois`.
+        if current == last && self.is_at(current - 2, &["AI", "OI"]) {
+            self.add_alternative("", "S");
+        } else {
+            self.add("S");
+        }
+
+        if self.is_at(current + 1, &["S", "Z"]) { current + 2 } else { current + 1 }
+    }
+
+    fn encode_w(&mut self, current: isize, last: isize) -> isize {
+        if self.is_at(current, &["WR"]) {
+            self.add("R");
+            return current + 2;
+        }
+
+        if current == 0 && (self.is_vowel(current + 1) || self.is_at(current, &["WH"])) {
+            // `Wasserman` matches `Vasserman`, and `Uomo` matches `Womo`.
+            if self.is_vowel(current + 1) {
+                self.add_alternative("A", "F");
+            } else {
+                self.add("A");
+            }
+        }
+
+        // `Arnow` matches `Arnoff`.
+        if (current == last && self.is_vowel(current - 1)) ||
+            self.is_at(current - 1, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"]) ||
+            self.is_at(0, &["SCH"]) {
+            self.add_alternative("", "F");
+            return current + 1;
+        }
+
+        // Polish, as in `filipowicz`.
+        if self.is_at(current, &["WICZ", "WITZ"]) {
+            self.add_alternative("TS", "FX");
+            return current + 4;
+        }
+
+        current + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_soundex() {
+        let pairs = [
+            ("Robert", "R163"),
+            ("Rupert", "R163"),
+            ("Rubin", "R150"),
+            ("Ashcraft", "A261"),
+            ("Tymczak", "T522"),
+            ("Pfister", "P236"),
+            ("Honeyman", "H555"),
+            ("lee", "L000"),
+            ("", ""),
+            ("123", ""),
+        ];
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, soundex(word), "encoding {}", word);
+        }
+    }
+
+    #[test]
+    fn test_double_metaphone() {
+        let keys = [
+            ("thumb", "0M", "TM"),
+            ("Smith", "SM0", "XMT"),
+            ("Schmidt", "XMT", "SMT"),
+            ("Jose", "HS", "HS"),
+            ("Czerny", "SRN", "XRN"),
+            ("Xavier", "SF", "SFR"),
+            ("Michael", "MKL", "MXL"),
+            ("Arnow", "ARN", "ARNF"),
+            ("Cabrillo", "KPRL", "KPR"),
+            ("Caesar", "SSR", "SSR"),
+            ("edge", "AJ", "AJ"),
+            ("knight", "NT", "NT"),
+            ("laugh", "LF", "LF"),
+            ("", "", ""),
+        ];
+        for &(word, primary, secondary) in keys.iter() {
+            assert_eq!((primary.to_string(), secondary.to_string()), double_metaphone(word), "encoding {}", word);
+        }
+    }
+
+    #[test]
+    fn test_double_metaphone_with_offsets() {
+        let keys = double_metaphone_with_offsets("Smith, Schmidt").collect::<Vec<_>>();
+
+        assert_eq!(2, keys.len());
+        assert_eq!((0..5, "Smith"), (keys[0].0.clone(), keys[0].1));
+        assert_eq!((7..14, "Schmidt"), (keys[1].0.clone(), keys[1].1));
+    }
+}
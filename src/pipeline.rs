@@ -0,0 +1,449 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Stemming as a stage of a multi-threaded pipeline.
+//!
+//! Stages exchange chunks of words over bounded channels, so a slow consumer
+//! applies backpressure all the way up the pipeline.
+//!
+//! ```
+//! use std::sync::mpsc::sync_channel;
+//! use porter_stemmer::pipeline::{spawn, StemStage};
+//!
+//! let (sender, receiver) = sync_channel(4);
+//! let (stems, handle) = spawn(StemStage, receiver, 4);
+//!
+//! sender.send(vec!["caresses".to_string(), "ponies".to_string()]).unwrap();
+//! drop(sender);
+//!
+//! assert_eq!(vec!["caress", "poni"], stems.recv().unwrap());
+//! handle.join().unwrap();
+//! ```
+//!
+//! `CorpusStemmer` puts these together for the common case of stemming a
+//! stream of whole documents in parallel.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::panic;
+use std::sync::mpsc::{channel, sync_channel, Receiver, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use stem;
+
+/// A unit of work in a pipeline, turning one chunk of words into another.
+pub trait Stage: Send + 'static {
+    /// Process a single chunk.
+    fn process(&mut self, chunk: Vec<String>) -> Vec<String>;
+}
+
+/// A `Stage` that stems every word in a chunk, reusing the chunk's storage.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StemStage;
+
+impl Stage for StemStage {
+    fn process(&mut self, mut chunk: Vec<String>) -> Vec<String> {
+        for word in chunk.iter_mut() {
+            *word = stem(word);
+        }
+
+        chunk
+    }
+}
+
+/// Run `stage` on a new thread, reading chunks from `input` until every
+/// sender has hung up.
+///
+/// Processed chunks are written, in order, to the returned receiver through
+/// a channel holding at most `bound` chunks. If the receiver is dropped the
+/// stage stops early.
+pub fn spawn<S: Stage>(mut stage: S, input: Receiver<Vec<String>>, bound: usize) -> (Receiver<Vec<String>>, JoinHandle<()>) {
+    let (sender, output) = sync_channel(bound);
+
+    let handle = thread::spawn(move || {
+        for chunk in input {
+            if sender.send(stage.process(chunk)).is_err() {
+                break;
+            }
+        }
+    });
+
+    (output, handle)
+}
+
+/// Run `workers` copies of `stage` on their own threads, sharing the chunks
+/// read from `input`.
+///
+/// This behaves like `spawn`, except that processed chunks are written in
+/// whichever order the workers finish them.
+pub fn spawn_pool<S: Stage + Clone>(stage: S, workers: usize, input: Receiver<Vec<String>>, bound: usize) -> (Receiver<Vec<String>>, Vec<JoinHandle<()>>) {
+    let (sender, output) = sync_channel(bound);
+    let input = Arc::new(Mutex::new(input));
+
+    let handles = (0..workers).map(|_| {
+        let mut stage = stage.clone();
+        let input = input.clone();
+        let sender = sender.clone();
+
+        thread::spawn(move || {
+            loop {
+                let chunk = match input.lock().unwrap().recv() {
+                    Ok(chunk) => chunk,
+                    Err(_) => break,
+                };
+
+                if sender.send(stage.process(chunk)).is_err() {
+                    break;
+                }
+            }
+        })
+    }).collect();
+
+    (output, handles)
+}
+
+/// A piece of a document, numbered in the order it was split off.
+struct Chunk {
+    index: usize,
+    words: Vec<String>,
+    /// Whether this is the document's last chunk.
+    last: bool,
+}
+
+/// Stems a sequence of documents on a pool of worker threads.
+///
+/// Each document is split into its Unicode words, and those into chunks of
+/// at most `chunk_size` words, which idle workers take from a shared queue.
+/// The stems are reassembled into documents and yielded in the order the
+/// documents were read. At most `bound` chunks are in flight at once, so
+/// reading documents never gets far ahead of consuming their stems.
+///
+/// ```
+/// use porter_stemmer::pipeline::CorpusStemmer;
+///
+/// let documents = vec!["Ponies were running".to_string(), "".to_string(), "hopeful cats".to_string()];
+/// let stems = CorpusStemmer::new().workers(2).stem(documents).collect::<Vec<_>>();
+///
+/// assert_eq!(vec![vec!["Poni", "were", "run"], vec![], vec!["hope", "cat"]], stems);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CorpusStemmer<S> {
+    stage: S,
+    workers: usize,
+    chunk_size: usize,
+    bound: usize,
+}
+
+impl CorpusStemmer<StemStage> {
+    /// Stem documents with `stem`, using a worker for each available CPU.
+    pub fn new() -> CorpusStemmer<StemStage> {
+        let workers = thread::available_parallelism().map_or(1, |workers| workers.get());
+
+        CorpusStemmer {
+            stage: StemStage,
+            workers,
+            chunk_size: 1024,
+            bound: workers * 4,
+        }
+    }
+}
+
+impl Default for CorpusStemmer<StemStage> {
+    fn default() -> CorpusStemmer<StemStage> {
+        CorpusStemmer::new()
+    }
+}
+
+impl<S: Stage + Clone> CorpusStemmer<S> {
+    /// Process each chunk with `stage` rather than stemming it.
+    pub fn stage<T: Stage + Clone>(self, stage: T) -> CorpusStemmer<T> {
+        CorpusStemmer {
+            stage,
+            workers: self.workers,
+            chunk_size: self.chunk_size,
+            bound: self.bound,
+        }
+    }
+
+    /// Use `workers` threads. Panics if `workers` is zero.
+    pub fn workers(mut self, workers: usize) -> CorpusStemmer<S> {
+        assert!(workers > 0, "a CorpusStemmer needs at least one worker");
+        self.workers = workers;
+        self
+    }
+
+    /// Split documents into chunks of at most `chunk_size` words. Panics if
+    /// `chunk_size` is zero.
+    pub fn chunk_size(mut self, chunk_size: usize) -> CorpusStemmer<S> {
+        assert!(chunk_size > 0, "a CorpusStemmer's chunks need room for a word");
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Have at most `bound` chunks in flight at once. Panics if `bound` is
+    /// zero.
+    pub fn bound(mut self, bound: usize) -> CorpusStemmer<S> {
+        assert!(bound > 0, "a CorpusStemmer needs at least one chunk in flight");
+        self.bound = bound;
+        self
+    }
+
+    /// Start stemming `documents`, returning an iterator over the stems of
+    /// each document, in order.
+    ///
+    /// If the stage panics, the other threads stop and the iterator passes
+    /// the panic on the next time it waits for stems.
+    pub fn stem<I>(&self, documents: I) -> CorpusStems
+        where I: IntoIterator<Item = String>, I::IntoIter: Send + 'static
+    {
+        let documents = documents.into_iter();
+        let chunk_size = self.chunk_size;
+
+        // Sending a chunk takes a ticket, which is only handed back once
+        // its stems have been yielded.
+        let (tickets, ticket_receiver) = sync_channel(self.bound);
+        for _ in 0..self.bound {
+            tickets.send(()).expect("the channel has room for every ticket");
+        }
+
+        let (chunk_sender, chunk_receiver) = channel();
+        let mut handles = vec![thread::spawn(move || {
+            let mut index = 0;
+
+            for document in documents {
+                let words = document.unicode_words().map(String::from).collect::<Vec<_>>();
+                let mut chunks = words.chunks(chunk_size).peekable();
+
+                if chunks.peek().is_none() {
+                    let chunk = Chunk { index, words: Vec::new(), last: true };
+                    if ticket_receiver.recv().is_err() || chunk_sender.send(chunk).is_err() {
+                        return;
+                    }
+                    index += 1;
+                }
+
+                while let Some(words) = chunks.next() {
+                    let chunk = Chunk { index, words: words.to_vec(), last: chunks.peek().is_none() };
+                    if ticket_receiver.recv().is_err() || chunk_sender.send(chunk).is_err() {
+                        return;
+                    }
+                    index += 1;
+                }
+            }
+        })];
+
+        let (stem_sender, stems) = channel();
+        let chunks = Arc::new(Mutex::new(chunk_receiver));
+
+        handles.extend((0..self.workers).map(|_| {
+            let mut stage = self.stage.clone();
+            let chunks = chunks.clone();
+            let stem_sender = stem_sender.clone();
+
+            thread::spawn(move || {
+                loop {
+                    let chunk = match chunks.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).recv() {
+                        Ok(chunk) => chunk,
+                        Err(_) => break,
+                    };
+
+                    // A panicking stage is caught so the iterator can stop
+                    // the other threads before passing the panic on.
+                    let Chunk { index, words, last } = chunk;
+                    let stemmed = panic::catch_unwind(panic::AssertUnwindSafe(|| stage.process(words)));
+                    let failed = stemmed.is_err();
+
+                    if stem_sender.send(stemmed.map(|words| Chunk { index, words, last })).is_err() || failed {
+                        break;
+                    }
+                }
+            })
+        }));
+
+        CorpusStems {
+            stems,
+            pending: HashMap::new(),
+            next: 0,
+            tickets: Some(tickets),
+            handles,
+        }
+    }
+}
+
+/// The stems of a corpus, a document at a time. See `CorpusStemmer::stem`.
+pub struct CorpusStems {
+    stems: Receiver<thread::Result<Chunk>>,
+    /// Chunks that arrived before those numbered ahead of them.
+    pending: HashMap<usize, Chunk>,
+    next: usize,
+    /// Dropped once a worker panics, so the reader stops sending chunks.
+    tickets: Option<SyncSender<()>>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl CorpusStems {
+    fn next_chunk(&mut self) -> Option<Chunk> {
+        if let Some(chunk) = self.pending.remove(&self.next) {
+            return Some(chunk);
+        }
+
+        loop {
+            let chunk = match self.stems.recv().ok()? {
+                Ok(chunk) => chunk,
+                Err(payload) => self.abort(payload),
+            };
+            if chunk.index == self.next {
+                return Some(chunk);
+            }
+            self.pending.insert(chunk.index, chunk);
+        }
+    }
+
+    /// Stop the reader, wait for every thread to finish, and pass on a
+    /// worker's panic.
+    fn abort(&mut self, payload: Box<dyn Any + Send>) -> ! {
+        self.tickets = None;
+
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+
+        panic::resume_unwind(payload);
+    }
+}
+
+impl Iterator for CorpusStems {
+    type Item = Vec<String>;
+
+    fn next(&mut self) -> Option<Vec<String>> {
+        let mut document = Vec::new();
+
+        loop {
+            let chunk = match self.next_chunk() {
+                Some(chunk) => chunk,
+                None => {
+                    // Every thread has finished, so pass on any panic.
+                    for handle in self.handles.drain(..) {
+                        if let Err(payload) = handle.join() {
+                            panic::resume_unwind(payload);
+                        }
+                    }
+                    return None;
+                },
+            };
+
+            self.next += 1;
+            if let Some(ref tickets) = self.tickets {
+                let _ = tickets.send(());
+            }
+
+            document.extend(chunk.words);
+            if chunk.last {
+                return Some(document);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::sync_channel;
+
+    use super::*;
+
+    fn chunk(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn test_stem_stage() {
+        assert_eq!(chunk(&["relat", "condit"]), StemStage.process(chunk(&["relational", "conditional"])));
+    }
+
+    #[test]
+    fn test_spawn() {
+        let (sender, receiver) = sync_channel(1);
+        let (stems, handle) = spawn(StemStage, receiver, 1);
+
+        thread::spawn(move || {
+            for _ in 0..10 {
+                sender.send(chunk(&["hopeful", "goodness"])).unwrap();
+            }
+        });
+
+        let stems = stems.iter().collect::<Vec<_>>();
+        assert_eq!(10, stems.len());
+        assert!(stems.iter().all(|stems| stems == &chunk(&["hope", "good"])));
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_spawn_pool() {
+        let (sender, receiver) = sync_channel(2);
+        let (stems, handles) = spawn_pool(StemStage, 3, receiver, 2);
+
+        thread::spawn(move || {
+            for _ in 0..20 {
+                sender.send(chunk(&["cats"])).unwrap();
+            }
+        });
+
+        assert_eq!(20, stems.iter().filter(|stems| stems == &chunk(&["cat"])).count());
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_corpus_stemmer() {
+        let documents = (0..200).map(|length| "cats ".repeat(length % 7)).collect::<Vec<_>>();
+        let expected = documents.iter()
+            .map(|document| document.split_whitespace().map(stem).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let stemmer = CorpusStemmer::new().workers(3).chunk_size(2).bound(2);
+        assert_eq!(expected, stemmer.stem(documents).collect::<Vec<_>>());
+    }
+
+    #[derive(Clone)]
+    struct Panicking;
+
+    impl Stage for Panicking {
+        fn process(&mut self, _: Vec<String>) -> Vec<String> {
+            panic!("processing failed");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "processing failed")]
+    fn test_corpus_stemmer_panic() {
+        let stemmer = CorpusStemmer::new().stage(Panicking).workers(1);
+        stemmer.stem(vec!["word".to_string()]).count();
+    }
+
+    /// Panics on chunks holding a particular word, and stems the rest.
+    #[derive(Clone)]
+    struct PanicsOn(&'static str);
+
+    impl Stage for PanicsOn {
+        fn process(&mut self, chunk: Vec<String>) -> Vec<String> {
+            if chunk.iter().any(|word| word == self.0) {
+                panic!("processing {} failed", self.0);
+            }
+
+            StemStage.process(chunk)
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "processing poison failed")]
+    fn test_corpus_stemmer_panic_in_flight() {
+        let documents = (0..100).map(|index| if index == 10 { "poison".to_string() } else { "cats".to_string() });
+        let stemmer = CorpusStemmer::new().stage(PanicsOn("poison")).workers(2).bound(2);
+        stemmer.stem(documents).count();
+    }
+}
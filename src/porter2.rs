@@ -0,0 +1,393 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Porter2 stemmer, also known as the Snowball English stemmer.
+//!
+//! Porter2 is Martin Porter's revision of his original algorithm. It fixes a
+//! number of its known weaknesses (e.g. `generically` now stems to `generic`
+//! rather than `gener`), and is what Lucene and Elasticsearch call the
+//! "english" stemmer.
+//!
+//! Words are expected to be lowercase, and are worked on `char` by `char` as
+//! the reference implementation does.
+//!
+//! See <http://snowball.tartarus.org/algorithms/english/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::porter2;
+//!
+//! assert_eq!("generic", porter2::stem("generically"));
+//! assert_eq!("conspicu", porter2::stem("conspicuously"));
+//! ```
+
+use primitives::ends_with;
+
+/// Words with irregular stems, and words that must not be stemmed at all.
+const EXCEPTIONS: &[(&str, &str)] = &[
+    ("skis", "ski"),
+    ("skies", "sky"),
+    ("dying", "die"),
+    ("lying", "lie"),
+    ("tying", "tie"),
+    ("idly", "idl"),
+    ("gently", "gentl"),
+    ("ugly", "ugli"),
+    ("early", "earli"),
+    ("only", "onli"),
+    ("singly", "singl"),
+    ("sky", "sky"),
+    ("news", "news"),
+    ("howe", "howe"),
+    ("atlas", "atlas"),
+    ("cosmos", "cosmos"),
+    ("bias", "bias"),
+    ("andes", "andes"),
+];
+
+/// Words that are left alone once their plural suffix has been removed.
+const EXCEPTIONS_AFTER_STEP_1A: &[&str] = &[
+    "inning", "outing", "canning", "herring", "earring", "proceed", "exceed", "succeed",
+];
+
+/// Prefixes after which R1 always starts, to avoid over-stemming.
+const R1_PREFIXES: &[&str] = &["gener", "commun", "arsen"];
+
+const DOUBLES: &[&str] = &["bb", "dd", "ff", "gg", "mm", "nn", "pp", "rr", "tt"];
+
+/// Given a lowercase word, return its stemmed form using Porter2.
+pub fn stem(word: &str) -> String {
+    if let Some(&(_, stemmed)) = EXCEPTIONS.iter().find(|&&(exception, _)| exception == word) {
+        return stemmed.to_string();
+    }
+
+    let mut word = word.chars().collect::<Vec<char>>();
+
+    if word.len() <= 2 {
+        return word.into_iter().collect();
+    }
+
+    prelude(&mut word);
+    let (p1, p2) = regions(&word);
+
+    step_1a(&mut word);
+
+    let stemmed = word.iter().collect::<String>();
+    if !EXCEPTIONS_AFTER_STEP_1A.contains(&&stemmed[..]) {
+        step_1b(&mut word, p1);
+        step_1c(&mut word);
+        step_2(&mut word, p1);
+        step_3(&mut word, p1, p2);
+        step_4(&mut word, p2);
+        step_5(&mut word, p1, p2);
+    }
+
+    word.into_iter()
+        .map(|c| if c == 'Y' { 'y' } else { c })
+        .collect()
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y')
+}
+
+/// Remove a leading apostrophe, and mark each `y` that is acting as a
+/// consonant as `Y`.
+fn prelude(word: &mut Vec<char>) {
+    if word[0] == '\'' {
+        word.remove(0);
+    }
+
+    for index in 0..word.len() {
+        if word[index] == 'y' && (index == 0 || is_vowel(word[index - 1])) {
+            word[index] = 'Y';
+        }
+    }
+}
+
+/// The position after the first non-vowel following a vowel at or after
+/// `start`, or the end of the word.
+fn after_vowel_consonant(word: &[char], start: usize) -> usize {
+    let vowel = match (start..word.len()).find(|&index| is_vowel(word[index])) {
+        Some(vowel) => vowel,
+        None => return word.len(),
+    };
+
+    match (vowel..word.len()).find(|&index| !is_vowel(word[index])) {
+        Some(consonant) => consonant + 1,
+        None => word.len(),
+    }
+}
+
+/// The starts of the regions R1 and R2.
+fn regions(word: &[char]) -> (usize, usize) {
+    let p1 = R1_PREFIXES.iter()
+        .find(|prefix| word.iter().take(prefix.len()).cloned().eq(prefix.chars()))
+        .map(|prefix| prefix.len())
+        .unwrap_or_else(|| after_vowel_consonant(word, 0));
+
+    (p1, after_vowel_consonant(word, p1))
+}
+
+/// The longest of `suffixes` that `word` ends with.
+fn longest_suffix<'a>(word: &[char], suffixes: &[&'a str]) -> Option<&'a str> {
+    suffixes.iter()
+        .filter(|suffix| ends_with(word, suffix))
+        .max_by_key(|suffix| suffix.len())
+        .cloned()
+}
+
+fn replace_suffix(word: &mut Vec<char>, suffix: &str, replacement: &str) {
+    let length = word.len() - suffix.len();
+    word.truncate(length);
+    word.extend(replacement.chars());
+}
+
+/// Whether the word ends in a short syllable: a vowel followed by a non-vowel
+/// other than `w`, `x` or `Y` and preceded by a non-vowel, or a vowel at the
+/// beginning of the word followed by a non-vowel.
+fn ends_short_syllable(word: &[char]) -> bool {
+    let length = word.len();
+
+    if length >= 3 {
+        let last = word[length - 1];
+        !is_vowel(last) && last != 'w' && last != 'x' && last != 'Y' &&
+            is_vowel(word[length - 2]) &&
+            !is_vowel(word[length - 3])
+    } else {
+        length == 2 && is_vowel(word[0]) && !is_vowel(word[1])
+    }
+}
+
+/// Step 0 and step 1a: remove possessives and plurals.
+fn step_1a(word: &mut Vec<char>) {
+    if let Some(suffix) = longest_suffix(word, &["'s'", "'s", "'"]) {
+        replace_suffix(word, suffix, "");
+    }
+
+    match longest_suffix(word, &["sses", "ied", "ies", "us", "ss", "s"]) {
+        Some("sses") => replace_suffix(word, "sses", "ss"),
+        Some(suffix @ "ied") | Some(suffix @ "ies") => {
+            if word.len() > 4 {
+                replace_suffix(word, suffix, "i");
+            } else {
+                replace_suffix(word, suffix, "ie");
+            }
+        },
+        Some("s") if word[..word.len() - 2].iter().any(|&c| is_vowel(c)) => {
+            replace_suffix(word, "s", "");
+        },
+        _ => {},
+    }
+}
+
+/// Step 1b: remove `-ed`, `-ing` and similar, tidying up the remaining stem.
+fn step_1b(word: &mut Vec<char>, p1: usize) {
+    let suffix = match longest_suffix(word, &["eed", "eedly", "ed", "edly", "ing", "ingly"]) {
+        Some(suffix) => suffix,
+        None => return,
+    };
+
+    let stem_length = word.len() - suffix.len();
+
+    if suffix == "eed" || suffix == "eedly" {
+        if stem_length >= p1 {
+            replace_suffix(word, suffix, "ee");
+        }
+    } else if word[..stem_length].iter().any(|&c| is_vowel(c)) {
+        word.truncate(stem_length);
+
+        if longest_suffix(word, &["at", "bl", "iz"]).is_some() {
+            word.push('e');
+        } else if longest_suffix(word, DOUBLES).is_some() {
+            word.pop();
+        } else if word.len() == p1 && ends_short_syllable(word) {
+            word.push('e');
+        }
+    }
+}
+
+/// Step 1c: replace a final `y` with `i` after a non-vowel that is not the
+/// first letter of the word.
+fn step_1c(word: &mut [char]) {
+    let length = word.len();
+
+    if length > 2 && (word[length - 1] == 'y' || word[length - 1] == 'Y') && !is_vowel(word[length - 2]) {
+        word[length - 1] = 'i';
+    }
+}
+
+/// Step 2: map double suffixes in R1 to single ones.
+fn step_2(word: &mut Vec<char>, p1: usize) {
+    let suffix = match longest_suffix(word, &[
+        "tional", "enci", "anci", "abli", "entli", "izer", "ization", "ational", "ation", "ator",
+        "alism", "aliti", "alli", "fulness", "ousli", "ousness", "iveness", "iviti", "biliti",
+        "bli", "ogi", "fulli", "lessli", "li",
+    ]) {
+        Some(suffix) if word.len() - suffix.len() >= p1 => suffix,
+        _ => return,
+    };
+
+    let preceding = word[..word.len() - suffix.len()].last().cloned();
+
+    match suffix {
+        "tional" => replace_suffix(word, suffix, "tion"),
+        "enci" => replace_suffix(word, suffix, "ence"),
+        "anci" => replace_suffix(word, suffix, "ance"),
+        "abli" => replace_suffix(word, suffix, "able"),
+        "entli" => replace_suffix(word, suffix, "ent"),
+        "izer" | "ization" => replace_suffix(word, suffix, "ize"),
+        "ational" | "ation" | "ator" => replace_suffix(word, suffix, "ate"),
+        "alism" | "aliti" | "alli" => replace_suffix(word, suffix, "al"),
+        "fulness" | "fulli" => replace_suffix(word, suffix, "ful"),
+        "ousli" | "ousness" => replace_suffix(word, suffix, "ous"),
+        "iveness" | "iviti" => replace_suffix(word, suffix, "ive"),
+        "biliti" | "bli" => replace_suffix(word, suffix, "ble"),
+        "ogi" if preceding == Some('l') => replace_suffix(word, suffix, "og"),
+        "lessli" => replace_suffix(word, suffix, "less"),
+        "li" if is_valid_li_ending(preceding) => replace_suffix(word, suffix, ""),
+        _ => {},
+    }
+}
+
+fn is_valid_li_ending(c: Option<char>) -> bool {
+    matches!(c, Some('c') | Some('d') | Some('e') | Some('g') | Some('h') |
+                Some('k') | Some('m') | Some('n') | Some('r') | Some('t'))
+}
+
+/// Step 3: simplify or remove further suffixes in R1.
+fn step_3(word: &mut Vec<char>, p1: usize, p2: usize) {
+    let suffix = match longest_suffix(word, &[
+        "tional", "ational", "alize", "icate", "iciti", "ative", "ical", "ness", "ful",
+    ]) {
+        Some(suffix) if word.len() - suffix.len() >= p1 => suffix,
+        _ => return,
+    };
+
+    match suffix {
+        "tional" => replace_suffix(word, suffix, "tion"),
+        "ational" => replace_suffix(word, suffix, "ate"),
+        "alize" => replace_suffix(word, suffix, "al"),
+        "icate" | "iciti" | "ical" => replace_suffix(word, suffix, "ic"),
+        "ful" | "ness" => replace_suffix(word, suffix, ""),
+        "ative" if word.len() - suffix.len() >= p2 => replace_suffix(word, suffix, ""),
+        _ => {},
+    }
+}
+
+/// Step 4: remove standard suffixes in R2.
+fn step_4(word: &mut Vec<char>, p2: usize) {
+    let suffix = match longest_suffix(word, &[
+        "al", "ance", "ence", "er", "ic", "able", "ible", "ant", "ement", "ment", "ent", "ism",
+        "ate", "iti", "ous", "ive", "ize", "ion",
+    ]) {
+        Some(suffix) if word.len() - suffix.len() >= p2 => suffix,
+        _ => return,
+    };
+
+    if suffix == "ion" {
+        match word[..word.len() - suffix.len()].last() {
+            Some(&'s') | Some(&'t') => {},
+            _ => return,
+        }
+    }
+
+    replace_suffix(word, suffix, "");
+}
+
+/// Step 5: remove a final `e`, or reduce a final double `l`.
+fn step_5(word: &mut Vec<char>, p1: usize, p2: usize) {
+    let length = word.len();
+
+    let remove = match word.last() {
+        Some(&'e') => length > p2 || (length > p1 && !ends_short_syllable(&word[..length - 1])),
+        Some(&'l') => length > p2 && word[length - 2] == 'l',
+        _ => false,
+    };
+
+    if remove {
+        word.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_stems(pairs: &[(&str, &str)]) {
+        for &(word, expected) in pairs {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+
+    #[test]
+    fn test_regions() {
+        let word = "beautiful".chars().collect::<Vec<char>>();
+        assert_eq!((5, 7), regions(&word));
+
+        let word = "beauty".chars().collect::<Vec<char>>();
+        assert_eq!((5, 6), regions(&word));
+
+        let word = "animadversion".chars().collect::<Vec<char>>();
+        assert_eq!((2, 4), regions(&word));
+
+        let word = "generous".chars().collect::<Vec<char>>();
+        assert_eq!((5, 8), regions(&word));
+    }
+
+    #[test]
+    fn test_ends_short_syllable() {
+        for word in &["rap", "trap", "entrap", "ow", "on", "at"] {
+            assert!(ends_short_syllable(&word.chars().collect::<Vec<char>>()), "{}", word);
+        }
+        for word in &["uproot", "bestow", "disturb", "a", "aw"] {
+            let short = ends_short_syllable(&word.chars().collect::<Vec<char>>());
+            assert_eq!(*word == "aw", short, "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_stem() {
+        assert_stems(&[
+            ("caresses", "caress"), ("flies", "fli"), ("dies", "die"), ("mules", "mule"),
+            ("denied", "deni"), ("died", "die"), ("agreed", "agre"), ("owned", "own"),
+            ("humbled", "humbl"), ("sized", "size"), ("meeting", "meet"), ("stating", "state"),
+            ("siezing", "siez"), ("itemization", "item"), ("sensational", "sensat"),
+            ("traditional", "tradit"), ("reference", "refer"), ("colonizer", "colon"),
+            ("plotted", "plot"),
+        ]);
+    }
+
+    #[test]
+    fn test_stem_vocabulary() {
+        assert_stems(&[
+            ("consign", "consign"), ("consigned", "consign"), ("consigning", "consign"),
+            ("consignment", "consign"), ("consist", "consist"), ("consisted", "consist"),
+            ("consistency", "consist"), ("consistent", "consist"), ("consistently", "consist"),
+            ("consisting", "consist"), ("consists", "consist"), ("consolation", "consol"),
+            ("consolations", "consol"), ("consolatory", "consolatori"), ("console", "consol"),
+            ("consoled", "consol"), ("consoles", "consol"), ("consolidate", "consolid"),
+            ("consolidated", "consolid"), ("consolidating", "consolid"), ("consoling", "consol"),
+            ("consolingly", "consol"), ("consols", "consol"), ("consonant", "conson"),
+            ("consort", "consort"), ("consorted", "consort"), ("consorting", "consort"),
+            ("conspicuous", "conspicu"), ("conspicuously", "conspicu"),
+            ("conspiracy", "conspiraci"), ("conspirator", "conspir"), ("conspirators", "conspir"),
+            ("conspire", "conspir"), ("conspired", "conspir"), ("conspiring", "conspir"),
+            ("constable", "constabl"), ("constables", "constabl"), ("constance", "constanc"),
+            ("constancy", "constanc"), ("constant", "constant"),
+        ]);
+    }
+
+    #[test]
+    fn test_stem_special_cases() {
+        assert_stems(&[
+            ("generically", "generic"), ("generously", "generous"), ("communism", "communism"),
+            ("skies", "sky"), ("dying", "die"), ("news", "news"), ("proceeding", "proceed"),
+            ("innings", "inning"), ("cry", "cri"), ("by", "by"), ("say", "say"),
+            ("'tis", "tis"), ("dog's", "dog"), ("dogs'", "dog"), ("ties", "tie"),
+            ("cries", "cri"), ("gas", "gas"), ("gaps", "gap"), ("kiwis", "kiwi"),
+            ("hopping", "hop"), ("hoped", "hope"), ("youth", "youth"), ("boyish", "boyish"),
+            ("geology", "geolog"), ("happily", "happili"), ("fluently", "fluentli"),
+        ]);
+    }
+}
@@ -0,0 +1,99 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Word n-grams, or shingles, of stemmed terms, for phrase-level features
+//! such as near-duplicate detection and shingled indexes.
+//!
+//! Shingling the terms of an `Analyzer` means shingles match however their
+//! words were inflected:
+//!
+//! ```
+//! use porter_stemmer::analysis::Analyzer;
+//! use porter_stemmer::shingle::shingles;
+//!
+//! let analyzer = Analyzer::english();
+//! let shingles = shingles(analyzer.analyze("Connected ponies running"), 2).collect::<Vec<_>>();
+//!
+//! assert_eq!(vec!["connect poni", "poni run"], shingles.iter().map(|shingle| &shingle.text[..]).collect::<Vec<_>>());
+//! assert_eq!(10..24, shingles[1].byte_range);
+//! assert_eq!(1, shingles[1].position);
+//! ```
+
+use std::collections::VecDeque;
+
+use analysis::Term;
+
+/// The shingles of a sequence of terms, from `shingles`.
+pub struct Shingles<I> {
+    terms: I,
+    size: usize,
+    window: VecDeque<Term>,
+}
+
+impl<I: Iterator<Item = Term>> Iterator for Shingles<I> {
+    type Item = Term;
+
+    fn next(&mut self) -> Option<Term> {
+        if self.window.len() == self.size {
+            self.window.pop_front();
+        }
+        while self.window.len() < self.size {
+            self.window.push_back(self.terms.next()?);
+        }
+
+        let (first, last) = (&self.window[0], &self.window[self.size - 1]);
+        Some(Term {
+            text: self.window.iter().map(|term| &term.text[..]).collect::<Vec<_>>().join(" "),
+            byte_range: first.byte_range.start..last.byte_range.end,
+            position: first.position,
+        })
+    }
+}
+
+/// Each run of `size` consecutive terms in `terms`, as a term of their texts
+/// joined by spaces, at the position of the first and spanning the text
+/// from the first to the last.
+///
+/// Terms an analyzer dropped, such as stopwords, are skipped over. Fewer
+/// than `size` terms make no shingles at all.
+///
+/// # Panics
+///
+/// If `size` is zero.
+pub fn shingles<I: IntoIterator<Item = Term>>(terms: I, size: usize) -> Shingles<I::IntoIter> {
+    assert!(size > 0, "shingles must be at least one term long");
+
+    Shingles { terms: terms.into_iter(), size, window: VecDeque::with_capacity(size) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use analysis::Analyzer;
+
+    fn texts<I: Iterator<Item = Term>>(shingles: I) -> Vec<String> {
+        shingles.map(|shingle| shingle.text).collect()
+    }
+
+    #[test]
+    fn test_shingles() {
+        let analyzer = Analyzer::english();
+        let text = "The ponies ran to the hopeful stables";
+
+        assert_eq!(vec!["poni ran hope", "ran hope stabl"], texts(shingles(analyzer.analyze(text), 3)));
+        assert_eq!(texts(analyzer.analyze(text)), texts(shingles(analyzer.analyze(text), 1)));
+        assert!(texts(shingles(analyzer.analyze(text), 5)).is_empty());
+
+        // Shingles skip over the stopwords between their terms.
+        let shingle = shingles(analyzer.analyze(text), 2).nth(1).unwrap();
+        assert_eq!(Term { text: "ran hope".to_string(), byte_range: 11..29, position: 2 }, shingle);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one term")]
+    fn test_empty_shingles() {
+        shingles(Analyzer::new().analyze("ponies"), 0);
+    }
+}
@@ -0,0 +1,103 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! A light stemmer for Arabic.
+//!
+//! Unlike the other stemmers here this is not a Snowball algorithm, but
+//! Light10 (Larkey, Ballesteros and Connell), which works well for retrieval.
+//! Diacritics and tatweel are removed and the forms of alef, yeh and teh
+//! marbuta are normalized. Then a leading `و` and a definite article are
+//! removed, and finally a fixed list of suffixes.
+//!
+//! See <https://ciir.cs.umass.edu/pubfiles/ir-249.pdf>.
+//!
+//! ```
+//! use porter_stemmer::snowball::arabic;
+//!
+//! assert_eq!("كتاب", arabic::stem("والكتاب"));
+//! assert_eq!("مكتب", arabic::stem("المكتبات"));
+//! ```
+
+use snowball::{ends_with, longest_prefix, suffix_start};
+
+const ARTICLES: &[&str] = &["ال", "وال", "بال", "كال", "فال", "لل"];
+
+/// Suffixes, removed in this order.
+const SUFFIXES: &[&str] = &["ها", "ان", "ات", "ون", "ين", "يه", "ية", "ه", "ة", "ي"];
+
+/// Given a word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = normalize(word);
+
+    if word.len() >= 4 && word[0] == 'و' {
+        word.remove(0);
+    }
+
+    if let Some(article) = longest_prefix(&word, ARTICLES) {
+        let length = article.chars().count();
+        if word.len() >= length + 2 {
+            word.drain(..length);
+        }
+    }
+
+    for suffix in SUFFIXES {
+        if ends_with(&word, suffix) && suffix_start(&word, suffix) >= 2 {
+            word.truncate(suffix_start(&word, suffix));
+        }
+    }
+
+    word.into_iter().collect()
+}
+
+/// Remove diacritics and tatweel, write hamzated alefs as a bare alef, and
+/// write a final alef maksura and teh marbuta as yeh and heh.
+fn normalize(word: &str) -> Vec<char> {
+    let mut word = word.chars()
+        .filter(|&c| !matches!(c, '\u{64B}'..='\u{652}' | '\u{640}'))
+        .map(|c| match c {
+            'أ' | 'إ' | 'آ' => 'ا',
+            c => c,
+        })
+        .collect::<Vec<char>>();
+
+    if let Some(last) = word.last_mut() {
+        *last = match *last {
+            'ى' => 'ي',
+            'ة' => 'ه',
+            c => c,
+        };
+    }
+
+    word
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!("كتب".chars().collect::<Vec<char>>(), normalize("كَتَبَ"));
+        assert_eq!("احمد".chars().collect::<Vec<char>>(), normalize("أحمد"));
+        assert_eq!("مدرسه".chars().collect::<Vec<char>>(), normalize("مدرسة"));
+        assert_eq!("مستشفي".chars().collect::<Vec<char>>(), normalize("مستشفى"));
+    }
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("والكتاب", "كتاب"),
+            ("المكتبات", "مكتب"),
+            ("كتابها", "كتاب"),
+            ("مدرسة", "مدرس"),
+            ("بالمدرسة", "مدرس"),
+            ("المعلمون", "معلم"),
+            ("كَتَبَ", "كتب"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
@@ -0,0 +1,103 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Armenian stemmer, for Eastern Armenian.
+//!
+//! Only RV, the region after the first vowel, is looked at. A case or
+//! article ending is removed if it is in R2, and then, wherever they are in
+//! RV, a verb ending, an adjectival suffix and a noun suffix, each the
+//! longest there is.
+//!
+//! See <https://snowballstem.org/algorithms/armenian/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::armenian;
+//!
+//! assert_eq!("քաղ", armenian::stem("քաղաքները"));
+//! assert_eq!("խոս", armenian::stem("խոսում"));
+//! ```
+
+use snowball::{after_first_vowel, after_vowel_consonant, longest_suffix, suffix_start};
+
+/// Case and article endings, removed in R2.
+const ENDINGS: &[&str] = &[
+    "եր", "ներ", "ց", "ուց", "ից", "երից", "ներից", "ցից", "վանից", "ոջից", "վից", "ոց", "սա",
+    "վա", "ամբ", "դ", "երդ", "ներդ", "ուդ", "անդ", "ությանդ", "վանդ", "ոջդ", "ը", "երը", "ները",
+    "անը", "ությանը", "վանը", "ոջը", "ի", "երի", "ների", "վի", "երում", "ներում", "անում", "ն",
+    "երն", "ներն", "ուն", "ան", "ության", "վան", "ին", "երին", "ներին", "ությանն", "ոջ", "ությանս",
+    "վանս", "ոջս", "ով", "երով", "ներով", "անով", "վով",
+];
+
+const VERBS: &[&str] = &[
+    "ար", "ացար", "եցար", "ացրիր", "ացիր", "եցիր", "վեցիր", "ալուց", "ելուց", "աց", "եց", "ացրեց",
+    "ալու", "ելու", "աք", "ցաք", "ացաք", "ացրիք", "ացիք", "եցիք", "վեցիք", "անք", "ցանք", "ացանք",
+    "ացրինք", "ացինք", "եցինք", "վեցինք", "ա", "ացա", "եցա", "վե", "ացրի", "ացի", "եցի", "վեցի",
+    "ալ", "ըալ", "ացնալ", "անալ", "ենալ", "ել", "ըել", "նել", "ցնել", "եցնել", "չել", "վել",
+    "ացվել", "եցվել", "տել", "ատել", "ոտել", "կոտել", "ված", "ում", "վում", "ան", "ցան", "ացան",
+    "ացրին", "ացին", "եցին", "վեցին", "ալիս", "ելիս", "ավ", "ացավ", "եցավ", "ալով", "ելով",
+];
+
+const ADJECTIVES: &[&str] = &[
+    "բար", "րորդ", "երորդ", "ալի", "ակի", "որակ", "եղ", "վուն", "արան", "ական", "են", "երեն",
+    "եկեն", "որէն", "ին", "գին", "ովին", "լայն", "պես", "իվ", "ատ", "ավետ", "կոտ",
+];
+
+const NOUNS: &[&str] = &[
+    "գար", "վոր", "ավոր", "անօց", "ոց", "ու", "ք", "արք", "չեք", "իք", "ալիք", "անիք", "վածք",
+    "ույք", "ունք", "մունք", "ենք", "ոնք", "իչք", "որդ", "ույթ", "ցի", "ուհի", "իլ", "ուկ", "ակ",
+    "յակ", "անակ", "իկ", "յուն", "ություն", "ան", "արան", "պան", "ստան", "եղէն", "ածո", "իչ",
+    "ուս", "ուստ",
+];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'ա' | 'է' | 'ի' | 'օ' | 'ւ' | 'ե' | 'ո' | 'ը')
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = word.chars().collect::<Vec<char>>();
+
+    let rv = after_first_vowel(&word, is_vowel);
+    let r1 = after_vowel_consonant(&word, 0, is_vowel);
+    let r2 = after_vowel_consonant(&word, r1, is_vowel);
+
+    remove_longest(&mut word, rv, r2, ENDINGS);
+    remove_longest(&mut word, rv, rv, VERBS);
+    remove_longest(&mut word, rv, rv, ADJECTIVES);
+    remove_longest(&mut word, rv, rv, NOUNS);
+
+    word.into_iter().collect()
+}
+
+/// Remove the longest of `suffixes` in RV, but only if it is in `region`.
+fn remove_longest(word: &mut Vec<char>, rv: usize, region: usize, suffixes: &[&str]) {
+    if let Some(suffix) = longest_suffix(word, rv, suffixes) {
+        let start = suffix_start(word, suffix);
+        if start >= region {
+            word.truncate(start);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("քաղաքներ", "քաղ"),
+            ("քաղաքները", "քաղ"),
+            ("քաղաքում", "քաղա"),
+            ("խոսել", "խոս"),
+            ("խոսում", "խոս"),
+            ("գեղեցկություն", "գեղեցկությ"),
+            ("տղաները", "տղաները"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
@@ -0,0 +1,206 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Basque stemmer.
+//!
+//! Basque is agglutinative, so verbal suffixes are removed for as long as
+//! there are any, then nominal suffixes, then a single adjectival suffix.
+//! Each time the longest suffix is found, and it is removed only if it is
+//! in the region its table gives it; RV is found as in the Spanish stemmer.
+//! A few words that look like they end in suffixes are kept, or written in
+//! a shorter form, and the search goes on in front of them.
+//!
+//! See <https://snowballstem.org/algorithms/basque/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::basque;
+//!
+//! assert_eq!("mendi", basque::stem("mendietan"));
+//! assert_eq!("gizo", basque::stem("gizonarekin"));
+//! ```
+
+use snowball::{after_vowel_consonant, ends_with, romance_rv};
+
+/// What to do with a suffix once it is found.
+#[derive(Clone, Copy)]
+enum Action {
+    /// Remove it if it is in RV.
+    InRv,
+    /// Remove it if it is in R1.
+    InR1,
+    /// Remove it if it is in R2.
+    InR2,
+    /// Write it as this, and go on looking in front of it.
+    Replace(&'static str),
+}
+
+type Table = &'static [(&'static [&'static str], Action)];
+
+/// Verbal suffixes removed in RV.
+const VERBAL_RV: &[&str] = &[
+    "idea", "bidea", "kidea", "pidea", "kundea", "galea", "tailea", "tzailea", "gunea", "kunea",
+    "tzaga", "gaia", "aldia", "taldia", "karia", "karria", "ka", "tzaka", "la", "mena", "pena",
+    "kina", "ezina", "tezina", "kuna", "tuna", "kizuna", "era", "bera", "kera", "pera", "orra",
+    "korra", "dura", "gura", "kura", "tura", "eta", "keta", "gailua", "eza", "erreza", "gaitza",
+    "kaitza", "kuntza", "ide", "bide", "kide", "pide", "kunde", "tzake", "tzeke", "le", "gale",
+    "taile", "tzaile", "gune", "kune", "tze", "atze", "gai", "aldi", "taldi", "ki", "ari", "kari",
+    "lari", "tari", "etari", "karri", "arazi", "tarazi", "an", "ean", "rean", "kan", "etan", "men",
+    "pen", "kin", "rekin", "ezin", "tezin", "tun", "kizun", "go", "ago", "tio", "dako", "or",
+    "kor", "tzat", "du", "gailu", "tu", "atu", "aldatu", "tatu", "ez", "errez", "tzez", "gaitz",
+    "kaitz",
+];
+
+/// Nominal suffixes removed in RV.
+const NOMINAL_RV: &[&str] = &[
+    "ada", "kada", "anda", "denda", "gabea", "kabea", "aldea", "kaldea", "taldea", "ordea",
+    "zalea", "tzalea", "gilea", "emea", "kumea", "nea", "enea", "zionea", "unea", "gunea", "pea",
+    "aurrea", "tea", "kotea", "artea", "ostea", "etxea", "ga", "anga", "gaia", "aldia", "taldia",
+    "handia", "mendia", "geia", "egia", "degia", "tegia", "nahia", "ohia", "kia", "tokia", "oia",
+    "koia", "aria", "karia", "laria", "taria", "eria", "keria", "teria", "larria", "kirria",
+    "duria", "asia", "tia", "ezia", "bizia", "ontzia", "ka", "ska", "xka", "zka", "gibela", "gela",
+    "kaila", "skila", "tila", "ola", "na", "kana", "ena", "garrena", "gerrena", "urrena", "zaina",
+    "tzaina", "kina", "mina", "garna", "una", "duna", "asuna", "tasuna", "ondoa", "kondoa", "ngoa",
+    "zioa", "koa", "takoa", "zkoa", "noa", "zinoa", "aroa", "taroa", "zaroa", "eroa", "oroa",
+    "osoa", "toa", "ttoa", "ztoa", "txoa", "tzoa", "ñoa", "ra", "ara", "dara", "liara", "tiara",
+    "tara", "etara", "tzara", "bera", "kera", "pera", "tzarra", "korra", "tra", "sa", "osa", "ta",
+    "eta", "keta", "sta", "dua", "mendua", "ordua", "lekua", "burua", "durua", "tsua", "tua",
+    "mentua", "estua", "txua", "zua", "tzua", "za", "eza", "eroza", "koitza", "antza", "gintza",
+    "kintza", "kuntza", "gabe", "kabe", "kide", "alde", "kalde", "talde", "orde", "ge", "zale",
+    "tzale", "gile", "eme", "kume", "ne", "zione", "une", "gune", "pe", "aurre", "te", "kote",
+    "arte", "oste", "etxe", "gai", "di", "aldi", "taldi", "handi", "mendi", "gei", "egi", "degi",
+    "tegi", "nahi", "ohi", "ki", "toki", "oi", "goi", "koi", "ari", "kari", "lari", "tari",
+    "larri", "kirri", "duri", "asi", "ti", "ontzi", "ñi", "ak", "ek", "tarik", "gibel", "ail",
+    "kail", "kan", "tan", "etan", "garren", "gerren", "urren", "zain", "tzain", "kin", "min",
+    "dun", "asun", "tasun", "aizun", "ondo", "kondo", "go", "ngo", "zio", "ko", "tako", "etako",
+    "eko", "tariko", "sko", "tuko", "zko", "no", "zino", "ro", "aro", "taro", "zaro", "ero",
+    "giro", "oro", "oso", "to", "tto", "zto", "txo", "tzo", "gintzo", "ño", "zp", "ar", "dar",
+    "behar", "liar", "tiar", "tar", "tzar", "kor", "os", "ket", "du", "mendu", "ordu", "leku",
+    "duru", "tsu", "tu", "mentu", "estu", "txu", "zu", "tzu", "gintzu", "z", "ez", "eroz", "tz",
+    "koitz",
+];
+
+/// Adjectival suffixes removed in RV.
+const ADJECTIVAL_RV: &[&str] = &[
+    "keria", "la", "era", "dade", "tade", "date", "tate", "gi", "ki", "ik", "lanik", "rik",
+    "larik", "ztik", "go", "ro", "ero", "to",
+];
+
+const VERBAL: Table = &[
+    (VERBAL_RV, Action::InRv),
+    (&["garria", "tza", "garri"], Action::InR2),
+    (&["atseden"], Action::Replace("atseden")),
+    (&["arabera"], Action::Replace("arabera")),
+    (&["baditu"], Action::Replace("baditu")),
+];
+
+const NOMINAL: Table = &[
+    (NOMINAL_RV, Action::InRv),
+    (&["garria", "ora", "tza", "garri", "ren", "or", "buru"], Action::InR2),
+    (&["en", "ten", "tzen", "tatu"], Action::InR1),
+    (&["joka"], Action::Replace("jok")),
+    (&["trako"], Action::Replace("tra")),
+    (&["minutuko"], Action::Replace("minutu")),
+    (&["zehar"], Action::Replace("zehar")),
+    (&["geldi"], Action::Replace("geldi")),
+    (&["igaro"], Action::Replace("igaro")),
+    (&["aurka"], Action::Replace("aurka")),
+];
+
+const ADJECTIVAL: Table = &[
+    (ADJECTIVAL_RV, Action::InRv),
+    (&["zlea"], Action::Replace("z")),
+];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+struct Regions {
+    rv: usize,
+    r1: usize,
+    r2: usize,
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = word.chars().collect::<Vec<char>>();
+
+    let r1 = after_vowel_consonant(&word, 0, is_vowel);
+    let regions = Regions {
+        rv: romance_rv(&word, is_vowel),
+        r1,
+        r2: after_vowel_consonant(&word, r1, is_vowel),
+    };
+
+    // Suffixes are looked for ending here, which is in front of whatever
+    // was kept or replaced.
+    let mut end = word.len();
+
+    while step(&mut word, &mut end, &regions, VERBAL) {}
+    while step(&mut word, &mut end, &regions, NOMINAL) {}
+    step(&mut word, &mut end, &regions, ADJECTIVAL);
+
+    word.into_iter().collect()
+}
+
+/// Act on the longest suffix in `table` that ends at `end`. Returns whether
+/// it was removed or replaced.
+fn step(word: &mut Vec<char>, end: &mut usize, regions: &Regions, table: Table) -> bool {
+    let found = table.iter()
+        .flat_map(|&(suffixes, action)| suffixes.iter().map(move |&suffix| (suffix, action)))
+        .filter(|&(suffix, _)| ends_with(&word[..*end], suffix))
+        .max_by_key(|&(suffix, _)| suffix.chars().count());
+
+    let (suffix, action) = match found {
+        Some(found) => found,
+        None => return false,
+    };
+    let start = *end - suffix.chars().count();
+
+    let region = match action {
+        Action::InRv => regions.rv,
+        Action::InR1 => regions.r1,
+        Action::InR2 => regions.r2,
+        Action::Replace(replacement) => {
+            word.splice(start..*end, replacement.chars());
+            *end = start;
+            return true;
+        }
+    };
+
+    if start < region {
+        return false;
+    }
+
+    word.drain(start..*end);
+    *end = start;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("mendietan", "mendi"),
+            ("gizonarekin", "gizo"),
+            ("lagunentzat", "lagun"),
+            ("lagunak", "lagun"),
+            ("etxeak", "etxe"),
+            ("etxeko", "etxeko"),
+            ("ikasketa", "ikas"),
+            ("gozoki", "gozo"),
+            ("etxe", "etxe"),
+            ("atsedenaldia", "atseden"),
+            ("joka", "jok"),
+            ("aurkako", "aurka"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
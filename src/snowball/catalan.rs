@@ -0,0 +1,184 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Catalan stemmer.
+//!
+//! An attached pronoun such as `-li` or `'l` is removed first, then either a
+//! standard suffix or, failing that, a verb ending, then a residual ending.
+//! Each time the longest suffix is found, and it is only removed, or
+//! reduced, if it is in the region its table gives it. Finally the accents
+//! are removed, and `·` becomes `.`.
+//!
+//! See <https://snowballstem.org/algorithms/catalan/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::catalan;
+//!
+//! assert_eq!("cant", catalan::stem("cantava"));
+//! assert_eq!("rapid", catalan::stem("ràpidament"));
+//! ```
+
+use snowball::{after_vowel_consonant, ends_with, replace_suffix, suffix_start};
+
+/// Attached pronouns, removed in R1.
+const PRONOUNS: &[&str] = &[
+    "la", "-la", "sela", "le", "me", "-me", "se", "-te", "hi", "'hi", "li", "-li", "'l", "'m",
+    "-m", "'n", "-n", "ho", "'ho", "lo", "selo", "'s", "las", "selas", "les", "-les", "'ls", "-ls",
+    "'ns", "-ns", "ens", "los", "selos", "nos", "-nos", "vos", "us", "-us", "'t",
+];
+
+/// Standard suffixes removed in R1.
+const STANDARD: &[&str] = &[
+    "enca", "ancia", "encia", "ència", "ícia", "inia", "íinia", "eria", "ària", "atòria", "alla",
+    "ella", "ívola", "ima", "íssima", "ana", "ina", "era", "sfera", "ora", "dora", "adora",
+    "adura", "esa", "osa", "assa", "essa", "issa", "eta", "ita", "ota", "ista", "ialista",
+    "ionista", "iva", "ativa", "nça", "ístic", "enc", "esc", "ud", "atge", "ble", "able", "ible",
+    "isme", "ialisme", "ionisme", "ivisme", "aire", "icte", "iste", "ici", "íci", "ari", "tori",
+    "al", "il", "all", "ell", "ívol", "isam", "issem", "ìssem", "íssem", "íssim", "amen", "ìssin",
+    "ar", "ificar", "egar", "ejar", "itar", "itzar", "fer", "or", "dor", "dur", "doras", "uds",
+    "nces", "ancies", "encies", "ències", "ícies", "inies", "ínies", "eries", "àries", "atòries",
+    "bles", "ables", "ibles", "imes", "íssimes", "formes", "ismes", "ialismes", "ines", "eres",
+    "ores", "dores", "idores", "dures", "eses", "oses", "asses", "ictes", "ites", "otes", "istes",
+    "ialistes", "ionistes", "ives", "atives", "allengües", "icis", "ícis", "aris", "toris", "ls",
+    "als", "ells", "ims", "íssims", "ions", "cions", "esos", "osos", "assos", "issos", "ers",
+    "ors", "dors", "adors", "idors", "ats", "itats", "bilitats", "ivitats", "ativitats", "ïtats",
+    "ets", "ants", "ents", "ments", "aments", "ots", "uts", "ius", "trius", "atius", "ès", "és",
+    "ís", "dís", "ós", "itat", "bilitat", "ivitat", "ativitat", "ïtat", "et", "ant", "ent", "ient",
+    "ment", "ament", "isament", "ot", "isseu", "ìsseu", "ísseu", "triu", "íssiu", "atiu", "ó",
+    "ió", "ció", "ació",
+];
+
+/// Standard suffixes removed only in R2.
+const STANDARD_R2: &[&str] = &["ada", "ades", "acions"];
+
+/// Standard suffixes reduced to `log` in R2.
+const LOGIA: &[&str] = &[
+    "lógica", "logia", "logía", "logi", "lógics", "logies", "lógiques", "logíes", "logis",
+];
+
+/// Standard suffixes reduced to `ic` in R2.
+const IC: &[&str] = &["ica", "ic", "ics", "iques"];
+
+/// Standard suffixes reduced to `c` in R1.
+const QUISSIM: &[&str] = &["quíssima", "quíssim", "quíssimes", "quíssims"];
+
+/// Verb endings removed in R1.
+const VERB: &[&str] = &[
+    "aba", "esca", "isca", "ïsca", "ada", "ida", "uda", "ïda", "ia", "aria", "iria", "ara", "iera",
+    "ira", "adora", "ïra", "ava", "ixa", "itza", "ía", "aría", "ería", "iría", "ïa", "isc", "ïsc",
+    "ad", "ed", "id", "ie", "re", "dre", "ase", "iese", "aste", "iste", "ii", "ini", "esqui",
+    "eixi", "itzi", "am", "em", "arem", "irem", "àrem", "írem", "àssem", "éssem", "iguem", "ïguem",
+    "avem", "àvem", "ávem", "irìem", "íem", "aríem", "iríem", "assim", "essim", "issim", "àssim",
+    "èssim", "éssim", "íssim", "ïm", "an", "aban", "arian", "aran", "ieran", "iran", "ían",
+    "arían", "erían", "irían", "en", "ien", "arien", "irien", "aren", "eren", "iren", "àren",
+    "ïren", "asen", "iesen", "assen", "essen", "issen", "éssen", "ïssen", "esquen", "isquen",
+    "ïsquen", "aven", "ixen", "eixen", "ïxen", "ïen", "in", "inin", "sin", "isin", "assin",
+    "essin", "issin", "ïssin", "esquin", "eixin", "aron", "ieron", "arán", "erán", "irán", "iïn",
+    "ado", "ido", "iendo", "io", "ixo", "eixo", "ïxo", "itzo", "ar", "tzar", "er", "eixer", "ir",
+    "ador", "as", "abas", "adas", "idas", "aras", "ieras", "ías", "arías", "erías", "irías", "ids",
+    "es", "ades", "ides", "udes", "ïdes", "atges", "ies", "aries", "iries", "ares", "ires",
+    "adores", "ïres", "ases", "ieses", "asses", "esses", "isses", "ïsses", "ques", "esques",
+    "ïsques", "aves", "ixes", "eixes", "ïxes", "ïes", "abais", "arais", "ierais", "íais", "aríais",
+    "eríais", "iríais", "aseis", "ieseis", "asteis", "isteis", "inis", "sis", "isis", "assis",
+    "essis", "issis", "ïssis", "esquis", "eixis", "itzis", "áis", "aréis", "eréis", "iréis", "ams",
+    "ados", "idos", "amos", "ábamos", "áramos", "iéramos", "íamos", "aríamos", "eríamos",
+    "iríamos", "aremos", "eremos", "iremos", "ásemos", "iésemos", "imos", "adors", "ass", "erass",
+    "ess", "ats", "its", "ents", "às", "aràs", "iràs", "arás", "erás", "irás", "és", "arés", "ís",
+    "iïs", "at", "it", "ant", "ent", "int", "ut", "ït", "au", "erau", "ieu", "ineu", "areu",
+    "ireu", "àreu", "íreu", "asseu", "esseu", "eresseu", "àsseu", "ésseu", "igueu", "ïgueu",
+    "àveu", "áveu", "itzeu", "ìeu", "irìeu", "íeu", "aríeu", "iríeu", "assiu", "issiu", "àssiu",
+    "èssiu", "éssiu", "íssiu", "ïu", "ix", "eix", "ïx", "itz", "ià", "arà", "irà", "itzà", "ará",
+    "erá", "irá", "irè", "aré", "eré", "iré", "í", "iï", "ió",
+];
+
+/// Residual endings removed in R1.
+const RESIDUAL: &[&str] = &[
+    "a", "e", "i", "ïn", "o", "ir", "s", "is", "os", "ïs", "it", "eu", "iu", "itz", "à", "á", "é",
+    "ì", "í", "ï", "ó",
+];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'á' | 'à' | 'é' | 'è' | 'í' | 'ï' | 'ó' | 'ò' | 'ú' | 'ü')
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = word.chars().collect::<Vec<char>>();
+
+    let r1 = after_vowel_consonant(&word, 0, is_vowel);
+    let r2 = after_vowel_consonant(&word, r1, is_vowel);
+
+    replace_longest(&mut word, &[(PRONOUNS, "", r1)]);
+
+    let standard = [
+        (STANDARD, "", r1),
+        (STANDARD_R2, "", r2),
+        (LOGIA, "log", r2),
+        (IC, "ic", r2),
+        (QUISSIM, "c", r1),
+    ];
+    if !replace_longest(&mut word, &standard) {
+        replace_longest(&mut word, &[(VERB, "", r1), (&["ando"], "", r2)]);
+    }
+
+    replace_longest(&mut word, &[(RESIDUAL, "", r1), (&["iqu"], "ic", r1)]);
+
+    word.into_iter()
+        .map(|c| match c {
+            'á' | 'à' => 'a',
+            'é' | 'è' => 'e',
+            'í' | 'ì' | 'ï' => 'i',
+            'ó' | 'ò' => 'o',
+            'ú' | 'ü' => 'u',
+            '·' => '.',
+            c => c,
+        })
+        .collect()
+}
+
+/// Replace the longest suffix in `rules`, each given with its replacement
+/// and its region, but only if it is in that region. Returns whether it was
+/// replaced.
+fn replace_longest(word: &mut Vec<char>, rules: &[(&[&str], &str, usize)]) -> bool {
+    let found = rules.iter()
+        .flat_map(|&(suffixes, replacement, region)| {
+            suffixes.iter().map(move |&suffix| (suffix, replacement, region))
+        })
+        .filter(|&(suffix, _, _)| ends_with(word, suffix))
+        .max_by_key(|&(suffix, _, _)| suffix.chars().count());
+
+    match found {
+        Some((suffix, replacement, region)) if suffix_start(word, suffix) >= region => {
+            replace_suffix(word, suffix, replacement);
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("cantava", "cant"),
+            ("cantar", "cant"),
+            ("nacionalitat", "nacional"),
+            ("ràpidament", "rapid"),
+            ("ràpid", "rap"),
+            ("cases", "case"),
+            ("casa", "cas"),
+            ("donar-li", "don"),
+            ("arqueologia", "arqueolog"),
+            ("polítiques", "politic"),
+            ("l·lícit", "l.lic"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
@@ -0,0 +1,106 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Danish stemmer.
+//!
+//! See <http://snowball.tartarus.org/algorithms/danish/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::danish;
+//!
+//! assert_eq!("bil", danish::stem("bilerne"));
+//! assert_eq!("kær", danish::stem("kærlighed"));
+//! ```
+
+use snowball::{ends_with, longest_suffix, remove_last_in, replace_suffix, scandinavian_r1, suffix_start};
+
+const STEP_1: &[&str] = &[
+    "hed", "ethed", "ered", "e", "erede", "ende", "erende", "ene", "erne", "ere", "en", "heden",
+    "eren", "er", "heder", "erer", "heds", "es", "endes", "erendes", "enes", "ernes", "eres",
+    "ens", "hedens", "erens", "ers", "ets", "erets", "et", "eret", "s",
+];
+
+const STEP_2: &[&str] = &["gd", "dt", "gt", "kt"];
+
+const STEP_3: &[&str] = &["ig", "lig", "elig", "els", "løst"];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y' | 'æ' | 'å' | 'ø')
+}
+
+/// Letters an `s` can be removed after.
+fn is_s_ending(c: char) -> bool {
+    "abcdfghjklmnoprtvyzå".contains(c)
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = word.chars().collect::<Vec<char>>();
+    let r1 = scandinavian_r1(&word, is_vowel);
+
+    step_1(&mut word, r1);
+    remove_last_in(&mut word, r1, STEP_2);
+    step_3(&mut word, r1);
+    undouble(&mut word, r1);
+
+    word.into_iter().collect()
+}
+
+/// Remove an inflectional ending in R1.
+fn step_1(word: &mut Vec<char>, r1: usize) {
+    if let Some(suffix) = longest_suffix(word, r1, STEP_1) {
+        let start = suffix_start(word, suffix);
+
+        if suffix != "s" || (start > 0 && is_s_ending(word[start - 1])) {
+            word.truncate(start);
+        }
+    }
+}
+
+/// Turn a final `igst` into `ig`, then remove a derivational suffix in R1.
+fn step_3(word: &mut Vec<char>, r1: usize) {
+    if ends_with(word, "igst") {
+        word.truncate(word.len() - 2);
+    }
+
+    match longest_suffix(word, r1, STEP_3) {
+        Some("løst") => replace_suffix(word, "løst", "løs"),
+        Some(suffix) => {
+            replace_suffix(word, suffix, "");
+            remove_last_in(word, r1, STEP_2);
+        }
+        None => {}
+    }
+}
+
+/// Remove one letter of a final double consonant whose last letter is in R1.
+fn undouble(word: &mut Vec<char>, r1: usize) {
+    let length = word.len();
+
+    if length >= 2 && length > r1 && !is_vowel(word[length - 1]) && word[length - 1] == word[length - 2] {
+        word.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("bilerne", "bil"),
+            ("huset", "hus"),
+            ("dags", "dag"),
+            ("kærlighed", "kær"),
+            ("hurtigst", "hurt"),
+            ("forløst", "forløs"),
+            ("bussen", "bus"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
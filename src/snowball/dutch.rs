@@ -0,0 +1,244 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Dutch stemmer.
+//!
+//! See <http://snowball.tartarus.org/algorithms/dutch/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::dutch;
+//!
+//! assert_eq!("kat", dutch::stem("katten"));
+//! assert_eq!("licham", dutch::stem("lichamelijk"));
+//! ```
+
+use snowball::{after_vowel_consonant, ends_with, longest_suffix, replace_suffix, suffix_start};
+
+const STEP_1: &[&str] = &["heden", "en", "ene", "s", "se"];
+
+const STEP_3B: &[&str] = &["end", "ing", "ig", "lijk", "baar", "bar"];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y' | 'è')
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = prelude(word);
+
+    let r1 = after_vowel_consonant(&word, 0, is_vowel);
+    let r2 = after_vowel_consonant(&word, r1, is_vowel);
+    let r1 = r1.max(3).min(word.len());
+
+    step_1(&mut word, r1);
+    let e_found = step_2(&mut word, r1);
+    step_3a(&mut word, r1, r2);
+    step_3b(&mut word, r1, r2, e_found);
+    step_4(&mut word);
+
+    word.into_iter()
+        .map(|c| match c {
+            'I' => 'i',
+            'Y' => 'y',
+            c => c,
+        })
+        .collect()
+}
+
+/// Remove accents other than `è`, and mark an initial `y`, a `y` after a
+/// vowel and an `i` between vowels as consonants by putting them into upper
+/// case.
+fn prelude(word: &str) -> Vec<char> {
+    let mut word = word.chars()
+        .map(|c| match c {
+            'ä' | 'á' => 'a',
+            'ë' | 'é' => 'e',
+            'ï' | 'í' => 'i',
+            'ö' | 'ó' => 'o',
+            'ü' | 'ú' => 'u',
+            c => c,
+        })
+        .collect::<Vec<char>>();
+
+    if word.first() == Some(&'y') {
+        word[0] = 'Y';
+    }
+
+    for index in 1..word.len() {
+        if !is_vowel(word[index - 1]) {
+            continue;
+        }
+
+        word[index] = match word[index] {
+            'y' => 'Y',
+            'i' if index + 1 < word.len() && is_vowel(word[index + 1]) => 'I',
+            c => c,
+        };
+    }
+
+    word
+}
+
+/// Remove one of a final `kk`, `dd` or `tt`.
+fn undouble(word: &mut Vec<char>) {
+    if ends_with(word, "kk") || ends_with(word, "dd") || ends_with(word, "tt") {
+        word.pop();
+    }
+}
+
+/// Remove the `en` starting at `start` if it is in R1 and follows a
+/// non-vowel other than the `gem` of `gemeente`, then undouble.
+fn remove_en(word: &mut Vec<char>, start: usize, r1: usize) -> bool {
+    if start < r1 || start == 0 || is_vowel(word[start - 1]) || ends_with(&word[..start], "gem") {
+        return false;
+    }
+
+    word.truncate(start);
+    undouble(word);
+    true
+}
+
+fn step_1(word: &mut Vec<char>, r1: usize) {
+    let suffix = match longest_suffix(word, 0, STEP_1) {
+        Some(suffix) => suffix,
+        None => return,
+    };
+    let start = suffix_start(word, suffix);
+
+    match suffix {
+        "heden" => {
+            if start >= r1 {
+                replace_suffix(word, suffix, "heid");
+            }
+        }
+        "en" | "ene" => {
+            remove_en(word, start, r1);
+        }
+        _ => {
+            if start >= r1 && start > 0 && !is_vowel(word[start - 1]) && word[start - 1] != 'j' {
+                word.truncate(start);
+            }
+        }
+    }
+}
+
+/// Remove a final `e` in R1 after a non-vowel, then undouble. Returns whether
+/// the `e` was removed.
+fn step_2(word: &mut Vec<char>, r1: usize) -> bool {
+    if !ends_with(word, "e") {
+        return false;
+    }
+
+    let start = suffix_start(word, "e");
+    if start < r1 || start == 0 || is_vowel(word[start - 1]) {
+        return false;
+    }
+
+    word.truncate(start);
+    undouble(word);
+    true
+}
+
+/// Remove `heid` in R2 unless it follows a `c`, along with an `en` before it.
+fn step_3a(word: &mut Vec<char>, r1: usize, r2: usize) {
+    if !ends_with(word, "heid") {
+        return;
+    }
+
+    let start = suffix_start(word, "heid");
+    if start < r2 || ends_with(&word[..start], "c") {
+        return;
+    }
+
+    word.truncate(start);
+    if ends_with(word, "en") {
+        let start = suffix_start(word, "en");
+        remove_en(word, start, r1);
+    }
+}
+
+/// Remove a derivational suffix in R2.
+fn step_3b(word: &mut Vec<char>, r1: usize, r2: usize, e_found: bool) {
+    let suffix = match longest_suffix(word, 0, STEP_3B) {
+        Some(suffix) => suffix,
+        None => return,
+    };
+    let start = suffix_start(word, suffix);
+
+    if start < r2 {
+        return;
+    }
+
+    match suffix {
+        "end" | "ing" => {
+            word.truncate(start);
+            if ends_with(word, "ig") && start >= r2 + 2 && !ends_with(&word[..start - 2], "e") {
+                word.truncate(start - 2);
+            } else {
+                undouble(word);
+            }
+        }
+        "ig" => {
+            if !ends_with(&word[..start], "e") {
+                word.truncate(start);
+            }
+        }
+        "lijk" => {
+            word.truncate(start);
+            step_2(word, r1);
+        }
+        "baar" => word.truncate(start),
+        _ => {
+            if e_found {
+                word.truncate(start);
+            }
+        }
+    }
+}
+
+/// Undouble the vowel of a final non-vowel, double vowel, non-vowel, as in
+/// `maan` to `man`.
+fn step_4(word: &mut Vec<char>) {
+    let length = word.len();
+    if length < 4 {
+        return;
+    }
+
+    let (c, v, w, d) = (word[length - 4], word[length - 3], word[length - 2], word[length - 1]);
+    if !is_vowel(c) && v == w && matches!(v, 'a' | 'e' | 'o' | 'u') && !is_vowel(d) && d != 'I' {
+        word.remove(length - 2);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prelude() {
+        assert_eq!("Yoghurt".chars().collect::<Vec<char>>(), prelude("yoghurt"));
+        assert_eq!("fraaIe".chars().collect::<Vec<char>>(), prelude("fraaie"));
+        assert_eq!("ideeen".chars().collect::<Vec<char>>(), prelude("ideeën"));
+    }
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("maanden", "maand"),
+            ("boeken", "boek"),
+            ("katten", "kat"),
+            ("kinderen", "kinder"),
+            ("lichamelijk", "licham"),
+            ("mogelijkheden", "mogelijk"),
+            ("gemaakte", "gemaakt"),
+            ("fraaie", "fraai"),
+            ("maan", "man"),
+            ("heden", "heden"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
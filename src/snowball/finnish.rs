@@ -0,0 +1,225 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Finnish stemmer.
+//!
+//! Finnish stacks particles, possessives, case endings and plural markers on
+//! the end of a word. They are removed in that order, outermost first, before
+//! the remaining stem is tidied up.
+//!
+//! See <http://snowball.tartarus.org/algorithms/finnish/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::finnish;
+//!
+//! assert_eq!("talo", finnish::stem("talossakin"));
+//! assert_eq!("käde", finnish::stem("kädessäni"));
+//! ```
+
+use snowball::{after_vowel_consonant, ends_with, longest_suffix, replace_suffix, suffix_start};
+
+const PARTICLES: &[&str] = &["kin", "kaan", "kään", "ko", "kö", "han", "hän", "pa", "pä", "sti"];
+
+const POSSESSIVES: &[&str] = &["si", "ni", "nsa", "nsä", "mme", "nne", "an", "än", "en"];
+
+const CASES: &[&str] = &[
+    "han", "hen", "hin", "hon", "hän", "hön", "siin", "den", "tten", "seen", "a", "ä", "tta",
+    "ttä", "ta", "tä", "ssa", "ssä", "sta", "stä", "lla", "llä", "lta", "ltä", "lle", "ksi", "ine",
+    "n",
+];
+
+const OTHER_ENDINGS: &[&str] = &[
+    "mpi", "mpa", "mpä", "mmi", "mma", "mmä", "impi", "impa", "impä", "immi", "imma", "immä", "eja",
+    "ejä",
+];
+
+const LONG_VOWELS: &[&str] = &["aa", "ee", "ii", "oo", "uu", "ää", "öö"];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y' | 'ä' | 'ö')
+}
+
+fn ends_with_long_vowel(word: &[char]) -> bool {
+    LONG_VOWELS.iter().any(|vowel| ends_with(word, vowel))
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = word.chars().collect::<Vec<char>>();
+
+    let r1 = after_vowel_consonant(&word, 0, is_vowel);
+    let r2 = after_vowel_consonant(&word, r1, is_vowel);
+
+    particle(&mut word, r1, r2);
+    possessive(&mut word, r1);
+    let case_removed = case_ending(&mut word, r1);
+    other_ending(&mut word, r2);
+
+    if case_removed {
+        if longest_suffix(&word, r1, &["i", "j"]).is_some() {
+            word.pop();
+        }
+    } else {
+        t_plural(&mut word, r1, r2);
+    }
+
+    tidy(&mut word, r1);
+
+    word.into_iter().collect()
+}
+
+/// Remove a particle such as the `kin` of `talossakin`.
+fn particle(word: &mut Vec<char>, r1: usize, r2: usize) {
+    let suffix = match longest_suffix(word, r1, PARTICLES) {
+        Some(suffix) => suffix,
+        None => return,
+    };
+    let start = suffix_start(word, suffix);
+
+    let remove = match suffix {
+        "sti" => start >= r2,
+        _ => start > 0 && (is_vowel(word[start - 1]) || word[start - 1] == 'n' || word[start - 1] == 't'),
+    };
+
+    if remove {
+        word.truncate(start);
+    }
+}
+
+/// Remove a possessive suffix such as the `ni` of `kädessäni`.
+fn possessive(word: &mut Vec<char>, r1: usize) {
+    let suffix = match longest_suffix(word, r1, POSSESSIVES) {
+        Some(suffix) => suffix,
+        None => return,
+    };
+    let start = suffix_start(word, suffix);
+    let before = &word[..start];
+
+    let remove = match suffix {
+        "si" => !ends_with(before, "k"),
+        "an" => longest_suffix(before, 0, &["ta", "ssa", "sta", "lla", "lta", "na"]).is_some(),
+        "än" => longest_suffix(before, 0, &["tä", "ssä", "stä", "llä", "ltä", "nä"]).is_some(),
+        "en" => longest_suffix(before, 0, &["lle", "ine"]).is_some(),
+        _ => true,
+    };
+
+    if remove {
+        word.truncate(start);
+
+        if suffix == "ni" && ends_with(word, "kse") {
+            replace_suffix(word, "kse", "ksi");
+        }
+    }
+}
+
+/// Remove a case ending in R1. Returns whether one was removed.
+fn case_ending(word: &mut Vec<char>, r1: usize) -> bool {
+    let suffix = match longest_suffix(word, r1, CASES) {
+        Some(suffix) => suffix,
+        None => return false,
+    };
+    let start = suffix_start(word, suffix);
+    let before = &word[..start];
+
+    let remove = match suffix {
+        "han" | "hen" | "hin" | "hon" | "hän" | "hön" => before.last() == suffix.chars().nth(1).as_ref(),
+        "siin" | "den" | "tten" => {
+            before.len() >= 2 && before[before.len() - 1] == 'i' && is_vowel(before[before.len() - 2]) && before[before.len() - 2] != 'y'
+        }
+        "seen" => ends_with_long_vowel(before),
+        "a" | "ä" => before.len() >= 2 && !is_vowel(before[before.len() - 2]) && is_vowel(before[before.len() - 1]),
+        "tta" | "ttä" => ends_with(before, "e"),
+        "n" => {
+            word.truncate(start);
+            if ends_with_long_vowel(word) || ends_with(word, "ie") {
+                word.pop();
+            }
+            return true;
+        }
+        _ => true,
+    };
+
+    if remove {
+        word.truncate(start);
+    }
+
+    remove
+}
+
+/// Remove a comparative or superlative ending in R2.
+fn other_ending(word: &mut Vec<char>, r2: usize) {
+    if let Some(suffix) = longest_suffix(word, r2, OTHER_ENDINGS) {
+        let start = suffix_start(word, suffix);
+
+        if !(suffix.starts_with('m') && ends_with(&word[..start], "po")) {
+            word.truncate(start);
+        }
+    }
+}
+
+/// Remove the plural `t` after a vowel in R1, and a superlative before it in
+/// R2.
+fn t_plural(word: &mut Vec<char>, r1: usize, r2: usize) {
+    let length = word.len();
+    if !ends_with(word, "t") || length < r1 + 2 || !is_vowel(word[length - 2]) {
+        return;
+    }
+
+    word.pop();
+
+    match longest_suffix(word, r2, &["mma", "imma"]) {
+        Some("mma") if ends_with(&word[..length - 4], "po") => {}
+        Some(suffix) => replace_suffix(word, suffix, ""),
+        None => {}
+    }
+}
+
+/// Shorten a long vowel, and remove a final vowel after a consonant and a
+/// final `j` or `o` of `oj`, `uj` or `jo`, all in R1. Then remove one
+/// consonant of a double consonant followed only by vowels.
+fn tidy(word: &mut Vec<char>, r1: usize) {
+    let in_r1 = |word: &[char]| word.len() >= r1 + 2;
+
+    if in_r1(word) && ends_with_long_vowel(word) {
+        word.pop();
+    }
+
+    if in_r1(word) && matches!(word[word.len() - 1], 'a' | 'ä' | 'e' | 'i') && !is_vowel(word[word.len() - 2]) {
+        word.pop();
+    }
+
+    if in_r1(word) && (ends_with(word, "oj") || ends_with(word, "uj") || ends_with(word, "jo")) {
+        word.pop();
+    }
+
+    if let Some(consonant) = word.iter().rposition(|&c| !is_vowel(c)) {
+        if consonant > 0 && word[consonant - 1] == word[consonant] {
+            word.remove(consonant);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("kirjoissa", "kirj"),
+            ("talossakin", "talo"),
+            ("kädessäni", "käde"),
+            ("taloja", "talo"),
+            ("kalaa", "kala"),
+            ("autot", "auto"),
+            ("talon", "talo"),
+            ("taloon", "talo"),
+            ("eläkkeellä", "eläk"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
@@ -0,0 +1,381 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball French stemmer.
+//!
+//! See <http://snowball.tartarus.org/algorithms/french/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::french;
+//!
+//! assert_eq!("continu", french::stem("continuité"));
+//! assert_eq!("abandon", french::stem("abandonnée"));
+//! ```
+
+use snowball::{after_vowel_consonant, ends_with, longest_suffix, replace_suffix, suffix_start};
+
+const STEP_1: &[&str] = &[
+    "ance", "iqUe", "isme", "able", "iste", "eux", "ances", "iqUes", "ismes", "ables", "istes",
+    "atrice", "ateur", "ation", "atrices", "ateurs", "ations",
+    "logie", "logies",
+    "usion", "ution", "usions", "utions",
+    "ence", "ences",
+    "ement", "ements",
+    "ité", "ités",
+    "if", "ive", "ifs", "ives",
+    "eaux",
+    "aux",
+    "euse", "euses",
+    "issement", "issements",
+    "amment",
+    "emment",
+    "ment", "ments",
+];
+
+const STEP_2A: &[&str] = &[
+    "îmes", "ît", "îtes", "i", "ie", "ies", "ir", "ira", "irai", "iraIent", "irais", "irait",
+    "iras", "irent", "irez", "iriez", "irions", "irons", "iront", "is", "issaIent", "issais",
+    "issait", "issant", "issante", "issantes", "issants", "isse", "issent", "isses", "issez",
+    "issiez", "issions", "issons", "it",
+];
+
+const STEP_2B: &[&str] = &[
+    "ions",
+    "é", "ée", "ées", "és", "èrent", "er", "era", "erai", "eraIent", "erais", "erait", "eras",
+    "erez", "eriez", "erions", "erons", "eront", "ez", "iez",
+    "âmes", "ât", "âtes", "a", "ai", "aIent", "ais", "ait", "ant", "ante", "antes", "ants", "as",
+    "asse", "assent", "asses", "assiez", "assions",
+];
+
+const STEP_4: &[&str] = &["ion", "ier", "ière", "Ier", "Ière", "e", "ë"];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y' | 'â' | 'à' | 'ë' | 'é' | 'ê' | 'è' | 'ï' | 'î' | 'ô' | 'û' | 'ù')
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = word.chars().collect::<Vec<char>>();
+
+    prelude(&mut word);
+    let (rv, r1, r2) = regions(&word);
+
+    let mut removed = step_1(&mut word, rv, r1, r2);
+
+    if !removed {
+        removed = step_2a(&mut word, rv) || step_2b(&mut word, rv, r2);
+    }
+
+    if removed {
+        if ends_with(&word, "Y") {
+            replace_suffix(&mut word, "Y", "i");
+        } else if ends_with(&word, "ç") {
+            replace_suffix(&mut word, "ç", "c");
+        }
+    } else {
+        step_4(&mut word, rv, r2);
+    }
+
+    undouble(&mut word);
+    unaccent(&mut word);
+
+    word.into_iter().map(|c| c.to_ascii_lowercase()).collect()
+}
+
+/// Mark `u` and `i` between vowels, `y` next to a vowel and `u` after `q` as
+/// consonants by putting them into upper case.
+fn prelude(word: &mut [char]) {
+    for index in 0..word.len() {
+        let before = index > 0 && is_vowel(word[index - 1]);
+        let after = index + 1 < word.len() && is_vowel(word[index + 1]);
+
+        word[index] = match word[index] {
+            'u' if before && after => 'U',
+            'i' if before && after => 'I',
+            'y' if before || after => 'Y',
+            'u' if index > 0 && word[index - 1] == 'q' => 'U',
+            c => c,
+        };
+    }
+}
+
+/// The starts of the regions RV, R1 and R2.
+fn regions(word: &[char]) -> (usize, usize, usize) {
+    let two_vowels = word.len() >= 3 && is_vowel(word[0]) && is_vowel(word[1]);
+    let prefixed = ["par", "col", "tap"].iter().any(|prefix| word.iter().take(3).cloned().eq(prefix.chars()));
+
+    let rv = if two_vowels || prefixed {
+        3
+    } else {
+        (1..word.len()).find(|&index| is_vowel(word[index])).map_or(word.len(), |index| index + 1)
+    };
+
+    let r1 = after_vowel_consonant(word, 0, is_vowel);
+    let r2 = after_vowel_consonant(word, r1, is_vowel);
+
+    (rv, r1, r2)
+}
+
+/// Remove a standard suffix. Returns whether step 2 should be skipped, which
+/// is not the case for the adverbial `ment` endings.
+fn step_1(word: &mut Vec<char>, rv: usize, r1: usize, r2: usize) -> bool {
+    let suffix = match longest_suffix(word, 0, STEP_1) {
+        Some(suffix) => suffix,
+        None => return false,
+    };
+    let start = suffix_start(word, suffix);
+
+    match suffix {
+        "ance" | "iqUe" | "isme" | "able" | "iste" | "eux" | "ances" | "iqUes" | "ismes" | "ables" | "istes" => {
+            if start < r2 {
+                return false;
+            }
+            word.truncate(start);
+        }
+        "atrice" | "ateur" | "ation" | "atrices" | "ateurs" | "ations" => {
+            if start < r2 {
+                return false;
+            }
+            word.truncate(start);
+            if ends_with(word, "ic") {
+                if start >= r2 + 2 {
+                    word.truncate(start - 2);
+                } else {
+                    replace_suffix(word, "ic", "iqU");
+                }
+            }
+        }
+        "logie" | "logies" if start >= r2 => replace_suffix(word, suffix, "log"),
+        "usion" | "ution" | "usions" | "utions" if start >= r2 => replace_suffix(word, suffix, "u"),
+        "ence" | "ences" if start >= r2 => replace_suffix(word, suffix, "ent"),
+        "ement" | "ements" => {
+            if start < rv {
+                return false;
+            }
+            word.truncate(start);
+            if ends_with(word, "iv") {
+                if start >= r2 + 2 {
+                    word.truncate(start - 2);
+                    if ends_with(word, "at") && start >= r2 + 4 {
+                        word.truncate(start - 4);
+                    }
+                }
+            } else if ends_with(word, "eus") {
+                if start >= r2 + 3 {
+                    word.truncate(start - 3);
+                } else if start >= r1 + 3 {
+                    replace_suffix(word, "eus", "eux");
+                }
+            } else if ends_with(word, "abl") || ends_with(word, "iqU") {
+                if start >= r2 + 3 {
+                    word.truncate(start - 3);
+                }
+            } else if start >= rv + 3 {
+                if ends_with(word, "ièr") {
+                    replace_suffix(word, "ièr", "i");
+                } else if ends_with(word, "Ièr") {
+                    replace_suffix(word, "Ièr", "i");
+                }
+            }
+        }
+        "ité" | "ités" => {
+            if start < r2 {
+                return false;
+            }
+            word.truncate(start);
+            if ends_with(word, "abil") {
+                if start >= r2 + 4 {
+                    word.truncate(start - 4);
+                } else {
+                    replace_suffix(word, "abil", "abl");
+                }
+            } else if ends_with(word, "ic") {
+                if start >= r2 + 2 {
+                    word.truncate(start - 2);
+                } else {
+                    replace_suffix(word, "ic", "iqU");
+                }
+            } else if ends_with(word, "iv") && start >= r2 + 2 {
+                word.truncate(start - 2);
+            }
+        }
+        "if" | "ive" | "ifs" | "ives" => {
+            if start < r2 {
+                return false;
+            }
+            word.truncate(start);
+            if ends_with(word, "at") && start >= r2 + 2 {
+                word.truncate(start - 2);
+                if ends_with(word, "ic") {
+                    if start >= r2 + 4 {
+                        word.truncate(start - 4);
+                    } else {
+                        replace_suffix(word, "ic", "iqU");
+                    }
+                }
+            }
+        }
+        "eaux" => replace_suffix(word, suffix, "eau"),
+        "aux" if start >= r1 => replace_suffix(word, suffix, "al"),
+        "euse" | "euses" => {
+            if start >= r2 {
+                word.truncate(start);
+            } else if start >= r1 {
+                replace_suffix(word, suffix, "eux");
+            } else {
+                return false;
+            }
+        }
+        "issement" | "issements" if start >= r1 && start > 0 && !is_vowel(word[start - 1]) => {
+            word.truncate(start);
+        }
+        "amment" => {
+            if start >= rv {
+                replace_suffix(word, suffix, "ant");
+            }
+            return false;
+        }
+        "emment" => {
+            if start >= rv {
+                replace_suffix(word, suffix, "ent");
+            }
+            return false;
+        }
+        "ment" | "ments" => {
+            if start > rv && is_vowel(word[start - 1]) {
+                word.truncate(start);
+            }
+            return false;
+        }
+        _ => return false,
+    }
+
+    true
+}
+
+/// Remove a verb suffix beginning with `i`, if it follows a non-vowel in RV.
+fn step_2a(word: &mut Vec<char>, rv: usize) -> bool {
+    match longest_suffix(word, rv, STEP_2A) {
+        Some(suffix) => {
+            let start = suffix_start(word, suffix);
+            if start > rv && !is_vowel(word[start - 1]) {
+                word.truncate(start);
+                true
+            } else {
+                false
+            }
+        }
+        None => false,
+    }
+}
+
+/// Remove any other verb suffix in RV.
+fn step_2b(word: &mut Vec<char>, rv: usize, r2: usize) -> bool {
+    let suffix = match longest_suffix(word, rv, STEP_2B) {
+        Some(suffix) => suffix,
+        None => return false,
+    };
+    let start = suffix_start(word, suffix);
+
+    match suffix {
+        "ions" => {
+            if start < r2 {
+                return false;
+            }
+            word.truncate(start);
+        }
+        "âmes" | "ât" | "âtes" | "a" | "ai" | "aIent" | "ais" | "ait" | "ant" | "ante" | "antes" | "ants" | "as" |
+        "asse" | "assent" | "asses" | "assiez" | "assions" => {
+            word.truncate(start);
+            if ends_with(word, "e") && start > rv {
+                word.pop();
+            }
+        }
+        _ => word.truncate(start),
+    }
+
+    true
+}
+
+/// Remove a residual suffix, when neither step 1 nor step 2 changed the word.
+fn step_4(word: &mut Vec<char>, rv: usize, r2: usize) {
+    let length = word.len();
+    if length >= 2 && word[length - 1] == 's' && !"aiouès".contains(word[length - 2]) {
+        word.pop();
+    }
+
+    if let Some(suffix) = longest_suffix(word, rv, STEP_4) {
+        let start = suffix_start(word, suffix);
+
+        match suffix {
+            "ion" => {
+                if start >= r2 && start > rv && (word[start - 1] == 's' || word[start - 1] == 't') {
+                    word.truncate(start);
+                }
+            }
+            "ier" | "ière" | "Ier" | "Ière" => replace_suffix(word, suffix, "i"),
+            "e" => word.truncate(start),
+            _ => {
+                if start >= rv + 2 && ends_with(&word[..start], "gu") {
+                    word.truncate(start);
+                }
+            }
+        }
+    }
+}
+
+fn undouble(word: &mut Vec<char>) {
+    if ["enn", "onn", "ett", "ell", "eill"].iter().any(|ending| ends_with(word, ending)) {
+        word.pop();
+    }
+}
+
+/// Remove the accent from an `é` or `è` followed only by non-vowels.
+fn unaccent(word: &mut [char]) {
+    let consonants = word.iter().rev().take_while(|&&c| !is_vowel(c)).count();
+
+    if consonants > 0 && consonants < word.len() {
+        let index = word.len() - consonants - 1;
+        if word[index] == 'é' || word[index] == 'è' {
+            word[index] = 'e';
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regions() {
+        let regions = |word: &str| super::regions(&word.chars().collect::<Vec<char>>());
+
+        assert_eq!((3, 3, 5), regions("aimer"));
+        assert_eq!((3, 3, 5), regions("parade"));
+        assert_eq!((2, 3, 6), regions("fameusement"));
+    }
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("continuation", "continu"),
+            ("continuellement", "continuel"),
+            ("continuer", "continu"),
+            ("continuité", "continu"),
+            ("contradictoire", "contradictoir"),
+            ("abandonnée", "abandon"),
+            ("abandonner", "abandon"),
+            ("académie", "academ"),
+            ("acceptation", "accept"),
+            ("accompagnaient", "accompagn"),
+            ("chevaux", "cheval"),
+            ("gâteaux", "gâteau"),
+            ("finissions", "fin"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
@@ -0,0 +1,376 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball German stemmer.
+//!
+//! See <http://snowball.tartarus.org/algorithms/german/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::german;
+//!
+//! assert_eq!("aufeinanderfolg", german::stem("aufeinanderfolgenden"));
+//! assert_eq!("haus", german::stem("häuser"));
+//! ```
+//!
+//! German writes compounds as one word, so on its own stemming leaves
+//! `Dampfschiff` and `Schiff` unrelated. A `Decompounder` splits compounds into
+//! their parts first, using the word frequencies of a lexicon to choose
+//! between possible splits.
+//!
+//! ```
+//! use porter_stemmer::snowball::german::Decompounder;
+//!
+//! let decompounder = Decompounder::new()
+//!     .with_word("donau", 500)
+//!     .with_word("dampf", 2000)
+//!     .with_word("schiff", 8000)
+//!     .with_word("schiffe", 3000);
+//!
+//! assert_eq!(vec!["Donau", "dampf", "schiff"], decompounder.split("Donaudampfschiff"));
+//! assert_eq!(vec!["donau", "dampf", "schiff"], decompounder.stem("Donaudampfschiffe"));
+//! ```
+
+use std::collections::HashMap;
+
+use snowball::{after_vowel_consonant, ends_with, longest_suffix, suffix_start};
+
+const STEP_1: &[&str] = &["em", "ern", "er", "e", "en", "es", "s"];
+
+const STEP_2: &[&str] = &["en", "er", "est", "st"];
+
+const STEP_3: &[&str] = &["end", "ung", "ig", "ik", "isch", "lich", "heit", "keit"];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y' | 'ä' | 'ö' | 'ü')
+}
+
+/// Letters an `s` can be removed after.
+fn is_s_ending(c: char) -> bool {
+    "bdfghklmnrt".contains(c)
+}
+
+/// Letters an `st` can be removed after.
+fn is_st_ending(c: char) -> bool {
+    "bdfghklmnt".contains(c)
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = prelude(word);
+    let (r1, r2) = regions(&word);
+
+    step_1(&mut word, r1);
+    step_2(&mut word, r1);
+    step_3(&mut word, r1, r2);
+
+    word.into_iter()
+        .map(|c| match c {
+            'U' => 'u',
+            'Y' => 'y',
+            'ä' => 'a',
+            'ö' => 'o',
+            'ü' => 'u',
+            c => c,
+        })
+        .collect()
+}
+
+/// Replace `ß` by `ss`, and mark `u` and `y` between vowels as consonants by
+/// putting them into upper case.
+fn prelude(word: &str) -> Vec<char> {
+    let mut word = word.replace('ß', "ss").chars().collect::<Vec<char>>();
+
+    for index in 1..word.len().saturating_sub(1) {
+        if is_vowel(word[index - 1]) && is_vowel(word[index + 1]) {
+            word[index] = match word[index] {
+                'u' => 'U',
+                'y' => 'Y',
+                c => c,
+            };
+        }
+    }
+
+    word
+}
+
+/// The starts of the regions R1 and R2. R1 always leaves at least three
+/// letters before it.
+fn regions(word: &[char]) -> (usize, usize) {
+    let r1 = after_vowel_consonant(word, 0, is_vowel);
+    let r2 = after_vowel_consonant(word, r1, is_vowel);
+
+    (r1.max(3).min(word.len()), r2)
+}
+
+fn step_1(word: &mut Vec<char>, r1: usize) {
+    let suffix = match longest_suffix(word, 0, STEP_1) {
+        Some(suffix) => suffix,
+        None => return,
+    };
+    let start = suffix_start(word, suffix);
+
+    if start < r1 {
+        return;
+    }
+
+    match suffix {
+        "e" | "en" | "es" => {
+            word.truncate(start);
+            if ends_with(word, "niss") {
+                word.pop();
+            }
+        }
+        "s" => {
+            if start > 0 && is_s_ending(word[start - 1]) {
+                word.truncate(start);
+            }
+        }
+        _ => word.truncate(start),
+    }
+}
+
+fn step_2(word: &mut Vec<char>, r1: usize) {
+    let suffix = match longest_suffix(word, 0, STEP_2) {
+        Some(suffix) => suffix,
+        None => return,
+    };
+    let start = suffix_start(word, suffix);
+
+    if start < r1 {
+        return;
+    }
+
+    if suffix != "st" || (start >= 4 && is_st_ending(word[start - 1])) {
+        word.truncate(start);
+    }
+}
+
+/// Remove a derivational suffix.
+fn step_3(word: &mut Vec<char>, r1: usize, r2: usize) {
+    let suffix = match longest_suffix(word, 0, STEP_3) {
+        Some(suffix) => suffix,
+        None => return,
+    };
+    let start = suffix_start(word, suffix);
+
+    if start < r2 {
+        return;
+    }
+
+    match suffix {
+        "end" | "ung" => {
+            word.truncate(start);
+            if ends_with(word, "ig") && start >= r2 + 2 && !ends_with(&word[..start - 2], "e") {
+                word.truncate(start - 2);
+            }
+        }
+        "ig" | "ik" | "isch" => {
+            if !ends_with(&word[..start], "e") {
+                word.truncate(start);
+            }
+        }
+        "lich" | "heit" => {
+            word.truncate(start);
+            if (ends_with(word, "er") || ends_with(word, "en")) && start >= r1 + 2 {
+                word.truncate(start - 2);
+            }
+        }
+        _ => {
+            word.truncate(start);
+            if let Some(inner) = longest_suffix(word, r2, &["lich", "ig"]) {
+                word.truncate(suffix_start(word, inner));
+            }
+        }
+    }
+}
+
+/// Linking elements that may join the parts of a compound, as in
+/// `Arbeit-s-amt` or `Blume-n-topf`.
+const LINKING_ELEMENTS: &[&str] = &["s", "es", "n", "en", "er", "e"];
+
+/// Splits German compounds into their parts, using a lexicon of word
+/// frequencies.
+///
+/// Of every way of dividing a word into known words, optionally joined by a
+/// linking element, the split whose parts have the highest geometric mean
+/// frequency wins. Leaving the word whole is one of the candidates if the word
+/// is itself in the lexicon, so common compounds aren't split needlessly.
+#[derive(Clone, Debug)]
+pub struct Decompounder {
+    frequencies: HashMap<String, u64>,
+    min_part_length: usize,
+}
+
+impl Default for Decompounder {
+    fn default() -> Decompounder {
+        Decompounder::new()
+    }
+}
+
+impl Decompounder {
+    /// A decompounder with an empty lexicon.
+    pub fn new() -> Decompounder {
+        Decompounder { frequencies: HashMap::new(), min_part_length: 3 }
+    }
+
+    /// A decompounder with the given `(word, frequency)` lexicon.
+    pub fn with_frequencies<I, S>(frequencies: I) -> Decompounder
+        where I: IntoIterator<Item = (S, u64)>, S: AsRef<str>
+    {
+        frequencies.into_iter().fold(Decompounder::new(), |decompounder, (word, frequency)| {
+            decompounder.with_word(word.as_ref(), frequency)
+        })
+    }
+
+    /// Add a word to the lexicon. Words are looked up ignoring case.
+    pub fn with_word(mut self, word: &str, frequency: u64) -> Decompounder {
+        *self.frequencies.entry(word.to_lowercase()).or_insert(0) += frequency;
+        self
+    }
+
+    /// The shortest part a word will be split into, in `char`s. Defaults to 3.
+    pub fn min_part_length(mut self, length: usize) -> Decompounder {
+        self.min_part_length = length;
+        self
+    }
+
+    /// Split a word into the parts of its best split, without linking
+    /// elements. Words that can't be split are returned whole.
+    pub fn split<'a>(&self, word: &'a str) -> Vec<&'a str> {
+        let lowercase = word.chars().flat_map(char::to_lowercase).collect::<String>();
+
+        // Lowercasing must not move char boundaries for the parts to be
+        // slices of the original word.
+        if lowercase.len() != word.len() {
+            return vec![word];
+        }
+
+        let mut best = None;
+        self.splits(&lowercase, 0, &mut Vec::new(), &mut best);
+
+        match best {
+            Some((_, parts)) => parts.into_iter().map(|(start, end)| &word[start..end]).collect(),
+            None => vec![word],
+        }
+    }
+
+    /// Split a word, then stem each of its parts.
+    pub fn stem(&self, word: &str) -> Vec<String> {
+        self.split(word).into_iter().map(|part| stem(&part.to_lowercase())).collect()
+    }
+
+    fn frequency(&self, part: &str) -> Option<u64> {
+        if part.chars().count() >= self.min_part_length {
+            self.frequencies.get(part).cloned()
+        } else {
+            None
+        }
+    }
+
+    /// Try every way of splitting `word[start..]` into known parts, keeping
+    /// the split with the highest geometric mean frequency in `best`.
+    fn splits(&self, word: &str, start: usize, parts: &mut Vec<(usize, usize)>, best: &mut Option<(f64, Vec<(usize, usize)>)>) {
+        for (offset, _) in word[start..].char_indices().skip(1).chain(Some((word.len() - start, ' '))) {
+            let end = start + offset;
+            if self.frequency(&word[start..end]).is_none() {
+                continue;
+            }
+
+            parts.push((start, end));
+
+            if end == word.len() {
+                let score = parts.iter()
+                    .map(|&(start, end)| (self.frequencies[&word[start..end]] as f64).ln())
+                    .sum::<f64>() / parts.len() as f64;
+
+                let better = match *best {
+                    Some((best, _)) => score > best,
+                    None => true,
+                };
+
+                if better {
+                    *best = Some((score, parts.clone()));
+                }
+            } else {
+                self.splits(word, end, parts, best);
+
+                for linking in LINKING_ELEMENTS {
+                    if word[end..].starts_with(linking) && end + linking.len() < word.len() {
+                        self.splits(word, end + linking.len(), parts, best);
+                    }
+                }
+            }
+
+            parts.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regions() {
+        let regions = |word: &str| super::regions(&prelude(word));
+
+        assert_eq!((5, 9), regions("freundlichkeit"));
+        assert_eq!((3, 6), regions("aufeinander"));
+        assert_eq!((2, 2), regions("ab"));
+    }
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("aufeinanderfolgenden", "aufeinanderfolg"),
+            ("kenntnisse", "kenntnis"),
+            ("häuser", "haus"),
+            ("bücher", "buch"),
+            ("laufen", "lauf"),
+            ("straße", "strass"),
+            ("freundlichkeit", "freundlich"),
+            ("zeitungen", "zeitung"),
+            ("bildung", "bildung"),
+            ("übereinstimmung", "ubereinstimm"),
+            ("gesundheit", "gesund"),
+            ("wirtschaftlichkeit", "wirtschaft"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+
+    #[test]
+    fn test_decompounder_split() {
+        let decompounder = Decompounder::with_frequencies(vec![
+            ("arbeit", 900),
+            ("amt", 400),
+            ("blume", 300),
+            ("topf", 200),
+            ("haus", 5000),
+            ("tür", 1500),
+            ("haustür", 800),
+            ("ur", 10),
+        ]);
+
+        assert_eq!(vec!["arbeit", "amt"], decompounder.split("arbeitsamt"));
+        assert_eq!(vec!["Blume", "topf"], decompounder.split("Blumentopf"));
+        assert_eq!(vec!["haus", "tür"], decompounder.split("haustür"));
+        assert_eq!(vec!["haustüren"], decompounder.split("haustüren"));
+        assert_eq!(vec!["hausur"], decompounder.split("hausur"));
+        assert_eq!(vec!["haus", "ur"], decompounder.min_part_length(2).split("hausur"));
+    }
+
+    #[test]
+    fn test_decompounder_whole_word() {
+        let decompounder = Decompounder::new()
+            .with_word("bahn", 100)
+            .with_word("hof", 100)
+            .with_word("bahnhof", 5000);
+
+        assert_eq!(vec!["bahnhof"], decompounder.split("bahnhof"));
+        assert_eq!(vec!["bahnhof"], decompounder.stem("bahnhofs"));
+    }
+}
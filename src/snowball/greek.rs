@@ -0,0 +1,496 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Greek stemmer.
+//!
+//! Accents and diaeresis are removed and final sigma `ς` is written `σ`
+//! before stemming, so `λόγος`, `λογος` and `ΛΟΓΟΣ` all stem alike. Stems
+//! keep the plain `σ`.
+//!
+//! After the endings of irregular nouns, a series of steps each removes a
+//! group of derivational, noun or verb suffixes, and puts part of the suffix
+//! back when what is left is one of a list of stems that need it, so that
+//! `παίζω` stems to `παι` rather than `πα`. Only if none of them removed
+//! anything is the longest plain inflectional ending removed. Finally the
+//! suffixes of comparatives and superlatives are removed.
+//!
+//! See <https://snowballstem.org/algorithms/greek/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::greek;
+//!
+//! assert_eq!("ανθρωπ", greek::stem("άνθρωπος"));
+//! assert_eq!("ανθρωπ", greek::stem("ΑΝΘΡΩΠΟΣ"));
+//! assert_eq!("παι", greek::stem("παίζω"));
+//! ```
+
+use snowball::{ends_with, longest_suffix, replace_suffix, suffix_start};
+
+/// Irregular nouns, and the stems their endings are replaced by.
+const IRREGULAR: &[(&[&str], &str)] = &[
+    (&["φαγια", "φαγιου", "φαγιων"], "φα"),
+    (&["σκαγια", "σκαγιου", "σκαγιων"], "σκα"),
+    (&["ολογιου", "ολογια", "ολογιων"], "ολο"),
+    (&["σογιου", "σογια", "σογιων"], "σο"),
+    (&["τατογια", "τατογιου", "τατογιων"], "τατο"),
+    (&["κρεασ", "κρεατοσ", "κρεατα", "κρεατων"], "κρε"),
+    (&["περασ", "περατοσ", "περατα", "περατων"], "περ"),
+    (&["τερασ", "τερατοσ", "τερατα", "τερατων"], "τερ"),
+    (&["φωσ", "φωτοσ", "φωτα", "φωτων"], "φω"),
+    (&["καθεστωσ", "καθεστωτοσ", "καθεστωτα", "καθεστωτων"], "καθεστ"),
+    (&["γεγονοσ", "γεγονοτοσ", "γεγονοτα", "γεγονοτων"], "γεγον"),
+];
+
+/// Words that are themselves a suffix, and their stems.
+const WHOLE_WORDS: &[(&str, &str)] = &[("ισα", "ισ"), ("αγαμε", "αγαμ")];
+
+/// What a stem left by a step must be for something to be done to it.
+#[derive(Clone, Copy)]
+enum Stem {
+    /// Any stem at all.
+    Any,
+    /// One of these stems.
+    Is(&'static [&'static str]),
+    /// A stem ending with one of these.
+    EndsWith(&'static [&'static str]),
+    /// A stem ending with a vowel.
+    Vowel,
+    /// A stem ending with a vowel other than `υ`.
+    VowelNotY,
+}
+
+/// What is done to a stem left by a step.
+#[derive(Clone, Copy)]
+enum Then {
+    /// Append this, usually part of the suffix that was removed.
+    Append(&'static str),
+    /// Remove this from the end of the stem as well.
+    Remove(&'static str),
+}
+
+/// A step: the suffixes it removes, and what is done to the stems left,
+/// according to the first of its rules the stem meets.
+struct Step {
+    suffixes: &'static [&'static str],
+    rules: &'static [(Stem, Then)],
+}
+
+const STEPS: &[Step] = &[
+    Step {
+        suffixes: &[
+            "ιζα", "ιζεσ", "ιζε", "ιζαμε", "ιζατε", "ιζαν", "ιζανε", "ιζω", "ιζεισ", "ιζει", "ιζουμε",
+            "ιζετε", "ιζουν", "ιζουνε",
+        ],
+        rules: &[
+            (Stem::Is(&["αναμπα", "εμπα", "επα", "ξαναπα", "πα", "περιπα", "αθρο", "συναθρο", "δανε"]), Then::Append("ι")),
+            (Stem::Is(&[
+                "μαρκ", "κορν", "αμπαρ", "αρρ", "βαθυρι", "βαρκ", "β", "βολβορ", "γκρ", "γλυκορ", "γλυκυρ",
+                "ιμπ", "λ", "λου", "μαρ", "μ", "πρ", "μπρ", "πολυρ", "π", "ρ", "πιπερορ",
+            ]), Then::Append("ιζ")),
+        ],
+    },
+    Step {
+        suffixes: &["ωθηκα", "ωθηκεσ", "ωθηκε", "ωθηκαμε", "ωθηκατε", "ωθηκαν", "ωθηκανε"],
+        rules: &[(Stem::Is(&["αλ", "βι", "εν", "υψ", "λι", "ζω", "σ", "χ"]), Then::Append("ων"))],
+    },
+    Step {
+        suffixes: &["ισα", "ισεσ", "ισε", "ισαμε", "ισατε", "ισαν", "ισανε"],
+        rules: &[
+            (Stem::Is(&[
+                "αναμπα", "αθρο", "εμπα", "εσε", "εσωκλε", "επα", "ξαναπα", "επε", "περιπα", "συναθρο",
+                "δανε", "κλε", "χαρτοπα", "εξαρχα", "μετεπε", "αποκλε", "απεκλε", "εκλε", "πε",
+            ]), Then::Append("ι")),
+            (Stem::Is(&[
+                "αν", "αφ", "γε", "γιγαντοαφ", "γκε", "δημοκρατ", "κομ", "γκ", "μ", "π", "πουκαμ", "ολο",
+                "λαρ",
+            ]), Then::Append("ισ")),
+        ],
+    },
+    Step {
+        suffixes: &["ισω", "ισεισ", "ισει", "ισουμε", "ισετε", "ισουν", "ισουνε"],
+        rules: &[(Stem::Is(&[
+            "αναμπα", "εμπα", "εσε", "εσωκλε", "επα", "ξαναπα", "επε", "περιπα", "συναθρο", "δανε",
+            "κλε", "χαρτοπα", "εξαρχα", "μετεπε", "αποκλε", "απεκλε", "εκλε", "πε",
+        ]), Then::Append("ι"))],
+    },
+    Step {
+        suffixes: &["ιστοσ", "ιστου", "ιστο", "ιστε", "ιστοι", "ιστων", "ιστουσ", "ιστη", "ιστησ", "ιστα", "ιστεσ"],
+        rules: &[
+            (Stem::Is(&[
+                "μ", "π", "απ", "αρ", "ηδ", "κτ", "σκ", "σχ", "υψ", "φα", "χρ", "χτ", "ακτ", "αορ", "ασχ",
+                "αταχ", "αχν", "αχρ", "απλ", "αεισ",
+            ]), Then::Append("ιστ")),
+            (Stem::Is(&["δανε", "συναθρο", "κλε", "σε", "εσωκλε", "ασε", "πλε"]), Then::Append("ι")),
+        ],
+    },
+    Step {
+        suffixes: &["ισμο", "ισμοι", "ισμοσ", "ισμου", "ισμουσ", "ισμων"],
+        rules: &[
+            (Stem::Is(&["σε", "μετασε", "μικροσε", "εγκλε", "αποκλε"]), Then::Append("ισμ")),
+            (Stem::Is(&["δανε", "αντιδανε"]), Then::Append("ι")),
+            (Stem::EndsWith(&["αγνωστικ", "ατομικ", "γνωστικ", "εθνικ", "εκλεκτικ", "σκεπτικ", "τοπικ"]), Then::Remove("ικ")),
+            (Stem::EndsWith(&["αλεξανδριν", "βυζαντιν", "θεατριν"]), Then::Remove("ιν")),
+        ],
+    },
+    Step {
+        suffixes: &["αρακι", "αρακια", "ουδακι", "ουδακια"],
+        rules: &[(Stem::Is(&["σ", "χ"]), Then::Append("αρακ"))],
+    },
+    Step {
+        suffixes: &["ακι", "ακια", "ιτσα", "ιτσασ", "ιτσεσ", "ιτσων", "αρακι", "αρακια"],
+        rules: &[
+            (Stem::Is(&[
+                "βαμβ", "βρ", "καιμ", "κον", "κορ", "λαβρ", "λουλ", "μερ", "μουστ", "ναγκασ", "πλ", "ρ",
+                "ρυ", "σ", "σκ", "σοκ", "σπαν", "τζ", "φαρμ", "χ", "καπακ", "αλισφ", "αμβρ", "ανθρ", "κ",
+                "φυλ", "κατραπ", "κλιμ", "μαλ", "σλοβ", "φ", "σφ", "τσεχοσλοβ",
+            ]), Then::Append("ακ")),
+            (Stem::Is(&[
+                "β", "βαλ", "γιαν", "γλ", "ζ", "ηγουμεν", "καρδ", "μακρυν", "νυφ", "πατερ", "π", "τοσ",
+                "τριπολ",
+            ]), Then::Append("ιτσ")),
+            (Stem::EndsWith(&["κορ"]), Then::Append("ιτσ")),
+        ],
+    },
+    Step {
+        suffixes: &["ιδιο", "ιδια", "ιδιων"],
+        rules: &[
+            (Stem::Is(&["αιφν", "ιρ", "ολο", "ψαλ"]), Then::Append("ιδ")),
+            (Stem::EndsWith(&["ε", "παιχν"]), Then::Append("ιδ")),
+        ],
+    },
+    Step {
+        suffixes: &["ισκοσ", "ισκου", "ισκο", "ισκε"],
+        rules: &[(Stem::Is(&["δ", "ιβ", "μην", "ρ", "φραγκ", "λυκ", "οβελ"]), Then::Append("ισκ"))],
+    },
+    Step {
+        suffixes: &["αδεσ", "αδων"],
+        rules: &[
+            (Stem::EndsWith(&["οκ", "μαμ", "μαν", "μπαμπ", "πατερ", "γιαγι", "νταντ", "κυρ", "θει", "πεθερ"]), Then::Append("")),
+            (Stem::Any, Then::Append("αδ")),
+        ],
+    },
+    Step {
+        suffixes: &["εδεσ", "εδων"],
+        rules: &[(Stem::EndsWith(&["οπ", "ιπ", "εμπ", "υπ", "γηπ", "δαπ", "κρασπ", "μιλ"]), Then::Append("εδ"))],
+    },
+    Step {
+        suffixes: &["ουδεσ", "ουδων"],
+        rules: &[(Stem::EndsWith(&[
+            "αρκ", "καλιακ", "πεταλ", "λιχ", "πλεξ", "σκ", "σ", "φλ", "φρ", "βελ", "λουλ", "χν", "σπ",
+            "τραγ", "φε",
+        ]), Then::Append("ουδ"))],
+    },
+    Step {
+        suffixes: &["εωσ", "εων"],
+        rules: &[(Stem::Is(&["θ", "δ", "ελ", "γαλ", "ν", "π", "ιδ", "παρ"]), Then::Append("ε"))],
+    },
+    Step {
+        suffixes: &["ια", "ιου", "ιων"],
+        rules: &[(Stem::Vowel, Then::Append("ι"))],
+    },
+    Step {
+        suffixes: &["ικα", "ικο", "ικου", "ικων"],
+        rules: &[
+            (Stem::Vowel, Then::Append("ικ")),
+            (Stem::Is(&[
+                "αλ", "αδ", "ενδ", "αμαν", "αμμοχαλ", "ηθ", "ανηθ", "αντιδ", "φυσ", "βρωμ", "γερ", "εξωδ",
+                "καλπ", "καλλιν", "καταδ", "μουλ", "μπαν", "μπαγιατ", "μπολ", "μποσ", "νιτ", "ξικ",
+                "συνομηλ", "πετσ", "πιτσ", "πικαντ", "πλιατσ", "ποστελν", "πρωτοδ", "σερτ", "συναδ",
+                "τσαμ", "υποδ", "φιλον", "φυλοδ", "χασ",
+            ]), Then::Append("ικ")),
+        ],
+    },
+    Step {
+        suffixes: &["αγαμε", "ησαμε", "ουσαμε", "ηκαμε", "ηθηκαμε"],
+        rules: &[],
+    },
+    Step {
+        suffixes: &["αμε"],
+        rules: &[(Stem::Is(&[
+            "αναπ", "αποθ", "αποκ", "αποστ", "βουβ", "ξεθ", "ουλ", "πεθ", "πικρ", "ποτ", "σιχ", "χ",
+        ]), Then::Append("αμ"))],
+    },
+    Step {
+        suffixes: &[
+            "ιουντανε", "ιοντανε", "ουντανε", "ηθηκανε", "ιοτανε", "οντανε", "ουσανε", "αγανε", "ησανε",
+            "οτανε", "ηκανε",
+        ],
+        rules: &[(Stem::Is(&["τρ", "τσ"]), Then::Append("αγαν"))],
+    },
+    Step {
+        suffixes: &["ανε"],
+        rules: &[
+            (Stem::VowelNotY, Then::Append("αν")),
+            (Stem::Is(&[
+                "βετερ", "βουλκ", "βραχμ", "γ", "δραδουμ", "θ", "καλπουζ", "καστελ", "κορμορ", "λαοπλ",
+                "μωαμεθ", "μ", "μουσουλμ", "ν", "ουλ", "π", "πελεκ", "πλ", "πολισ", "πορτολ", "σαρακατσ",
+                "σουλτ", "τσαρλατ", "ορφ", "τσιγγ", "τσοπ", "φωτοστεφ", "χ", "ψυχοπλ", "αγ", "γαλ", "γερ",
+                "δεκ", "διπλ", "αμερικαν", "ουρ", "πιθ", "πουριτ", "σ", "ζωντ", "ικ", "καστ", "κοπ", "λιχ",
+                "λουθηρ", "μαιντ", "μελ", "σιγ", "σπ", "στεγ", "τραγ", "τσαγ", "φ", "ερ", "αδαπ", "αθιγγ",
+                "αμηχ", "ανικ", "ανοργ", "απηγ", "απιθ", "ατσιγγ", "βασ", "βασκ", "βαθυγαλ", "βιομηχ",
+                "βραχυκ", "διατ", "διαφ", "ενοργ", "θυσ", "καπνοβιομηχ", "καταγαλ", "κλιβ", "κοιλαρφ",
+                "λιβ", "μεγλοβιομηχ", "μικροβιομηχ", "νταβ", "ξηροκλιβ", "ολιγοδαμ", "ολογαλ", "πενταρφ",
+                "περηφ", "περιτρ", "πλατ", "πολυδαπ", "πολυμηχ", "στεφ", "ταβ", "τετ", "υπερηφ", "υποκοπ",
+                "χαμηλοδαπ", "ψηλοταβ",
+            ]), Then::Append("αν")),
+        ],
+    },
+    Step {
+        suffixes: &["ησετε"],
+        rules: &[],
+    },
+    Step {
+        suffixes: &["ετε"],
+        rules: &[
+            (Stem::VowelNotY, Then::Append("ετ")),
+            (Stem::EndsWith(&[
+                "οδ", "αιρ", "φορ", "ταθ", "διαθ", "σχ", "ενδ", "ευρ", "τιθ", "υπερθ", "ραθ", "ενθ", "ροθ",
+                "σθ", "πυρ", "αιν", "συνδ", "συν", "συνθ", "χωρ", "πον", "βρ", "καθ", "ευθ", "εκθ", "νετ",
+                "ρον", "αρκ", "βαρ", "βολ", "ωφελ",
+            ]), Then::Append("ετ")),
+            (Stem::Is(&[
+                "αβαρ", "βεν", "εναρ", "αβρ", "αδ", "αθ", "αν", "απλ", "βαρον", "ντρ", "σκ", "κοπ", "μπορ",
+                "νιφ", "παγ", "παρακαλ", "σερπ", "σκελ", "συρφ", "τοκ", "υ", "δ", "εμ", "θαρρ", "θ",
+            ]), Then::Append("ετ")),
+        ],
+    },
+    Step {
+        suffixes: &["οντασ", "ωντασ"],
+        rules: &[
+            (Stem::Is(&["αρχ"]), Then::Append("οντ")),
+            (Stem::EndsWith(&["κρε"]), Then::Append("ωντ")),
+        ],
+    },
+    Step {
+        suffixes: &["ομαστε", "ιομαστε"],
+        rules: &[(Stem::Is(&["ον"]), Then::Append("ομαστ"))],
+    },
+    Step {
+        suffixes: &["ιεστε"],
+        rules: &[(Stem::Is(&["π", "απ", "συμπ", "ασυμπ", "ακαταπ", "αμεταμφ"]), Then::Append("ιεστ"))],
+    },
+    Step {
+        suffixes: &["εστε"],
+        rules: &[(Stem::Is(&["αλ", "αρ", "εκτελ", "ζ", "μ", "ξ", "παρακαλ", "προ", "νισ"]), Then::Append("ιεστ"))],
+    },
+    Step {
+        suffixes: &["ηθηκα", "ηθηκεσ", "ηθηκε"],
+        rules: &[],
+    },
+    Step {
+        suffixes: &["ηκα", "ηκεσ", "ηκε"],
+        rules: &[
+            (Stem::EndsWith(&["σκωλ", "σκουλ", "ναρθ", "σφ", "οθ", "πιθ"]), Then::Append("ηκ")),
+            (Stem::Is(&["διαθ", "θ", "παρακαταθ", "προσθ", "συνθ"]), Then::Append("ηκ")),
+        ],
+    },
+    Step {
+        suffixes: &["ουσα", "ουσεσ", "ουσε"],
+        rules: &[
+            (Stem::Vowel, Then::Append("ουσ")),
+            (Stem::EndsWith(&[
+                "ποδαρ", "βλεπ", "πανταχ", "φρυδ", "μαντιλ", "μαλλ", "κυματ", "λαχ", "ληγ", "φαγ", "ομ",
+                "πρωτ",
+            ]), Then::Append("ουσ")),
+            (Stem::Is(&[
+                "φαρμακ", "χαδ", "αγκ", "αναρρ", "βρομ", "εκλιπ", "λαμπιδ", "λεχ", "μ", "πατ", "ρ", "λ",
+                "μεδ", "μεσαζ", "υποτειν", "αμ", "αιθ", "ανηκ", "δεσποζ", "ενδιαφερ", "δε", "δευτερευ",
+                "καθαρευ", "πλε", "τσα",
+            ]), Then::Append("ουσ")),
+        ],
+    },
+    Step {
+        suffixes: &["αγα", "αγεσ", "αγε"],
+        rules: &[
+            (Stem::Is(&["ψοφ", "ναυλοχ"]), Then::Append("")),
+            (Stem::EndsWith(&["κολλ"]), Then::Append("")),
+            (Stem::EndsWith(&["οφ", "πελ", "χορτ", "λλ", "σφ", "ρπ", "φρ", "πρ", "λοχ", "σμην"]), Then::Append("αγ")),
+            (Stem::Is(&[
+                "αβαστ", "πολυφ", "αδηφ", "παμφ", "ρ", "ασπ", "αφ", "αμαλ", "αμαλλι", "ανυστ", "απερ",
+                "ασπαρ", "αχαρ", "δερβεν", "δροσοπ", "ξεφ", "νεοπ", "νομοτ", "ολοπ", "ομοτ", "προστ",
+                "προσωποπ", "συμπ", "συντ", "τ", "υποτ", "χαρ", "αειπ", "αιμοστ", "ανυπ", "αποτ", "αρτιπ",
+                "διατ", "εν", "επιτ", "κροκαλοπ", "σιδηροπ", "λ", "ναυ", "ουλαμ", "ουρ", "π", "τρ", "μ",
+            ]), Then::Append("αγ")),
+        ],
+    },
+    Step {
+        suffixes: &["ησε", "ησου", "ησα"],
+        rules: &[(Stem::Is(&["ν", "χερσον", "δωδεκαν", "ερημον", "μεγαλον", "επταν"]), Then::Append("ησ"))],
+    },
+    Step {
+        suffixes: &["ηστε"],
+        rules: &[(Stem::Is(&[
+            "ασβ", "σβ", "αχρ", "χρ", "απλ", "αειμν", "δυσχρ", "ευχρ", "κοινοχρ", "παλιμψ",
+        ]), Then::Append("ηστ"))],
+    },
+    Step {
+        suffixes: &["ουνε", "ησουνε", "ηθουνε"],
+        rules: &[(Stem::Is(&["ν", "ρ", "σπι", "στραβομουτσ", "κακομουτσ", "εξων"]), Then::Append("ουν"))],
+    },
+    Step {
+        suffixes: &["ουμε", "ησουμε", "ηθουμε"],
+        rules: &[(Stem::Is(&["παρασουσ", "φ", "χ", "ωριοπλ", "αζ", "αλλοσουσ", "ασουσ"]), Then::Append("ουμ"))],
+    },
+];
+
+/// Inflectional endings, removed only if none of `STEPS` removed anything.
+const ENDINGS: &[&str] = &[
+    "α", "αγατε", "αγαν", "αει", "αμαι", "αν", "ασ", "ασαι", "αται", "αω", "ε", "ει", "εισ",
+    "ειτε", "εσαι", "εσ", "εται", "ι", "ιεμαι", "ιεμαστε", "ιεται", "ιεσαι", "ιεσαστε",
+    "ιομασταν", "ιομουν", "ιομουνα", "ιονταν", "ιοντουσαν", "ιοσασταν", "ιοσαστε", "ιοσουν",
+    "ιοσουνα", "ιοταν", "ιουμα", "ιουμαστε", "ιουνται", "ιουνταν", "η", "ηδεσ", "ηδων", "ηθει",
+    "ηθεισ", "ηθειτε", "ηθηκατε", "ηθηκαν", "ηθουν", "ηθω", "ηκατε", "ηκαν", "ησ", "ησαν",
+    "ησατε", "ησει", "ησεσ", "ησουν", "ησω", "ο", "οι", "ομαι", "ομασταν", "ομουν", "ομουνα",
+    "ονται", "ονταν", "οντουσαν", "οσ", "οσασταν", "οσαστε", "οσουν", "οσουνα", "οταν", "ου",
+    "ουμαι", "ουμαστε", "ουν", "ουνται", "ουνταν", "ουσ", "ουσαν", "ουσατε", "υ", "υσ", "ω", "ων",
+];
+
+const COMPARATIVES: &[&str] = &["εστερ", "εστατ", "οτερ", "οτατ", "υτερ", "υτατ", "ωτερ", "ωτατ"];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'α' | 'ε' | 'η' | 'ι' | 'ο' | 'υ' | 'ω')
+}
+
+/// Given a word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = prelude(word);
+
+    if word.len() < 3 {
+        return word.into_iter().collect();
+    }
+
+    let whole = word.iter().collect::<String>();
+    if let Some(&(_, stem)) = WHOLE_WORDS.iter().find(|&&(whole_word, _)| whole == whole_word) {
+        return stem.to_string();
+    }
+
+    // Whether no step has removed anything, leaving the word to lose a
+    // plain ending instead.
+    let mut inflected = !irregular(&mut word);
+
+    for step in STEPS {
+        if apply(&mut word, step) {
+            inflected = false;
+        }
+    }
+
+    if let Some(suffix) = longest_suffix(&word, 0, &["ματα", "ματων", "ματοσ"]) {
+        replace_suffix(&mut word, suffix, "μα");
+    }
+
+    if inflected {
+        if let Some(suffix) = longest_suffix(&word, 1, ENDINGS) {
+            replace_suffix(&mut word, suffix, "");
+        }
+    }
+
+    if let Some(suffix) = longest_suffix(&word, 0, COMPARATIVES) {
+        replace_suffix(&mut word, suffix, "");
+    }
+
+    word.into_iter().collect()
+}
+
+/// Lowercase the word, remove accents and diaeresis, and write final sigma
+/// as `σ`.
+fn prelude(word: &str) -> Vec<char> {
+    word.chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            'ά' => 'α',
+            'έ' => 'ε',
+            'ή' => 'η',
+            'ί' | 'ϊ' | 'ΐ' => 'ι',
+            'ό' => 'ο',
+            'ύ' | 'ϋ' | 'ΰ' => 'υ',
+            'ώ' => 'ω',
+            'ς' => 'σ',
+            c => c,
+        })
+        .collect()
+}
+
+/// Replace the ending of an irregular noun. Returns whether the word was one.
+fn irregular(word: &mut Vec<char>) -> bool {
+    for &(endings, stem) in IRREGULAR {
+        if let Some(ending) = longest_suffix(word, 0, endings) {
+            replace_suffix(word, ending, stem);
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Whether `stem` meets `condition`.
+fn meets(stem: &[char], condition: Stem) -> bool {
+    match condition {
+        Stem::Any => true,
+        Stem::Is(stems) => stems.iter().any(|&other| stem.iter().cloned().eq(other.chars())),
+        Stem::EndsWith(endings) => endings.iter().any(|ending| ends_with(stem, ending)),
+        Stem::Vowel => stem.last().is_some_and(|&c| is_vowel(c)),
+        Stem::VowelNotY => stem.last().is_some_and(|&c| c != 'υ' && is_vowel(c)),
+    }
+}
+
+/// Remove the longest of the step's suffixes, leaving at least a letter
+/// before it, then deal with the stem by the first rule it meets. Returns
+/// whether a suffix was removed.
+fn apply(word: &mut Vec<char>, step: &Step) -> bool {
+    let suffix = match longest_suffix(word, 1, step.suffixes) {
+        Some(suffix) => suffix,
+        None => return false,
+    };
+    let start = suffix_start(word, suffix);
+    word.truncate(start);
+
+    if let Some(&(_, then)) = step.rules.iter().find(|&&(condition, _)| meets(word, condition)) {
+        match then {
+            Then::Append(ending) => word.extend(ending.chars()),
+            Then::Remove(ending) => replace_suffix(word, ending, ""),
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prelude() {
+        assert_eq!("λογοσ".chars().collect::<Vec<char>>(), prelude("λόγος"));
+        assert_eq!("λογοσ".chars().collect::<Vec<char>>(), prelude("ΛΟΓΟΣ"));
+        assert_eq!("προυποθεση".chars().collect::<Vec<char>>(), prelude("προϋπόθεση"));
+    }
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("άνθρωπος", "ανθρωπ"),
+            ("ανθρώπων", "ανθρωπ"),
+            ("ανθρώπους", "ανθρωπ"),
+            ("φώτα", "φω"),
+            ("κρέατος", "κρε"),
+            ("γράμματα", "γραμμ"),
+            ("καλύτερος", "καλ"),
+            ("ως", "ωσ"),
+            ("παίζω", "παι"),
+            ("δανείζω", "δανει"),
+            ("σεισμός", "σεισμ"),
+            ("Χριστός", "χριστ"),
+            ("μαμάδες", "μαμ"),
+            ("αγαπάμε", "αγαπ"),
+            ("αγαμε", "αγαμ"),
+            ("αρχοντας", "αρχοντ"),
+            ("παιχνίδια", "παιχνιδ"),
+            ("καθεστώτα", "καθεστ"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Hindi stemmer, for words written in Devanagari.
+//!
+//! This is the lightweight stemmer of Ramanathan and Rao: the longest
+//! inflectional suffix is removed, nothing else, as long as it starts after
+//! the word's first character. Vowel signs are suffixes in their own right,
+//! so `की` becomes `क`. Suffixes that start with `त`, `न` or `क` are only
+//! removed after a consonant, so `करता` becomes `कर` but `पीता` only loses
+//! its `ा`.
+//!
+//! See <https://snowballstem.org/algorithms/hindi/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::hindi;
+//!
+//! assert_eq!("लड़क", hindi::stem("लड़कियों"));
+//! assert_eq!("क", hindi::stem("की"));
+//! ```
+
+use snowball::{longest_suffix, suffix_start};
+
+const SUFFIXES: &[&str] = &[
+    "ी", "ु", "ू", "अ", "आ", "इ", "े", "ई", "उ", "ऊ", "ो", "्", "ए", "ओ", "ा", "ि",
+    "आँ", "ाँ", "ीं", "आं", "ें", "ईं", "ों", "एं", "ओं", "ां", "आई", "ाई", "आए", "इए", "ाए", "िए",
+    "आओ", "ाओ",
+    "ेगी", "ोगी", "एगी", "ओगी", "अती", "आती", "ाती", "अनी", "ुआं", "उआं", "आईं", "ाईं", "ुएं",
+    "आएं", "उएं", "ाएं", "ुओं", "आओं", "उओं", "ाओं", "ोगे", "ओगे", "अते", "आते", "ाते", "अने",
+    "आने", "ाने", "आइए", "ाइए", "अकर", "आकर", "ाकर", "ेगा", "एगा", "अता", "आता", "ाता", "अना",
+    "आना", "ाना", "आया", "ाया",
+    "ूंगी", "ेंगी", "ऊंगी", "एंगी", "आएगी", "ाएगी", "आओगी", "ाओगी", "इयाँ", "ियाँ", "अतीं", "आतीं",
+    "ातीं", "इयों", "ियों", "इयां", "ियां", "ेंगे", "एंगे", "आओगे", "ाओगे", "ूंगा", "ऊंगा", "आएगा",
+    "ाएगा",
+    "आऊंगी", "ाऊंगी", "आएंगी", "ाएंगी", "आइयाँ", "ाइयाँ", "आइयों", "ाइयों", "अताएं", "अनाएं",
+    "अताओं", "अनाओं", "आइयां", "ाइयां", "आएंगे", "ाएंगे", "आऊंगा", "ाऊंगा",
+];
+
+/// Suffixes that are only removed after a consonant.
+const AFTER_CONSONANT: &[&str] = &[
+    "ती", "नी", "ते", "ने", "कर", "ता", "ना", "तीं", "ताएं", "नाएं", "ताओं", "नाओं",
+];
+
+fn is_consonant(c: char) -> bool {
+    matches!(c, '\u{915}'..='\u{939}' | '\u{93c}' | '\u{958}'..='\u{95f}')
+}
+
+/// Given a word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = word.chars().collect::<Vec<char>>();
+
+    // A suffix needing a consonant that isn't there may still end with a
+    // shorter one, as `ता` ends with `ा`.
+    let after_consonant = longest_suffix(&word, 2, AFTER_CONSONANT)
+        .filter(|suffix| is_consonant(word[suffix_start(&word, suffix) - 1]));
+    let suffix = match (longest_suffix(&word, 1, SUFFIXES), after_consonant) {
+        (Some(suffix), Some(other)) if other.chars().count() > suffix.chars().count() => Some(other),
+        (None, other) => other,
+        (suffix, _) => suffix,
+    };
+
+    if let Some(suffix) = suffix {
+        let start = suffix_start(&word, suffix);
+        word.truncate(start);
+    }
+
+    word.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("लड़कियों", "लड़क"),
+            ("लड़का", "लड़क"),
+            ("लड़के", "लड़क"),
+            ("किताबें", "किताब"),
+            ("जाएंगे", "ज"),
+            ("करता", "कर"),
+            ("पीता", "पीत"),
+            ("की", "क"),
+            ("अ", "अ"),
+            ("घर", "घर"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
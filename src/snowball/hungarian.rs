@@ -0,0 +1,169 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Hungarian stemmer.
+//!
+//! Hungarian stacks case, possessive and plural suffixes onto a word. Each
+//! kind is removed in turn, from the outside in, and a long `á` or `é` left
+//! at the end of the stem is shortened to `a` or `e`.
+//!
+//! See <http://snowball.tartarus.org/algorithms/hungarian/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::hungarian;
+//!
+//! assert_eq!("ház", hungarian::stem("házakat"));
+//! assert_eq!("kéz", hungarian::stem("kézzel"));
+//! ```
+
+use snowball::{after_first_vowel, ends_with, longest_prefix, longest_suffix, remove_longest_in, replace_suffix, suffix_start};
+
+const DIGRAPHS: &[&str] = &["cs", "dzs", "gy", "ly", "ny", "sz", "ty", "zs"];
+
+const DOUBLE_CONSONANTS: &[&str] = &[
+    "bb", "cc", "ccs", "dd", "ff", "gg", "ggy", "jj", "kk", "ll", "lly", "mm", "nn", "nny", "pp",
+    "rr", "ss", "ssz", "tt", "tty", "vv", "zz", "zzs",
+];
+
+const CASES: &[&str] = &[
+    "ban", "ben", "ba", "be", "ra", "re", "nak", "nek", "val", "vel", "tól", "től", "ról", "ről",
+    "ból", "ből", "hoz", "hez", "höz", "nál", "nél", "ig", "at", "et", "ot", "öt", "ért", "képp",
+    "képpen", "kor", "ul", "ül", "vá", "vé", "onként", "enként", "anként", "ként", "en", "on",
+    "an", "ön", "n", "t",
+];
+
+const SPECIAL_CASES: &[(&str, &str)] = &[("én", "e"), ("án", "a"), ("ánként", "a")];
+
+const OTHER_CASES: &[(&str, &str)] = &[
+    ("astul", ""), ("estül", ""), ("stul", ""), ("stül", ""), ("ástul", "a"), ("éstül", "e"),
+];
+
+const OWNED: &[(&str, &str)] = &[
+    ("oké", ""), ("öké", ""), ("aké", ""), ("eké", ""), ("éké", "e"), ("áké", "a"), ("ké", ""),
+    ("ééi", "e"), ("áéi", "a"), ("éi", ""), ("éé", "e"), ("é", ""),
+];
+
+const SINGULAR_OWNER: &[(&str, &str)] = &[
+    ("ünk", ""), ("unk", ""), ("ánk", "a"), ("énk", "e"), ("nk", ""), ("ájuk", "a"),
+    ("éjük", "e"), ("juk", ""), ("jük", ""), ("uk", ""), ("ük", ""), ("em", ""), ("om", ""),
+    ("am", ""), ("ám", "a"), ("ém", "e"), ("m", ""), ("od", ""), ("ed", ""), ("ad", ""),
+    ("öd", ""), ("ád", "a"), ("éd", "e"), ("d", ""), ("ja", ""), ("je", ""), ("a", ""), ("e", ""),
+    ("o", ""), ("á", "a"), ("é", "e"),
+];
+
+const PLURAL_OWNER: &[(&str, &str)] = &[
+    ("jaim", ""), ("jeim", ""), ("áim", "a"), ("éim", "e"), ("aim", ""), ("eim", ""), ("im", ""),
+    ("jaid", ""), ("jeid", ""), ("áid", "a"), ("éid", "e"), ("aid", ""), ("eid", ""), ("id", ""),
+    ("jai", ""), ("jei", ""), ("ái", "a"), ("éi", "e"), ("ai", ""), ("ei", ""), ("i", ""),
+    ("jaink", ""), ("jeink", ""), ("eink", ""), ("aink", ""), ("áink", "a"), ("éink", "e"),
+    ("ink", ""), ("jaitok", ""), ("jeitek", ""), ("aitok", ""), ("eitek", ""), ("áitok", "a"),
+    ("éitek", "e"), ("itek", ""), ("jeik", ""), ("jaik", ""), ("aik", ""), ("eik", ""),
+    ("áik", "a"), ("éik", "e"), ("ik", ""),
+];
+
+const PLURAL: &[(&str, &str)] = &[("ák", "a"), ("ék", "e"), ("ök", ""), ("ok", ""), ("ek", ""), ("ak", ""), ("k", "")];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'á' | 'e' | 'é' | 'i' | 'í' | 'o' | 'ó' | 'ö' | 'ő' | 'u' | 'ú' | 'ü' | 'ű')
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = word.chars().collect::<Vec<char>>();
+    let r1 = region_1(&word);
+
+    remove_after_double(&mut word, r1, &["al", "el"]);
+
+    if remove_longest_in(&mut word, r1, CASES) {
+        replace_longest(&mut word, r1, &[("á", "a"), ("é", "e")]);
+    }
+
+    replace_longest(&mut word, r1, SPECIAL_CASES);
+    replace_longest(&mut word, r1, OTHER_CASES);
+    remove_after_double(&mut word, r1, &["áá", "éé"]);
+    replace_longest(&mut word, r1, OWNED);
+    replace_longest(&mut word, r1, SINGULAR_OWNER);
+    replace_longest(&mut word, r1, PLURAL_OWNER);
+    replace_longest(&mut word, r1, PLURAL);
+
+    word.into_iter().collect()
+}
+
+/// The start of R1. If the word starts with a vowel, R1 is the region after
+/// the first consonant or digraph, otherwise it is the region after the
+/// first vowel.
+fn region_1(word: &[char]) -> usize {
+    if matches!(word.first(), Some(&c) if is_vowel(c)) {
+        if let Some(consonant) = word.iter().position(|&c| !is_vowel(c)) {
+            let digraph = longest_prefix(&word[consonant..], DIGRAPHS).map_or(1, |digraph| digraph.chars().count());
+            return consonant + digraph;
+        }
+    }
+
+    after_first_vowel(word, is_vowel)
+}
+
+/// Replace the longest of `suffixes` the word ends with by its replacement,
+/// if it is in R1.
+fn replace_longest(word: &mut Vec<char>, r1: usize, suffixes: &[(&str, &str)]) {
+    let suffix = suffixes.iter()
+        .filter(|&&(suffix, _)| ends_with(word, suffix))
+        .max_by_key(|&&(suffix, _)| suffix.chars().count());
+
+    if let Some(&(suffix, replacement)) = suffix {
+        if suffix_start(word, suffix) >= r1 {
+            replace_suffix(word, suffix, replacement);
+        }
+    }
+}
+
+/// Remove one of `suffixes` in R1 if it follows a double consonant, then
+/// undouble the consonant, as in `kézzel` to `kéz`.
+fn remove_after_double(word: &mut Vec<char>, r1: usize, suffixes: &[&str]) {
+    let suffix = match longest_suffix(word, 0, suffixes) {
+        Some(suffix) => suffix,
+        None => return,
+    };
+    let start = suffix_start(word, suffix);
+
+    if start >= r1 && longest_suffix(&word[..start], 0, DOUBLE_CONSONANTS).is_some() {
+        word.truncate(start);
+        word.remove(start - 2);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(word: &str) -> Vec<char> {
+        word.chars().collect()
+    }
+
+    #[test]
+    fn test_region_1() {
+        assert_eq!(2, region_1(&chars("ablak")));
+        assert_eq!(3, region_1(&chars("acsa")));
+        assert_eq!(3, region_1(&chars("szép")));
+        assert_eq!(1, region_1(&chars("ó")));
+    }
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("házban", "ház"),
+            ("házakat", "ház"),
+            ("almával", "alm"),
+            ("ablakban", "abl"),
+            ("kézzel", "kéz"),
+            ("emberek", "ember"),
+            ("szépen", "szép"),
+            ("lányok", "lány"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
@@ -0,0 +1,205 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Indonesian stemmer, which also suits Malay.
+//!
+//! This is the algorithm of Tala, which removes prefixes as well as
+//! suffixes. Particles and possessive pronouns are removed from the end of
+//! the word, then a first order prefix such as `meng` or `di`, a derivational
+//! suffix and a second order prefix such as `ber`. Which suffixes may go
+//! depends on the prefix removed. Every step needs the word to have more
+//! than two vowels left, so short roots are never cut down.
+//!
+//! See <https://snowballstem.org/algorithms/indonesian/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::indonesian;
+//!
+//! assert_eq!("baca", indonesian::stem("membaca"));
+//! assert_eq!("kerja", indonesian::stem("pekerjaan"));
+//! ```
+
+use snowball::{ends_with, longest_prefix, longest_suffix, replace_prefix, suffix_start};
+
+const PARTICLES: &[&str] = &["kah", "lah", "pun"];
+
+const POSSESSIVES: &[&str] = &["ku", "mu", "nya"];
+
+const FIRST_ORDER: &[&str] = &["di", "meng", "men", "me", "ter", "ke", "peng", "pen", "meny", "peny", "mem", "pem"];
+
+const SECOND_ORDER: &[&str] = &["pe", "pelajar", "ber", "belajar", "be"];
+
+const SUFFIXES: &[&str] = &["kan", "an", "i"];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+/// The prefix that was removed, which decides the suffixes that may follow.
+#[derive(Clone, Copy, PartialEq)]
+enum Prefix {
+    None,
+    /// `di`, `meng`, `me`, `ter` and their variants.
+    Di,
+    /// `pe` and `ber`.
+    Ber,
+    /// `ke`, `peng` and their variants.
+    Ke,
+    /// `be` before a consonant and `er`, or `belajar`.
+    Be,
+}
+
+struct Word {
+    chars: Vec<char>,
+    /// The number of vowels left.
+    measure: usize,
+    prefix: Prefix,
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let chars = word.chars().collect::<Vec<char>>();
+    let measure = chars.iter().filter(|&&c| is_vowel(c)).count();
+    let mut word = Word { chars, measure, prefix: Prefix::None };
+
+    if word.measure <= 2 {
+        return word.chars.into_iter().collect();
+    }
+
+    word.remove_suffix(PARTICLES);
+    if word.measure > 2 {
+        word.remove_suffix(POSSESSIVES);
+    }
+
+    if word.measure > 2 {
+        if word.first_order_prefix() {
+            if word.measure > 2 {
+                word.derivational_suffix();
+            }
+            if word.measure > 2 {
+                word.second_order_prefix();
+            }
+        } else {
+            word.second_order_prefix();
+            if word.measure > 2 {
+                word.derivational_suffix();
+            }
+        }
+    }
+
+    word.chars.into_iter().collect()
+}
+
+impl Word {
+    fn remove_suffix(&mut self, suffixes: &[&str]) {
+        if let Some(suffix) = longest_suffix(&self.chars, 0, suffixes) {
+            self.chars.truncate(suffix_start(&self.chars, suffix));
+            self.measure -= 1;
+        }
+    }
+
+    fn followed_by_vowel(&self, prefix: &str) -> bool {
+        matches!(self.chars.get(prefix.chars().count()), Some(&c) if is_vowel(c))
+    }
+
+    /// Remove a first order prefix. Returns whether one was removed.
+    fn first_order_prefix(&mut self) -> bool {
+        let prefix = match longest_prefix(&self.chars, FIRST_ORDER) {
+            Some(prefix) => prefix,
+            None => return false,
+        };
+
+        let replacement = match prefix {
+            "meny" | "peny" if self.followed_by_vowel(prefix) => "s",
+            "mem" | "pem" if self.followed_by_vowel(prefix) => "p",
+            _ => "",
+        };
+
+        replace_prefix(&mut self.chars, prefix, replacement);
+        self.measure -= 1;
+        self.prefix = match prefix {
+            "ke" | "peng" | "pen" | "peny" | "pem" => Prefix::Ke,
+            _ => Prefix::Di,
+        };
+
+        true
+    }
+
+    /// Remove a second order prefix.
+    fn second_order_prefix(&mut self) {
+        let prefix = match longest_prefix(&self.chars, SECOND_ORDER) {
+            Some(prefix) => prefix,
+            None => return,
+        };
+
+        match prefix {
+            "pelajar" | "belajar" => replace_prefix(&mut self.chars, prefix, "ajar"),
+            "be" => {
+                let before_er = matches!(self.chars.get(2), Some(&c) if !is_vowel(c))
+                    && self.chars.get(3..5) == Some(&['e', 'r'][..]);
+                if !before_er {
+                    return;
+                }
+                replace_prefix(&mut self.chars, prefix, "");
+            }
+            _ => replace_prefix(&mut self.chars, prefix, ""),
+        }
+
+        self.measure -= 1;
+        self.prefix = match prefix {
+            "be" | "belajar" => Prefix::Be,
+            "pelajar" => self.prefix,
+            _ => Prefix::Ber,
+        };
+    }
+
+    /// Remove `kan`, `an` or `i`, if the prefix allows it.
+    fn derivational_suffix(&mut self) {
+        let suffix = SUFFIXES.iter()
+            .filter(|suffix| ends_with(&self.chars, suffix) && self.allows(suffix))
+            .max_by_key(|suffix| suffix.chars().count());
+
+        if let Some(suffix) = suffix {
+            self.chars.truncate(suffix_start(&self.chars, suffix));
+            self.measure -= 1;
+        }
+    }
+
+    fn allows(&self, suffix: &str) -> bool {
+        match suffix {
+            "kan" => !matches!(self.prefix, Prefix::Ber | Prefix::Ke),
+            "an" => self.prefix != Prefix::Di,
+            _ => matches!(self.prefix, Prefix::None | Prefix::Di | Prefix::Ber) && !ends_with(&self.chars, "si"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("bukunya", "buku"),
+            ("sayalah", "saya"),
+            ("membaca", "baca"),
+            ("memukul", "pukul"),
+            ("menyapu", "sapu"),
+            ("dimakan", "makan"),
+            ("pekerjaan", "kerja"),
+            ("bermain", "main"),
+            ("mendengarkan", "dengar"),
+            ("bekerja", "kerja"),
+            ("belajar", "ajar"),
+            ("pelajaran", "ajar"),
+            ("mengambil", "ambil"),
+            ("buku", "buku"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
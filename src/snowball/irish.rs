@@ -0,0 +1,137 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Irish stemmer.
+//!
+//! Irish changes the start of a word as well as its end. Lenition inserts an
+//! `h` after the first consonant, as in `bhád` for `bád`, and eclipsis puts a
+//! consonant in front of it, as in `mbád`. A prefixed `h-`, `n-` or `t-` and
+//! an elided `d'`, `b'` or `m'` are removed too. These initial mutations
+//! are undone first, then noun, derivational and verb suffixes are removed.
+//!
+//! See <https://snowballstem.org/algorithms/irish/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::irish;
+//!
+//! assert_eq!("fear", irish::stem("bhfear"));
+//! assert_eq!("glan", irish::stem("glanfaidh"));
+//! ```
+
+use snowball::{after_first_vowel, after_vowel_consonant, remove_longest_in, replace_prefix, starts_with};
+
+/// Initial mutations, and what each is undone to.
+const MUTATIONS: &[(&str, &str)] = &[
+    ("h-", ""), ("n-", ""), ("t-", ""), ("d'", ""), ("d'fh", "f"), ("b'", ""), ("m'", ""),
+    ("sh", "s"), ("mb", "b"), ("gc", "c"), ("nd", "d"), ("bhf", "f"), ("ng", "g"), ("bp", "p"),
+    ("ts", "s"), ("dt", "t"), ("bh", "b"), ("ch", "c"), ("dh", "d"), ("fh", "f"), ("gh", "g"),
+    ("mh", "m"), ("ph", "p"), ("th", "t"),
+];
+
+/// Noun suffixes removed in R1.
+const NOUN_R1: &[&str] = &[
+    "amh", "eamh", "abh", "eabh", "aibh", "ibh", "aimh", "imh", "aíocht", "íocht", "aíochta",
+    "íochta",
+];
+
+/// Noun suffixes removed in R2.
+const NOUN_R2: &[&str] = &[
+    "ire", "irí", "aire", "airí", "ireacht", "ireachta", "íochtaí", "eachtaí", "achtaí",
+];
+
+/// Derivational suffixes removed in R1.
+const DERIVATIONAL: &[&str] = &["acht", "eacht", "ach", "each", "achta", "eachta", "úil", "iúil"];
+
+/// Verb endings removed in RV.
+const VERB_RV: &[&str] = &["imid", "aimid", "ímid", "aímid", "faidh", "fidh"];
+
+/// Verb endings removed in R1.
+const VERB_R1: &[&str] = &["ain", "eadh", "adh", "áil", "tear", "tar"];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'á' | 'é' | 'í' | 'ó' | 'ú')
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = word.chars()
+        .map(|c| match c {
+            '’' => '\'',
+            c => c,
+        })
+        .collect::<Vec<char>>();
+
+    initial_mutation(&mut word);
+
+    let rv = after_first_vowel(&word, is_vowel);
+    let r1 = after_vowel_consonant(&word, 0, is_vowel);
+    let r2 = after_vowel_consonant(&word, r1, is_vowel);
+
+    if !remove_longest_in(&mut word, r2, NOUN_R2) {
+        remove_longest_in(&mut word, r1, NOUN_R1);
+    }
+    remove_longest_in(&mut word, r1, DERIVATIONAL);
+    if !remove_longest_in(&mut word, rv, VERB_RV) {
+        remove_longest_in(&mut word, r1, VERB_R1);
+    }
+
+    word.into_iter().collect()
+}
+
+/// Undo lenition, eclipsis, an elided `d'` and a prefixed `h-`, `n-` or `t-`.
+fn initial_mutation(word: &mut Vec<char>) {
+    let mutation = MUTATIONS.iter()
+        .filter(|&&(prefix, _)| starts_with(word, prefix))
+        .max_by_key(|&&(prefix, _)| prefix.chars().count());
+
+    if let Some(&(prefix, replacement)) = mutation {
+        replace_prefix(word, prefix, replacement);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(word: &str) -> Vec<char> {
+        word.chars().collect()
+    }
+
+    #[test]
+    fn test_initial_mutation() {
+        let pairs = [
+            ("bhfear", "fear"),
+            ("mbád", "bád"),
+            ("bhád", "bád"),
+            ("tsráid", "sráid"),
+            ("d'fhág", "fág"),
+            ("t-athair", "athair"),
+            ("n-éan", "éan"),
+            ("gcat", "cat"),
+            ("cat", "cat"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            let mut word = chars(word);
+            initial_mutation(&mut word);
+            assert_eq!(chars(expected), word);
+        }
+    }
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("cuirimid", "cuir"),
+            ("glanfaidh", "glan"),
+            ("glanadh", "glan"),
+            ("ionadaíocht", "ionad"),
+            ("bhfear", "fear"),
+            ("d’fhág", "fág"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Italian stemmer.
+//!
+//! See <http://snowball.tartarus.org/algorithms/italian/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::italian;
+//!
+//! assert_eq!("abbandon", italian::stem("abbandonata"));
+//! assert_eq!("abit", italian::stem("abitazione"));
+//! ```
+
+use snowball::{after_vowel_consonant, ends_with, longest_suffix, remove_longest_in, replace_suffix, romance_rv, suffix_start};
+
+const PRONOUNS: &[&str] = &[
+    "ci", "gli", "la", "le", "li", "lo", "mi", "ne", "si", "ti", "vi", "sene", "gliela", "gliele",
+    "glieli", "glielo", "gliene", "mela", "mele", "meli", "melo", "mene", "tela", "tele", "teli",
+    "telo", "tene", "cela", "cele", "celi", "celo", "cene", "vela", "vele", "veli", "velo", "vene",
+];
+
+const STEP_1: &[&str] = &[
+    "anza", "anze", "ico", "ici", "ica", "ice", "iche", "ichi", "ismo", "ismi", "abile", "abili",
+    "ibile", "ibili", "ista", "iste", "isti", "istà", "istè", "istì", "oso", "osi", "osa", "ose",
+    "mente", "atrice", "atrici", "ante", "anti",
+    "azione", "azioni", "atore", "atori",
+    "logia", "logie",
+    "uzione", "uzioni", "usione", "usioni",
+    "enza", "enze",
+    "amento", "amenti", "imento", "imenti",
+    "amente",
+    "ità",
+    "ivo", "ivi", "iva", "ive",
+];
+
+const STEP_2: &[&str] = &[
+    "ammo", "ando", "ano", "are", "arono", "asse", "assero", "assi", "assimo", "ata", "ate",
+    "ati", "ato", "ava", "avamo", "avano", "avate", "avi", "avo", "emmo", "enda", "ende", "endi",
+    "endo", "erà", "erai", "eranno", "ere", "erebbe", "erebbero", "erei", "eremmo", "eremo",
+    "ereste", "eresti", "erete", "erò", "erono", "essero", "ete", "eva", "evamo", "evano", "evate",
+    "evi", "evo", "iamo", "immo", "irà", "irai", "iranno", "ire", "irebbe", "irebbero", "irei",
+    "iremmo", "iremo", "ireste", "iresti", "irete", "irò", "irono", "isca", "iscano", "isce",
+    "isci", "isco", "iscono", "issero", "ita", "ite", "iti", "ito", "iva", "ivamo", "ivano",
+    "ivate", "ivi", "ivo", "ar", "ir",
+];
+
+const VOWEL_ENDINGS: &[&str] = &["a", "e", "i", "o", "à", "è", "ì", "ò"];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'à' | 'è' | 'ì' | 'ò' | 'ù')
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = prelude(word);
+
+    let rv = romance_rv(&word, is_vowel);
+    let r1 = after_vowel_consonant(&word, 0, is_vowel);
+    let r2 = after_vowel_consonant(&word, r1, is_vowel);
+
+    step_0(&mut word, rv);
+
+    if !step_1(&mut word, rv, r1, r2) {
+        if let Some(suffix) = longest_suffix(&word, rv, STEP_2) {
+            replace_suffix(&mut word, suffix, "");
+        }
+    }
+
+    step_3(&mut word, rv);
+
+    word.into_iter()
+        .map(|c| match c {
+            'I' => 'i',
+            'U' => 'u',
+            c => c,
+        })
+        .collect()
+}
+
+/// Replace acute accents by grave ones, and mark `u` after `q`, and `u` and
+/// `i` between vowels, as consonants by putting them into upper case.
+fn prelude(word: &str) -> Vec<char> {
+    let mut word = word.chars()
+        .map(|c| match c {
+            'á' => 'à',
+            'é' => 'è',
+            'í' => 'ì',
+            'ó' => 'ò',
+            'ú' => 'ù',
+            c => c,
+        })
+        .collect::<Vec<char>>();
+
+    for index in 1..word.len() {
+        let between = is_vowel(word[index - 1]) && index + 1 < word.len() && is_vowel(word[index + 1]);
+
+        word[index] = match word[index] {
+            'u' if word[index - 1] == 'q' => 'U',
+            'u' if between => 'U',
+            'i' if between => 'I',
+            c => c,
+        };
+    }
+
+    word
+}
+
+/// Remove a pronoun attached to the end of a gerund or infinitive, as in
+/// `mangiarlo`.
+fn step_0(word: &mut Vec<char>, rv: usize) {
+    let pronoun = match longest_suffix(word, 0, PRONOUNS) {
+        Some(pronoun) => pronoun,
+        None => return,
+    };
+    let start = suffix_start(word, pronoun);
+
+    match longest_suffix(&word[..start], rv, &["ando", "endo", "ar", "er", "ir"]) {
+        Some("ando") | Some("endo") => word.truncate(start),
+        Some(_) => replace_suffix(word, pronoun, "e"),
+        None => {}
+    }
+}
+
+/// Remove a standard suffix. Returns whether one was removed.
+fn step_1(word: &mut Vec<char>, rv: usize, r1: usize, r2: usize) -> bool {
+    let suffix = match longest_suffix(word, 0, STEP_1) {
+        Some(suffix) => suffix,
+        None => return false,
+    };
+    let start = suffix_start(word, suffix);
+
+    match suffix {
+        "amento" | "amenti" | "imento" | "imenti" => {
+            if start < rv {
+                return false;
+            }
+            word.truncate(start);
+        }
+        "amente" => {
+            if start < r1 {
+                return false;
+            }
+            word.truncate(start);
+            if ends_with(word, "iv") {
+                if start >= r2 + 2 {
+                    word.truncate(start - 2);
+                    remove_longest_in(word, r2, &["at"]);
+                }
+            } else {
+                remove_longest_in(word, r2, &["os", "ic", "abil"]);
+            }
+        }
+        _ if start < r2 => return false,
+        "logia" | "logie" => replace_suffix(word, suffix, "log"),
+        "uzione" | "uzioni" | "usione" | "usioni" => replace_suffix(word, suffix, "u"),
+        "enza" | "enze" => replace_suffix(word, suffix, "ente"),
+        "azione" | "azioni" | "atore" | "atori" => {
+            word.truncate(start);
+            remove_longest_in(word, r2, &["ic"]);
+        }
+        "ità" => {
+            word.truncate(start);
+            remove_longest_in(word, r2, &["abil", "ic", "iv"]);
+        }
+        "ivo" | "ivi" | "iva" | "ive" => {
+            word.truncate(start);
+            if ends_with(word, "at") && start >= r2 + 2 {
+                word.truncate(start - 2);
+                remove_longest_in(word, r2, &["ic"]);
+            }
+        }
+        _ => word.truncate(start),
+    }
+
+    true
+}
+
+/// Remove a final vowel, and an `i` before it, in RV, then turn a final `ch`
+/// or `gh` in RV into `c` or `g`.
+fn step_3(word: &mut Vec<char>, rv: usize) {
+    if longest_suffix(word, rv, VOWEL_ENDINGS).is_some() {
+        word.pop();
+
+        if ends_with(word, "i") && word.len() > rv {
+            word.pop();
+        }
+    }
+
+    if (ends_with(word, "ch") || ends_with(word, "gh")) && word.len() >= rv + 2 {
+        word.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prelude() {
+        assert_eq!("qUesto".chars().collect::<Vec<char>>(), prelude("questo"));
+        assert_eq!("aIuto".chars().collect::<Vec<char>>(), prelude("aiuto"));
+        assert_eq!("perchè".chars().collect::<Vec<char>>(), prelude("perché"));
+    }
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("abbandonata", "abbandon"),
+            ("abbandonato", "abbandon"),
+            ("abitazione", "abit"),
+            ("amiche", "amic"),
+            ("parlando", "parl"),
+            ("mangiarlo", "mang"),
+            ("felicità", "felic"),
+            ("gatti", "gatt"),
+            ("lentamente", "lent"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
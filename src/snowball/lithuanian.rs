@@ -0,0 +1,144 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Lithuanian stemmer.
+//!
+//! A few endings that would be mistaken for shorter ones are first written
+//! out in full, then the longest inflectional ending in R1 is removed. Stems
+//! that end in `č` or `dž`, which are written for `t` and `d` before a soft
+//! ending, get the plain consonant back, so `svečias` and `svetys` stem
+//! alike. Derivational suffixes in R1 are then removed for as long as there
+//! are any, the consonants are fixed again, and a final `gd` becomes `g`.
+//!
+//! See <https://snowballstem.org/algorithms/lithuanian/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::lithuanian;
+//!
+//! assert_eq!("nam", lithuanian::stem("namuose"));
+//! assert_eq!("mok", lithuanian::stem("mokytojams"));
+//! ```
+
+use snowball::{after_vowel_consonant, ends_with, longest_suffix, replace_suffix, suffix_start};
+
+/// Endings written out in full before the others are removed, with what
+/// they are written as.
+const CONFLICTS: &[(&str, &str)] = &[
+    ("ojime", "ojimas"), ("ėjime", "ėjimas"), ("avime", "avimas"),
+    ("okate", "okatė"), ("okatės", "okatė"), ("aite", "aitė"), ("aitės", "aitė"),
+    ("uote", "uotė"), ("uotės", "uotė"), ("asius", "asys"), ("esiu", "esys"),
+];
+
+const INFLECTIONAL: &[&str] = &[
+    "a", "ia", "eria", "osna", "iosna", "uosna", "iuosna", "ysna", "ėsna", "e", "ie", "enie",
+    "erie", "oje", "ioje", "uje", "iuje", "yje", "enyje", "eryje", "ėje", "ame", "iame", "sime",
+    "ome", "ėme", "tumėme", "ose", "iose", "uose", "iuose", "yse", "enyse", "eryse", "ėse", "ate",
+    "iate", "ite", "kite", "site", "ote", "tute", "ėte", "tumėte", "i", "ai", "iai", "eriai", "ei",
+    "tumei", "ki", "imi", "erimi", "umi", "iumi", "si", "asi", "iasi", "esi", "iesi", "siesi",
+    "isi", "aisi", "eisi", "tumeisi", "uisi", "osi", "ėjosi", "uosi", "iuosi", "siuosi", "usi",
+    "ausi", "čiausi", "ąsi", "ėsi", "ųsi", "tųsi", "ti", "enti", "inti", "oti", "ioti", "uoti",
+    "iuoti", "auti", "iauti", "yti", "ėti", "telėti", "inėti", "terėti", "ui", "iui", "eniui",
+    "oj", "ėj", "k", "am", "iam", "iem", "im", "sim", "om", "tum", "ėm", "tumėm", "an", "on",
+    "ion", "un", "iun", "ėn", "o", "io", "enio", "ėjo", "uo", "s", "as", "ias", "es", "ies", "is",
+    "ais", "iais", "tumeis", "imis", "enimis", "omis", "iomis", "umis", "ėmis", "enis", "asis",
+    "ysis", "ams", "iams", "iems", "ims", "enims", "erims", "oms", "ioms", "ums", "ėms", "ens",
+    "os", "ios", "uos", "iuos", "ers", "us", "aus", "iaus", "ius", "ys", "enys", "erys", "ąs",
+    "iąs", "ės", "amės", "iamės", "imės", "kimės", "simės", "omės", "ėmės", "tumėmės", "atės",
+    "iatės", "sitės", "otės", "ėtės", "tumėtės", "ūs", "įs", "tųs", "at", "iat", "it", "sit", "ot",
+    "ėt", "tumėt", "u", "au", "iau", "čiau", "iu", "eniu", "siu", "y", "ą", "ią", "ė", "ę", "į",
+    "enį", "erį", "ų", "ių", "erų",
+];
+
+const DERIVATIONAL: &[&str] = &[
+    "ing", "aj", "iaj", "iej", "oj", "ioj", "uoj", "iuoj", "auj", "ąj", "iąj", "ėj", "ųj", "iųj",
+    "ok", "iok", "iuk", "uliuk", "učiuk", "išk", "iul", "yl", "ėl", "am", "dam", "jam", "zgan",
+    "ain", "esn", "op", "iop", "ias", "ies", "ais", "iais", "os", "ios", "uos", "iuos", "aus",
+    "iaus", "ąs", "iąs", "ęs", "utėait", "ant", "iant", "siant", "int", "ot", "uot", "iuot", "yt",
+    "ėt", "ykšt", "iau", "dav", "sv", "šv", "ykšč", "ę", "ėję",
+];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'y' | 'o' | 'u' | 'ą' | 'ę' | 'į' | 'ų' | 'ė' | 'ū')
+}
+
+/// Remove the longest of `suffixes` that starts in `region`. Returns whether
+/// one was removed.
+fn remove_longest(word: &mut Vec<char>, region: usize, suffixes: &[&str]) -> bool {
+    if let Some(suffix) = longest_suffix(word, region, suffixes) {
+        let start = suffix_start(word, suffix);
+        word.truncate(start);
+        return true;
+    }
+
+    false
+}
+
+fn fix_conflicts(word: &mut Vec<char>) {
+    let endings = CONFLICTS.iter().map(|&(ending, _)| ending).collect::<Vec<_>>();
+
+    if let Some(ending) = longest_suffix(word, 0, &endings) {
+        let &(_, replacement) = CONFLICTS.iter().find(|&&(other, _)| other == ending).unwrap();
+        replace_suffix(word, ending, replacement);
+    }
+}
+
+fn fix_consonants(word: &mut Vec<char>) {
+    if ends_with(word, "č") {
+        replace_suffix(word, "č", "t");
+    } else if ends_with(word, "dž") {
+        replace_suffix(word, "dž", "d");
+    }
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = word.chars().collect::<Vec<char>>();
+
+    // A long word starting with `a` usually has it as a prefix, so the
+    // search for R1 skips it.
+    let start = if word.len() > 6 && word[0] == 'a' { 1 } else { 0 };
+    let r1 = after_vowel_consonant(&word, start, is_vowel);
+
+    fix_conflicts(&mut word);
+    remove_longest(&mut word, r1, INFLECTIONAL);
+    fix_consonants(&mut word);
+    while remove_longest(&mut word, r1, DERIVATIONAL) {}
+    fix_consonants(&mut word);
+
+    if ends_with(&word, "gd") {
+        replace_suffix(&mut word, "gd", "g");
+    }
+
+    word.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("namas", "nam"),
+            ("namai", "nam"),
+            ("namuose", "nam"),
+            ("namų", "nam"),
+            ("knygos", "knyg"),
+            ("gražus", "graž"),
+            ("dirbti", "dirb"),
+            ("dirbau", "dirb"),
+            ("mergaitė", "mergait"),
+            ("svečias", "svet"),
+            ("medžiai", "med"),
+            ("mokytojas", "mok"),
+            ("mokytojams", "mok"),
+            ("lietuviškai", "lietuv"),
+            ("gražiausias", "graž"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
@@ -0,0 +1,229 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Snowball stemmers for languages other than English.
+//!
+//! Each language's stemmer follows its Snowball definition, working on
+//! lowercase words `char` by `char`, except for Arabic, which has a light
+//! stemmer instead. Most callers will want to select one through `Language`
+//! rather than use these modules directly.
+//!
+//! See <http://snowball.tartarus.org/>.
+
+pub mod arabic;
+pub mod armenian;
+pub mod basque;
+pub mod catalan;
+pub mod danish;
+pub mod dutch;
+pub mod finnish;
+pub mod french;
+pub mod german;
+pub mod greek;
+pub mod hindi;
+pub mod hungarian;
+pub mod indonesian;
+pub mod irish;
+pub mod italian;
+pub mod lithuanian;
+pub mod nepali;
+pub mod norwegian;
+pub mod portuguese;
+pub mod romanian;
+pub mod russian;
+pub mod script;
+pub mod serbian;
+pub mod spanish;
+pub mod swedish;
+pub mod tamil;
+pub mod turkish;
+pub mod yiddish;
+
+/// Whether `word` ends with `suffix`, comparing `char`s.
+pub(crate) fn ends_with(word: &[char], suffix: &str) -> bool {
+    let length = suffix.chars().count();
+
+    word.len() >= length && word[word.len() - length..].iter().cloned().eq(suffix.chars())
+}
+
+/// Where `suffix` would start at the end of `word`, in `char`s.
+pub(crate) fn suffix_start(word: &[char], suffix: &str) -> usize {
+    word.len() - suffix.chars().count()
+}
+
+/// The longest of `suffixes` that `word` ends with, starting no earlier than
+/// `region`.
+pub(crate) fn longest_suffix<'a>(word: &[char], region: usize, suffixes: &[&'a str]) -> Option<&'a str> {
+    suffixes.iter()
+        .filter(|suffix| ends_with(word, suffix) && suffix_start(word, suffix) >= region)
+        .max_by_key(|suffix| suffix.chars().count())
+        .cloned()
+}
+
+/// Remove the longest of `suffixes` that `word` ends with, but only if it
+/// starts no earlier than `region`. Returns whether it was removed.
+pub(crate) fn remove_longest_in(word: &mut Vec<char>, region: usize, suffixes: &[&str]) -> bool {
+    if let Some(suffix) = longest_suffix(word, 0, suffixes) {
+        let start = suffix_start(word, suffix);
+        if start >= region {
+            word.truncate(start);
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Whether `word` starts with `prefix`, comparing `char`s.
+pub(crate) fn starts_with(word: &[char], prefix: &str) -> bool {
+    let length = prefix.chars().count();
+
+    word.len() >= length && word[..length].iter().cloned().eq(prefix.chars())
+}
+
+/// The longest of `prefixes` that `word` starts with.
+pub(crate) fn longest_prefix<'a>(word: &[char], prefixes: &[&'a str]) -> Option<&'a str> {
+    prefixes.iter()
+        .filter(|prefix| starts_with(word, prefix))
+        .max_by_key(|prefix| prefix.chars().count())
+        .cloned()
+}
+
+pub(crate) fn replace_prefix(word: &mut Vec<char>, prefix: &str, replacement: &str) {
+    word.splice(..prefix.chars().count(), replacement.chars());
+}
+
+pub(crate) fn replace_suffix(word: &mut Vec<char>, suffix: &str, replacement: &str) {
+    let start = suffix_start(word, suffix);
+    word.truncate(start);
+    word.extend(replacement.chars());
+}
+
+/// The position after the first non-vowel following a vowel at or after
+/// `start`, or the end of the word. This is how R1 and R2 are found.
+pub(crate) fn after_vowel_consonant(word: &[char], start: usize, is_vowel: fn(char) -> bool) -> usize {
+    let vowel = match (start..word.len()).find(|&index| is_vowel(word[index])) {
+        Some(vowel) => vowel,
+        None => return word.len(),
+    };
+
+    match (vowel..word.len()).find(|&index| !is_vowel(word[index])) {
+        Some(consonant) => consonant + 1,
+        None => word.len(),
+    }
+}
+
+/// The position after the first vowel, or the end of the word. This is RV
+/// for Russian, and R1 for Hungarian words that start with a consonant.
+pub(crate) fn after_first_vowel(word: &[char], is_vowel: fn(char) -> bool) -> usize {
+    word.iter().position(|&c| is_vowel(c)).map_or(word.len(), |vowel| vowel + 1)
+}
+
+/// The start of R1 as the Scandinavian stemmers define it, which always
+/// leaves at least three letters before it.
+pub(crate) fn scandinavian_r1(word: &[char], is_vowel: fn(char) -> bool) -> usize {
+    after_vowel_consonant(word, 0, is_vowel).max(3).min(word.len())
+}
+
+/// Remove the last letter of whichever of `endings` the word ends with in
+/// `region`, as the Scandinavian stemmers do to undouble consonant pairs.
+pub(crate) fn remove_last_in(word: &mut Vec<char>, region: usize, endings: &[&str]) {
+    if longest_suffix(word, region, endings).is_some() {
+        word.pop();
+    }
+}
+
+/// The start of RV as the Spanish, Portuguese and Italian stemmers define it.
+///
+/// If the second letter is a consonant, RV is the region after the next
+/// vowel. If the first two letters are vowels, it is the region after the
+/// next consonant. Otherwise RV is the region after the third letter.
+pub(crate) fn romance_rv(word: &[char], is_vowel: fn(char) -> bool) -> usize {
+    if word.len() < 2 {
+        return word.len();
+    }
+
+    let after = |vowel: bool| {
+        (2..word.len())
+            .find(|&index| is_vowel(word[index]) == vowel)
+            .map_or(word.len(), |index| index + 1)
+    };
+
+    match (is_vowel(word[0]), is_vowel(word[1])) {
+        (_, false) => after(true),
+        (true, true) => after(false),
+        (false, true) => 3.min(word.len()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(word: &str) -> Vec<char> {
+        word.chars().collect()
+    }
+
+    #[test]
+    fn test_longest_suffix() {
+        let word = chars("continuité");
+
+        assert!(ends_with(&word, "ité"));
+        assert_eq!(7, suffix_start(&word, "ité"));
+        assert_eq!(Some("ité"), longest_suffix(&word, 0, &["é", "ité", "er"]));
+        assert_eq!(Some("é"), longest_suffix(&word, 8, &["é", "ité", "er"]));
+        assert_eq!(None, longest_suffix(&word, 10, &["é", "ité", "er"]));
+    }
+
+    #[test]
+    fn test_longest_prefix() {
+        let mut word = chars("menyapu");
+
+        assert!(starts_with(&word, "me"));
+        assert!(!starts_with(&word, "menyapukan"));
+        assert_eq!(Some("meny"), longest_prefix(&word, &["me", "men", "meny", "mem"]));
+        assert_eq!(None, longest_prefix(&word, &["di", "ter"]));
+
+        replace_prefix(&mut word, "meny", "s");
+        assert_eq!(chars("sapu"), word);
+    }
+
+    #[test]
+    fn test_after_vowel_consonant() {
+        fn is_vowel(c: char) -> bool {
+            "aeiouyé".contains(c)
+        }
+
+        let word = chars("fameusement");
+        let r1 = after_vowel_consonant(&word, 0, is_vowel);
+
+        assert_eq!(3, r1);
+        assert_eq!(6, after_vowel_consonant(&word, r1, is_vowel));
+    }
+
+    #[test]
+    fn test_scandinavian_r1() {
+        fn is_vowel(c: char) -> bool {
+            "aeiouyæåø".contains(c)
+        }
+
+        assert_eq!(3, scandinavian_r1(&chars("øye"), is_vowel));
+        assert_eq!(3, scandinavian_r1(&chars("bilene"), is_vowel));
+        assert_eq!(4, scandinavian_r1(&chars("spiste"), is_vowel));
+        assert_eq!(2, scandinavian_r1(&chars("ål"), is_vowel));
+    }
+
+    #[test]
+    fn test_romance_rv() {
+        fn is_vowel(c: char) -> bool {
+            "aeiou".contains(c)
+        }
+
+        assert_eq!(3, romance_rv(&chars("macho"), is_vowel));
+        assert_eq!(3, romance_rv(&chars("oliva"), is_vowel));
+        assert_eq!(3, romance_rv(&chars("trabajo"), is_vowel));
+        assert_eq!(3, romance_rv(&chars("áureo"), |c| "aeiouá".contains(c)));
+        assert_eq!(2, romance_rv(&chars("ab"), is_vowel));
+    }
+}
@@ -0,0 +1,124 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Nepali stemmer, for words written in Devanagari.
+//!
+//! A case marker or postposition such as `मा` or `लाई` is removed first.
+//! Then verb endings and plural markers are removed for as long as there
+//! are any, each time dropping a nasal or `ै` that was only there to join
+//! them on. As in the Hindi stemmer, a suffix is only removed if it starts
+//! after the word's first character.
+//!
+//! See <https://snowballstem.org/algorithms/nepali/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::nepali;
+//!
+//! assert_eq!("घर", nepali::stem("घरहरूमा"));
+//! assert_eq!("राम", nepali::stem("रामले"));
+//! ```
+
+use snowball::{ends_with, longest_suffix, suffix_start};
+
+const CATEGORY_1: &[&str] = &[
+    "लाइ", "ले", "लाई", "सँगै", "मै", "सँग", "संग", "मार्फत", "रत", "मा", "द्वारा", "पछि",
+];
+
+/// The genitive markers, which are part of a verb ending after `ए` or `े`.
+const GENITIVE: &[&str] = &["की", "कै", "को", "का", "कि"];
+
+const CATEGORY_3: &[&str] = &[
+    "ेकी", "एकी", "इएकी", "िएकी", "देखी", "थी", "दी", "छु", "ेछु", "नेछु", "एछु", "नु", "हरु",
+    "हरू", "छे", "थे", "ने", "ेकै", "नेकै", "एकै", "दै", "इदै", "िदै", "ेको", "नेको", "एको",
+    "इएको", "िएको", "दो", "इदो", "िदो", "यो", "इयो", "थ्यो", "भयो", "ियो", "थियो", "दियो", "छौ",
+    "इछौ", "ेछौ", "नेछौ", "एछौ", "िछौ", "यौ", "छ्यौ", "थ्यौ", "थियौ", "छन्", "इछन्", "ेछन्",
+    "नेछन्", "एछन्", "िछन्", "लान्", "छिन्", "थिन्", "पर्", "इस्", "थिइस्", "छेस्", "होस्", "छस्",
+    "इछस्", "ेछस्", "नेछस्", "एछस्", "िछस्", "िस्", "थिस्", "थिए", "छ", "इछ", "ेछ", "नेछ", "हुनेछ",
+    "हुन्छ", "इन्छ", "िन्छ", "एछ", "िछ", "ेका", "नेका", "एका", "इएका", "िएका", "दा", "इदा", "िदा",
+    "देखि", "माथि",
+];
+
+/// Given a word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = word.chars().collect::<Vec<char>>();
+
+    category_1(&mut word);
+
+    loop {
+        category_2(&mut word);
+        if !remove_longest(&mut word, CATEGORY_3) {
+            break;
+        }
+    }
+
+    word.into_iter().collect()
+}
+
+/// Remove the longest of `suffixes` that starts after the first character.
+fn remove_longest(word: &mut Vec<char>, suffixes: &[&str]) -> bool {
+    match longest_suffix(word, 1, suffixes) {
+        Some(suffix) => {
+            let start = suffix_start(word, suffix);
+            word.truncate(start);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Remove a case marker or postposition, unless it's a genitive marker
+/// after `ए` or `े`.
+fn category_1(word: &mut Vec<char>) {
+    if let Some(genitive) = longest_suffix(word, 1, GENITIVE) {
+        let start = suffix_start(word, genitive);
+        if !ends_with(&word[..start], "ए") && !ends_with(&word[..start], "े") {
+            word.truncate(start);
+        }
+    } else {
+        remove_longest(word, CATEGORY_1);
+    }
+}
+
+/// Remove a nasal after `यौ`, `छौ`, `नौ` or `थे`, or `ै` after `त्र`.
+fn category_2(word: &mut Vec<char>) {
+    let before = match longest_suffix(word, 1, &["ँ", "ं", "ै"]) {
+        Some("ै") => &["त्र"][..],
+        Some(_) => &["यौ", "छौ", "नौ", "थे"][..],
+        None => return,
+    };
+
+    let start = word.len() - 1;
+    if longest_suffix(&word[..start], 0, before).is_some() {
+        word.truncate(start);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("घरहरूमा", "घर"),
+            ("रामले", "राम"),
+            ("किताबहरू", "किताब"),
+            ("खायो", "खा"),
+            ("बसेको", "बस"),
+            ("बसेकोमा", "बस"),
+            ("गर्छन्", "गर्"),
+            ("नेपालको", "नेपाल"),
+            ("गएले", "गए"),
+            ("गएको", "ग"),
+            ("उसको", "उस"),
+            ("घरमै", "घर"),
+            ("छ", "छ"),
+            ("को", "को"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
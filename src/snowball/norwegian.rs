@@ -0,0 +1,98 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Norwegian (Bokmål) stemmer.
+//!
+//! See <http://snowball.tartarus.org/algorithms/norwegian/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::norwegian;
+//!
+//! assert_eq!("bil", norwegian::stem("bilene"));
+//! assert_eq!("lever", norwegian::stem("leverte"));
+//! ```
+
+use snowball::{longest_suffix, remove_last_in, replace_suffix, scandinavian_r1, suffix_start};
+
+const STEP_1: &[&str] = &[
+    "a", "e", "ede", "ande", "ende", "ane", "ene", "hetene", "en", "heten", "ar", "er", "heter",
+    "as", "es", "edes", "endes", "enes", "hetenes", "ens", "hetens", "ers", "ets", "et", "het",
+    "ast", "s", "erte", "ert",
+];
+
+const STEP_2: &[&str] = &["dt", "vt"];
+
+const STEP_3: &[&str] = &[
+    "leg", "eleg", "ig", "eig", "lig", "elig", "els", "lov", "elov", "slov", "hetslov",
+];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y' | 'æ' | 'å' | 'ø')
+}
+
+/// Whether an `s` can be removed after `word`.
+fn is_s_ending(word: &[char]) -> bool {
+    match word.split_last() {
+        Some((&'k', rest)) => !matches!(rest.last(), Some(&c) if is_vowel(c)),
+        Some((&c, _)) => "bcdfghjlmnoprtvyz".contains(c),
+        None => false,
+    }
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = word.chars().collect::<Vec<char>>();
+    let r1 = scandinavian_r1(&word, is_vowel);
+
+    step_1(&mut word, r1);
+    remove_last_in(&mut word, r1, STEP_2);
+
+    if let Some(suffix) = longest_suffix(&word, r1, STEP_3) {
+        replace_suffix(&mut word, suffix, "");
+    }
+
+    word.into_iter().collect()
+}
+
+/// Remove an inflectional ending in R1.
+fn step_1(word: &mut Vec<char>, r1: usize) {
+    let suffix = match longest_suffix(word, r1, STEP_1) {
+        Some(suffix) => suffix,
+        None => return,
+    };
+    let start = suffix_start(word, suffix);
+
+    match suffix {
+        "s" => {
+            if is_s_ending(&word[..start]) {
+                word.truncate(start);
+            }
+        }
+        "erte" | "ert" => replace_suffix(word, suffix, "er"),
+        _ => word.truncate(start),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("bilene", "bil"),
+            ("gutten", "gutt"),
+            ("husets", "hus"),
+            ("spiste", "spist"),
+            ("leverte", "lever"),
+            ("dags", "dag"),
+            ("parks", "park"),
+            ("kjærlighet", "kjær"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Portuguese stemmer.
+//!
+//! Covers both Brazilian and European spelling. The nasal vowels `ã` and `õ`
+//! are written as `a~` and `o~` while stemming, so `~` counts as a consonant
+//! when the regions are found, as in the reference implementation.
+//!
+//! See <http://snowball.tartarus.org/algorithms/portuguese/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::portuguese;
+//!
+//! assert_eq!("inform", portuguese::stem("informações"));
+//! assert_eq!("naçõ", portuguese::stem("nações"));
+//! ```
+
+use snowball::{after_vowel_consonant, ends_with, longest_suffix, remove_longest_in, replace_suffix, romance_rv, suffix_start};
+
+const STEP_1: &[&str] = &[
+    "eza", "ezas", "ico", "ica", "icos", "icas", "ismo", "ismos", "ável", "ível", "ista", "istas",
+    "oso", "osa", "osos", "osas", "amento", "amentos", "imento", "imentos", "adora", "ador",
+    "aça~o", "adoras", "adores", "aço~es", "ante", "antes", "ância",
+    "logia", "logias",
+    "uça~o", "uço~es",
+    "ência", "ências",
+    "amente",
+    "mente",
+    "idade", "idades",
+    "iva", "ivo", "ivas", "ivos",
+    "ira", "iras",
+];
+
+const STEP_2: &[&str] = &[
+    "ada", "ida", "ia", "aria", "eria", "iria", "ará", "ara", "erá", "era", "irá", "ava", "asse",
+    "esse", "isse", "aste", "este", "iste", "ei", "arei", "erei", "irei", "am", "iam", "ariam",
+    "eriam", "iriam", "aram", "eram", "iram", "avam", "em", "arem", "erem", "irem", "assem",
+    "essem", "issem", "ado", "ido", "ando", "endo", "indo", "ara~o", "era~o", "ira~o", "ar", "er",
+    "ir", "as", "adas", "idas", "ias", "arias", "erias", "irias", "arás", "aras", "erás", "eras",
+    "irás", "avas", "es", "ardes", "erdes", "irdes", "ares", "eres", "ires", "asses", "esses",
+    "isses", "astes", "estes", "istes", "is", "ais", "eis", "íeis", "aríeis", "eríeis", "iríeis",
+    "áreis", "areis", "éreis", "ereis", "íreis", "ireis", "ásseis", "ésseis", "ísseis", "áveis",
+    "ados", "idos", "ámos", "amos", "íamos", "aríamos", "eríamos", "iríamos", "áramos", "éramos",
+    "íramos", "ávamos", "emos", "aremos", "eremos", "iremos", "ássemos", "êssemos", "íssemos",
+    "imos", "armos", "ermos", "irmos", "eu", "iu", "ou", "ira", "iras",
+];
+
+const STEP_4: &[&str] = &["os", "a", "i", "o", "á", "í", "ó"];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'á' | 'é' | 'í' | 'ó' | 'ú' | 'â' | 'ê' | 'ô')
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = word.replace('ã', "a~").replace('õ', "o~").chars().collect::<Vec<char>>();
+
+    let rv = romance_rv(&word, is_vowel);
+    let r1 = after_vowel_consonant(&word, 0, is_vowel);
+    let r2 = after_vowel_consonant(&word, r1, is_vowel);
+
+    let altered = step_1(&mut word, rv, r1, r2) || step_2(&mut word, rv);
+
+    if altered {
+        if ends_with(&word, "ci") && word.len() > rv {
+            word.pop();
+        }
+    } else if let Some(suffix) = longest_suffix(&word, rv, STEP_4) {
+        replace_suffix(&mut word, suffix, "");
+    }
+
+    step_5(&mut word, rv);
+
+    word.into_iter().collect::<String>().replace("a~", "ã").replace("o~", "õ")
+}
+
+/// Remove a standard suffix. Returns whether one was removed.
+fn step_1(word: &mut Vec<char>, rv: usize, r1: usize, r2: usize) -> bool {
+    let suffix = match longest_suffix(word, 0, STEP_1) {
+        Some(suffix) => suffix,
+        None => return false,
+    };
+    let start = suffix_start(word, suffix);
+
+    match suffix {
+        "amente" => {
+            if start < r1 {
+                return false;
+            }
+            word.truncate(start);
+            if ends_with(word, "iv") {
+                if start >= r2 + 2 {
+                    word.truncate(start - 2);
+                    remove_longest_in(word, r2, &["at"]);
+                }
+            } else {
+                remove_longest_in(word, r2, &["os", "ic", "ad"]);
+            }
+        }
+        "ira" | "iras" => {
+            if start < rv || !ends_with(&word[..start], "e") {
+                return false;
+            }
+            replace_suffix(word, suffix, "ir");
+        }
+        _ if start < r2 => return false,
+        "logia" | "logias" => replace_suffix(word, suffix, "log"),
+        "uça~o" | "uço~es" => replace_suffix(word, suffix, "u"),
+        "ência" | "ências" => replace_suffix(word, suffix, "ente"),
+        "mente" => {
+            word.truncate(start);
+            remove_longest_in(word, r2, &["ante", "avel", "ível"]);
+        }
+        "idade" | "idades" => {
+            word.truncate(start);
+            remove_longest_in(word, r2, &["abil", "ic", "iv"]);
+        }
+        "iva" | "ivo" | "ivas" | "ivos" => {
+            word.truncate(start);
+            remove_longest_in(word, r2, &["at"]);
+        }
+        _ => word.truncate(start),
+    }
+
+    true
+}
+
+/// Remove a verb suffix in RV. Returns whether one was removed.
+fn step_2(word: &mut Vec<char>, rv: usize) -> bool {
+    match longest_suffix(word, rv, STEP_2) {
+        Some(suffix) => {
+            replace_suffix(word, suffix, "");
+            true
+        }
+        None => false,
+    }
+}
+
+/// Remove a final `e` in RV, along with the `u` of `gu` or the `i` of `ci`
+/// before it if that is also in RV, or remove the cedilla from a final `ç`.
+fn step_5(word: &mut Vec<char>, rv: usize) {
+    if longest_suffix(word, rv, &["e", "é", "ê"]).is_some() {
+        word.pop();
+
+        if (ends_with(word, "gu") || ends_with(word, "ci")) && word.len() > rv {
+            word.pop();
+        }
+    } else if ends_with(word, "ç") {
+        replace_suffix(word, "ç", "c");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("informações", "inform"),
+            ("nações", "naçõ"),
+            ("pães", "pã"),
+            ("trabalhando", "trabalh"),
+            ("felizmente", "feliz"),
+            ("cantaríamos", "cant"),
+            ("quilométricas", "quilométr"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
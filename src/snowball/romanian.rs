@@ -0,0 +1,239 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Romanian stemmer.
+//!
+//! Romanian is written with `ș` and `ț` (comma below), but legacy encodings
+//! only had `ş` and `ţ` (cedilla), which are still common. Both are accepted,
+//! and stems are always written with the comma below.
+//!
+//! See <http://snowball.tartarus.org/algorithms/romanian/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::romanian;
+//!
+//! assert_eq!("organiz", romanian::stem("organizație"));
+//! assert_eq!("organiz", romanian::stem("organizaţie"));
+//! ```
+
+use snowball::{after_vowel_consonant, ends_with, longest_suffix, replace_suffix, romance_rv, suffix_start};
+
+const STEP_0: &[&str] = &[
+    "ul", "ului", "aua", "ea", "ele", "elor", "ii", "iua", "iei", "iile", "iilor", "ilor", "ile",
+    "atei", "ație", "ația",
+];
+
+const COMBINING: &[(&str, &str)] = &[
+    ("abilitate", "abil"), ("abilitati", "abil"), ("abilităi", "abil"), ("abilități", "abil"),
+    ("ibilitate", "ibil"),
+    ("ivitate", "iv"), ("ivitati", "iv"), ("ivităi", "iv"), ("ivități", "iv"),
+    ("icitate", "ic"), ("icitati", "ic"), ("icităi", "ic"), ("icități", "ic"), ("icator", "ic"),
+    ("icatori", "ic"), ("iciv", "ic"), ("iciva", "ic"), ("icive", "ic"), ("icivi", "ic"),
+    ("icivă", "ic"), ("ical", "ic"), ("icala", "ic"), ("icale", "ic"), ("icali", "ic"),
+    ("icală", "ic"),
+    ("ativ", "at"), ("ativa", "at"), ("ative", "at"), ("ativi", "at"), ("ativă", "at"),
+    ("ațiune", "at"), ("atoare", "at"), ("ator", "at"), ("atori", "at"), ("ătoare", "at"),
+    ("ător", "at"), ("ători", "at"),
+    ("itiv", "it"), ("itiva", "it"), ("itive", "it"), ("itivi", "it"), ("itivă", "it"),
+    ("ițiune", "it"), ("itoare", "it"), ("itor", "it"), ("itori", "it"),
+];
+
+const STANDARD: &[&str] = &[
+    "at", "ata", "ată", "ati", "ate", "ut", "uta", "ută", "uti", "ute", "it", "ita", "ită", "iti",
+    "ite", "ic", "ica", "ice", "ici", "ică", "abil", "abila", "abile", "abili", "abilă", "ibil",
+    "ibila", "ibile", "ibili", "ibilă", "oasa", "oasă", "oase", "os", "osi", "oși", "ant", "anta",
+    "ante", "anti", "antă", "ator", "atori", "itate", "itati", "ităi", "ități", "iv", "iva", "ive",
+    "ivi", "ivă", "iune", "iuni", "ism", "isme", "ist", "ista", "iste", "isti", "istă", "iști",
+];
+
+/// Verb suffixes removed only after a consonant or `u`.
+const VERB_1: &[&str] = &[
+    "are", "ere", "ire", "âre", "ind", "ând", "indu", "ându", "eze", "ească", "ez", "ezi", "ează",
+    "esc", "ești", "ește", "ăsc", "ăști", "ăște", "am", "ai", "au", "eam", "eai", "ea", "eați",
+    "eau", "iam", "iai", "ia", "iați", "iau", "ui", "ași", "arăm", "arăți", "ară", "uși", "urăm",
+    "urăți", "ură", "iși", "irăm", "irăți", "iră", "âi", "âși", "ârăm", "ârăți", "âră", "asem",
+    "aseși", "ase", "aserăm", "aserăți", "aseră", "isem", "iseși", "ise", "iserăm", "iserăți",
+    "iseră", "âsem", "âseși", "âse", "âserăm", "âserăți", "âseră", "usem", "useși", "use",
+    "userăm", "userăți", "useră",
+];
+
+const VERB_2: &[&str] = &[
+    "ăm", "ați", "em", "eți", "im", "iți", "âm", "âți", "seși", "serăm", "serăți", "seră", "sei",
+    "se", "sesem", "seseși", "sese", "seserăm", "seserăți", "seseră",
+];
+
+const VOWEL_ENDINGS: &[&str] = &["a", "e", "i", "ie", "ă"];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'ă' | 'â' | 'e' | 'i' | 'î' | 'o' | 'u')
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = prelude(word);
+
+    let rv = romance_rv(&word, is_vowel);
+    let r1 = after_vowel_consonant(&word, 0, is_vowel);
+    let r2 = after_vowel_consonant(&word, r1, is_vowel);
+
+    step_0(&mut word, r1);
+
+    let mut removed = false;
+    while step_1(&mut word, r1) {
+        removed = true;
+    }
+
+    if !step_2(&mut word, r2) && !removed {
+        step_3(&mut word, rv);
+    }
+
+    if let Some(suffix) = longest_suffix(&word, 0, VOWEL_ENDINGS) {
+        if suffix_start(&word, suffix) >= rv {
+            replace_suffix(&mut word, suffix, "");
+        }
+    }
+
+    word.into_iter()
+        .map(|c| match c {
+            'I' => 'i',
+            'U' => 'u',
+            c => c,
+        })
+        .collect()
+}
+
+/// Write `ș` and `ț` with the comma below, and mark `i` and `u` between
+/// vowels as consonants by putting them into upper case.
+fn prelude(word: &str) -> Vec<char> {
+    let mut word = word.chars()
+        .map(|c| match c {
+            'ş' => 'ș',
+            'ţ' => 'ț',
+            c => c,
+        })
+        .collect::<Vec<char>>();
+
+    for index in 1..word.len().saturating_sub(1) {
+        if is_vowel(word[index - 1]) && is_vowel(word[index + 1]) {
+            word[index] = match word[index] {
+                'i' => 'I',
+                'u' => 'U',
+                c => c,
+            };
+        }
+    }
+
+    word
+}
+
+/// Simplify a plural or article ending in R1.
+fn step_0(word: &mut Vec<char>, r1: usize) {
+    let suffix = match longest_suffix(word, 0, STEP_0) {
+        Some(suffix) => suffix,
+        None => return,
+    };
+    let start = suffix_start(word, suffix);
+
+    if start < r1 {
+        return;
+    }
+
+    match suffix {
+        "ul" | "ului" => word.truncate(start),
+        "aua" => replace_suffix(word, suffix, "a"),
+        "ea" | "ele" | "elor" => replace_suffix(word, suffix, "e"),
+        "ile" => {
+            if !ends_with(&word[..start], "ab") {
+                replace_suffix(word, suffix, "i");
+            }
+        }
+        "atei" => replace_suffix(word, suffix, "at"),
+        "ație" | "ația" => replace_suffix(word, suffix, "ați"),
+        _ => replace_suffix(word, suffix, "i"),
+    }
+}
+
+/// Reduce a combining suffix in R1. Returns whether one was reduced.
+fn step_1(word: &mut Vec<char>, r1: usize) -> bool {
+    let combining = COMBINING.iter()
+        .filter(|&&(suffix, _)| ends_with(word, suffix))
+        .max_by_key(|&&(suffix, _)| suffix.chars().count());
+
+    match combining {
+        Some(&(suffix, replacement)) if suffix_start(word, suffix) >= r1 => {
+            replace_suffix(word, suffix, replacement);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Remove a standard suffix in R2. Returns whether one was removed.
+fn step_2(word: &mut Vec<char>, r2: usize) -> bool {
+    let suffix = match longest_suffix(word, 0, STANDARD) {
+        Some(suffix) if suffix_start(word, suffix) >= r2 => suffix,
+        _ => return false,
+    };
+    let start = suffix_start(word, suffix);
+
+    match suffix {
+        "iune" | "iuni" => {
+            if !ends_with(&word[..start], "ț") {
+                return false;
+            }
+            replace_suffix(word, suffix, "");
+            replace_suffix(word, "ț", "t");
+        }
+        "ism" | "isme" | "ist" | "ista" | "iste" | "isti" | "istă" | "iști" => replace_suffix(word, suffix, "ist"),
+        _ => word.truncate(start),
+    }
+
+    true
+}
+
+/// Remove a verb suffix in RV.
+fn step_3(word: &mut Vec<char>, rv: usize) {
+    let length = |suffix: Option<&str>| suffix.map_or(0, |suffix| suffix.chars().count());
+    let (first, second) = (longest_suffix(word, rv, VERB_1), longest_suffix(word, rv, VERB_2));
+
+    match (first, second) {
+        (Some(suffix), _) if length(first) > length(second) => {
+            let start = suffix_start(word, suffix);
+            if start > rv && (!is_vowel(word[start - 1]) || word[start - 1] == 'u') {
+                word.truncate(start);
+            }
+        }
+        (_, Some(suffix)) => replace_suffix(word, suffix, ""),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prelude() {
+        assert_eq!("băIat".chars().collect::<Vec<char>>(), prelude("băiat"));
+        assert_eq!("nației".chars().collect::<Vec<char>>(), prelude("naţiei"));
+    }
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("copiilor", "cop"),
+            ("frumoasele", "frumoas"),
+            ("nației", "naț"),
+            ("naţiei", "naț"),
+            ("cântare", "cânt"),
+            ("posibilitate", "posibil"),
+            ("organizație", "organiz"),
+            ("băiatul", "băiat"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
@@ -0,0 +1,161 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Russian stemmer.
+//!
+//! Works on Cyrillic text. `ё` is treated as `е`, as most Russian text is
+//! written without the diaeresis.
+//!
+//! See <http://snowball.tartarus.org/algorithms/russian/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::russian;
+//!
+//! assert_eq!("книг", russian::stem("книги"));
+//! assert_eq!("красив", russian::stem("красивейший"));
+//! ```
+
+use snowball::{after_first_vowel, after_vowel_consonant, ends_with, longest_suffix, suffix_start};
+
+const PERFECTIVE_GERUND_1: &[&str] = &["в", "вши", "вшись"];
+
+const PERFECTIVE_GERUND_2: &[&str] = &["ив", "ивши", "ившись", "ыв", "ывши", "ывшись"];
+
+const ADJECTIVE: &[&str] = &[
+    "ее", "ие", "ые", "ое", "ими", "ыми", "ей", "ий", "ый", "ой", "ем", "им", "ым", "ом", "его",
+    "ого", "ему", "ому", "их", "ых", "ую", "юю", "ая", "яя", "ою", "ею",
+];
+
+const PARTICIPLE_1: &[&str] = &["ем", "нн", "вш", "ющ", "щ"];
+
+const PARTICIPLE_2: &[&str] = &["ивш", "ывш", "ующ"];
+
+const REFLEXIVE: &[&str] = &["ся", "сь"];
+
+const VERB_1: &[&str] = &[
+    "ла", "на", "ете", "йте", "ли", "й", "л", "ем", "н", "ло", "но", "ет", "ют", "ны", "ть", "ешь",
+    "нно",
+];
+
+const VERB_2: &[&str] = &[
+    "ила", "ыла", "ена", "ейте", "уйте", "ите", "или", "ыли", "ей", "уй", "ил", "ыл", "им", "ым",
+    "ен", "ило", "ыло", "ено", "ят", "ует", "уют", "ит", "ыт", "ены", "ить", "ыть", "ишь", "ую",
+    "ю",
+];
+
+const NOUN: &[&str] = &[
+    "а", "ев", "ов", "ие", "ье", "е", "иями", "ями", "ами", "еи", "ии", "и", "ией", "ей", "ой",
+    "ий", "й", "иям", "ям", "ием", "ем", "ам", "ом", "о", "у", "ах", "иях", "ях", "ы", "ь", "ию",
+    "ью", "ю", "ия", "ья", "я",
+];
+
+const SUPERLATIVE: &[&str] = &["ейш", "ейше"];
+
+const DERIVATIONAL: &[&str] = &["ост", "ость"];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'а' | 'е' | 'и' | 'о' | 'у' | 'ы' | 'э' | 'ю' | 'я')
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = word.replace('ё', "е").chars().collect::<Vec<char>>();
+
+    let rv = after_first_vowel(&word, is_vowel);
+    let r1 = after_vowel_consonant(&word, 0, is_vowel);
+    let r2 = after_vowel_consonant(&word, r1, is_vowel);
+
+    step_1(&mut word, rv);
+
+    if ends_with(&word, "и") && word.len() > rv {
+        word.pop();
+    }
+
+    if let Some(suffix) = longest_suffix(&word, r2, DERIVATIONAL) {
+        word.truncate(suffix_start(&word, suffix));
+    }
+
+    step_4(&mut word, rv);
+
+    word.into_iter().collect()
+}
+
+/// Remove the longest ending in RV of either group. Endings of the first
+/// group must follow an `а` or `я`, which is kept. Returns whether an ending
+/// was removed.
+fn remove_ending(word: &mut Vec<char>, rv: usize, first: &[&str], second: &[&str]) -> bool {
+    let length = |suffix: Option<&str>| suffix.map_or(0, |suffix| suffix.chars().count());
+    let (first, second) = (longest_suffix(word, rv, first), longest_suffix(word, rv, second));
+
+    let start = match (first, second) {
+        (Some(suffix), _) if length(first) > length(second) => {
+            let start = suffix_start(word, suffix);
+            if start <= rv || !matches!(word[start - 1], 'а' | 'я') {
+                return false;
+            }
+            start
+        }
+        (_, Some(suffix)) => suffix_start(word, suffix),
+        _ => return false,
+    };
+
+    word.truncate(start);
+    true
+}
+
+/// Remove an inflectional ending: a perfective gerund, or else a reflexive
+/// ending followed by an adjectival, verb or noun ending.
+fn step_1(word: &mut Vec<char>, rv: usize) {
+    if remove_ending(word, rv, PERFECTIVE_GERUND_1, PERFECTIVE_GERUND_2) {
+        return;
+    }
+
+    remove_ending(word, rv, &[], REFLEXIVE);
+
+    if remove_ending(word, rv, &[], ADJECTIVE) {
+        remove_ending(word, rv, PARTICIPLE_1, PARTICIPLE_2);
+    } else if !remove_ending(word, rv, VERB_1, VERB_2) {
+        remove_ending(word, rv, &[], NOUN);
+    }
+}
+
+/// Remove a superlative ending, then undouble `нн`. Otherwise remove a soft
+/// sign.
+fn step_4(word: &mut Vec<char>, rv: usize) {
+    let superlative = match longest_suffix(word, rv, SUPERLATIVE) {
+        Some(suffix) => {
+            word.truncate(suffix_start(word, suffix));
+            true
+        }
+        None => false,
+    };
+
+    if longest_suffix(word, rv, &["нн"]).is_some() || (!superlative && longest_suffix(word, rv, &["ь"]).is_some()) {
+        word.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("книги", "книг"),
+            ("красивая", "красив"),
+            ("красивейший", "красив"),
+            ("читали", "чита"),
+            ("прочитав", "прочита"),
+            ("улыбнувшись", "улыбнувш"),
+            ("длинный", "длин"),
+            ("радость", "радост"),
+            ("ёлка", "елк"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
@@ -0,0 +1,1471 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Running Snowball programs at run time.
+//!
+//! A `Program` is parsed from the source of a Snowball `.sbl` file and can
+//! then stem words like any other `Stemmer`, so a new language can be added
+//! as a data file rather than by porting its algorithm to Rust. Programs
+//! are interpreted, so they run slower than the stemmers in the other
+//! modules.
+//!
+//! Most of the language is supported: routines, groupings, integer, boolean
+//! and string variables, `among` with conditions, `setlimit`, `backwards`
+//! and the string escapes declared with `stringescapes` and `stringdef`.
+//! Not supported are `among` starters, `reverse`, `attach`, `=>` and string
+//! commands of the form `$s C`.
+//!
+//! See <https://snowballstem.org/compiler/snowman.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::script::Program;
+//!
+//! let program: Program = "
+//!     externals ( stem )
+//!     define stem as backwards ( ['ing'] delete )
+//! ".parse().unwrap();
+//!
+//! assert_eq!("sing", program.stem("singing"));
+//! assert_eq!("sang", program.stem("sang"));
+//! ```
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use algorithm::Stemmer;
+
+/// A parsed Snowball program.
+#[derive(Clone, Debug)]
+pub struct Program {
+    routines: Vec<Option<Command>>,
+    externals: Vec<(String, usize)>,
+    groupings: Vec<Vec<char>>,
+    amongs: Vec<Among>,
+    integers: usize,
+    booleans: usize,
+    strings: usize,
+}
+
+/// The error returned when a Snowball program cannot be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    message: String,
+}
+
+impl ParseError {
+    /// The line the error was found on, counting from 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+impl Program {
+    /// Run the external routine `name` on `word`, returning the word as the
+    /// routine left it, or `None` if the program has no such external.
+    pub fn run(&self, name: &str, word: &str) -> Option<String> {
+        let &(_, routine) = self.externals.iter().find(|(external, _)| external == name)?;
+
+        let mut env = Env::new(self, word);
+        env.call(routine);
+
+        Some(env.s.into_iter().collect())
+    }
+
+    /// Given a word, return its stemmed form by running the program's `stem`
+    /// routine. A program without one leaves every word unchanged.
+    pub fn stem(&self, word: &str) -> String {
+        self.run("stem", word).unwrap_or_else(|| word.to_string())
+    }
+}
+
+impl Stemmer for Program {
+    fn stem(&self, word: &str) -> String {
+        Program::stem(self, word)
+    }
+}
+
+impl FromStr for Program {
+    type Err = ParseError;
+
+    fn from_str(source: &str) -> Result<Program, ParseError> {
+        Parser::new(tokenize(source)?).program()
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Command {
+    Sequence(Vec<Command>),
+    Or(Box<Command>, Box<Command>),
+    And(Box<Command>, Box<Command>),
+    Not(Box<Command>),
+    Test(Box<Command>),
+    Try(Box<Command>),
+    Do(Box<Command>),
+    Fail(Box<Command>),
+    Repeat(Box<Command>),
+    Loop(Expression, Box<Command>),
+    AtLeast(Expression, Box<Command>),
+    Goto(Box<Command>),
+    GoPast(Box<Command>),
+    Backwards(Box<Command>),
+    SetLimit(Box<Command>, Box<Command>),
+    Literal(Vec<char>),
+    MatchString(usize),
+    Grouping(usize),
+    NonGrouping(usize),
+    Call(usize),
+    Boolean(usize),
+    Set(usize),
+    Unset(usize),
+    Next,
+    Hop(Expression),
+    SetMark(usize),
+    ToMark(Expression),
+    AtMark(Expression),
+    ToLimit,
+    AtLimit,
+    Bra,
+    Ket,
+    Delete,
+    SliceFrom(Text),
+    Insert(Text),
+    SliceTo(usize),
+    Assign(usize, Assignment, Expression),
+    Compare(Expression, Comparison, Expression),
+    Substring(usize),
+    Among(usize),
+    True,
+    False,
+}
+
+#[derive(Clone, Debug)]
+enum Text {
+    Literal(Vec<char>),
+    Variable(usize),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Assignment {
+    Set,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Comparison {
+    Equal,
+    NotEqual,
+    Greater,
+    Less,
+    GreaterOrEqual,
+    LessOrEqual,
+}
+
+#[derive(Clone, Debug)]
+enum Expression {
+    Number(i64),
+    Integer(usize),
+    Cursor,
+    Limit,
+    Size,
+    SizeOf(usize),
+    Negate(Box<Expression>),
+    Binary(Box<Expression>, char, Box<Expression>),
+}
+
+#[derive(Clone, Debug)]
+struct Among {
+    /// Entries, longest string first.
+    entries: Vec<Entry>,
+    actions: Vec<Command>,
+    /// Whether a `substring` earlier in the routine does the matching.
+    has_substring: bool,
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+    string: Vec<char>,
+    condition: Option<usize>,
+    action: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Literal(String),
+    Number(i64),
+    Symbol(&'static str),
+    Escapes(char, char),
+}
+
+const SYMBOLS: &[&str] = &[
+    "<-", "<+", "->", "=>", "+=", "-=", "*=", "/=", "==", "!=", ">=", "<=", "(", ")", "[", "]",
+    "$", "=", ">", "<", "+", "-", "*", "/", "?",
+];
+
+fn error<T>(line: usize, message: String) -> Result<T, ParseError> {
+    Err(ParseError { line, message })
+}
+
+/// Split Snowball source into tokens, each with its line number. Escapes in
+/// literals are resolved later, once the `stringdef`s they refer to are known.
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let chars = source.chars().collect::<Vec<char>>();
+    let mut tokens = Vec::new();
+    let mut escapes = None;
+    let mut line = 1;
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+
+        if c == '\n' {
+            line += 1;
+            index += 1;
+        } else if c.is_whitespace() {
+            index += 1;
+        } else if chars[index..].starts_with(&['/', '/']) {
+            while index < chars.len() && chars[index] != '\n' {
+                index += 1;
+            }
+        } else if chars[index..].starts_with(&['/', '*']) {
+            index += 2;
+            while index < chars.len() && !chars[index..].starts_with(&['*', '/']) {
+                if chars[index] == '\n' {
+                    line += 1;
+                }
+                index += 1;
+            }
+            index += 2;
+        } else if matches!(tokens.last(), Some(&(Token::Word(ref word), _)) if word == "stringescapes") {
+            match chars[index..].iter().filter(|c| !c.is_whitespace()).take(2).collect::<Vec<&char>>()[..] {
+                [&open, &close] => {
+                    tokens.push((Token::Escapes(open, close), line));
+                    escapes = Some((open, close));
+                    index = chars[index..].iter().position(|&c| c == close).map_or(chars.len(), |end| index + end + 1);
+                }
+                _ => return error(line, "expected two escape characters".to_string()),
+            }
+        } else if matches!(tokens.last(), Some(&(Token::Word(ref word), _)) if word == "stringdef") {
+            let start = index;
+            while index < chars.len() && !chars[index].is_whitespace() {
+                index += 1;
+            }
+            tokens.push((Token::Word(chars[start..index].iter().collect()), line));
+        } else if c == '\'' {
+            let start = index + 1;
+            let mut escaped = false;
+            index += 1;
+            while index < chars.len() && (escaped || chars[index] != '\'') {
+                match escapes {
+                    Some((open, _)) if chars[index] == open => escaped = true,
+                    Some((_, close)) if chars[index] == close => escaped = false,
+                    _ => {}
+                }
+                index += 1;
+            }
+            if index == chars.len() {
+                return error(line, "unterminated string literal".to_string());
+            }
+            tokens.push((Token::Literal(chars[start..index].iter().collect()), line));
+            index += 1;
+        } else if c.is_ascii_digit() {
+            let start = index;
+            while index < chars.len() && chars[index].is_ascii_digit() {
+                index += 1;
+            }
+            let digits = chars[start..index].iter().collect::<String>();
+            match digits.parse() {
+                Ok(number) => tokens.push((Token::Number(number), line)),
+                Err(_) => return error(line, format!("number `{}` is too large", digits)),
+            }
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let start = index;
+            while index < chars.len() && (chars[index].is_ascii_alphanumeric() || chars[index] == '_') {
+                index += 1;
+            }
+            tokens.push((Token::Word(chars[start..index].iter().collect()), line));
+        } else {
+            let symbol = SYMBOLS.iter().find(|symbol| {
+                let length = symbol.chars().count();
+                index + length <= chars.len() && symbol.chars().eq(chars[index..index + length].iter().cloned())
+            });
+            match symbol {
+                Some(symbol) => {
+                    tokens.push((Token::Symbol(symbol), line));
+                    index += symbol.len();
+                }
+                None => return error(line, format!("unexpected character `{}`", c)),
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Name {
+    Routine(usize),
+    Grouping(usize),
+    Integer(usize),
+    Boolean(usize),
+    String(usize),
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    names: HashMap<String, Name>,
+    stringdefs: HashMap<String, Vec<char>>,
+    escapes: Option<(char, char)>,
+    program: Program,
+    pending_substring: bool,
+}
+
+impl Parser {
+    fn new(tokens: Vec<(Token, usize)>) -> Parser {
+        Parser {
+            tokens,
+            position: 0,
+            names: HashMap::new(),
+            stringdefs: HashMap::new(),
+            escapes: None,
+            program: Program {
+                routines: Vec::new(),
+                externals: Vec::new(),
+                groupings: Vec::new(),
+                amongs: Vec::new(),
+                integers: 0,
+                booleans: 0,
+                strings: 0,
+            },
+            pending_substring: false,
+        }
+    }
+
+    fn line(&self) -> usize {
+        self.tokens.get(self.position).or_else(|| self.tokens.last()).map_or(1, |&(_, line)| line)
+    }
+
+    fn next(&mut self) -> Result<Token, ParseError> {
+        match self.tokens.get(self.position) {
+            Some((token, _)) => {
+                self.position += 1;
+                Ok(token.clone())
+            }
+            None => error(self.line(), "unexpected end of program".to_string()),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    /// Consume the next token if it is the word or symbol `expected`.
+    fn accept(&mut self, expected: &str) -> bool {
+        let found = match self.peek() {
+            Some(Token::Word(word)) => word == expected,
+            Some(&Token::Symbol(symbol)) => symbol == expected,
+            _ => false,
+        };
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), ParseError> {
+        if self.accept(expected) {
+            Ok(())
+        } else {
+            error(self.line(), format!("expected `{}`", expected))
+        }
+    }
+
+    fn word(&mut self) -> Result<String, ParseError> {
+        match self.next()? {
+            Token::Word(word) => Ok(word),
+            token => error(self.line(), format!("expected a name, found {:?}", token)),
+        }
+    }
+
+    fn name(&mut self) -> Result<Name, ParseError> {
+        let word = self.word()?;
+        match self.names.get(&word) {
+            Some(&name) => Ok(name),
+            None => error(self.line(), format!("`{}` is not declared", word)),
+        }
+    }
+
+    fn program(mut self) -> Result<Program, ParseError> {
+        while self.peek().is_some() {
+            self.declaration()?;
+        }
+
+        let undefined = self.names.iter().find(|&(_, &name)| match name {
+            Name::Routine(routine) => self.program.routines[routine].is_none(),
+            _ => false,
+        });
+        if let Some((name, _)) = undefined {
+            return error(self.line(), format!("routine `{}` is declared but not defined", name));
+        }
+
+        Ok(self.program)
+    }
+
+    fn declaration(&mut self) -> Result<(), ParseError> {
+        let line = self.line();
+
+        match self.next()? {
+            Token::Word(ref word) if word == "backwardmode" => {
+                self.expect("(")?;
+                while !self.accept(")") {
+                    self.declaration()?;
+                }
+            }
+            Token::Word(ref word) if word == "define" => self.definition()?,
+            Token::Word(ref word) if word == "stringdef" => {
+                let name = self.word()?;
+                let value = self.stringdef()?;
+                self.stringdefs.insert(name, value);
+            }
+            Token::Word(ref word) if word == "stringescapes" => match self.next()? {
+                Token::Escapes(open, close) => self.escapes = Some((open, close)),
+                _ => return error(line, "expected two escape characters".to_string()),
+            },
+            Token::Word(ref kind) => {
+                self.expect("(")?;
+                while !self.accept(")") {
+                    let word = self.word()?;
+                    let name = match &kind[..] {
+                        "routines" | "externals" => {
+                            let routine = match self.names.get(&word) {
+                                Some(&Name::Routine(routine)) => routine,
+                                _ => {
+                                    self.program.routines.push(None);
+                                    self.program.routines.len() - 1
+                                }
+                            };
+                            if kind == "externals" {
+                                self.program.externals.push((word.clone(), routine));
+                            }
+                            Name::Routine(routine)
+                        }
+                        "groupings" => {
+                            self.program.groupings.push(Vec::new());
+                            Name::Grouping(self.program.groupings.len() - 1)
+                        }
+                        "integers" => {
+                            self.program.integers += 1;
+                            Name::Integer(self.program.integers - 1)
+                        }
+                        "booleans" => {
+                            self.program.booleans += 1;
+                            Name::Boolean(self.program.booleans - 1)
+                        }
+                        "strings" => {
+                            self.program.strings += 1;
+                            Name::String(self.program.strings - 1)
+                        }
+                        _ => return error(line, format!("unexpected `{}`", kind)),
+                    };
+                    self.names.insert(word, name);
+                }
+            }
+            token => return error(line, format!("unexpected {:?}", token)),
+        }
+
+        Ok(())
+    }
+
+    fn stringdef(&mut self) -> Result<Vec<char>, ParseError> {
+        let line = self.line();
+        let radix = if self.accept("hex") {
+            16
+        } else if self.accept("decimal") {
+            10
+        } else {
+            return self.literal();
+        };
+
+        match self.next()? {
+            Token::Literal(digits) => digits.split_whitespace()
+                .map(|code| u32::from_str_radix(code, radix).ok().and_then(::std::char::from_u32))
+                .collect::<Option<Vec<char>>>()
+                .map_or_else(|| error(line, format!("invalid character code in `{}`", digits)), Ok),
+            _ => error(line, "expected a string literal".to_string()),
+        }
+    }
+
+    /// Read a literal, resolving its escapes.
+    fn literal(&mut self) -> Result<Vec<char>, ParseError> {
+        let line = self.line();
+        let raw = match self.next()? {
+            Token::Literal(raw) => raw,
+            _ => return error(line, "expected a string literal".to_string()),
+        };
+
+        let (open, close) = match self.escapes {
+            Some(escapes) => escapes,
+            None => return Ok(raw.chars().collect()),
+        };
+
+        let mut resolved = Vec::new();
+        let mut rest = &raw[..];
+
+        while let Some(start) = rest.find(open) {
+            resolved.extend(rest[..start].chars());
+            let end = match rest[start..].find(close) {
+                Some(end) => start + end,
+                None => return error(line, format!("unterminated escape in `{}`", raw)),
+            };
+            let name = &rest[start + open.len_utf8()..end];
+
+            if let Some(value) = self.stringdefs.get(name) {
+                resolved.extend(value.iter().cloned());
+            } else if let Some(code) = name.strip_prefix("U+") {
+                match u32::from_str_radix(code, 16).ok().and_then(::std::char::from_u32) {
+                    Some(c) => resolved.push(c),
+                    None => return error(line, format!("invalid escape `{}`", name)),
+                }
+            } else if name.is_empty() {
+                // `{}` stands for no characters at all.
+            } else {
+                return error(line, format!("unknown escape `{}`", name));
+            }
+
+            rest = &rest[end + close.len_utf8()..];
+        }
+        resolved.extend(rest.chars());
+
+        Ok(resolved)
+    }
+
+    fn definition(&mut self) -> Result<(), ParseError> {
+        let line = self.line();
+
+        match self.name()? {
+            Name::Routine(routine) => {
+                self.expect("as")?;
+                let body = self.command()?;
+                self.pending_substring = false;
+                self.program.routines[routine] = Some(body);
+            }
+            Name::Grouping(grouping) => {
+                let mut members = self.grouping_item()?;
+                loop {
+                    if self.accept("+") {
+                        let item = self.grouping_item()?;
+                        members.extend(item.into_iter().filter(|c| !members.contains(c)).collect::<Vec<char>>());
+                    } else if self.accept("-") {
+                        let item = self.grouping_item()?;
+                        members.retain(|c| !item.contains(c));
+                    } else {
+                        break;
+                    }
+                }
+                self.program.groupings[grouping] = members;
+            }
+            _ => return error(line, "only routines and groupings can be defined".to_string()),
+        }
+
+        Ok(())
+    }
+
+    fn grouping_item(&mut self) -> Result<Vec<char>, ParseError> {
+        if let Some(&Token::Literal(_)) = self.peek() {
+            return self.literal();
+        }
+
+        match self.name()? {
+            Name::Grouping(grouping) => Ok(self.program.groupings[grouping].clone()),
+            _ => error(self.line(), "expected a grouping".to_string()),
+        }
+    }
+
+    /// A command, with any `or` and `and` that follow it.
+    fn command(&mut self) -> Result<Command, ParseError> {
+        let mut command = self.unary()?;
+
+        loop {
+            if self.accept("or") {
+                command = Command::Or(Box::new(command), Box::new(self.unary()?));
+            } else if self.accept("and") {
+                command = Command::And(Box::new(command), Box::new(self.unary()?));
+            } else {
+                return Ok(command);
+            }
+        }
+    }
+
+    fn boxed(&mut self) -> Result<Box<Command>, ParseError> {
+        self.unary().map(Box::new)
+    }
+
+    fn unary(&mut self) -> Result<Command, ParseError> {
+        let line = self.line();
+
+        let word = match self.next()? {
+            Token::Symbol("(") => {
+                let mut commands = Vec::new();
+                while !self.accept(")") {
+                    commands.push(self.command()?);
+                }
+                return Ok(Command::Sequence(commands));
+            }
+            Token::Literal(_) => {
+                self.position -= 1;
+                return Ok(Command::Literal(self.literal()?));
+            }
+            Token::Symbol("[") => return Ok(Command::Bra),
+            Token::Symbol("]") => return Ok(Command::Ket),
+            Token::Symbol("<-") => return Ok(Command::SliceFrom(self.text()?)),
+            Token::Symbol("<+") => return Ok(Command::Insert(self.text()?)),
+            Token::Symbol("->") => return Ok(Command::SliceTo(self.string_variable()?)),
+            Token::Symbol("$") => return self.integer_command(),
+            Token::Word(word) => word,
+            token => return error(line, format!("unexpected {:?}", token)),
+        };
+
+        Ok(match &word[..] {
+            "not" => Command::Not(self.boxed()?),
+            "test" => Command::Test(self.boxed()?),
+            "try" => Command::Try(self.boxed()?),
+            "do" => Command::Do(self.boxed()?),
+            "fail" => Command::Fail(self.boxed()?),
+            "repeat" => Command::Repeat(self.boxed()?),
+            "goto" => Command::Goto(self.boxed()?),
+            "gopast" => Command::GoPast(self.boxed()?),
+            "backwards" => Command::Backwards(self.boxed()?),
+            "loop" => Command::Loop(self.expression()?, self.boxed()?),
+            "atleast" => Command::AtLeast(self.expression()?, self.boxed()?),
+            "setlimit" => {
+                let limit = self.boxed()?;
+                self.expect("for")?;
+                Command::SetLimit(limit, self.boxed()?)
+            }
+            "hop" => Command::Hop(self.expression()?),
+            "tomark" => Command::ToMark(self.expression()?),
+            "atmark" => Command::AtMark(self.expression()?),
+            "setmark" => match self.name()? {
+                Name::Integer(integer) => Command::SetMark(integer),
+                _ => return error(line, "expected an integer".to_string()),
+            },
+            "next" => Command::Next,
+            "tolimit" => Command::ToLimit,
+            "atlimit" => Command::AtLimit,
+            "delete" => Command::Delete,
+            "insert" => Command::Insert(self.text()?),
+            "true" => Command::True,
+            "false" => Command::False,
+            "set" | "unset" => match self.name()? {
+                Name::Boolean(boolean) if word == "set" => Command::Set(boolean),
+                Name::Boolean(boolean) => Command::Unset(boolean),
+                _ => return error(line, "expected a boolean".to_string()),
+            },
+            "non" => {
+                self.accept("-");
+                match self.name()? {
+                    Name::Grouping(grouping) => Command::NonGrouping(grouping),
+                    _ => return error(line, "expected a grouping".to_string()),
+                }
+            }
+            "substring" => {
+                self.pending_substring = true;
+                Command::Substring(self.program.amongs.len())
+            }
+            "among" => self.among()?,
+            "reverse" | "attach" => return error(line, format!("`{}` is not supported", word)),
+            _ => match self.names.get(&word) {
+                Some(&Name::Routine(routine)) => Command::Call(routine),
+                Some(&Name::Grouping(grouping)) => Command::Grouping(grouping),
+                Some(&Name::Boolean(boolean)) => Command::Boolean(boolean),
+                Some(&Name::String(string)) => Command::MatchString(string),
+                Some(&Name::Integer(_)) => return error(line, format!("integer `{}` used as a command", word)),
+                None => return error(line, format!("`{}` is not declared", word)),
+            },
+        })
+    }
+
+    fn string_variable(&mut self) -> Result<usize, ParseError> {
+        match self.name()? {
+            Name::String(string) => Ok(string),
+            _ => error(self.line(), "expected a string variable".to_string()),
+        }
+    }
+
+    fn text(&mut self) -> Result<Text, ParseError> {
+        match self.peek() {
+            Some(&Token::Literal(_)) => Ok(Text::Literal(self.literal()?)),
+            _ => Ok(Text::Variable(self.string_variable()?)),
+        }
+    }
+
+    fn among(&mut self) -> Result<Command, ParseError> {
+        let has_substring = self.pending_substring;
+        self.pending_substring = false;
+        self.expect("(")?;
+
+        let mut entries = Vec::<Entry>::new();
+        let mut actions = Vec::new();
+        let mut waiting = 0;
+
+        while !self.accept(")") {
+            if self.accept("(") {
+                self.position -= 1;
+                actions.push(self.unary()?);
+                for entry in entries.iter_mut().rev().take(waiting) {
+                    entry.action = Some(actions.len() - 1);
+                }
+                waiting = 0;
+                continue;
+            }
+
+            let string = self.literal()?;
+            let condition = match self.peek() {
+                Some(Token::Word(word)) => match self.names.get(word) {
+                    Some(&Name::Routine(routine)) => Some(routine),
+                    _ => None,
+                },
+                _ => None,
+            };
+            if condition.is_some() {
+                self.position += 1;
+            }
+
+            entries.push(Entry { string, condition, action: None });
+            waiting += 1;
+        }
+
+        entries.sort_by_key(|entry| ::std::cmp::Reverse(entry.string.len()));
+        self.program.amongs.push(Among { entries, actions, has_substring });
+
+        Ok(Command::Among(self.program.amongs.len() - 1))
+    }
+
+    fn integer_command(&mut self) -> Result<Command, ParseError> {
+        let line = self.line();
+
+        if self.accept("(") {
+            let left = self.expression()?;
+            let comparison = self.comparison()?;
+            let right = self.expression()?;
+            self.expect(")")?;
+            return Ok(Command::Compare(left, comparison, right));
+        }
+
+        let integer = match self.name()? {
+            Name::Integer(integer) => integer,
+            _ => return error(line, "expected an integer".to_string()),
+        };
+
+        let assignment = match self.peek() {
+            Some(&Token::Symbol("=")) => Some(Assignment::Set),
+            Some(&Token::Symbol("+=")) => Some(Assignment::Add),
+            Some(&Token::Symbol("-=")) => Some(Assignment::Subtract),
+            Some(&Token::Symbol("*=")) => Some(Assignment::Multiply),
+            Some(&Token::Symbol("/=")) => Some(Assignment::Divide),
+            _ => None,
+        };
+
+        match assignment {
+            Some(assignment) => {
+                self.position += 1;
+                Ok(Command::Assign(integer, assignment, self.expression()?))
+            }
+            None => {
+                let comparison = self.comparison()?;
+                Ok(Command::Compare(Expression::Integer(integer), comparison, self.expression()?))
+            }
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Comparison, ParseError> {
+        let line = self.line();
+
+        match self.next()? {
+            Token::Symbol("==") => Ok(Comparison::Equal),
+            Token::Symbol("!=") => Ok(Comparison::NotEqual),
+            Token::Symbol(">") => Ok(Comparison::Greater),
+            Token::Symbol("<") => Ok(Comparison::Less),
+            Token::Symbol(">=") => Ok(Comparison::GreaterOrEqual),
+            Token::Symbol("<=") => Ok(Comparison::LessOrEqual),
+            token => error(line, format!("expected a comparison, found {:?}", token)),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Expression, ParseError> {
+        let mut expression = self.term()?;
+
+        loop {
+            let operator = match self.peek() {
+                Some(&Token::Symbol("+")) => '+',
+                Some(&Token::Symbol("-")) => '-',
+                _ => return Ok(expression),
+            };
+            self.position += 1;
+            expression = Expression::Binary(Box::new(expression), operator, Box::new(self.term()?));
+        }
+    }
+
+    fn term(&mut self) -> Result<Expression, ParseError> {
+        let mut term = self.factor()?;
+
+        loop {
+            let operator = match self.peek() {
+                Some(&Token::Symbol("*")) => '*',
+                Some(&Token::Symbol("/")) => '/',
+                _ => return Ok(term),
+            };
+            self.position += 1;
+            term = Expression::Binary(Box::new(term), operator, Box::new(self.factor()?));
+        }
+    }
+
+    fn factor(&mut self) -> Result<Expression, ParseError> {
+        let line = self.line();
+
+        match self.next()? {
+            Token::Number(number) => Ok(Expression::Number(number)),
+            Token::Symbol("-") => Ok(Expression::Negate(Box::new(self.factor()?))),
+            Token::Symbol("(") => {
+                let expression = self.expression()?;
+                self.expect(")")?;
+                Ok(expression)
+            }
+            Token::Word(ref word) if word == "cursor" => Ok(Expression::Cursor),
+            Token::Word(ref word) if word == "limit" => Ok(Expression::Limit),
+            Token::Word(ref word) if word == "size" || word == "len" => Ok(Expression::Size),
+            Token::Word(ref word) if word == "maxint" => Ok(Expression::Number(i64::from(i32::MAX))),
+            Token::Word(ref word) if word == "minint" => Ok(Expression::Number(i64::from(i32::MIN))),
+            Token::Word(ref word) if word == "sizeof" || word == "lenof" => Ok(Expression::SizeOf(self.string_variable()?)),
+            Token::Word(word) => match self.names.get(&word) {
+                Some(&Name::Integer(integer)) => Ok(Expression::Integer(integer)),
+                _ => error(line, format!("`{}` is not an integer", word)),
+            },
+            token => error(line, format!("expected an integer expression, found {:?}", token)),
+        }
+    }
+}
+
+/// The state of a program while it runs on one word.
+struct Env<'a> {
+    program: &'a Program,
+    s: Vec<char>,
+    c: usize,
+    l: usize,
+    lb: usize,
+    bra: usize,
+    ket: usize,
+    backward: bool,
+    integers: Vec<i64>,
+    booleans: Vec<bool>,
+    strings: Vec<Vec<char>>,
+    /// The entry each `among` matched, set by its `substring`.
+    matched: Vec<Option<usize>>,
+}
+
+impl<'a> Env<'a> {
+    fn new(program: &'a Program, word: &str) -> Env<'a> {
+        let s = word.chars().collect::<Vec<char>>();
+        let length = s.len();
+
+        Env {
+            program,
+            s,
+            c: 0,
+            l: length,
+            lb: 0,
+            bra: 0,
+            ket: length,
+            backward: false,
+            integers: vec![0; program.integers],
+            booleans: vec![false; program.booleans],
+            strings: vec![Vec::new(); program.strings],
+            matched: vec![None; program.amongs.len()],
+        }
+    }
+
+    fn call(&mut self, routine: usize) -> bool {
+        let program = self.program;
+        match program.routines[routine] {
+            Some(ref body) => self.run(body),
+            None => false,
+        }
+    }
+
+    /// The limit the cursor moves towards.
+    fn limit(&self) -> usize {
+        if self.backward {
+            self.lb
+        } else {
+            self.l
+        }
+    }
+
+    /// The cursor, measured from the limit when going backwards so that it
+    /// can be restored after the string changes length.
+    fn save(&self) -> usize {
+        if self.backward {
+            self.l - self.c
+        } else {
+            self.c
+        }
+    }
+
+    fn restore(&mut self, saved: usize) {
+        self.c = if self.backward { self.l - saved } else { saved };
+    }
+
+    /// Move the cursor over `text` if it comes next.
+    fn eq(&mut self, text: &[char]) -> bool {
+        if self.backward {
+            if self.c < self.lb + text.len() || self.s[self.c - text.len()..self.c] != *text {
+                return false;
+            }
+            self.c -= text.len();
+        } else {
+            if self.c + text.len() > self.l || self.s[self.c..self.c + text.len()] != *text {
+                return false;
+            }
+            self.c += text.len();
+        }
+        true
+    }
+
+    /// Move the cursor over the next character if `accept` holds for it.
+    fn step(&mut self, accept: &dyn Fn(char) -> bool) -> bool {
+        if self.backward {
+            if self.c <= self.lb || !accept(self.s[self.c - 1]) {
+                return false;
+            }
+            self.c -= 1;
+        } else {
+            if self.c >= self.l || !accept(self.s[self.c]) {
+                return false;
+            }
+            self.c += 1;
+        }
+        true
+    }
+
+    fn replace(&mut self, start: usize, end: usize, text: &[char]) -> isize {
+        let adjustment = text.len() as isize - (end - start) as isize;
+        self.s.splice(start..end, text.iter().cloned());
+        self.l = (self.l as isize + adjustment) as usize;
+        if self.c >= end {
+            self.c = (self.c as isize + adjustment) as usize;
+        } else if self.c > start {
+            self.c = start;
+        }
+        adjustment
+    }
+
+    fn slice_from(&mut self, text: &[char]) -> bool {
+        if self.bra > self.ket || self.ket > self.s.len() {
+            return false;
+        }
+        let (bra, ket) = (self.bra, self.ket);
+        self.replace(bra, ket, text);
+        self.ket = bra + text.len();
+        true
+    }
+
+    fn insert(&mut self, text: &[char]) {
+        let c = self.c;
+        let adjustment = self.replace(c, c, text);
+        if c <= self.bra {
+            self.bra = (self.bra as isize + adjustment) as usize;
+        }
+        if c <= self.ket {
+            self.ket = (self.ket as isize + adjustment) as usize;
+        }
+        if self.backward {
+            self.c = c;
+        }
+    }
+
+    fn text(&self, text: &Text) -> Vec<char> {
+        match *text {
+            Text::Literal(ref literal) => literal.clone(),
+            Text::Variable(string) => self.strings[string].clone(),
+        }
+    }
+
+    fn evaluate(&self, expression: &Expression) -> i64 {
+        match *expression {
+            Expression::Number(number) => number,
+            Expression::Integer(integer) => self.integers[integer],
+            Expression::Cursor => self.c as i64,
+            Expression::Limit => self.limit() as i64,
+            Expression::Size => self.s.len() as i64,
+            Expression::SizeOf(string) => self.strings[string].len() as i64,
+            Expression::Negate(ref operand) => -self.evaluate(operand),
+            Expression::Binary(ref left, operator, ref right) => {
+                let (left, right) = (self.evaluate(left), self.evaluate(right));
+                match operator {
+                    '+' => left + right,
+                    '-' => left - right,
+                    '*' => left * right,
+                    _ if right == 0 => 0,
+                    _ => left / right,
+                }
+            }
+        }
+    }
+
+    /// Find the longest string of `among` at the cursor whose condition, if
+    /// any, holds, and move the cursor over it.
+    fn find_among(&mut self, among: usize) -> Option<usize> {
+        let program = self.program;
+        let start = self.c;
+
+        for (index, entry) in program.amongs[among].entries.iter().enumerate() {
+            if !self.eq(&entry.string) {
+                continue;
+            }
+            match entry.condition {
+                Some(routine) if !self.call(routine) => self.c = start,
+                _ => {
+                    self.c = if self.backward { start - entry.string.len() } else { start + entry.string.len() };
+                    return Some(index);
+                }
+            }
+        }
+
+        None
+    }
+
+    fn run(&mut self, command: &Command) -> bool {
+        match *command {
+            Command::Sequence(ref commands) => commands.iter().all(|command| self.run(command)),
+            Command::Or(ref first, ref second) => {
+                let c = self.save();
+                self.run(first) || {
+                    self.restore(c);
+                    self.run(second)
+                }
+            }
+            Command::And(ref first, ref second) => {
+                let c = self.save();
+                self.run(first) && {
+                    self.restore(c);
+                    self.run(second)
+                }
+            }
+            Command::Not(ref command) => {
+                let c = self.save();
+                if self.run(command) {
+                    return false;
+                }
+                self.restore(c);
+                true
+            }
+            Command::Test(ref command) => {
+                let c = self.save();
+                if !self.run(command) {
+                    return false;
+                }
+                self.restore(c);
+                true
+            }
+            Command::Try(ref command) => {
+                let c = self.save();
+                if !self.run(command) {
+                    self.restore(c);
+                }
+                true
+            }
+            Command::Do(ref command) => {
+                let c = self.save();
+                self.run(command);
+                self.restore(c);
+                true
+            }
+            Command::Fail(ref command) => {
+                self.run(command);
+                false
+            }
+            Command::Repeat(ref command) => {
+                self.repeat(command);
+                true
+            }
+            Command::Loop(ref count, ref command) => {
+                let count = self.evaluate(count);
+                (0..count).all(|_| self.run(command))
+            }
+            Command::AtLeast(ref count, ref command) => {
+                let count = self.evaluate(count);
+                if !(0..count).all(|_| self.run(command)) {
+                    return false;
+                }
+                self.repeat(command);
+                true
+            }
+            Command::Goto(ref command) => self.go(command, false),
+            Command::GoPast(ref command) => self.go(command, true),
+            Command::Backwards(ref command) => {
+                let (c, lb, backward) = (self.c, self.lb, self.backward);
+                self.lb = c;
+                self.c = self.l;
+                self.backward = true;
+                let result = self.run(command);
+                self.c = self.lb;
+                self.lb = lb;
+                self.backward = backward;
+                result
+            }
+            Command::SetLimit(ref limit, ref command) => {
+                let c = self.save();
+                if !self.run(limit) {
+                    return false;
+                }
+                let result;
+                if self.backward {
+                    let lb = self.lb;
+                    self.lb = self.c;
+                    self.restore(c);
+                    result = self.run(command);
+                    self.lb = lb;
+                } else {
+                    let l = self.l;
+                    let shortened = l - self.c;
+                    self.l = self.c;
+                    self.restore(c);
+                    result = self.run(command);
+                    // The string may have changed length inside the limit.
+                    self.l += shortened;
+                }
+                result
+            }
+            Command::Literal(ref literal) => self.eq(literal),
+            Command::MatchString(string) => {
+                let text = self.strings[string].clone();
+                self.eq(&text)
+            }
+            Command::Grouping(grouping) => {
+                let members = &self.program.groupings[grouping];
+                self.step(&|c| members.contains(&c))
+            }
+            Command::NonGrouping(grouping) => {
+                let members = &self.program.groupings[grouping];
+                self.step(&|c| !members.contains(&c))
+            }
+            Command::Call(routine) => self.call(routine),
+            Command::Boolean(boolean) => self.booleans[boolean],
+            Command::Set(boolean) => {
+                self.booleans[boolean] = true;
+                true
+            }
+            Command::Unset(boolean) => {
+                self.booleans[boolean] = false;
+                true
+            }
+            Command::Next => self.step(&|_| true),
+            Command::Hop(ref count) => {
+                let count = self.evaluate(count);
+                let target = if self.backward { self.c as i64 - count } else { self.c as i64 + count };
+                if count < 0 || target < self.lb as i64 || target > self.l as i64 {
+                    return false;
+                }
+                self.c = target as usize;
+                true
+            }
+            Command::SetMark(integer) => {
+                self.integers[integer] = self.c as i64;
+                true
+            }
+            Command::ToMark(ref mark) => {
+                let mark = self.evaluate(mark);
+                let c = self.c as i64;
+                let allowed = if self.backward {
+                    c >= mark && mark >= self.lb as i64
+                } else {
+                    c <= mark && mark <= self.l as i64
+                };
+                if allowed {
+                    self.c = mark as usize;
+                }
+                allowed
+            }
+            Command::AtMark(ref mark) => self.c as i64 == self.evaluate(mark),
+            Command::ToLimit => {
+                self.c = self.limit();
+                true
+            }
+            Command::AtLimit => self.c == self.limit(),
+            Command::Bra => {
+                if self.backward {
+                    self.ket = self.c;
+                } else {
+                    self.bra = self.c;
+                }
+                true
+            }
+            Command::Ket => {
+                if self.backward {
+                    self.bra = self.c;
+                } else {
+                    self.ket = self.c;
+                }
+                true
+            }
+            Command::Delete => self.slice_from(&[]),
+            Command::SliceFrom(ref text) => {
+                let text = self.text(text);
+                self.slice_from(&text)
+            }
+            Command::Insert(ref text) => {
+                let text = self.text(text);
+                self.insert(&text);
+                true
+            }
+            Command::SliceTo(string) => {
+                if self.bra > self.ket || self.ket > self.s.len() {
+                    return false;
+                }
+                self.strings[string] = self.s[self.bra..self.ket].to_vec();
+                true
+            }
+            Command::Assign(integer, assignment, ref expression) => {
+                let value = self.evaluate(expression);
+                let current = &mut self.integers[integer];
+                *current = match assignment {
+                    Assignment::Set => value,
+                    Assignment::Add => *current + value,
+                    Assignment::Subtract => *current - value,
+                    Assignment::Multiply => *current * value,
+                    Assignment::Divide if value == 0 => 0,
+                    Assignment::Divide => *current / value,
+                };
+                true
+            }
+            Command::Compare(ref left, comparison, ref right) => {
+                let (left, right) = (self.evaluate(left), self.evaluate(right));
+                match comparison {
+                    Comparison::Equal => left == right,
+                    Comparison::NotEqual => left != right,
+                    Comparison::Greater => left > right,
+                    Comparison::Less => left < right,
+                    Comparison::GreaterOrEqual => left >= right,
+                    Comparison::LessOrEqual => left <= right,
+                }
+            }
+            Command::Substring(among) => {
+                let found = self.find_among(among);
+                self.matched[among] = found;
+                found.is_some()
+            }
+            Command::Among(among) => {
+                let program = self.program;
+                let found = if program.amongs[among].has_substring {
+                    self.matched[among]
+                } else {
+                    self.find_among(among)
+                };
+                match found.map(|entry| program.amongs[among].entries[entry].action) {
+                    Some(Some(action)) => self.run(&program.amongs[among].actions[action]),
+                    Some(None) => true,
+                    None => false,
+                }
+            }
+            Command::True => true,
+            Command::False => false,
+        }
+    }
+
+    fn repeat(&mut self, command: &Command) {
+        loop {
+            let c = self.save();
+            if !self.run(command) {
+                self.restore(c);
+                return;
+            }
+        }
+    }
+
+    /// Move the cursor forward, one character at a time, until `command`
+    /// succeeds. The cursor is left before what `command` matched, or after
+    /// it if `past` is set.
+    fn go(&mut self, command: &Command, past: bool) -> bool {
+        loop {
+            let c = self.save();
+            if self.run(command) {
+                if !past {
+                    self.restore(c);
+                }
+                return true;
+            }
+            self.restore(c);
+            if !self.step(&|_| true) {
+                return false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snowball::danish;
+
+    /// The Snowball Danish stemmer, as published.
+    const DANISH: &str = "
+        routines ( mark_regions main_suffix consonant_pair other_suffix undouble )
+        externals ( stem )
+        strings ( ch )
+        integers ( p1 x )
+        groupings ( c v s_ending )
+
+        stringescapes {}
+
+        /* special characters */
+        stringdef ae   '{U+00E6}'
+        stringdef ao   '{U+00E5}'
+        stringdef o/   hex 'F8'
+
+        define c 'bcdfghjklmnpqrstvwxz'
+        define v 'aeiouy{ae}{ao}{o/}'
+        define s_ending  'abcdfghjklmnoprtvyz{ao}'
+
+        define mark_regions as (
+            $p1 = limit
+            test ( hop 3 setmark x )
+            goto v gopast non-v  setmark p1
+            try ( $p1 < x  $p1 = x )
+        )
+
+        backwardmode (
+            define main_suffix as (
+                setlimit tomark p1 for ([substring])
+                among(
+                    'hed' 'ethed' 'ered' 'e' 'erede' 'ende' 'erende' 'ene' 'erne' 'ere'
+                    'en' 'heden' 'eren' 'er' 'heder' 'erer' 'heds' 'es' 'endes'
+                    'erendes' 'enes' 'ernes' 'eres' 'ens' 'hedens' 'erens' 'ers' 'ets'
+                    'erets' 'et' 'eret'
+                        (delete)
+                    's'
+                        (s_ending delete)
+                )
+            )
+
+            define consonant_pair as (
+                test (
+                    setlimit tomark p1 for ([substring])
+                    among(
+                        'gd' // significant in the call from other_suffix
+                        'dt' 'gt' 'kt'
+                    )
+                )
+                next] delete
+            )
+
+            define other_suffix as (
+                do ( ['st'] 'ig' delete )
+                setlimit tomark p1 for ([substring])
+                among(
+                    'ig' 'lig' 'elig' 'els'
+                        (delete do consonant_pair)
+                    'l{o/}st'
+                        (<-'l{o/}s')
+                )
+            )
+
+            define undouble as (
+                setlimit tomark p1 for ([c] ->ch)
+                ch
+                delete
+            )
+        )
+
+        define stem as (
+            do mark_regions
+            backwards (
+                do main_suffix
+                do consonant_pair
+                do other_suffix
+                do undouble
+            )
+        )
+    ";
+
+    #[test]
+    fn test_danish() {
+        let program: Program = DANISH.parse().unwrap();
+
+        let words = [
+            "bilerne", "hestene", "undervisningen", "lykkelig", "kærlighed", "spiste", "løst",
+            "bøger", "indtægter", "ejendommelig", "fortsætte", "kendte", "huse", "hus", "at",
+            "åbenbaring", "mennesker", "vejledningen", "bestemmelserne",
+        ];
+        for word in words.iter() {
+            assert_eq!(danish::stem(word), program.stem(word), "stemming {}", word);
+        }
+    }
+
+    #[test]
+    fn test_commands() {
+        let program: Program = "
+            routines ( r )
+            externals ( stem )
+            integers ( n )
+            booleans ( found )
+            strings ( s )
+            define r as ( 'b' or 'c' )
+            define stem as (
+                [ next ] -> s
+                $n = 0
+                repeat ( gopast r $n += 1 )
+                $n == 2 set found
+                found
+                <+ s
+                $(n * 2 >= 4)
+            )
+        ".parse().unwrap();
+
+        assert_eq!("abca", program.stem("abc"));
+        assert_eq!("abcb", program.stem("abcb"));
+        assert_eq!(None, program.run("missing", "abc"));
+    }
+
+    #[test]
+    fn test_among_condition() {
+        let program: Program = "
+            routines ( double )
+            externals ( stem )
+            define double as ( 'l' )
+            define stem as backwards ( [substring] among ( 'ly' double (<- 'L') 'y' (<- 'I') ) )
+        ".parse().unwrap();
+
+        assert_eq!("hapI", program.stem("hapy"));
+        assert_eq!("silL", program.stem("silly"));
+        assert_eq!("onlI", program.stem("only"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = "externals ( stem )\ndefine stem as ( unknown )".parse::<Program>().unwrap_err();
+        assert_eq!(2, error.line());
+        assert_eq!("line 2: `unknown` is not declared", error.to_string());
+
+        let error = "externals ( stem )".parse::<Program>().unwrap_err();
+        assert_eq!("line 1: routine `stem` is declared but not defined", error.to_string());
+
+        assert!("define stem as 'a".parse::<Program>().is_err());
+        assert!("externals ( stem ) define stem as reverse ( 'a' )".parse::<Program>().is_err());
+    }
+}
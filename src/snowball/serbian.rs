@@ -0,0 +1,722 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Serbian stemmer.
+//!
+//! Serbian is written in both Cyrillic and Latin, so Cyrillic is first
+//! transliterated and stems are always in Latin: `књига` and `knjiga` stem
+//! alike. Ijekavian spellings between consonants are then written as
+//! ekavian, so `mlijeko` and `mleko` stem alike too.
+//!
+//! A few suffixes are first written in a more regular form, then the
+//! longest of some two thousand suffixes in R1 is replaced by what is left
+//! of it, or failing that, a plain ending in R1 is removed. Some suffixes
+//! are only looked for in words written without diacritics, where `c` may
+//! stand for `č` or `ć`.
+//!
+//! See <https://snowballstem.org/algorithms/serbian/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::serbian;
+//!
+//! assert_eq!("knjig", serbian::stem("knjigama"));
+//! assert_eq!("knjig", serbian::stem("књигама"));
+//! ```
+
+use snowball::{ends_with, suffix_start};
+
+/// Suffixes, each with what they are replaced with.
+type Rules = &'static [(&'static [&'static str], &'static str)];
+
+/// Suffixes written in a more regular form, with what they are written as.
+const STEP_1: Rules = &[
+    (&["lozima", "lozi"], "loga"),
+    (&["pesima", "pesi"], "peh"),
+    (&["vojci"], "vojka"),
+    (&["bojci"], "bojka"),
+    (&["jacima", "jaci"], "jak"),
+    (&["čajan"], "čajni"),
+    (&["eran"], "erni"),
+    (&["laran"], "larni"),
+    (&["esan"], "esni"),
+    (&["anjac"], "anjca"),
+    (&["ajaca", "ajac"], "ajca"),
+    (&["ljaca", "ljac"], "ljca"),
+    (&["ejaca", "ejac"], "ejca"),
+    (&["ojaca", "ojac"], "ojca"),
+    (&["ajaka"], "ajka"),
+    (&["ojaka"], "ojka"),
+    (&["šaca", "šac"], "šca"),
+    (&["inzima", "inzi"], "ing"),
+    (&["tvenici"], "tvenik"),
+    (&["teticima", "tetici"], "tetika"),
+    (&["nstava"], "nstva"),
+    (&["nicima"], "nik"),
+    (&["ticima"], "tik"),
+    (&["zicima"], "zik"),
+    (&["snici"], "snik"),
+    (&["kuse"], "kusi"),
+    (&["kusan"], "kusni"),
+    (&["kustava"], "kustva"),
+    (&["dušan"], "dušni"),
+    (&["antan"], "antni"),
+    (&["bilan"], "bilni"),
+    (&["tilan"], "tilni"),
+    (&["avilan"], "avilni"),
+    (&["silan"], "silni"),
+    (&["gilan"], "gilni"),
+    (&["rilan"], "rilni"),
+    (&["nilan"], "nilni"),
+    (&["alan"], "alni"),
+    (&["ozan"], "ozni"),
+    (&["rave"], "ravi"),
+    (&["stavan"], "stavni"),
+    (&["pravan"], "pravni"),
+    (&["tivan"], "tivni"),
+    (&["sivan"], "sivni"),
+    (&["atan"], "atni"),
+    (&["enat"], "enta"),
+    (&["tetan"], "tetni"),
+    (&["pletan"], "pletni"),
+    (&["šave"], "šavi"),
+    (&["anata"], "anta"),
+    (&["ačaka", "ačak"], "ačka"),
+    (&["ušak"], "uška"),
+    (&["ataka", "atcima", "atci", "atak"], "atka"),
+    (&["etaka", "etak"], "etka"),
+    (&["itaka", "itci", "itak"], "itka"),
+    (&["otaka", "otak"], "otka"),
+    (&["utaka", "utcima", "utci", "utak"], "utka"),
+    (&["eskan"], "eskna"),
+    (&["tičan"], "tični"),
+    (&["ojsci"], "ojska"),
+    (&["esama"], "esma"),
+    (&["metara", "metar"], "metra"),
+    (&["centara", "centar"], "centra"),
+    (&["istara", "istar"], "istra"),
+    (&["ošću"], "osti"),
+    (&["daba"], "dba"),
+    (&["čcima", "čci"], "čka"),
+    (&["maca", "mac"], "mca"),
+    (&["naca", "nac"], "nca"),
+    (&["voljan"], "voljni"),
+    (&["anaka"], "anki"),
+    (&["vaca", "vac"], "vca"),
+    (&["saca", "sac"], "sca"),
+    (&["raca", "rac"], "rca"),
+    (&["alaca", "aoca", "alac"], "alca"),
+    (&["elaca", "elac"], "elca"),
+    (&["olaca", "olac", "olce"], "olca"),
+    (&["njaca", "njac"], "njca"),
+    (&["ekata", "ekat"], "ekta"),
+    (&["izama", "izam"], "izma"),
+    (&["jebe"], "jebi"),
+    (&["baci"], "baci"),
+    (&["ašan"], "ašni"),
+];
+
+/// Like `STEP_1`, but only for words written without diacritics.
+const STEP_1_NO_DIACRITICS: Rules = &[
+    (&["cajan"], "cajni"),
+    (&["dusan"], "dusni"),
+    (&["save"], "savi"),
+    (&["acaka", "acak"], "acka"),
+    (&["usak"], "uska"),
+    (&["tican"], "ticni"),
+    (&["oscu"], "osti"),
+    (&["asan"], "asni"),
+];
+
+/// Suffixes replaced in R1, with what they are replaced with.
+const STEP_2: Rules = &[
+    (&[
+        "skijega", "skega", "skoga", "skija", "ska", "skijima", "skima", "skije", "ske", "skome",
+        "skijeg", "skog", "skijih", "skih", "skiji", "ski", "skijoj", "skoj", "skijem", "skem",
+        "skijim", "skim", "skom", "sko", "sku", "skijemu", "skemu", "skomu",
+    ], "sk"),
+    (&[
+        "škijega", "škega", "škoga", "škija", "ška", "škijima", "škima", "škije", "ške", "škome",
+        "škijeg", "škog", "škijih", "ških", "škiji", "ški", "škijoj", "škoj", "škijem", "škem",
+        "škijim", "škim", "škom", "ško", "šku", "škijemu", "škemu", "škomu",
+    ], "šk"),
+    (&["stvima", "stva", "stvom", "stvo", "stvu"], "stv"),
+    (&["štvima", "štva", "štvom", "štvo", "štvu"], "štv"),
+    (&["tanija", "tanijama", "tanijima", "tanije", "taniji", "tanijom", "taniju"], "tanij"),
+    (&["manija", "manijama", "manijima", "manije", "maniji", "manijom", "maniju"], "manij"),
+    (&["panija", "panijama", "panijima", "panije", "paniji", "panijom", "paniju"], "panij"),
+    (&["ranija", "ranijama", "ranijima", "ranije", "raniji", "ranijom", "raniju"], "ranij"),
+    (&["ganija", "ganijama", "ganijima", "ganije", "ganiji", "ganijom", "ganiju"], "ganij"),
+    (&[
+        "anega", "anima", "ana", "anina", "ane", "anine", "aneg", "ani", "anem", "anom", "aninom",
+        "anin", "anemu", "anu", "aninu",
+    ], "an"),
+    (&["inama", "inima", "ina", "ine", "ini", "inom", "ino", "inu"], "in"),
+    (&["onima", "onovima", "ona", "onova", "one", "onove", "oni", "onovi", "onom", "onu"], "on"),
+    (&[
+        "nijega", "nega", "noga", "nija", "nula", "nijima", "nima", "na", "nuta", "nije", "nule",
+        "nome", "ne", "nete", "nite", "nuste", "nijeg", "neg", "nog", "nijih", "nih", "niji",
+        "nuli", "ni", "nuti", "nući", "nuvši", "nijoj", "noj", "nijem", "nem", "nijim", "nim",
+        "nom", "nulo", "nemo", "nimo", "nusmo", "no", "nuto", "nuo", "nut", "niju", "nijemu",
+        "nemu", "nomu", "nu", "neš",
+    ], "n"),
+    (&[
+        "aćega", "aćoga", "aćima", "aća", "aćome", "aće", "aćeg", "aćog", "aćih", "aćuh", "aćoj",
+        "aćem", "aćim", "aćom", "aćemu", "aćomu",
+    ], "ać"),
+    (&[
+        "ećega", "ećoga", "ećima", "eća", "ećome", "eće", "ećeg", "ećog", "ećih", "ećuh", "ećoj",
+        "ećem", "ećim", "ećom", "ećemu", "ećomu",
+    ], "eć"),
+    (&[
+        "ućega", "ućoga", "ućima", "uća", "ućome", "uće", "ućeg", "ućog", "ućih", "ućuh", "ućoj",
+        "ućem", "ućim", "ućom", "ućemu", "ućomu",
+    ], "uć"),
+    (&["ugovima", "ugova", "ugove", "ugovi"], "ugov"),
+    (&["uga", "ugama", "uge", "ugi", "ugom", "ugo", "ugu"], "ug"),
+    (&["loga", "logama", "loge", "logom", "logu"], "log"),
+    (&["ga", "gama", "govima", "gova", "ge", "gove", "gi", "govi", "gom", "go", "gu"], "g"),
+    (&["rarija", "rarijem", "rario", "rariju"], "rari"),
+    (&["otija", "otijem", "otio", "otiju"], "oti"),
+    (&["sija", "sijem", "sio", "siju"], "si"),
+    (&["lija", "lijem", "lio", "liju"], "li"),
+    (&["uje", "ujete", "ujući", "ujem", "ujemo", "ujmo", "uju", "uješ"], "uj"),
+    (&[
+        "caja", "cajama", "cajima", "cajevima", "cajeva", "caje", "cajeve", "caji", "cajevi",
+        "cajem", "caju",
+    ], "caj"),
+    (&[
+        "čaja", "čajama", "čajima", "čajevima", "čajeva", "čaje", "čajeve", "čaji", "čajevi",
+        "čajem", "čaju",
+    ], "čaj"),
+    (&[
+        "ćaja", "ćajama", "ćajima", "ćajevima", "ćajeva", "ćaje", "ćajeve", "ćaji", "ćajevi",
+        "ćajem", "ćaju",
+    ], "ćaj"),
+    (&[
+        "đaja", "đajama", "đajima", "đajevima", "đajeva", "đaje", "đajeve", "đaji", "đajevi",
+        "đajem", "đaju",
+    ], "đaj"),
+    (&[
+        "laja", "lajama", "lajima", "lajevima", "lajeva", "laje", "lajeve", "laji", "lajevi",
+        "lajem", "laju",
+    ], "laj"),
+    (&[
+        "raja", "rajama", "rajima", "rajevima", "rajeva", "raje", "rajeve", "raji", "rajevi",
+        "rajem", "raju",
+    ], "raj"),
+    (&["bija", "bijama", "bijima", "bije", "biji", "bijom", "bijo", "biju"], "bij"),
+    (&["cija", "cijama", "cijima", "cije", "ciji", "cijom", "cijo", "ciju"], "cij"),
+    (&["dija", "dijama", "dijima", "dije", "diji", "dijom", "dijo", "diju"], "dij"),
+    (&["lijama", "lijima", "lije", "liji", "lijom", "lijo"], "lij"),
+    (&["nijama", "nijom", "nijo"], "nij"),
+    (&["mija", "mijama", "mijima", "mije", "miji", "mijom", "mijo", "miju"], "mij"),
+    (&["žija", "žijama", "žijima", "žije", "žiji", "žijom", "žijo", "žiju"], "žij"),
+    (&["gija", "gijama", "gijima", "gije", "giji", "gijom", "gijo", "giju"], "gij"),
+    (&["fija", "fijama", "fijima", "fije", "fiji", "fijom", "fijo", "fiju"], "fij"),
+    (&["pija", "pijama", "pijima", "pije", "piji", "pijom", "pijo", "piju"], "pij"),
+    (&["rija", "rijama", "rijima", "rije", "riji", "rijom", "rijo", "riju"], "rij"),
+    (&["sijama", "sijima", "sije", "siji", "sijom", "sijo"], "sij"),
+    (&["tija", "tijama", "tijima", "tije", "tiji", "tijom", "tijo", "tiju"], "tij"),
+    (&["zija", "zijama", "zijima", "zije", "ziji", "zijom", "zijo", "ziju"], "zij"),
+    (&["nala", "nalama", "nalima", "nale", "nali", "nalom", "nalo", "nalu"], "nal"),
+    (&["ijala", "ijalama", "ijalima", "ijale", "ijali", "ijalom", "ijalo", "ijalu"], "ijal"),
+    (&["ozila", "ozilima", "ozile", "ozili", "ozilom", "ozilu"], "ozil"),
+    (&["olovima", "olova", "olove", "olovi"], "olov"),
+    (&["ola", "olima", "ole", "oli", "olom", "olu"], "ol"),
+    (&["lemama", "lema", "lemima", "leme", "lemi", "lemom", "lemo", "lemu"], "lem"),
+    (&["ramama", "rama", "rame", "rami", "ramom", "ramo", "ramu"], "ram"),
+    (&["arama", "arima", "ara", "are", "ari", "arom", "aru"], "ar"),
+    (&["drama", "drima", "dra", "dre", "dri", "drom", "dru"], "dr"),
+    (&["erama", "erima", "era", "ere", "eri", "erom", "eru"], "er"),
+    (&["orama", "orima", "ora", "ore", "ori", "orom", "oru"], "or"),
+    (&["esima", "esa", "ese", "esom", "esu"], "es"),
+    (&["isima", "isa", "ise", "isom", "isu"], "is"),
+    (&["tašama", "tašima", "taša", "taše", "taši", "tašem", "tašom", "tašu"], "taš"),
+    (&["našama", "našima", "naša", "naše", "naši", "našem", "našom", "našu"], "naš"),
+    (&["jašama", "jašima", "jaša", "jaše", "jaši", "jašem", "jašom", "jašu"], "jaš"),
+    (&["kašama", "kašima", "kaša", "kaše", "kaši", "kašem", "kašom", "kašu"], "kaš"),
+    (&["bašama", "bašima", "baša", "baše", "baši", "bašem", "bašom", "bašu"], "baš"),
+    (&["gašama", "gašima", "gaša", "gaše", "gaši", "gašem", "gašom", "gašu"], "gaš"),
+    (&["vašama", "vašima", "vaša", "vaše", "vaši", "vašem", "vašom", "vašu"], "vaš"),
+    (&["ešama", "ešima", "eša", "eše", "eši", "ešem", "ešom", "ešu"], "eš"),
+    (&["išama", "išima", "iša", "iše", "iši", "išem", "išom", "išu"], "iš"),
+    (&["ikatima", "ikata", "ikate", "ikati", "ikatom", "ikato", "ikatu"], "ikat"),
+    (&["latima", "lata", "late", "lati", "latom", "lato", "latu"], "lat"),
+    (&[
+        "etama", "etima", "eta", "ete", "eti", "etući", "etavši", "etem", "etom", "etemo", "etimo",
+        "eto", "etu", "eteš",
+    ], "et"),
+    (&["estama", "estima", "esta", "este", "esti", "estom", "esto", "estu"], "est"),
+    (&["istama", "istima", "ista", "iste", "isti", "istom", "isto", "istu"], "ist"),
+    (&["kstama", "kstima", "ksta", "kste", "ksti", "kstom", "ksto", "kstu"], "kst"),
+    (&["ostama", "ostima", "osta", "oste", "osti", "ostom", "osto", "ostu"], "ost"),
+    (&["ištima", "išta", "ište", "ištem", "ištu"], "išt"),
+    (&[
+        "ovala", "ovale", "ovaste", "ovaše", "ovah", "ovali", "ovati", "ovao", "ovalo", "ovasmo",
+        "ovat", "ovahu",
+    ], "ova"),
+    (&[
+        "avijega", "avega", "avoga", "avija", "avama", "avijima", "avima", "ava", "avije", "avome",
+        "ave", "avijeg", "aveg", "avog", "avijih", "avih", "aviji", "avi", "avijoj", "avoj",
+        "avijem", "avem", "avijim", "avim", "avom", "avo", "aviju", "avijemu", "avemu", "avomu",
+        "avu",
+    ], "av"),
+    (&[
+        "evijega", "evega", "evoga", "evija", "evama", "evijima", "evima", "eva", "evije", "evome",
+        "eve", "evijeg", "eveg", "evog", "evijih", "evih", "eviji", "evi", "evijoj", "evoj",
+        "evijem", "evem", "evijim", "evim", "evom", "evo", "eviju", "evijemu", "evemu", "evomu",
+        "evu",
+    ], "ev"),
+    (&[
+        "ivijega", "ivega", "ivoga", "ivija", "ivama", "ivijima", "ivima", "iva", "ivije", "ivome",
+        "ive", "ivijeg", "iveg", "ivog", "ivijih", "ivih", "iviji", "ivi", "ivijoj", "ivoj",
+        "ivijem", "ivem", "ivijim", "ivim", "ivom", "ivo", "iviju", "ivijemu", "ivemu", "ivomu",
+        "ivu",
+    ], "iv"),
+    (&[
+        "ovijega", "ovega", "ovoga", "ovija", "ovijima", "ovima", "ova", "ovije", "ovome", "ove",
+        "ovijeg", "oveg", "ovog", "ovijih", "ovih", "oviji", "ovi", "ovijoj", "ovoj", "ovijem",
+        "ovijim", "ovim", "ovom", "ovo", "oviju", "ovijemu", "ovemu", "ovomu", "ovu",
+    ], "ov"),
+    (&["movima", "mova", "move", "movi", "movom", "movu"], "mov"),
+    (&["lovima", "lova", "love", "lovi", "lovom", "lovu"], "lov"),
+    (&[
+        "elijega", "elega", "eloga", "elija", "ela", "elijima", "elima", "elije", "ele", "elome",
+        "elijeg", "eleg", "elog", "elijih", "elih", "eliji", "eli", "elijoj", "eloj", "elijem",
+        "elem", "elijim", "elim", "elom", "elo", "eliju", "elu", "elijemu", "elemu", "elomu",
+    ], "el"),
+    (&[
+        "anjijega", "anjega", "anjoga", "anjija", "anja", "anjijima", "anjima", "anjije", "anje",
+        "anjome", "anjijeg", "anjeg", "anjog", "anjijih", "anjih", "anjiji", "anji", "anjijoj",
+        "anjoj", "anjijem", "anjem", "anjijim", "anjim", "anjom", "anjo", "anjiju", "anju",
+        "anjijemu", "anjemu", "anjomu",
+    ], "anj"),
+    (&[
+        "enjijega", "enjega", "enjoga", "enjija", "enja", "enjijima", "enjima", "enjije", "enje",
+        "enjome", "enjijeg", "enjeg", "enjog", "enjijih", "enjih", "enjiji", "enji", "enjijoj",
+        "enjoj", "enjijem", "enjem", "enjijim", "enjim", "enjom", "enjo", "enjiju", "enju",
+        "enjijemu", "enjemu", "enjomu",
+    ], "enj"),
+    (&[
+        "šnjijega", "šnjega", "šnjoga", "šnjija", "šnja", "šnjijima", "šnjima", "šnjije", "šnje",
+        "šnjome", "šnjijeg", "šnjeg", "šnjog", "šnjijih", "šnjih", "šnjiji", "šnji", "šnjijoj",
+        "šnjoj", "šnjijem", "šnjem", "šnjijim", "šnjim", "šnjom", "šnjo", "šnjiju", "šnju",
+        "šnjijemu", "šnjemu", "šnjomu",
+    ], "šnj"),
+    (&["enega", "eneg", "enem", "enemu"], "en"),
+    (&["šnega", "šneg", "šnem", "šnemu"], "šn"),
+    (&[
+        "činoga", "činama", "činima", "čina", "činome", "čine", "činog", "činih", "čini", "činoj",
+        "činim", "činom", "čino", "činomu", "činu",
+    ], "čin"),
+    (&[
+        "rošila", "rošile", "rošite", "rošiste", "rošiše", "roših", "rošili", "rošiti", "rošivši",
+        "rošim", "rošio", "rošilo", "rošimo", "rošismo", "rošit", "rošiš",
+    ], "roši"),
+    (&[
+        "ošijega", "ošega", "ošoga", "ošija", "ošijima", "ošima", "oša", "ošije", "ošome", "oše",
+        "ošijeg", "ošeg", "ošog", "ošijih", "oših", "ošiji", "oši", "ošijoj", "ošoj", "ošijem",
+        "ošem", "ošijim", "ošim", "ošom", "ošiju", "ošijemu", "ošemu", "ošomu", "ošu",
+    ], "oš"),
+    (&[
+        "evitijega", "evitega", "evitoga", "evitija", "evitijima", "evitima", "evita", "evitije",
+        "evitome", "evite", "evitijeg", "evitog", "evitijih", "evitih", "evitiji", "eviti",
+        "evitijoj", "evitoj", "evitijem", "evitem", "evitijim", "evitim", "evitom", "evito",
+        "evitijemu", "evitemu", "evitomu", "evitu",
+    ], "evit"),
+    (&[
+        "ovitijega", "ovitega", "ovitoga", "ovitija", "ovitijima", "ovitima", "ovita", "ovitije",
+        "ovitome", "ovite", "ovitijeg", "ovitog", "ovitijih", "ovitih", "ovitiji", "oviti",
+        "ovitijoj", "ovitoj", "ovitijem", "ovitem", "ovitijim", "ovitim", "ovitom", "ovito",
+        "ovitijemu", "ovitemu", "ovitomu", "ovitu",
+    ], "ovit"),
+    (&[
+        "astijega", "astega", "astoga", "astija", "astijima", "astima", "asta", "astije", "astome",
+        "aste", "astijeg", "astog", "astijih", "astih", "astiji", "asti", "astijoj", "astoj",
+        "astijem", "astem", "astijim", "astim", "astom", "asto", "astijemu", "astemu", "astomu",
+        "astu",
+    ], "ast"),
+    (&[
+        "kijega", "kega", "koga", "kija", "ka", "kijima", "kima", "kije", "ke", "kome", "kijeg",
+        "keg", "kog", "kijih", "kih", "kiji", "ki", "kijoj", "koj", "kijem", "kem", "kijim", "kim",
+        "kom", "ko", "kiju", "ku", "kijemu", "kemu", "komu", "kov",
+    ], "k"),
+    (&[
+        "evala", "evana", "evale", "evane", "evate", "evajte", "evaste", "evaše", "evah", "evali",
+        "evani", "evati", "evajući", "evaj", "evam", "evan", "evao", "evalo", "evamo", "evajmo",
+        "evasmo", "evano", "evat", "evahu", "evaju", "evav", "evaš",
+    ], "eva"),
+    (&[
+        "avala", "avana", "avale", "avane", "avate", "avajte", "avaste", "avaše", "avah", "avali",
+        "avani", "avati", "avajući", "avaj", "avam", "avan", "avao", "avalo", "avamo", "avajmo",
+        "avasmo", "avano", "avat", "avahu", "avaju", "avav", "avaš",
+    ], "ava"),
+    (&[
+        "ivala", "ivana", "ivale", "ivane", "ivate", "ivajte", "ivaste", "ivaše", "ivah", "ivali",
+        "ivani", "ivati", "ivajući", "ivaj", "ivam", "ivan", "ivao", "ivalo", "ivamo", "ivajmo",
+        "ivasmo", "ivano", "ivat", "ivahu", "ivaju", "ivav", "ivaš",
+    ], "iva"),
+    (&[
+        "uvala", "uvana", "uvale", "uvane", "uvate", "uvajte", "uvaste", "uvaše", "uvah", "uvali",
+        "uvani", "uvati", "uvajući", "uvaj", "uvam", "uvan", "uvao", "uvalo", "uvamo", "uvajmo",
+        "uvasmo", "uvano", "uvat", "uvahu", "uvaju", "uvav", "uvaš",
+    ], "uva"),
+    (&[
+        "irala", "iruje", "irale", "irate", "irujete", "irujte", "iraste", "iraše", "irah", "irali",
+        "irati", "irajući", "irujući", "iravši", "iruj", "iral", "iram", "irujem", "iran", "irao",
+        "iralo", "iramo", "irujemo", "irujmo", "irasmo", "irat", "irivat", "irahu", "iraju",
+        "iruju", "iraš", "iruješ",
+    ], "ir"),
+    (&[
+        "ačila", "ačena", "ačile", "ačene", "ačite", "ačiste", "ače", "ačiše", "ačih", "ačili",
+        "ačeni", "ačiti", "ačeći", "ači", "ačim", "ačen", "ačio", "ačilo", "ačimo", "ačismo",
+        "ačeno", "ačit", "ačiš",
+    ], "ač"),
+    (&[
+        "ačala", "ačana", "ačale", "ačane", "ačate", "ačaste", "ačaše", "ačah", "ačali", "ačani",
+        "ačati", "ačavši", "ačam", "ačan", "ačao", "ačalo", "ačamo", "ačasmo", "ačano", "ačat",
+        "ačahu", "ačaju", "ačaš",
+    ], "ača"),
+    (&["nila", "nile", "niste", "nili", "niti", "nivši", "nio", "nilo", "nismo", "niš"], "ni"),
+    (&[
+        "ala", "alama", "alima", "aje", "ale", "ate", "ajte", "aše", "ah", "ali", "ati", "ajući",
+        "avši", "aj", "aloj", "al", "am", "ajem", "alom", "an", "ao", "alo", "amo", "ajmo", "asmo",
+        "ano", "at", "ahu", "aju", "alu", "aš",
+    ], "a"),
+    (&[
+        "urala", "urana", "urale", "urane", "urate", "urajte", "uraste", "uraše", "urah", "urali",
+        "urani", "urati", "urajući", "uraj", "ural", "uram", "uran", "urao", "uralo", "uramo",
+        "urajmo", "urasmo", "urano", "urat", "urahu", "uraju", "uraš",
+    ], "ur"),
+    (&[
+        "astajala", "astaje", "astajale", "astajete", "astajte", "astajaste", "astajaše", "astajah",
+        "astajali", "astajati", "astajući", "astajem", "astajao", "astajalo", "astajemo", "astajmo",
+        "astajasmo", "astajat", "astajahu", "astaju", "astaješ",
+    ], "astaj"),
+    (&[
+        "istajala", "istaje", "istajale", "istajete", "istajte", "istajaste", "istajaše", "istajah",
+        "istajali", "istajati", "istajući", "istajem", "istajao", "istajalo", "istajemo", "istajmo",
+        "istajasmo", "istajat", "istajahu", "istaju", "istaješ",
+    ], "istaj"),
+    (&[
+        "ostajala", "ostaje", "ostajale", "ostajete", "ostajte", "ostajaste", "ostajaše", "ostajah",
+        "ostajali", "ostajati", "ostajući", "ostajem", "ostajao", "ostajalo", "ostajemo", "ostajmo",
+        "ostajasmo", "ostajat", "ostajahu", "ostaju", "ostaješ",
+    ], "ostaj"),
+    (&["aja", "ajama", "ajima", "ajevima", "ajeva", "ajeve", "aji", "ajevi"], "aj"),
+    (&[
+        "astala", "astanila", "astade", "astale", "astane", "astadete", "astanete", "astanite",
+        "astadoste", "astadoše", "astadoh", "astali", "astati", "astavši", "astadem", "astanem",
+        "astao", "astalo", "astademo", "astanemo", "astanimo", "astadosmo", "astat", "astadu",
+        "astanu", "astadeš", "astaneš",
+    ], "asta"),
+    (&[
+        "istala", "istanila", "istade", "istale", "istane", "istadete", "istanete", "istanite",
+        "istadoste", "istadoše", "istadoh", "istali", "istati", "istavši", "istadem", "istanem",
+        "istao", "istalo", "istademo", "istanemo", "istanimo", "istadosmo", "istat", "istadu",
+        "istanu", "istadeš", "istaneš",
+    ], "ista"),
+    (&[
+        "ostala", "ostanila", "ostade", "ostale", "ostane", "ostadete", "ostanete", "ostanite",
+        "ostadoste", "ostadoše", "ostadoh", "ostali", "ostati", "ostavši", "ostadem", "ostanem",
+        "ostao", "ostalo", "ostademo", "ostanemo", "ostanimo", "ostadosmo", "ostat", "ostadu",
+        "ostanu", "ostadeš", "ostaneš",
+    ], "osta"),
+    (&[
+        "tala", "tana", "tale", "tane", "tate", "tajte", "taste", "tah", "tali", "tani", "tati",
+        "tavši", "taj", "tam", "tan", "tao", "talo", "tamo", "tajmo", "tasmo", "tano", "tat",
+        "taju", "taš",
+    ], "ta"),
+    (&[
+        "injala", "injale", "injete", "injaste", "injaše", "injah", "injali", "injati", "injem",
+        "injao", "injalo", "injemo", "injasmo", "injat", "injahu", "inješ",
+    ], "inj"),
+    (&[
+        "asla", "asle", "astete", "astite", "asli", "astući", "aslo", "astemo", "astimo", "asteš",
+    ], "as"),
+    (&[
+        "ila", "ilama", "ilima", "ije", "ile", "ijete", "ite", "ijte", "ih", "ili", "iti", "ieći",
+        "ivši", "ij", "il", "ijem", "im", "ilom", "io", "ilo", "ijemo", "imo", "ijmo", "ismo", "it",
+        "iju", "ilu", "iješ", "iš",
+    ], "i"),
+    (&[
+        "lučila", "lučena", "lučuje", "lučile", "lučene", "lučujete", "lučite", "lučujte",
+        "lučiste", "luče", "lučiše", "lučih", "lučili", "lučeni", "lučiti", "lučujući", "luči",
+        "lučuj", "lučujem", "lučim", "lučen", "lučio", "lučilo", "lučujemo", "lučimo", "lučujmo",
+        "lučismo", "lučeno", "lučit", "lučuju", "lučuješ", "lučiš",
+    ], "luč"),
+    (&[
+        "jetila", "jetile", "jetite", "jetiste", "jetiše", "jetih", "jetili", "jetiti", "jetim",
+        "jetio", "jetilo", "jetimo", "jetismo", "jetit", "jetiš",
+    ], "jeti"),
+    (&["elama", "el", "em", "emo", "eš"], "e"),
+    (&[
+        "atijega", "atega", "atoga", "atija", "atijima", "atima", "ata", "atije", "atome", "atijeg",
+        "ateg", "atog", "atijih", "atih", "atiji", "atoj", "atijem", "atem", "atijim", "atim",
+        "atom", "ato", "atiju", "atijemu", "atemu", "atomu", "atu",
+    ], "at"),
+];
+
+/// Like `STEP_2`, but only for words written without diacritics.
+const STEP_2_NO_DIACRITICS: Rules = &[
+    (&[
+        "lucila", "lucena", "luce", "lucuje", "lucile", "lucene", "lucise", "lucujete", "lucite",
+        "lucujte", "luciste", "lucih", "lucujuci", "luci", "lucili", "luceni", "luciti", "lucuj",
+        "lucujem", "lucim", "lucen", "lucio", "lucilo", "lucujemo", "lucimo", "lucujmo", "lucismo",
+        "luceno", "lucujes", "lucis", "lucit", "lucuju",
+    ], "luc"),
+    (&[
+        "snjijega", "snjega", "snjoga", "snjija", "snja", "snjijima", "snjima", "snjije", "snje",
+        "snjome", "snjijeg", "snjeg", "snjog", "snjijih", "snjih", "snjiji", "snji", "snjijoj",
+        "snjoj", "snjem", "snjijim", "snjim", "snjom", "snjo", "snjiju", "snju", "snjijemu",
+        "snjemu", "snjomu",
+    ], "snj"),
+    (&[
+        "osijega", "osega", "osoga", "osija", "osijima", "osima", "osa", "osije", "osome", "ose",
+        "osijeg", "oseg", "osog", "osijih", "osih", "osiji", "osi", "osijoj", "osoj", "snjijem",
+        "osijem", "osem", "osijim", "osim", "osom", "osiju", "osijemu", "osemu", "osomu", "osu",
+    ], "os"),
+    (&[
+        "aca", "acega", "acoga", "acila", "acima", "acena", "ace", "acile", "acome", "acene",
+        "acise", "acite", "aciste", "aceg", "acog", "acih", "acuh", "aci", "aceci", "acili",
+        "aceni", "aciti", "acoj", "acem", "acim", "acom", "acen", "acio", "acilo", "acimo",
+        "acismo", "aceno", "acis", "acit", "acemu", "acomu",
+    ], "ac"),
+    (&[
+        "eca", "ecega", "ecoga", "ecima", "ece", "ecome", "eceg", "ecog", "ecih", "ecuh", "ecoj",
+        "ecem", "ecim", "ecom", "ecemu", "ecomu",
+    ], "ec"),
+    (&[
+        "uca", "ucega", "ucoga", "ucima", "uce", "ucome", "uceg", "ucog", "ucih", "ucuh", "ucoj",
+        "ucem", "ucim", "ucom", "ucemu", "ucomu",
+    ], "uc"),
+    (&[
+        "rosila", "rosile", "rosise", "rosite", "rosiste", "rosih", "rosili", "rosivsi", "rositi",
+        "rosim", "rosio", "rosilo", "rosimo", "rosismo", "rosis", "rosit",
+    ], "rosi"),
+    (&[
+        "acala", "acana", "acale", "acane", "acase", "acate", "acaste", "acah", "acali", "acani",
+        "acavsi", "acati", "acam", "acan", "acao", "acalo", "acamo", "acasmo", "acano", "acas",
+        "acat", "acahu", "acaju",
+    ], "aca"),
+    (&["jasama", "jasima", "jasa", "jase", "jasi", "jasem", "jasom", "jasu"], "jas"),
+    (&["tasama", "tasima", "tasa", "tase", "tasi", "tasem", "tasom", "tasu"], "tas"),
+    (&["gasama", "gasima", "gasa", "gase", "gasi", "gasem", "gasom", "gasu"], "gas"),
+    (&["nasama", "nasima", "nasa", "nase", "nasi", "nasem", "nasom", "nasu"], "nas"),
+    (&["kasama", "kasima", "kasa", "kase", "kasi", "kasem", "kasom", "kasu"], "kas"),
+    (&["vasama", "vasima", "vasa", "vase", "vasi", "vasem", "vasom", "vasu"], "vas"),
+    (&["basama", "basima", "basa", "base", "basi", "basem", "basom", "basu"], "bas"),
+    (&["astuci", "astes"], "as"),
+    (&[
+        "cinoga", "cinama", "cinima", "cina", "cinome", "cine", "cinog", "cinih", "cini", "cinoj",
+        "cinim", "cinom", "cino", "cinomu", "cinu",
+    ], "cin"),
+    (&["astajase", "astajuci", "astajes"], "astaj"),
+    (&["istajase", "istajuci", "istajes"], "istaj"),
+    (&["ostajase", "ostajuci", "ostajes"], "ostaj"),
+    (&["astadose", "astavsi", "astades", "astanes"], "asta"),
+    (&["istadose", "istavsi", "istades", "istanes"], "ista"),
+    (&["ostadose", "ostavsi", "ostades", "ostanes"], "osta"),
+    (&["avase", "avajuci", "avas"], "ava"),
+    (&["evase", "evajuci", "evas"], "eva"),
+    (&["ivase", "ivajuci", "ivas"], "iva"),
+    (&["uvase", "uvajuci", "uvas"], "uva"),
+    (&["ovase"], "ova"),
+    (&["jetise", "jetis"], "jeti"),
+    (&["injase", "injes"], "inj"),
+    (&["istem"], "ist"),
+    (&["esama", "esi", "esem"], "es"),
+    (&["etuci", "etavsi", "etes"], "et"),
+    (&["isama", "isi", "isem"], "is"),
+    (&["irase", "irajuci", "irujuci", "iravsi", "iras", "irujes"], "ir"),
+    (&["urase", "urajuci", "uras"], "ur"),
+    (&["ujuci", "ujes"], "uj"),
+    (&["nivsi", "nis"], "ni"),
+    (&["snega", "sneg", "snem", "snemu"], "sn"),
+    (&["tavsi", "tas"], "ta"),
+    (&["ase", "ajuci", "avsi", "as"], "a"),
+    (&["ieci", "ivsi", "ijes", "is"], "i"),
+    (&["es"], "e"),
+    (&["nuci", "nuvsi", "nes"], "n"),
+];
+
+/// Endings removed in R1 when there was no suffix for `STEP_2`.
+const STEP_3: &[&str] = &[
+    "a", "oga", "ama", "ima", "ena", "e", "og", "anog", "enog", "anih", "enih", "i", "ani", "eni",
+    "anoj", "enoj", "anim", "enim", "om", "enom", "o", "ano", "eno", "ost", "u", "enu",
+];
+
+const CYRILLIC: &[(char, &str)] = &[
+    ('а', "a"), ('б', "b"), ('в', "v"), ('г', "g"), ('д', "d"), ('ђ', "đ"), ('е', "e"), ('ж', "ž"),
+    ('з', "z"), ('и', "i"), ('ј', "j"), ('к', "k"), ('л', "l"), ('љ', "lj"), ('м', "m"), ('н', "n"),
+    ('њ', "nj"), ('о', "o"), ('п', "p"), ('р', "r"), ('с', "s"), ('т', "t"), ('ћ', "ć"), ('у', "u"),
+    ('ф', "f"), ('х', "h"), ('ц', "c"), ('ч', "č"), ('џ', "dž"), ('ш', "š"),
+];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+fn is_consonant(c: char) -> bool {
+    matches!(c, 'b'..='d' | 'f'..='h' | 'j'..='n' | 'p' | 'r'..='t' | 'v' | 'z')
+        || is_diacritic(c)
+}
+
+fn is_diacritic(c: char) -> bool {
+    matches!(c, 'ć' | 'č' | 'đ' | 'š' | 'ž')
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = prelude(word);
+
+    let no_diacritics = !word.iter().cloned().any(is_diacritic);
+    let r1 = r1(&word);
+
+    let step_1 = longest(&word, STEP_1, STEP_1_NO_DIACRITICS, no_diacritics);
+    if let Some((suffix, replacement)) = step_1 {
+        replace(&mut word, suffix, replacement);
+    }
+
+    match longest(&word, STEP_2, STEP_2_NO_DIACRITICS, no_diacritics) {
+        Some((suffix, replacement)) if suffix_start(&word, suffix) >= r1 => {
+            replace(&mut word, suffix, replacement);
+        }
+        _ => {
+            let ending = STEP_3.iter().filter(|ending| ends_with(&word, ending))
+                .max_by_key(|ending| ending.chars().count());
+            if let Some(ending) = ending {
+                let start = suffix_start(&word, ending);
+                if start >= r1 {
+                    word.truncate(start);
+                }
+            }
+        }
+    }
+
+    word.into_iter().collect()
+}
+
+/// The region after the first vowel, or after the consonant that follows it
+/// if the word starts with a vowel. A word with a syllabic `r`, like `prst`,
+/// has the region after the `r` if that starts more than a letter earlier.
+fn r1(word: &[char]) -> usize {
+    let mut r1 = match word.iter().position(|&c| is_vowel(c)) {
+        Some(0) => word.iter().skip(1).position(|&c| !is_vowel(c)).map_or(1, |index| index + 2),
+        Some(index) => index + 1,
+        None => word.len(),
+    };
+
+    if let Some(r) = word.iter().position(|&c| c == 'r') {
+        let after = match r {
+            0 => word.iter().skip(1).position(|&c| c != 'r').map(|index| index + 2),
+            r => Some(r + 1),
+        };
+        if let Some(after) = after {
+            if r1 > after + 1 {
+                r1 = after;
+            }
+        }
+    }
+
+    r1
+}
+
+/// The longest suffix of `word` in `rules`, or in `no_diacritics_rules` if
+/// the word has no diacritics, and what it is replaced with. A suffix only
+/// in `no_diacritics_rules` still hides shorter ones otherwise.
+fn longest(
+    word: &[char],
+    rules: Rules,
+    no_diacritics_rules: Rules,
+    no_diacritics: bool,
+) -> Option<(&'static str, &'static str)> {
+    let found = rules.iter().map(|rule| (rule, true))
+        .chain(no_diacritics_rules.iter().map(|rule| (rule, no_diacritics)))
+        .flat_map(|(&(suffixes, replacement), allowed)| {
+            suffixes.iter().map(move |&suffix| (suffix, replacement, allowed))
+        })
+        .filter(|&(suffix, _, _)| ends_with(word, suffix))
+        .max_by_key(|&(suffix, _, _)| suffix.chars().count());
+
+    match found {
+        Some((suffix, replacement, true)) => Some((suffix, replacement)),
+        _ => None,
+    }
+}
+
+fn replace(word: &mut Vec<char>, suffix: &str, replacement: &str) {
+    let start = suffix_start(word, suffix);
+    word.truncate(start);
+    word.extend(replacement.chars());
+}
+
+/// Transliterate Cyrillic into Latin, write `ije` and then `je` between
+/// consonants as `e`, and write `dj` as `đ`.
+fn prelude(word: &str) -> Vec<char> {
+    let mut latin = String::with_capacity(word.len());
+
+    for c in word.chars() {
+        match CYRILLIC.iter().find(|&&(cyrillic, _)| cyrillic == c) {
+            Some(&(_, letters)) => latin.push_str(letters),
+            None => latin.push(c),
+        }
+    }
+
+    let mut word = latin.chars().collect::<Vec<char>>();
+    ekavian(&mut word, &['i', 'j', 'e']);
+    ekavian(&mut word, &['j', 'e']);
+
+    word.into_iter().collect::<String>().replace("dj", "đ").chars().collect()
+}
+
+/// Write `ijekavian` between two consonants as `e`.
+fn ekavian(word: &mut Vec<char>, ijekavian: &[char]) {
+    let mut index = 1;
+
+    while index + ijekavian.len() < word.len() {
+        let end = index + ijekavian.len();
+        let between = is_consonant(word[index - 1]) && is_consonant(word[end]);
+        if between && word[index..end] == *ijekavian {
+            word.splice(index..end, Some('e'));
+        }
+        index += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prelude() {
+        assert_eq!("knjiga".chars().collect::<Vec<char>>(), prelude("књига"));
+        assert_eq!("mleko".chars().collect::<Vec<char>>(), prelude("mlijeko"));
+        assert_eq!("đak".chars().collect::<Vec<char>>(), prelude("djak"));
+        assert_eq!("džep".chars().collect::<Vec<char>>(), prelude("џеп"));
+        assert_eq!("moje".chars().collect::<Vec<char>>(), prelude("moje"));
+        assert_eq!("znanje".chars().collect::<Vec<char>>(), prelude("знање"));
+        assert_eq!("leto".chars().collect::<Vec<char>>(), prelude("ljeto"));
+    }
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("knjiga", "knjig"),
+            ("књига", "knjig"),
+            ("knjige", "knjig"),
+            ("gradovima", "gradov"),
+            ("градови", "gradov"),
+            ("radio", "radi"),
+            ("čitati", "čita"),
+            ("mlijeko", "mlek"),
+            ("mleko", "mlek"),
+            ("prst", "prst"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
@@ -0,0 +1,232 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Spanish stemmer.
+//!
+//! See <http://snowball.tartarus.org/algorithms/spanish/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::spanish;
+//!
+//! assert_eq!("absolut", spanish::stem("absolutamente"));
+//! assert_eq!("com", spanish::stem("comiéndolo"));
+//! ```
+
+use snowball::{after_vowel_consonant, ends_with, longest_suffix, remove_longest_in, replace_suffix, romance_rv, suffix_start};
+
+const PRONOUNS: &[&str] = &[
+    "me", "se", "sela", "selo", "selas", "selos", "la", "le", "lo", "las", "les", "los", "nos",
+];
+
+/// Verb endings an attached pronoun can follow, and what they become once it
+/// is removed.
+const PRONOUN_ENDINGS: &[(&str, &str)] = &[
+    ("iéndo", "iendo"), ("ándo", "ando"), ("ár", "ar"), ("ér", "er"), ("ír", "ir"),
+    ("ando", "ando"), ("iendo", "iendo"), ("ar", "ar"), ("er", "er"), ("ir", "ir"),
+    ("yendo", "yendo"),
+];
+
+const STEP_1: &[&str] = &[
+    "anza", "anzas", "ico", "ica", "icos", "icas", "ismo", "ismos", "able", "ables", "ible",
+    "ibles", "ista", "istas", "oso", "osa", "osos", "osas", "amiento", "amientos", "imiento",
+    "imientos",
+    "adora", "ador", "ación", "adoras", "adores", "aciones", "ante", "antes", "ancia", "ancias",
+    "logía", "logías",
+    "ución", "uciones",
+    "encia", "encias",
+    "amente",
+    "mente",
+    "idad", "idades",
+    "iva", "ivo", "ivas", "ivos",
+];
+
+const STEP_2A: &[&str] = &[
+    "ya", "ye", "yan", "yen", "yeron", "yendo", "yo", "yó", "yas", "yes", "yais", "yamos",
+];
+
+const STEP_2B: &[&str] = &[
+    "en", "es", "éis", "emos",
+    "arían", "arías", "arán", "arás", "aríais", "aría", "aréis", "aríamos", "aremos", "ará",
+    "aré", "erían", "erías", "erán", "erás", "eríais", "ería", "eréis", "eríamos", "eremos",
+    "erá", "eré", "irían", "irías", "irán", "irás", "iríais", "iría", "iréis", "iríamos",
+    "iremos", "irá", "iré", "aba", "ada", "ida", "ía", "ara", "iera", "ad", "ed", "id", "ase",
+    "iese", "aste", "iste", "an", "aban", "ían", "aran", "ieran", "asen", "iesen", "aron",
+    "ieron", "ado", "ido", "ando", "iendo", "ió", "ar", "er", "ir", "as", "abas", "adas", "idas",
+    "ías", "aras", "ieras", "ases", "ieses", "ís", "áis", "abais", "íais", "arais", "ierais",
+    "aseis", "ieseis", "asteis", "isteis", "ados", "idos", "amos", "ábamos", "íamos", "imos",
+    "áramos", "iéramos", "iésemos", "ásemos",
+];
+
+const STEP_3: &[&str] = &["os", "a", "o", "á", "í", "ó", "e", "é"];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'á' | 'é' | 'í' | 'ó' | 'ú' | 'ü')
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = word.chars().collect::<Vec<char>>();
+
+    let rv = romance_rv(&word, is_vowel);
+    let r1 = after_vowel_consonant(&word, 0, is_vowel);
+    let r2 = after_vowel_consonant(&word, r1, is_vowel);
+
+    step_0(&mut word, rv);
+
+    if !step_1(&mut word, r1, r2) && !step_2a(&mut word, rv) {
+        step_2b(&mut word, rv);
+    }
+
+    step_3(&mut word, rv);
+
+    word.into_iter()
+        .map(|c| match c {
+            'á' => 'a',
+            'é' => 'e',
+            'í' => 'i',
+            'ó' => 'o',
+            'ú' => 'u',
+            c => c,
+        })
+        .collect()
+}
+
+/// Remove a pronoun attached to the end of a gerund or infinitive, as in
+/// `comiéndolo`.
+fn step_0(word: &mut Vec<char>, rv: usize) {
+    let pronoun = match longest_suffix(word, 0, PRONOUNS) {
+        Some(pronoun) => pronoun,
+        None => return,
+    };
+    let start = suffix_start(word, pronoun);
+
+    let verb = &word[..start];
+    let ending = PRONOUN_ENDINGS.iter()
+        .filter(|&&(ending, _)| ends_with(verb, ending) && suffix_start(verb, ending) >= rv)
+        .max_by_key(|&&(ending, _)| ending.chars().count());
+
+    if let Some(&(ending, replacement)) = ending {
+        if ending == "yendo" && !ends_with(&verb[..suffix_start(verb, ending)], "u") {
+            return;
+        }
+
+        word.truncate(start);
+        replace_suffix(word, ending, replacement);
+    }
+}
+
+/// Remove a standard suffix. Returns whether one was removed.
+fn step_1(word: &mut Vec<char>, r1: usize, r2: usize) -> bool {
+    let suffix = match longest_suffix(word, 0, STEP_1) {
+        Some(suffix) => suffix,
+        None => return false,
+    };
+    let start = suffix_start(word, suffix);
+
+    match suffix {
+        "amente" => {
+            if start < r1 {
+                return false;
+            }
+            word.truncate(start);
+            if ends_with(word, "iv") {
+                if start >= r2 + 2 {
+                    word.truncate(start - 2);
+                    if ends_with(word, "at") && start >= r2 + 4 {
+                        word.truncate(start - 4);
+                    }
+                }
+            } else if ["os", "ic", "ad"].iter().any(|ending| ends_with(word, ending)) && start >= r2 + 2 {
+                word.truncate(start - 2);
+            }
+        }
+        _ if start < r2 => return false,
+        "logía" | "logías" => replace_suffix(word, suffix, "log"),
+        "ución" | "uciones" => replace_suffix(word, suffix, "u"),
+        "encia" | "encias" => replace_suffix(word, suffix, "ente"),
+        "adora" | "ador" | "ación" | "adoras" | "adores" | "aciones" | "ante" | "antes" | "ancia" | "ancias" => {
+            word.truncate(start);
+            remove_longest_in(word, r2, &["ic"]);
+        }
+        "mente" => {
+            word.truncate(start);
+            remove_longest_in(word, r2, &["ante", "able", "ible"]);
+        }
+        "idad" | "idades" => {
+            word.truncate(start);
+            remove_longest_in(word, r2, &["abil", "ic", "iv"]);
+        }
+        "iva" | "ivo" | "ivas" | "ivos" => {
+            word.truncate(start);
+            remove_longest_in(word, r2, &["at"]);
+        }
+        _ => word.truncate(start),
+    }
+
+    true
+}
+
+/// Remove a verb suffix beginning with `y` in RV, if it follows a `u`.
+fn step_2a(word: &mut Vec<char>, rv: usize) -> bool {
+    if let Some(suffix) = longest_suffix(word, rv, STEP_2A) {
+        let start = suffix_start(word, suffix);
+        if ends_with(&word[..start], "u") {
+            word.truncate(start);
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Remove any other verb suffix in RV.
+fn step_2b(word: &mut Vec<char>, rv: usize) {
+    if let Some(suffix) = longest_suffix(word, rv, STEP_2B) {
+        let start = suffix_start(word, suffix);
+        word.truncate(start);
+
+        if matches!(suffix, "en" | "es" | "éis" | "emos") && ends_with(word, "gu") {
+            word.pop();
+        }
+    }
+}
+
+/// Remove a residual vowel ending in RV.
+fn step_3(word: &mut Vec<char>, rv: usize) {
+    if let Some(suffix) = longest_suffix(word, rv, STEP_3) {
+        let start = suffix_start(word, suffix);
+        word.truncate(start);
+
+        if (suffix == "e" || suffix == "é") && ends_with(word, "gu") && start > rv {
+            word.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("abandonada", "abandon"),
+            ("abandonado", "abandon"),
+            ("abarcando", "abarc"),
+            ("absolutamente", "absolut"),
+            ("aceptación", "acept"),
+            ("actividades", "activ"),
+            ("comiéndolo", "com"),
+            ("haciéndola", "hac"),
+            ("construyendo", "constru"),
+            ("cantaríamos", "cant"),
+            ("alegremente", "alegr"),
+            ("sigue", "sig"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Swedish stemmer.
+//!
+//! See <http://snowball.tartarus.org/algorithms/swedish/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::swedish;
+//!
+//! assert_eq!("flick", swedish::stem("flickorna"));
+//! assert_eq!("upplös", swedish::stem("upplöst"));
+//! ```
+
+use snowball::{longest_suffix, remove_last_in, replace_suffix, scandinavian_r1, suffix_start};
+
+const STEP_1: &[&str] = &[
+    "a", "arna", "erna", "heterna", "orna", "ad", "e", "ade", "ande", "arne", "are", "aste", "en",
+    "anden", "aren", "heten", "ern", "ar", "er", "heter", "or", "as", "arnas", "ernas", "ornas",
+    "es", "ades", "andes", "ens", "arens", "hetens", "erns", "at", "andet", "het", "ast", "s",
+];
+
+const STEP_2: &[&str] = &["dd", "gd", "nn", "dt", "gt", "kt", "tt"];
+
+const STEP_3: &[&str] = &["lig", "ig", "els", "löst", "fullt"];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y' | 'ä' | 'å' | 'ö')
+}
+
+/// Letters an `s` can be removed after.
+fn is_s_ending(c: char) -> bool {
+    "bcdfghjklmnoprtvy".contains(c)
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = word.chars().collect::<Vec<char>>();
+    let r1 = scandinavian_r1(&word, is_vowel);
+
+    step_1(&mut word, r1);
+    remove_last_in(&mut word, r1, STEP_2);
+    step_3(&mut word, r1);
+
+    word.into_iter().collect()
+}
+
+/// Remove an inflectional ending in R1.
+fn step_1(word: &mut Vec<char>, r1: usize) {
+    if let Some(suffix) = longest_suffix(word, r1, STEP_1) {
+        let start = suffix_start(word, suffix);
+
+        if suffix != "s" || (start > 0 && is_s_ending(word[start - 1])) {
+            word.truncate(start);
+        }
+    }
+}
+
+/// Remove a derivational suffix in R1.
+fn step_3(word: &mut Vec<char>, r1: usize) {
+    match longest_suffix(word, r1, STEP_3) {
+        Some("löst") => replace_suffix(word, "löst", "lös"),
+        Some("fullt") => replace_suffix(word, "fullt", "full"),
+        Some(suffix) => replace_suffix(word, suffix, ""),
+        None => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("flickorna", "flick"),
+            ("pojkarna", "pojk"),
+            ("bilar", "bil"),
+            ("klokhet", "klok"),
+            ("ögonen", "ögon"),
+            ("hundens", "hund"),
+            ("dags", "dag"),
+            ("upplöst", "upplös"),
+            ("kärleksfullt", "kärleksfull"),
+            ("hemligt", "hem"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
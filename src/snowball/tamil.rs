@@ -0,0 +1,255 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Tamil stemmer.
+//!
+//! Tamil suffixes usually start with a vowel, which is written as a sign on
+//! the stem's last consonant. Removing one leaves that consonant bare, so it
+//! is given a pulli (`்`), the sign for a dead consonant, just as if the
+//! word had been written without the suffix.
+//!
+//! Words of more than four `char`s lose, in order:
+//!
+//! - a question prefix `எ` or demonstrative prefix `அ`, `இ` or `உ`, with
+//!   the consonant doubled after it, as in `எப்படி` and `அவ்வூர்`,
+//! - a question suffix and `உம்`,
+//! - common word endings and auxiliaries such as `இல்லை` and `பட்ட`,
+//! - case suffixes and postpositions,
+//! - plural and command suffixes,
+//! - person, number and tense suffixes, for as long as any are left.
+//!
+//! After most steps, and once more at the end, the ending is fixed up,
+//! undoing the sandhi that joined stem and suffix: doubled consonants are
+//! undoubled, `த்த்` left by a case suffix goes back to `ம்`, and a final
+//! short `உ` is written as a pulli.
+//!
+//! See <https://snowballstem.org/algorithms/tamil/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::tamil;
+//!
+//! assert_eq!("மரம்", tamil::stem("மரங்கள்"));
+//! assert_eq!("மரம்", tamil::stem("மரத்தில்"));
+//! assert_eq!("படி", tamil::stem("படிக்கிறான்"));
+//! ```
+
+use snowball::{ends_with, replace_prefix, replace_suffix, starts_with, suffix_start};
+
+type Suffixes = &'static [(&'static str, &'static str)];
+
+const PULLI: char = '்';
+
+/// The consonants doubled after a question or demonstrative prefix.
+const PREFIX_CONSONANTS: &[char] = &['க', 'ச', 'த', 'ப', 'ய', 'வ'];
+
+const QUESTION_PREFIXES: &[char] = &['எ'];
+
+const PRONOUN_PREFIXES: &[char] = &['அ', 'இ', 'உ'];
+
+/// Vowels written with `வ` when a prefix comes before them.
+const VA_START: &[(&str, &str)] = &[("வொ", "ஒ"), ("வோ", "ஓ"), ("வு", "உ"), ("வூ", "ஊ")];
+
+/// Consonants a suffix can leave dead at the end of a stem, which no Tamil
+/// word ends with.
+const SANDHI_CONSONANTS: &[char] = &['க', 'ச', 'த', 'ப'];
+
+/// Dead consonant pairs that sandhi makes of a final `ள்` or `ல்`.
+const SANDHI: Suffixes = &[("ட்ப்", "ள்"), ("ட்க்", "ள்"), ("ன்ற்", "ல்"), ("ற்க்", "ல்")];
+
+const QUESTIONS: Suffixes = &[("ா", "்"), ("ே", "்"), ("ோ", "்")];
+
+const UM: Suffixes = &[("ும்", "்")];
+
+const COMMON_ENDINGS: Suffixes = &[
+    ("ில்லை", "்"), ("ின்றி", "்"), ("ாகி", "்"), ("ாகிய", "்"), ("ென்று", "்"), ("ுள்ள", "்"),
+    ("ெனும்", "்"),
+    ("படு", ""), ("பட்டு", ""), ("பட்டி", ""), ("பட்டது", ""), ("பட்டிருந்தது", ""), ("பட்ட", ""),
+    ("மாட்டாது", ""), ("மாட்டாத", ""),
+];
+
+const CASES: Suffixes = &[
+    ("ை", "்"), ("ில்", "்"), ("ிற்", "்"), ("ின்", "்"), ("ுக்கு", "்"), ("க்கு", ""), ("ால்", "்"),
+    ("ோடு", "்"), ("ொடு", "்"), ("ுடன்", "்"), ("ிடம்", "்"), ("ுடைய", "்"), ("ிலிருந்து", "்"),
+    ("ினின்று", "்"), ("ிலே", "்"), ("ீ", "ி"),
+    ("கண்", ""), ("முன்", ""), ("மேல்", ""), ("மேற்", ""), ("கீழ்", ""), ("பின்", ""),
+];
+
+const PLURALS: Suffixes = &[("ுங்கள்", "்"), ("ற்கள்", "ல்"), ("ட்கள்", "ள்"), ("ங்கள்", "ம்"), ("கள்", "")];
+
+const COMMANDS: Suffixes = &[("பி", ""), ("வி", "")];
+
+const TENSES: Suffixes = &[
+    ("ான்", "்"), ("ாள்", "்"), ("ார்", "்"), ("ார்கள்", "்"), ("ேன்", "்"), ("ோம்", "்"), ("ீர்", "்"),
+    ("ீர்கள்", "்"), ("ீங்கள்", "்"), ("ாய்", "்"), ("து", ""),
+    ("க்கிற்", ""), ("கிற்", ""), ("க்கிற", ""), ("கிற", ""), ("க்கின்ற்", ""), ("கின்ற்", ""),
+    ("ஆநின்ற்", ""),
+    ("ந்த்", ""), ("த்த்", ""), ("ய்த்", "ய்"), ("ப்ப்", ""), ("வ்", ""),
+];
+
+/// Given a word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut word = word.chars().collect::<Vec<char>>();
+
+    if has_min_length(&word) {
+        remove_prefix(&mut word, QUESTION_PREFIXES);
+        remove_prefix(&mut word, PRONOUN_PREFIXES);
+
+        if has_min_length(&word) && replace_longest(&mut word, QUESTIONS) {
+            fix_endings(&mut word, false);
+        }
+
+        if has_min_length(&word) && replace_longest(&mut word, UM) {
+            fix_ending(&mut word, false);
+        }
+
+        if has_min_length(&word) && replace_longest(&mut word, COMMON_ENDINGS) {
+            fix_endings(&mut word, false);
+        }
+
+        if has_min_length(&word) && replace_longest(&mut word, CASES) {
+            // The oblique `இன்` a case suffix is often added to.
+            if ends_with(&word, "ின்") {
+                replace_suffix(&mut word, "ின்", "்");
+            }
+
+            fix_endings(&mut word, true);
+        }
+
+        replace_longest(&mut word, PLURALS);
+
+        if has_min_length(&word) {
+            replace_longest(&mut word, COMMANDS);
+        }
+
+        while has_min_length(&word) && replace_longest(&mut word, TENSES) {}
+    }
+
+    fix_endings(&mut word, false);
+
+    word.into_iter().collect()
+}
+
+fn has_min_length(word: &[char]) -> bool {
+    word.len() > 4
+}
+
+/// Remove one of `prefixes` followed by a doubled consonant, as in `அப்படி`,
+/// leaving the second consonant, and write a vowel left after `வ` as a
+/// vowel of its own.
+fn remove_prefix(word: &mut Vec<char>, prefixes: &[char]) {
+    if word.len() > 4
+        && prefixes.contains(&word[0])
+        && PREFIX_CONSONANTS.contains(&word[1])
+        && word[2] == PULLI
+        && word[3] == word[1]
+    {
+        word.drain(..3);
+
+        if let Some(&(start, vowel)) = VA_START.iter().find(|&&(start, _)| starts_with(word, start)) {
+            replace_prefix(word, start, vowel);
+        }
+    }
+}
+
+/// Replace the longest of `suffixes` the word ends with by its replacement,
+/// if at least two `char`s of stem are left in front of it. Returns whether
+/// one was replaced.
+fn replace_longest(word: &mut Vec<char>, suffixes: Suffixes) -> bool {
+    let suffix = suffixes.iter()
+        .filter(|&&(suffix, _)| ends_with(word, suffix) && suffix_start(word, suffix) >= 2)
+        .max_by_key(|&&(suffix, _)| suffix.chars().count());
+
+    match suffix {
+        Some(&(suffix, replacement)) => {
+            replace_suffix(word, suffix, replacement);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Fix the ending for as long as there is something to fix.
+fn fix_endings(word: &mut Vec<char>, after_case: bool) {
+    while fix_ending(word, after_case) {}
+}
+
+/// Undo one piece of sandhi at the end of a word of more than three `char`s,
+/// so `வீடு`, `வீட்டில்` and `வீட்டை` all end in `வீட்`. `after_case` is
+/// whether a case suffix was removed, after which `த்த்` stands for `ம்`.
+/// Returns whether anything changed.
+fn fix_ending(word: &mut Vec<char>, after_case: bool) -> bool {
+    let length = word.len();
+
+    if length <= 3 {
+        return false;
+    }
+
+    if after_case && ends_with(word, "த்த்") {
+        replace_suffix(word, "த்த்", "ம்");
+    } else if word[length - 1] == PULLI && word[length - 3] == PULLI && word[length - 2] == word[length - 4] {
+        word.truncate(length - 2);
+    } else if let Some(&(pair, replacement)) = SANDHI.iter().find(|&&(pair, _)| ends_with(word, pair)) {
+        replace_suffix(word, pair, replacement);
+    } else if ends_with(word, "ய்") && "ாிீேை".contains(word[length - 3]) {
+        word.truncate(length - 2);
+    } else if word[length - 1] == 'ு' {
+        word[length - 1] = PULLI;
+    } else if word[length - 1] == PULLI && SANDHI_CONSONANTS.contains(&word[length - 2]) {
+        word.truncate(length - 2);
+    } else {
+        return false;
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("மரங்கள்", "மரம்"),
+            ("மரத்தில்", "மரம்"),
+            ("மரத்தை", "மரம்"),
+            ("மரத்தின்மேல்", "மரம்"),
+            ("மரத்துடன்", "மரம்"),
+            ("மரம்", "மரம்"),
+            ("வீடு", "வீட்"),
+            ("வீட்டில்", "வீட்"),
+            ("வீட்டுக்கு", "வீட்"),
+            ("புத்தகங்கள்", "புத்தகம்"),
+            ("நாட்கள்", "நாள்"),
+            ("நீ", "நீ"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+
+    #[test]
+    fn test_prefixes() {
+        assert_eq!("படி", stem("எப்படி"));
+        assert_eq!("ஊர்", stem("அவ்வூர்"));
+        assert_eq!("இந்தி", stem("இந்தியா"));
+    }
+
+    #[test]
+    fn test_tenses() {
+        let pairs = [
+            ("படிக்கிறான்", "படி"),
+            ("இருந்தான்", "இரு"),
+            ("பார்த்தான்", "பார்"),
+            ("படிப்பான்", "படி"),
+            ("செய்வான்", "செய்"),
+            ("வந்தானா", "வந்"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
@@ -0,0 +1,613 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Turkish stemmer.
+//!
+//! Turkish suffixes chain onto each other in a fixed order, and which can
+//! follow which depends on vowel harmony. The stemmer walks those chains back
+//! from the end of the word, first through the nominal verb suffixes and then
+//! through the noun suffixes, the way the reference implementation does.
+//! Words of one syllable are left alone, and so are words that are nothing
+//! but suffixes, like `leri`, rather than being stemmed to nothing.
+//!
+//! Words must be lower case with Turkish rules, where `I` becomes `ı` and `İ`
+//! becomes `i`. Lowercasing with `str::to_lowercase` gets both wrong, so use
+//! `to_lowercase` from this module. A combining dot left over from lowercasing
+//! `İ` the default way is ignored.
+//!
+//! See <http://snowball.tartarus.org/algorithms/turkish/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::turkish;
+//!
+//! assert_eq!("kitap", turkish::stem("kitaplar"));
+//! assert_eq!("ıspanak", turkish::stem(&turkish::to_lowercase("ISPANAK")));
+//! ```
+
+/// How a suffix may be joined to the stem.
+#[derive(Clone, Copy)]
+enum Buffer {
+    /// By a consonant, which must then follow a vowel.
+    Consonant(char),
+    /// By one of `ı`, `i`, `u` or `ü`, which must then follow a consonant.
+    Vowel,
+}
+
+/// A family of suffixes that differ only by vowel harmony and voicing.
+struct Suffix {
+    harmony: bool,
+    forms: &'static [&'static str],
+    buffer: Option<Buffer>,
+}
+
+const fn suffix(harmony: bool, forms: &'static [&'static str], buffer: Option<Buffer>) -> Suffix {
+    Suffix { harmony, forms, buffer }
+}
+
+const POSSESSIVES: Suffix = suffix(false, &["mız", "miz", "muz", "müz", "nız", "niz", "nuz", "nüz", "m", "n"], Some(Buffer::Vowel));
+const SU: Suffix = suffix(true, &["ı", "i", "u", "ü"], Some(Buffer::Consonant('s')));
+const LARI: Suffix = suffix(false, &["leri", "ları"], None);
+const YU: Suffix = suffix(true, &["ı", "i", "u", "ü"], Some(Buffer::Consonant('y')));
+const NU: Suffix = suffix(true, &["nı", "ni", "nu", "nü"], None);
+const NUN: Suffix = suffix(true, &["ın", "in", "un", "ün"], Some(Buffer::Consonant('n')));
+const YA: Suffix = suffix(true, &["a", "e"], Some(Buffer::Consonant('y')));
+const NA: Suffix = suffix(true, &["na", "ne"], None);
+const DA: Suffix = suffix(true, &["da", "de", "ta", "te"], None);
+const NDA: Suffix = suffix(true, &["nda", "nde"], None);
+const DAN: Suffix = suffix(true, &["dan", "den", "tan", "ten"], None);
+const NDAN: Suffix = suffix(true, &["ndan", "nden"], None);
+const YLA: Suffix = suffix(true, &["la", "le"], Some(Buffer::Consonant('y')));
+const KI: Suffix = suffix(false, &["ki"], None);
+const NCA: Suffix = suffix(true, &["ca", "ce"], Some(Buffer::Consonant('n')));
+const YUM: Suffix = suffix(true, &["ım", "im", "um", "üm"], Some(Buffer::Consonant('y')));
+const SUN: Suffix = suffix(true, &["sın", "sin", "sun", "sün"], None);
+const YUZ: Suffix = suffix(true, &["ız", "iz", "uz", "üz"], Some(Buffer::Consonant('y')));
+const SUNUZ: Suffix = suffix(false, &["sınız", "siniz", "sunuz", "sünüz"], None);
+const LAR: Suffix = suffix(true, &["ler", "lar"], None);
+const NUZ: Suffix = suffix(true, &["nız", "niz", "nuz", "nüz"], None);
+const DUR: Suffix = suffix(true, &["tır", "tir", "tur", "tür", "dır", "dir", "dur", "dür"], None);
+const CASINA: Suffix = suffix(false, &["casına", "cesine"], None);
+const YDU: Suffix = suffix(true, &[
+    "tım", "tim", "tum", "tüm", "dım", "dim", "dum", "düm", "tın", "tin", "tun", "tün", "dın", "din",
+    "dun", "dün", "tık", "tik", "tuk", "tük", "dık", "dik", "duk", "dük", "tı", "ti", "tu", "tü",
+    "dı", "di", "du", "dü",
+], Some(Buffer::Consonant('y')));
+const YSA: Suffix = suffix(false, &["sam", "san", "sak", "sem", "sen", "sek", "sa", "se"], Some(Buffer::Consonant('y')));
+const YMUS: Suffix = suffix(true, &["mış", "miş", "muş", "müş"], Some(Buffer::Consonant('y')));
+const YKEN: Suffix = suffix(false, &["ken"], Some(Buffer::Consonant('y')));
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'ı' | 'i' | 'o' | 'ö' | 'u' | 'ü')
+}
+
+fn is_u(c: char) -> bool {
+    matches!(c, 'ı' | 'i' | 'u' | 'ü')
+}
+
+/// Lowercase a word with Turkish rules, so that `I` becomes `ı` and `İ`
+/// becomes `i`.
+pub fn to_lowercase(word: &str) -> String {
+    let mut lowercase = String::with_capacity(word.len());
+
+    for c in word.chars() {
+        match c {
+            'I' => lowercase.push('ı'),
+            'İ' => lowercase.push('i'),
+            c => lowercase.extend(c.to_lowercase()),
+        }
+    }
+
+    lowercase
+}
+
+/// Given a lowercase word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let word = word.replace("i\u{307}", "i");
+
+    // Words of one syllable have no suffixes to remove.
+    if word.chars().filter(|&c| is_vowel(c)).count() < 2 {
+        return word;
+    }
+
+    let mut stem = Word::new(&word);
+
+    if stem.nominal_verb_suffixes() {
+        stem.cursor = stem.chars.len();
+        stem.noun_suffixes();
+        stem.postlude();
+    }
+
+    // A word made only of suffixes, like `leri`, is left as it is.
+    if stem.chars.is_empty() {
+        return word;
+    }
+
+    stem.chars.into_iter().collect()
+}
+
+/// A word being stemmed from the end, with a cursor and the bounds of the
+/// slice to delete.
+struct Word {
+    chars: Vec<char>,
+    cursor: usize,
+    bra: usize,
+    ket: usize,
+}
+
+impl Word {
+    fn new(word: &str) -> Word {
+        let chars = word.chars().collect::<Vec<char>>();
+        let length = chars.len();
+
+        Word { chars, cursor: length, bra: length, ket: length }
+    }
+
+    /// Run `f`, putting the cursor back where it was if it fails.
+    fn attempt<F: FnOnce(&mut Word) -> bool>(&mut self, f: F) -> bool {
+        let from_end = self.chars.len() - self.cursor;
+
+        if f(self) {
+            true
+        } else {
+            self.cursor = self.chars.len() - from_end;
+            false
+        }
+    }
+
+    fn open(&mut self) {
+        self.ket = self.cursor;
+    }
+
+    fn close(&mut self) {
+        self.bra = self.cursor;
+    }
+
+    fn delete(&mut self) {
+        let length = self.ket - self.bra;
+        self.chars.drain(self.bra..self.ket);
+
+        if self.cursor >= self.ket {
+            self.cursor -= length;
+        } else if self.cursor > self.bra {
+            self.cursor = self.bra;
+        }
+        self.ket = self.bra;
+    }
+
+    fn close_and_delete(&mut self) {
+        self.close();
+        self.delete();
+    }
+
+    fn before(&self, offset: usize) -> Option<char> {
+        if self.cursor >= offset {
+            Some(self.chars[self.cursor - offset])
+        } else {
+            None
+        }
+    }
+
+    /// Whether the last vowel before the cursor agrees with some vowel before
+    /// it.
+    fn vowel_harmony(&self) -> bool {
+        let chars = &self.chars[..self.cursor];
+
+        let last = match chars.iter().rposition(|&c| is_vowel(c)) {
+            Some(last) => last,
+            None => return false,
+        };
+
+        let agrees: fn(char) -> bool = match chars[last] {
+            'a' => |c| matches!(c, 'a' | 'ı' | 'o' | 'u'),
+            'e' => |c| matches!(c, 'e' | 'i' | 'ö' | 'ü'),
+            'ı' => |c| matches!(c, 'a' | 'ı'),
+            'i' => |c| matches!(c, 'e' | 'i'),
+            'o' | 'u' => |c| matches!(c, 'o' | 'u'),
+            _ => |c| matches!(c, 'ö' | 'ü'),
+        };
+
+        chars[..last].iter().any(|&c| agrees(c))
+    }
+
+    /// Move the cursor over one of the forms of `suffix`, and its buffer
+    /// letter if it has one.
+    fn mark(&mut self, suffix: &Suffix) -> bool {
+        self.attempt(|word| {
+            if suffix.harmony && !word.vowel_harmony() {
+                return false;
+            }
+
+            let form = suffix.forms.iter()
+                .filter(|form| {
+                    let length = form.chars().count();
+                    length <= word.cursor && word.chars[word.cursor - length..word.cursor].iter().cloned().eq(form.chars())
+                })
+                .max_by_key(|form| form.chars().count());
+
+            match form {
+                Some(form) => word.cursor -= form.chars().count(),
+                None => return false,
+            }
+
+            match suffix.buffer {
+                Some(Buffer::Consonant(consonant)) => word.buffer(|c| c == consonant, is_vowel),
+                Some(Buffer::Vowel) => word.buffer(is_u, |c| !is_vowel(c)),
+                None => true,
+            }
+        })
+    }
+
+    /// Move the cursor over an optional buffer letter, which must follow a
+    /// letter of the given kind. Without a buffer letter, the letter before
+    /// last must be of that kind.
+    fn buffer<F: Fn(char) -> bool>(&mut self, is_buffer: F, follows: fn(char) -> bool) -> bool {
+        match self.before(1) {
+            Some(c) if is_buffer(c) => {
+                if matches!(self.before(2), Some(c) if follows(c)) {
+                    self.cursor -= 1;
+                    true
+                } else {
+                    false
+                }
+            }
+            _ => matches!(self.before(2), Some(c) if follows(c)),
+        }
+    }
+
+    fn mark_any(&mut self, suffixes: &[&Suffix]) -> bool {
+        suffixes.iter().any(|suffix| self.mark(suffix))
+    }
+
+    /// Remove an optional plural before a chain ending in `ki`.
+    fn plural_then_chain(&mut self) -> bool {
+        self.open();
+        if !self.mark(&LAR) {
+            return false;
+        }
+        self.close_and_delete();
+        self.chain_before_ki()
+    }
+
+    /// Remove a possessive or `sU`, then an optional plural and chain ending
+    /// in `ki`.
+    fn possessive_then_chain(&mut self) -> bool {
+        self.open();
+        if !self.mark_any(&[&POSSESSIVES, &SU]) {
+            return false;
+        }
+        self.close_and_delete();
+        self.attempt(Word::plural_then_chain);
+        true
+    }
+
+    /// Remove a chain of suffixes ending in `ki`, as in `evdekiler`.
+    fn chain_before_ki(&mut self) -> bool {
+        self.attempt(|word| {
+            word.open();
+            if !word.mark(&KI) {
+                return false;
+            }
+
+            word.attempt(|word| {
+                if !word.mark(&DA) {
+                    return false;
+                }
+                word.close_and_delete();
+                word.attempt(|word| {
+                    word.open();
+                    word.attempt(|word| {
+                        if !word.mark(&LAR) {
+                            return false;
+                        }
+                        word.close_and_delete();
+                        word.attempt(Word::chain_before_ki);
+                        true
+                    }) || word.attempt(|word| {
+                        if !word.mark(&POSSESSIVES) {
+                            return false;
+                        }
+                        word.close_and_delete();
+                        word.attempt(Word::plural_then_chain);
+                        true
+                    })
+                });
+                true
+            }) || word.attempt(|word| {
+                if !word.mark(&NUN) {
+                    return false;
+                }
+                word.close_and_delete();
+                word.attempt(|word| {
+                    word.open();
+                    word.attempt(|word| {
+                        if !word.mark(&LARI) {
+                            return false;
+                        }
+                        word.close_and_delete();
+                        true
+                    }) || word.attempt(Word::possessive_then_chain) || word.attempt(Word::chain_before_ki)
+                });
+                true
+            }) || word.attempt(|word| word.mark(&NDA) && word.after_nda())
+        })
+    }
+
+    /// Remove what may precede an `ndA` or `nA`, along with it.
+    fn after_nda(&mut self) -> bool {
+        self.attempt(|word| {
+            if !word.mark(&LARI) {
+                return false;
+            }
+            word.close_and_delete();
+            true
+        }) || self.attempt(|word| {
+            if !word.mark(&SU) {
+                return false;
+            }
+            word.close_and_delete();
+            word.attempt(Word::plural_then_chain);
+            true
+        }) || self.attempt(Word::chain_before_ki)
+    }
+
+    /// Remove the suffixes that make a noun into a predicate, such as the
+    /// `dır` of `evdedir`. Returns whether noun suffixes should be removed
+    /// after them.
+    fn nominal_verb_suffixes(&mut self) -> bool {
+        let mut continue_stemming = true;
+
+        self.open();
+
+        let matched = self.mark_any(&[&YMUS, &YDU, &YSA, &YKEN])
+            || self.attempt(|word| {
+                word.mark(&CASINA) && {
+                    word.mark_any(&[&SUNUZ, &LAR, &YUM, &SUN, &YUZ]);
+                    word.mark(&YMUS)
+                }
+            })
+            || self.attempt(|word| {
+                if !word.mark(&LAR) {
+                    return false;
+                }
+                word.close_and_delete();
+                word.attempt(|word| {
+                    word.open();
+                    word.mark_any(&[&DUR, &YDU, &YSA, &YMUS])
+                });
+                continue_stemming = false;
+                true
+            })
+            || self.attempt(|word| word.mark(&NUZ) && word.mark_any(&[&YDU, &YSA]))
+            || self.attempt(|word| {
+                if !word.mark_any(&[&SUNUZ, &YUZ, &SUN, &YUM]) {
+                    return false;
+                }
+                word.close_and_delete();
+                word.attempt(|word| {
+                    word.open();
+                    word.mark(&YMUS)
+                });
+                true
+            })
+            || self.attempt(|word| {
+                if !word.mark(&DUR) {
+                    return false;
+                }
+                word.close_and_delete();
+                word.attempt(|word| {
+                    word.open();
+                    word.mark_any(&[&SUNUZ, &LAR, &YUM, &SUN, &YUZ]);
+                    word.mark(&YMUS)
+                });
+                true
+            });
+
+        if matched {
+            self.close_and_delete();
+        }
+
+        continue_stemming
+    }
+
+    /// Remove the case, possessive and plural suffixes of a noun. Returns
+    /// whether any were removed.
+    fn noun_suffixes(&mut self) -> bool {
+        self.attempt(|word| {
+            word.open();
+            if !word.mark(&LAR) {
+                return false;
+            }
+            word.close_and_delete();
+            word.attempt(Word::chain_before_ki);
+            true
+        }) || self.attempt(|word| {
+            word.open();
+            if !word.mark(&NCA) {
+                return false;
+            }
+            word.close_and_delete();
+            word.attempt(|word| {
+                word.attempt(|word| {
+                    word.open();
+                    if !word.mark(&LARI) {
+                        return false;
+                    }
+                    word.close_and_delete();
+                    true
+                }) || word.attempt(Word::possessive_then_chain) || word.attempt(Word::plural_then_chain)
+            });
+            true
+        }) || self.attempt(|word| {
+            word.open();
+            if !(word.mark_any(&[&NDA, &NA]) && word.after_nda()) {
+                return false;
+            }
+            word.close();
+            true
+        }) || self.attempt(|word| {
+            word.open();
+            if !word.mark_any(&[&NDAN, &NU]) {
+                return false;
+            }
+
+            let matched = word.attempt(|word| {
+                if !word.mark(&SU) {
+                    return false;
+                }
+                word.close_and_delete();
+                word.attempt(Word::plural_then_chain);
+                true
+            }) || word.mark(&LARI);
+
+            if matched {
+                word.close();
+            }
+            matched
+        }) || self.attempt(|word| {
+            word.open();
+            if !word.mark(&DAN) {
+                return false;
+            }
+            word.close_and_delete();
+            word.attempt(|word| {
+                word.open();
+                word.attempt(|word| {
+                    if !word.mark(&POSSESSIVES) {
+                        return false;
+                    }
+                    word.close_and_delete();
+                    word.attempt(Word::plural_then_chain);
+                    true
+                }) || word.attempt(|word| {
+                    if !word.mark(&LAR) {
+                        return false;
+                    }
+                    word.close_and_delete();
+                    word.attempt(Word::chain_before_ki);
+                    true
+                }) || word.attempt(Word::chain_before_ki)
+            });
+            true
+        }) || self.attempt(|word| {
+            word.open();
+            if !word.mark_any(&[&NUN, &YLA]) {
+                return false;
+            }
+            word.close_and_delete();
+            word.attempt(|word| {
+                word.attempt(Word::plural_then_chain)
+                    || word.attempt(Word::possessive_then_chain)
+                    || word.attempt(Word::chain_before_ki)
+            });
+            true
+        }) || self.attempt(|word| {
+            word.open();
+            if !word.mark(&LARI) {
+                return false;
+            }
+            word.close_and_delete();
+            true
+        }) || self.attempt(Word::chain_before_ki) || self.attempt(|word| {
+            word.open();
+            if !word.mark_any(&[&DA, &YU, &YA]) {
+                return false;
+            }
+            word.close_and_delete();
+            word.attempt(|word| {
+                word.open();
+                let matched = word.attempt(|word| {
+                    if !word.mark(&POSSESSIVES) {
+                        return false;
+                    }
+                    word.close_and_delete();
+                    word.attempt(|word| {
+                        word.open();
+                        word.mark(&LAR)
+                    });
+                    true
+                }) || word.mark(&LAR);
+
+                if !matched {
+                    return false;
+                }
+                word.close_and_delete();
+                word.chain_before_ki()
+            });
+            true
+        }) || self.attempt(Word::possessive_then_chain)
+    }
+
+    /// Give a stem ending in `d` or `g` a final vowel in harmony with it, and
+    /// devoice a final `b`, `c`, `d` or `ğ`. The stems `ad` and `soyad` are
+    /// left alone.
+    fn postlude(&mut self) {
+        if self.chars == ['a', 'd'] || self.chars == ['s', 'o', 'y', 'a', 'd'] {
+            return;
+        }
+
+        if let Some(&last) = self.chars.last() {
+            if last == 'd' || last == 'g' {
+                let vowel = self.chars.iter().rev().find(|&&c| is_vowel(c)).map(|&vowel| match vowel {
+                    'a' | 'ı' => 'ı',
+                    'e' | 'i' => 'i',
+                    'o' | 'u' => 'u',
+                    _ => 'ü',
+                });
+                self.chars.extend(vowel);
+            }
+        }
+
+        let devoiced = match self.chars.last() {
+            Some(&'b') => 'p',
+            Some(&'c') => 'ç',
+            Some(&'d') => 't',
+            Some(&'ğ') => 'k',
+            _ => return,
+        };
+        self.chars.pop();
+        self.chars.push(devoiced);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_suffixes() {
+        for &word in ["leri", "ları", "siniz", "sunuz", "sünüz"].iter() {
+            assert_eq!(word, stem(word));
+        }
+    }
+
+    #[test]
+    fn test_to_lowercase() {
+        assert_eq!("ıspanak", to_lowercase("ISPANAK"));
+        assert_eq!("istanbul", to_lowercase("İSTANBUL"));
+        assert_eq!("çiçek", to_lowercase("ÇİÇEK"));
+    }
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("kitaplar", "kitap"),
+            ("kitabı", "kitap"),
+            ("ağacı", "ağaç"),
+            ("evlerinden", "ev"),
+            ("arabalarımızdan", "araba"),
+            ("okullarda", "okul"),
+            ("gelmişsiniz", "gel"),
+            ("evdedir", "ev"),
+            ("güzelleşmiş", "güzelleş"),
+            ("kedi", "kedi"),
+            ("adları", "ad"),
+            ("soyadı", "soyad"),
+            ("i\u{307}stanbul", "istanbul"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+}
@@ -0,0 +1,383 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The Snowball Yiddish stemmer, for words in Hebrew script.
+//!
+//! Points are removed, the doubled letters `וו`, `וי` and `יי` are written as
+//! the ligatures `װ`, `ױ` and `ײ`, and final letters are written in their
+//! normal forms, which stems keep. The participle prefix `גע` and the
+//! particle `צו` are marked, at the start of the word or after a separable
+//! prefix like `אַרײַן`, and R1 starts after them. Then inflectional,
+//! derivational and adjectival suffixes are removed in R1, irregular
+//! participles get the stem of their present tense, and the marked prefixes
+//! are removed. Words are stored in logical order, so the end of a word is
+//! the end of the string even though it is displayed at the left.
+//!
+//! See <https://snowballstem.org/algorithms/yiddish/stemmer.html>.
+//!
+//! ```
+//! use porter_stemmer::snowball::yiddish;
+//!
+//! assert_eq!("קינד", yiddish::stem("קינדער"));
+//! assert_eq!("שרײב", yiddish::stem("געשריבן"));
+//! ```
+
+use snowball::{ends_with, longest_prefix, replace_suffix, starts_with, suffix_start};
+
+/// Final letters and their normal forms.
+const FINALS: &[(char, char)] = &[('ך', 'כ'), ('ם', 'מ'), ('ן', 'נ'), ('ף', 'פ'), ('ץ', 'צ')];
+
+/// Separable prefixes, after which `גע` and `צו` are marked too.
+const PREFIXES: &[&str] = &[
+    "אדורכ", "אהינ", "אהער", "אהײמ", "אומ", "אונטער", "איבער", "אנ", "אנט", "אנטקעגנ", "אנידער",
+    "אפ", "אפיר", "אקעגנ", "אראפ", "ארומ", "ארונטער", "אריבער", "ארױס", "ארױפ", "ארײנ", "אװעק",
+    "אױס", "אױפ", "אײנ", "בא", "בײ", "דורכ", "דער", "מיט", "נאכ", "פאר", "פארבײ", "פארױס",
+    "פונאנדער", "צו", "צוזאמענ", "צונױפ", "צוריק", "צע",
+];
+
+/// Clusters of three consonants that R1 never starts within.
+const CLUSTERS: &[&str] = &["דזש", "שטר", "שטש", "שפר"];
+
+/// What happens to a word ending with one of `SUFFIXES`.
+#[derive(Clone, Copy)]
+enum Action {
+    /// Remove the suffix in R1.
+    Remove,
+    /// Replace the suffix in R1.
+    ReplaceInR1(&'static str),
+    /// Replace the suffix, which holds the stem of an irregular participle,
+    /// wherever it starts.
+    Replace(&'static str),
+    /// Remove the suffix in R1, then give an irregular participle stem
+    /// before it the stem of its present tense.
+    RemoveAfterParticiple,
+    /// Remove the suffix in R1, or write it as `ט` before R1, then write a
+    /// `בראכ` before it as `ברענג`.
+    RemoveT,
+    /// Write `יסט` after `ג` or `ש` as `יס` if R1 starts within it, or
+    /// otherwise remove it in R1.
+    RemoveIst,
+    /// Leave the word alone.
+    Keep,
+}
+
+/// Inflectional suffixes, and what is done with each.
+const SUFFIXES: &[(&[&str], Action)] = &[
+    (&["ונג", "סטו", "ט", "סט", "עט", "שאפט", "הײט", "קײט", "יקײט", "לעכ", "עלעכ", "יזמ", "ימ",
+        "עמ", "נ", "סטנ", "יסטנ", "עטנ", "ענ", "עלענ", "יזמענ", "ס", "עטס", "נס", "עס", "עלעס",
+        "ערס", "ענערס", "ע", "סטע", "עטע", "עלע", "ער", "סטער", "עטער"], Action::Remove),
+    (&["יעס"], Action::ReplaceInR1("יע")),
+    (&["ות"], Action::ReplaceInR1("ה")),
+    (&["ענעמ", "ענס", "ענע", "ענער"], Action::RemoveAfterParticiple),
+    (&["טענעמ", "טנ", "טס", "טנס", "טע", "טענע", "טער", "טענער"], Action::RemoveT),
+    (&["יסט"], Action::RemoveIst),
+    (&["יע"], Action::Keep),
+    (&["גאנגענ", "גײנ"], Action::Replace("גײ")),
+    (&["נומענ"], Action::Replace("נעמ")),
+    (&["שריבנ"], Action::Replace("שרײב")),
+    (&["GEמיטנ"], Action::Replace("מײד")),
+    (&["GEביטנ"], Action::Replace("בײט")),
+    (&["GEביסנ"], Action::Replace("בײס")),
+    (&["װיזנ"], Action::Replace("װײז")),
+    (&["טריבנ"], Action::Replace("טרײב")),
+    (&["GEליטנ"], Action::Replace("לײט")),
+    (&["קליבנ"], Action::Replace("קלײב")),
+    (&["ריבנ"], Action::Replace("רײב")),
+    (&["GEריסנ"], Action::Replace("רײס")),
+    (&["שװיגנ"], Action::Replace("שװײג")),
+    (&["שמיסנ"], Action::Replace("שמײס")),
+    (&["שניטנ"], Action::Replace("שנײד")),
+    (&["בונדנ"], Action::Replace("בינד")),
+    (&["װוטשנ"], Action::Replace("װיטש")),
+    (&["זונגנ"], Action::Replace("זינג")),
+    (&["טרונקנ"], Action::Replace("טרינק")),
+    (&["צװונגנ"], Action::Replace("צװינג")),
+    (&["שלונגנ"], Action::Replace("שלינג")),
+    (&["בױגנ"], Action::Replace("בײג")),
+    (&["הױבנ"], Action::Replace("הײב")),
+    (&["פארלױרנ"], Action::Replace("פארליר")),
+    (&["שטאנענ"], Action::Replace("שטײ")),
+    (&["שװױרנ"], Action::Replace("שװער")),
+    (&["בראכט"], Action::Replace("ברענג")),
+];
+
+/// Stems of irregular participles, with the stems of their present tense.
+const PARTICIPLES: &[(&str, &str)] = &[
+    ("קליב", "קלײב"), ("ריב", "רײב"), ("טריב", "טרײב"), ("שריב", "שרײב"), ("הױב", "הײב"),
+    ("שװיג", "שװײג"), ("גאנג", "גײ"), ("זונג", "זינג"), ("שלונג", "שלינג"), ("צװונג", "צװינג"),
+    ("בױג", "בײג"), ("בונד", "בינד"), ("װיז", "װײז"), ("ביט", "בײט"), ("ליט", "לײט"),
+    ("מיט", "מײד"), ("שניט", "שנײד"), ("נומ", "נעמ"), ("שטאנ", "שטײ"), ("ביס", "בײס"),
+    ("שמיס", "שמײס"), ("ריס", "רײס"), ("טרונק", "טרינק"), ("פארלױר", "פארליר"), ("שװױר", "שװער"),
+    ("װוטש", "װיטש"),
+];
+
+/// Derivational suffixes removed in R1. `ל` is only removed after a
+/// consonant.
+const DERIVATIONAL: &[&str] = &["ונג", "שאפט", "הײט", "קײט", "יקײט", "ל"];
+
+/// Adjectival suffixes removed in R1. `בליק` and `גליק` are kept whole.
+const ADJECTIVAL: &[&str] = &["יג", "יק", "דיק", "נדיק", "ענדיק", "בליק", "גליק", "ניק", "יש"];
+
+/// How the prefixes `גע` and `צו` are marked while stemming.
+const MARKERS: &[&str] = &["GE", "TSU"];
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'א' | 'ו' | 'י' | 'ע' | 'ױ' | 'ײ')
+}
+
+fn is_consonant(c: char) -> bool {
+    matches!(c, 'ב'..='ת' | 'װ') && !is_vowel(c)
+}
+
+/// Whether `c` is one of the points that are removed.
+fn is_point(c: char) -> bool {
+    matches!(c, '\u{5B0}'..='\u{5B9}' | '\u{5BB}' | '\u{5BC}' | '\u{5BF}' | '\u{5C1}' | '\u{5C2}')
+}
+
+/// Given a word, return its stemmed form.
+pub fn stem(word: &str) -> String {
+    let mut stem = prelude(word);
+
+    let r1 = mark_regions(&mut stem);
+    remove_suffixes(&mut stem, r1);
+
+    let derivational = longest_suffix(&stem, DERIVATIONAL);
+    if let Some(suffix) = derivational {
+        let start = suffix_start(&stem, suffix);
+        if start >= r1 && (suffix != "ל" || (start > 0 && is_consonant(stem[start - 1]))) {
+            stem.truncate(start);
+        }
+    }
+
+    match longest_suffix(&stem, ADJECTIVAL) {
+        Some("בליק") | Some("גליק") | None => {}
+        Some(suffix) => {
+            let start = suffix_start(&stem, suffix);
+            if start >= r1 {
+                stem.truncate(start);
+            }
+        }
+    }
+
+    remove_markers(&mut stem);
+
+    // A word made only of points, or of marked prefixes, is left as it is.
+    if stem.is_empty() {
+        return word.to_string();
+    }
+
+    stem.into_iter().collect()
+}
+
+/// Remove points, write doubled letters as ligatures and write final letters
+/// in their normal forms. A doubled letter followed by a point that shows it
+/// is two letters, like `ווּ` or `ייִ`, is left alone.
+fn prelude(word: &str) -> Vec<char> {
+    let chars = word.chars().collect::<Vec<char>>();
+    let mut result = Vec::<char>::with_capacity(chars.len());
+    let mut index = 0;
+
+    while index < chars.len() {
+        let point = chars.get(index + 2).cloned();
+        let ligature = match (chars[index], chars.get(index + 1).cloned()) {
+            ('ו', Some('ו')) if point != Some('\u{5BC}') => Some('װ'),
+            ('ו', Some('י')) if point != Some('\u{5B4}') => Some('ױ'),
+            ('י', Some('י')) if point != Some('\u{5B4}') => Some('ײ'),
+            _ => None,
+        };
+
+        match ligature {
+            Some(ligature) => {
+                result.push(ligature);
+                index += 2;
+            }
+            None => {
+                let c = chars[index];
+                let normal = FINALS.iter().find(|&&(final_form, _)| final_form == c);
+                result.push(normal.map_or(c, |&(_, normal)| normal));
+                index += 1;
+            }
+        }
+    }
+
+    result.retain(|&c| !is_point(c));
+    result
+}
+
+/// Mark the prefixes `גע` and `צו`, and return the start of R1.
+///
+/// `גע` is marked at the start of the word, unless it is part of `געלט`
+/// or `געבן`, and both are marked after a separable prefix. R1 starts
+/// after three consonants following the marked prefixes, or otherwise
+/// after the vowels that follow them, but never within three letters of
+/// them.
+fn mark_regions(word: &mut Vec<char>) -> usize {
+    let mut start = 0;
+
+    if starts_with(word, "גע") && !starts_with(&word[2..], "לט") && !starts_with(&word[2..], "בנ") {
+        word.splice(..2, "GE".chars());
+        start = 2;
+    }
+
+    if let Some(prefix) = longest_prefix(&word[start..], PREFIXES) {
+        let after = start + prefix.chars().count();
+        let infinitive = ["צוגנ", "צוקט", "צוקנ"].iter()
+            .any(|ending| word.len() == after + 4 && starts_with(&word[after..], ending));
+
+        if infinitive || starts_with(&word[after..], "געבנ") {
+            start = after;
+        } else if starts_with(&word[after..], "גע") {
+            word.splice(after..after + 2, "GE".chars());
+            start = after + 2;
+        } else if starts_with(&word[after..], "צו") {
+            word.splice(after..after + 2, "TSU".chars());
+            start = after + 3;
+        }
+    }
+
+    if word.len() < start + 3 {
+        return word.len();
+    }
+
+    let mut cursor = start;
+    if CLUSTERS.iter().any(|cluster| starts_with(&word[start..], cluster)) {
+        cursor += 3;
+    }
+
+    if word.len() >= cursor + 3 && word[cursor..cursor + 3].iter().all(|&c| is_consonant(c)) {
+        return cursor + 3;
+    }
+
+    let vowel = match (cursor..word.len()).find(|&index| is_vowel(word[index])) {
+        Some(vowel) => vowel,
+        None => return word.len(),
+    };
+    let after_vowels = (vowel..word.len())
+        .find(|&index| !is_vowel(word[index]))
+        .unwrap_or(word.len());
+
+    after_vowels.max(start + 3)
+}
+
+/// The longest of `suffixes` that `word` ends with.
+fn longest_suffix(word: &[char], suffixes: &[&'static str]) -> Option<&'static str> {
+    suffixes.iter()
+        .filter(|suffix| ends_with(word, suffix))
+        .max_by_key(|suffix| suffix.chars().count())
+        .cloned()
+}
+
+/// Deal with the longest of `SUFFIXES` that `word` ends with.
+fn remove_suffixes(word: &mut Vec<char>, r1: usize) {
+    let found = SUFFIXES.iter()
+        .flat_map(|&(suffixes, action)| suffixes.iter().map(move |&suffix| (suffix, action)))
+        .filter(|&(suffix, _)| ends_with(word, suffix))
+        .max_by_key(|&(suffix, _)| suffix.chars().count());
+    let (suffix, action) = match found {
+        Some(found) => found,
+        None => return,
+    };
+    let start = suffix_start(word, suffix);
+
+    match action {
+        Action::Remove if start >= r1 => word.truncate(start),
+        Action::ReplaceInR1(replacement) if start >= r1 => {
+            replace_suffix(word, suffix, replacement);
+        }
+        Action::Replace(replacement) => replace_suffix(word, suffix, replacement),
+        Action::RemoveAfterParticiple if start >= r1 => {
+            word.truncate(start);
+
+            let participle = PARTICIPLES.iter()
+                .filter(|&&(participle, _)| ends_with(word, participle))
+                .max_by_key(|&&(participle, _)| participle.chars().count());
+            if let Some(&(participle, present)) = participle {
+                replace_suffix(word, participle, present);
+            }
+        }
+        Action::RemoveT => {
+            if start >= r1 {
+                word.truncate(start);
+            } else {
+                replace_suffix(word, suffix, "ט");
+            }
+
+            if ends_with(&word[..start], "בראכ") {
+                let mut from = start - 4;
+                if ends_with(&word[..from], "גע") {
+                    from -= 2;
+                }
+                word.splice(from..start, "ברענג".chars());
+            }
+        }
+        Action::RemoveIst => {
+            if start > 0 && matches!(word[start - 1], 'ג' | 'ש') {
+                if r1 <= start + 2 {
+                    replace_suffix(word, suffix, "יס");
+                }
+            } else if start >= r1 {
+                word.truncate(start);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Remove the marked prefixes, working from the end of the word.
+fn remove_markers(word: &mut Vec<char>) {
+    let mut end = word.len();
+
+    while end > 0 {
+        match MARKERS.iter().find(|marker| ends_with(&word[..end], marker)) {
+            Some(marker) => {
+                let start = suffix_start(&word[..end], marker);
+                word.drain(start..end);
+                end = start;
+            }
+            None => end -= 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prelude() {
+        assert_eq!("שרײבנ".chars().collect::<Vec<char>>(), prelude("שרײַבן"));
+        assert_eq!("שרײבנ".chars().collect::<Vec<char>>(), prelude("שרייבן"));
+        assert_eq!("װאסער".chars().collect::<Vec<char>>(), prelude("וואַסער"));
+        assert_eq!("ייד".chars().collect::<Vec<char>>(), prelude("ייִד"));
+    }
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("קינדער", "קינד"),
+            ("שרײַבן", "שרײב"),
+            ("שרייבן", "שרײב"),
+            ("הײַזער", "הײז"),
+            ("ביכער", "ביכ"),
+            ("געשריבן", "שרײב"),
+            ("טאָג", "טאג"),
+            ("געבראַכט", "ברענג"),
+            ("געגאַנגען", "גײ"),
+            ("אַרײַנגעקומען", "ארײנקומ"),
+            ("אָנצוהייבן", "אנהײב"),
+            ("שיינקייט", "שײנ"),
+            ("פֿרײַנדלעך", "פרײנד"),
+            ("וווּ", "װו"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+
+    #[test]
+    fn test_points_only() {
+        assert_eq!("ַ", stem("ַ"));
+    }
+}
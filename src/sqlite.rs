@@ -0,0 +1,243 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! A SQLite extension registering `porter_rs`, an FTS5 tokenizer, so
+//! full-text indexes stem words exactly as the Rust code querying them does.
+//!
+//! Loaded from the crate's `cdylib`, the tokenizer splits text into words,
+//! lowercases them and stems them with Porter's algorithm, or with the
+//! algorithm or language given as its argument:
+//!
+//! ```sql
+//! .load ./libporter_stemmer
+//! CREATE VIRTUAL TABLE documents USING fts5(body, tokenize = 'porter_rs');
+//! CREATE VIRTUAL TABLE dokumente USING fts5(body, tokenize = 'porter_rs german');
+//! ```
+//!
+//! Requires the `sqlite` feature.
+
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr;
+use std::slice;
+use std::str;
+
+use libsqlite3_sys as ffi;
+use unicode_segmentation::UnicodeSegmentation;
+
+use algorithm::{Algorithm, Stemmer};
+use language::Language;
+
+/// The name the tokenizer is registered under.
+const NAME: &[u8] = b"porter_rs\0";
+
+/// The callback FTS5 is given each token through.
+type TokenCallback = unsafe extern "C" fn(*mut c_void, c_int, *const c_char, c_int, c_int, c_int) -> c_int;
+
+/// A tokenizer instance, as declared in `sqlite3.h`. Ours are `Tokenizer`s.
+enum Fts5Tokenizer {}
+
+/// The functions making up a tokenizer, as declared in `sqlite3.h`.
+#[repr(C)]
+struct Fts5TokenizerModule {
+    create: unsafe extern "C" fn(*mut c_void, *mut *const c_char, c_int, *mut *mut Fts5Tokenizer) -> c_int,
+    delete: unsafe extern "C" fn(*mut Fts5Tokenizer),
+    tokenize: unsafe extern "C" fn(*mut Fts5Tokenizer, *mut c_void, c_int, *const c_char, c_int, Option<TokenCallback>) -> c_int,
+}
+
+/// The start of the FTS5 API, as declared in `sqlite3.h`; only the first
+/// function is needed.
+#[repr(C)]
+struct Fts5Api {
+    version: c_int,
+    create_tokenizer: unsafe extern "C" fn(
+        *mut Fts5Api,
+        *const c_char,
+        *mut c_void,
+        *mut Fts5TokenizerModule,
+        Option<unsafe extern "C" fn(*mut c_void)>,
+    ) -> c_int,
+}
+
+/// A `porter_rs` tokenizer, with the stemmer named by its arguments.
+struct Tokenizer {
+    stemmer: Box<dyn Stemmer>,
+}
+
+impl Tokenizer {
+    /// A tokenizer for the arguments given to `porter_rs` in a table's
+    /// `tokenize` option: nothing, or the name of an algorithm or language.
+    fn new(arguments: &[&str]) -> Option<Tokenizer> {
+        let stemmer: Box<dyn Stemmer> = match *arguments {
+            [] => Box::new(Algorithm::Porter),
+            [name] => match (name.parse::<Algorithm>(), name.parse::<Language>()) {
+                (Ok(algorithm), _) => Box::new(algorithm),
+                (_, Ok(language)) => Box::new(language),
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        Some(Tokenizer { stemmer })
+    }
+
+    /// Pass each word of `text` to `token` as its lowercased stem, with its
+    /// start and end in `text`, stopping at the first result that isn't
+    /// `SQLITE_OK`.
+    fn tokenize<F>(&self, text: &str, mut token: F) -> c_int
+        where F: FnMut(&str, usize, usize) -> c_int
+    {
+        for (start, word) in text.split_word_bound_indices() {
+            if !word.chars().any(char::is_alphanumeric) {
+                continue;
+            }
+
+            let result = token(&self.stemmer.stem(&word.to_lowercase()), start, start + word.len());
+            if result != ffi::SQLITE_OK {
+                return result;
+            }
+        }
+
+        ffi::SQLITE_OK
+    }
+}
+
+unsafe extern "C" fn create(_: *mut c_void, arguments: *mut *const c_char, count: c_int, tokenizer: *mut *mut Fts5Tokenizer) -> c_int {
+    let arguments = if count > 0 { slice::from_raw_parts(arguments, count as usize) } else { &[] };
+    let arguments = arguments.iter()
+        .map(|&argument| CStr::from_ptr(argument).to_str().ok())
+        .collect::<Option<Vec<_>>>();
+
+    match arguments.as_ref().and_then(|arguments| Tokenizer::new(arguments)) {
+        Some(created) => {
+            *tokenizer = Box::into_raw(Box::new(created)) as *mut Fts5Tokenizer;
+            ffi::SQLITE_OK
+        },
+        None => ffi::SQLITE_ERROR,
+    }
+}
+
+unsafe extern "C" fn delete(tokenizer: *mut Fts5Tokenizer) {
+    drop(Box::from_raw(tokenizer as *mut Tokenizer));
+}
+
+unsafe extern "C" fn tokenize(
+    tokenizer: *mut Fts5Tokenizer,
+    context: *mut c_void,
+    _flags: c_int,
+    text: *const c_char,
+    length: c_int,
+    token: Option<TokenCallback>,
+) -> c_int {
+    let (tokenizer, token) = match (&*(tokenizer as *const Tokenizer), token) {
+        (tokenizer, Some(token)) => (tokenizer, token),
+        (_, None) => return ffi::SQLITE_ERROR,
+    };
+    let text = if length > 0 { slice::from_raw_parts(text as *const u8, length as usize) } else { &[] };
+    let text = match str::from_utf8(text) {
+        Ok(text) => text,
+        Err(_) => return ffi::SQLITE_ERROR,
+    };
+
+    tokenizer.tokenize(text, |stem, start, end| {
+        token(context, 0, stem.as_ptr() as *const c_char, stem.len() as c_int, start as c_int, end as c_int)
+    })
+}
+
+/// The FTS5 API of the database `db`, or null if it hasn't got FTS5.
+unsafe fn fts5_api(db: *mut ffi::sqlite3) -> *mut Fts5Api {
+    let mut statement = ptr::null_mut();
+    if ffi::sqlite3_prepare_v2(db, b"SELECT fts5(?1)\0".as_ptr() as *const c_char, -1, &mut statement, ptr::null_mut()) != ffi::SQLITE_OK {
+        return ptr::null_mut();
+    }
+
+    let mut api: *mut Fts5Api = ptr::null_mut();
+    ffi::sqlite3_bind_pointer(statement, 1, &mut api as *mut _ as *mut c_void, b"fts5_api_ptr\0".as_ptr() as *const c_char, None);
+    ffi::sqlite3_step(statement);
+    ffi::sqlite3_finalize(statement);
+
+    api
+}
+
+/// Set the extension's error message to `message`, allocated with
+/// `sqlite3_malloc` as SQLite expects.
+unsafe fn fail(error: *mut *mut c_char, message: &str) -> c_int {
+    if !error.is_null() {
+        let copy = ffi::sqlite3_malloc(message.len() as c_int + 1) as *mut u8;
+        if !copy.is_null() {
+            ptr::copy_nonoverlapping(message.as_ptr(), copy, message.len());
+            *copy.add(message.len()) = 0;
+            *error = copy as *mut c_char;
+        }
+    }
+
+    ffi::SQLITE_ERROR
+}
+
+/// The extension's entry point, which `.load ./libporter_stemmer` and
+/// `sqlite3_load_extension` find from the library's name.
+///
+/// # Safety
+///
+/// Only to be called by SQLite, when loading the extension.
+#[no_mangle]
+pub unsafe extern "C" fn sqlite3_porterstemmer_init(
+    db: *mut ffi::sqlite3,
+    error: *mut *mut c_char,
+    routines: *mut ffi::sqlite3_api_routines,
+) -> c_int {
+    if ffi::rusqlite_extension_init2(routines).is_err() {
+        return ffi::SQLITE_ERROR;
+    }
+
+    let api = fts5_api(db);
+    if api.is_null() || (*api).version < 2 {
+        return fail(error, "porter_rs: FTS5 isn't available");
+    }
+
+    // FTS5 copies the functions when the tokenizer is registered.
+    let mut module = Fts5TokenizerModule { create, delete, tokenize };
+    ((*api).create_tokenizer)(api, NAME.as_ptr() as *const c_char, ptr::null_mut(), &mut module, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(tokenizer: &Tokenizer, text: &str) -> Vec<(String, usize, usize)> {
+        let mut tokens = Vec::new();
+        tokenizer.tokenize(text, |stem, start, end| {
+            tokens.push((stem.to_string(), start, end));
+            ffi::SQLITE_OK
+        });
+        tokens
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let porter = Tokenizer::new(&[]).unwrap();
+        assert_eq!(
+            vec![("poni".to_string(), 0, 6), ("ran".to_string(), 7, 10), ("hopefulli".to_string(), 12, 21)],
+            tokens(&porter, "Ponies ran, hopefully."),
+        );
+
+        let german = Tokenizer::new(&["german"]).unwrap();
+        assert_eq!(vec![("haus".to_string(), 0, 7)], tokens(&german, "Häuser"));
+
+        // A token the callback rejects stops tokenizing.
+        let mut count = 0;
+        let result = porter.tokenize("one two three", |_, _, _| {
+            count += 1;
+            if count == 2 { ffi::SQLITE_NOMEM } else { ffi::SQLITE_OK }
+        });
+        assert_eq!((ffi::SQLITE_NOMEM, 2), (result, count));
+    }
+
+    #[test]
+    fn test_arguments() {
+        assert!(Tokenizer::new(&["lovins"]).is_some());
+        assert!(Tokenizer::new(&["klingon"]).is_none());
+        assert!(Tokenizer::new(&["porter", "german"]).is_none());
+    }
+}
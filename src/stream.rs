@@ -0,0 +1,60 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Stemming for asynchronous streams of words.
+//!
+//! Requires the `futures` feature.
+//!
+//! ```
+//! extern crate futures;
+//! extern crate porter_stemmer;
+//!
+//! use futures::executor::block_on;
+//! use futures::stream::{self, StreamExt};
+//! use porter_stemmer::stream::StemStreamExt;
+//!
+//! # fn main() {
+//! let words = stream::iter(vec!["connected", "connecting", "connections"]);
+//! let stems = block_on(words.stemmed().collect::<Vec<String>>());
+//!
+//! assert_eq!(vec!["connect", "connect", "connect"], stems);
+//! # }
+//! ```
+
+use futures::stream::{Map, Stream, StreamExt};
+
+use stem;
+
+fn stem_item<T: AsRef<str>>(word: T) -> String {
+    stem(word.as_ref())
+}
+
+/// An extension trait for `Stream`s of words.
+pub trait StemStreamExt: Stream + Sized where Self::Item: AsRef<str> {
+    /// Stem each word produced by this stream.
+    ///
+    /// Each item is stemmed as it is polled, so no batching is needed and
+    /// the executor thread is never held for longer than one word.
+    fn stemmed(self) -> Map<Self, fn(Self::Item) -> String> {
+        self.map(stem_item::<Self::Item> as fn(Self::Item) -> String)
+    }
+}
+
+impl<S> StemStreamExt for S where S: Stream, S::Item: AsRef<str> {}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use futures::stream::{self, StreamExt};
+
+    use super::*;
+
+    #[test]
+    fn test_stemmed() {
+        let words = stream::iter(vec!["caresses".to_string(), "ponies".to_string(), "a".to_string()]);
+        let stems = block_on(words.stemmed().collect::<Vec<String>>());
+
+        assert_eq!(vec!["caress", "poni", "a"], stems);
+    }
+}
@@ -0,0 +1,174 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! A tantivy token filter, so this crate's stemmers can stand in for
+//! tantivy's own in an analyzer.
+//!
+//! Requires the `tantivy` feature.
+//!
+//! ```ignore
+//! use porter_stemmer::tantivy::StemFilter;
+//! use tantivy::tokenizer::{LowerCaser, SimpleTokenizer, TextAnalyzer};
+//!
+//! let analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+//!     .filter(LowerCaser)
+//!     .filter(StemFilter::default())
+//!     .build();
+//! index.tokenizers().register("porter", analyzer);
+//! ```
+
+use tantivy_tokenizer_api::{Token, TokenFilter, TokenStream, Tokenizer};
+
+use algorithm::{Algorithm, Stemmer};
+
+/// Stems each token with a stemmer, Porter's algorithm by default.
+///
+/// Only each token's text is changed; its offsets and position stay those
+/// of the word it was stemmed from, so phrase queries and highlighting work
+/// as before.
+#[derive(Clone, Debug)]
+pub struct StemFilter<S> {
+    stemmer: S,
+}
+
+impl<S: Stemmer + Clone + Send + Sync + 'static> StemFilter<S> {
+    /// A filter stemming with `stemmer`.
+    pub fn new(stemmer: S) -> StemFilter<S> {
+        StemFilter { stemmer }
+    }
+}
+
+impl Default for StemFilter<Algorithm> {
+    fn default() -> StemFilter<Algorithm> {
+        StemFilter::new(Algorithm::Porter)
+    }
+}
+
+impl<S: Stemmer + Clone + Send + Sync + 'static> TokenFilter for StemFilter<S> {
+    type Tokenizer<T: Tokenizer> = StemTokenizer<S, T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> StemTokenizer<S, T> {
+        StemTokenizer { stemmer: self.stemmer, inner: tokenizer }
+    }
+}
+
+/// A tokenizer followed by a `StemFilter`.
+#[derive(Clone, Debug)]
+pub struct StemTokenizer<S, T> {
+    stemmer: S,
+    inner: T,
+}
+
+impl<S: Stemmer + Clone + Send + Sync + 'static, T: Tokenizer> Tokenizer for StemTokenizer<S, T> {
+    type TokenStream<'a> = StemTokenStream<'a, S, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> StemTokenStream<'a, S, T::TokenStream<'a>> {
+        StemTokenStream { stemmer: &self.stemmer, inner: self.inner.token_stream(text) }
+    }
+}
+
+/// The tokens of a `StemTokenizer`.
+pub struct StemTokenStream<'a, S: 'a, T> {
+    stemmer: &'a S,
+    inner: T,
+}
+
+impl<'a, S: Stemmer, T: TokenStream> TokenStream for StemTokenStream<'a, S, T> {
+    fn advance(&mut self) -> bool {
+        if !self.inner.advance() {
+            return false;
+        }
+
+        let token = self.inner.token_mut();
+        token.text = self.stemmer.stem(&token.text);
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.inner.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.inner.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use language::Language;
+
+    /// Splits text at spaces, as tantivy's tokenizers would at word
+    /// boundaries.
+    #[derive(Clone)]
+    struct Spaces;
+
+    struct SpacesStream<'a> {
+        words: Vec<(usize, &'a str)>,
+        token: Token,
+    }
+
+    impl Tokenizer for Spaces {
+        type TokenStream<'a> = SpacesStream<'a>;
+
+        fn token_stream<'a>(&'a mut self, text: &'a str) -> SpacesStream<'a> {
+            let mut start = 0;
+            let mut words = Vec::new();
+            for word in text.split(' ') {
+                words.push((start, word));
+                start += word.len() + 1;
+            }
+            words.reverse();
+
+            SpacesStream { words, token: Token::default() }
+        }
+    }
+
+    impl<'a> TokenStream for SpacesStream<'a> {
+        fn advance(&mut self) -> bool {
+            let (start, word) = match self.words.pop() {
+                Some(word) => word,
+                None => return false,
+            };
+
+            let position = self.token.position.wrapping_add(1);
+            self.token = Token { offset_from: start, offset_to: start + word.len(), position, text: word.to_string(), ..Token::default() };
+            true
+        }
+
+        fn token(&self) -> &Token {
+            &self.token
+        }
+
+        fn token_mut(&mut self) -> &mut Token {
+            &mut self.token
+        }
+    }
+
+    fn tokens<T: Tokenizer>(mut tokenizer: T, text: &str) -> Vec<(String, usize, usize, usize)> {
+        let mut tokens = Vec::new();
+        let mut stream = tokenizer.token_stream(text);
+        while stream.advance() {
+            let token = stream.token();
+            tokens.push((token.text.clone(), token.offset_from, token.offset_to, token.position));
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_stem_filter() {
+        assert_eq!(
+            vec![
+                ("poni".to_string(), 0, 6, 0),
+                ("ran".to_string(), 7, 10, 1),
+                ("hopefulli".to_string(), 11, 20, 2),
+            ],
+            tokens(StemFilter::default().transform(Spaces), "ponies ran hopefully"),
+        );
+
+        let german = StemFilter::new(Language::German).transform(Spaces);
+        assert_eq!(vec![("Haus".to_string(), 0, 7, 0), ("Katz".to_string(), 8, 14, 1)], tokens(german, "Häuser Katzen"));
+    }
+}
@@ -0,0 +1,226 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Term frequencies and TF-IDF weights, for ranking and for turning stemmed
+//! documents into feature vectors.
+//!
+//! Each document's terms are counted into `TermFrequencies`, and added to a
+//! `Corpus`, which counts the documents each term appears in. The corpus
+//! then weighs each term of a document by how rare it is:
+//!
+//! ```
+//! use porter_stemmer::analysis::Analyzer;
+//! use porter_stemmer::tfidf::{Corpus, TermFrequencies};
+//!
+//! let analyzer = Analyzer::english();
+//! let documents = ["The ponies ran", "A pony runs", "The stables"]
+//!     .iter()
+//!     .map(|document| analyzer.analyze(document).collect::<TermFrequencies>())
+//!     .collect::<Vec<_>>();
+//!
+//! let corpus = documents.iter().collect::<Corpus>();
+//! let weights = corpus.tf_idf(&documents[0]);
+//!
+//! assert_eq!(2, corpus.document_frequency("poni"));
+//! assert!(weights["ran"] > weights["poni"]);
+//! ```
+//!
+//! With the `serde` feature, each of these can be serialized.
+
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How many times each term appears in a document.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TermFrequencies {
+    counts: BTreeMap<String, usize>,
+    total: usize,
+}
+
+impl TermFrequencies {
+    /// No terms at all.
+    pub fn new() -> TermFrequencies {
+        TermFrequencies::default()
+    }
+
+    /// Count another appearance of `term`.
+    pub fn add(&mut self, term: &str) {
+        if let Some(count) = self.counts.get_mut(term) {
+            *count += 1;
+        } else {
+            self.counts.insert(term.to_string(), 1);
+        }
+        self.total += 1;
+    }
+
+    /// The number of times `term` appears.
+    pub fn count(&self, term: &str) -> usize {
+        self.counts.get(term).cloned().unwrap_or(0)
+    }
+
+    /// The share of all the terms that are `term`, from zero to one.
+    pub fn frequency(&self, term: &str) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+
+        self.count(term) as f64 / self.total as f64
+    }
+
+    /// The number of terms counted, with repeats.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// The number of different terms.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Whether no terms were counted.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Each different term and its count, in alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.counts.iter().map(|(term, &count)| (&term[..], count))
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for TermFrequencies {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, terms: I) {
+        for term in terms {
+            self.add(term.as_ref());
+        }
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for TermFrequencies {
+    fn from_iter<I: IntoIterator<Item = S>>(terms: I) -> TermFrequencies {
+        let mut frequencies = TermFrequencies::new();
+        frequencies.extend(terms);
+        frequencies
+    }
+}
+
+/// How many of a corpus' documents each term appears in.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Corpus {
+    documents: usize,
+    document_frequencies: BTreeMap<String, usize>,
+}
+
+impl Corpus {
+    /// A corpus of no documents.
+    pub fn new() -> Corpus {
+        Corpus::default()
+    }
+
+    /// Add a document, given its terms.
+    pub fn add(&mut self, document: &TermFrequencies) {
+        self.documents += 1;
+        for (term, _) in document.iter() {
+            *self.document_frequencies.entry(term.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    /// The number of documents added.
+    pub fn documents(&self) -> usize {
+        self.documents
+    }
+
+    /// The number of documents `term` appears in.
+    pub fn document_frequency(&self, term: &str) -> usize {
+        self.document_frequencies.get(term).cloned().unwrap_or(0)
+    }
+
+    /// The inverse document frequency of `term`: `ln((1 + n) / (1 + df)) + 1`
+    /// for `n` documents, `df` of which have the term in them.
+    ///
+    /// Smoothing as if one more document had every term keeps the weight of
+    /// terms no document has finite, and that of terms every document has
+    /// above zero.
+    pub fn idf(&self, term: &str) -> f64 {
+        let documents = self.documents as f64;
+        let frequency = self.document_frequency(term) as f64;
+
+        ((1.0 + documents) / (1.0 + frequency)).ln() + 1.0
+    }
+
+    /// The inverse document frequency of every term in the corpus.
+    pub fn idf_weights(&self) -> BTreeMap<String, f64> {
+        self.document_frequencies.keys().map(|term| (term.clone(), self.idf(term))).collect()
+    }
+
+    /// The weight of each term in `document`: its frequency there times its
+    /// inverse document frequency.
+    pub fn tf_idf(&self, document: &TermFrequencies) -> BTreeMap<String, f64> {
+        document.iter()
+            .map(|(term, _)| (term.to_string(), document.frequency(term) * self.idf(term)))
+            .collect()
+    }
+}
+
+impl<'a> Extend<&'a TermFrequencies> for Corpus {
+    fn extend<I: IntoIterator<Item = &'a TermFrequencies>>(&mut self, documents: I) {
+        for document in documents {
+            self.add(document);
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a TermFrequencies> for Corpus {
+    fn from_iter<I: IntoIterator<Item = &'a TermFrequencies>>(documents: I) -> Corpus {
+        let mut corpus = Corpus::new();
+        corpus.extend(documents);
+        corpus
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_term_frequencies() {
+        let frequencies = "poni ran poni".split(' ').collect::<TermFrequencies>();
+
+        assert_eq!(2, frequencies.count("poni"));
+        assert_eq!(0, frequencies.count("stabl"));
+        assert_eq!(3, frequencies.total());
+        assert_eq!(2, frequencies.len());
+        assert_eq!(2.0 / 3.0, frequencies.frequency("poni"));
+        assert_eq!(vec![("poni", 2), ("ran", 1)], frequencies.iter().collect::<Vec<_>>());
+
+        let empty = TermFrequencies::new();
+        assert!(empty.is_empty());
+        assert_eq!(0.0, empty.frequency("poni"));
+    }
+
+    #[test]
+    fn test_corpus() {
+        let documents = [
+            "poni ran".split(' ').collect::<TermFrequencies>(),
+            "poni poni stabl".split(' ').collect::<TermFrequencies>(),
+        ];
+        let corpus = documents.iter().collect::<Corpus>();
+
+        assert_eq!(2, corpus.documents());
+        assert_eq!(2, corpus.document_frequency("poni"));
+        assert_eq!(1.0, corpus.idf("poni"));
+        assert_eq!((3.0f64 / 2.0).ln() + 1.0, corpus.idf("ran"));
+        assert_eq!(3.0f64.ln() + 1.0, corpus.idf("unseen"));
+        assert_eq!(vec!["poni", "ran", "stabl"], corpus.idf_weights().keys().collect::<Vec<_>>());
+
+        let weights = corpus.tf_idf(&documents[1]);
+        assert_eq!(2.0 / 3.0, weights["poni"]);
+        assert_eq!(1.0 / 3.0 * ((3.0f64 / 2.0).ln() + 1.0), weights["stabl"]);
+    }
+}
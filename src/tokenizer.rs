@@ -0,0 +1,85 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Splitting text into words, keeping track of where each was found.
+//!
+//! Words are found at Unicode word boundaries, as `unicode_words` finds
+//! them, and each comes with its byte range in the text and its position
+//! among the words, ready to be stemmed and indexed:
+//!
+//! ```
+//! use porter_stemmer::stem;
+//! use porter_stemmer::tokenizer::tokenize;
+//!
+//! let text = "Ponies ran, hopefully.";
+//! let tokens = tokenize(text).collect::<Vec<_>>();
+//!
+//! assert_eq!(3, tokens.len());
+//! assert_eq!("ran", &text[tokens[1].byte_range.clone()]);
+//! assert_eq!(1, tokens[1].position);
+//! assert_eq!("hopefulli", stem(tokens[2].text));
+//! ```
+
+use std::ops::Range;
+
+use unicode_segmentation::{UWordBoundIndices, UnicodeSegmentation};
+
+/// A word in a text.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Token<'a> {
+    /// The word itself.
+    pub text: &'a str,
+    /// Where the word is in the text, so `&text[byte_range] == word`.
+    pub byte_range: Range<usize>,
+    /// The number of words before it in the text.
+    pub position: usize,
+}
+
+/// The words of a text, from `tokenize`.
+pub struct Tokens<'a> {
+    segments: UWordBoundIndices<'a>,
+    position: usize,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        // Whitespace and punctuation lie between the words.
+        let (start, text) = self.segments.by_ref().find(|&(_, segment)| segment.chars().any(char::is_alphanumeric))?;
+
+        let token = Token { text, byte_range: start..start + text.len(), position: self.position };
+        self.position += 1;
+        Some(token)
+    }
+}
+
+/// Split `text` into words.
+pub fn tokenize(text: &str) -> Tokens<'_> {
+    Tokens { segments: text.split_word_bound_indices(), position: 0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        let text = "\"It's 3.5 ponies,\" she said — naïvely.";
+        let tokens = tokenize(text).collect::<Vec<_>>();
+
+        assert_eq!(vec!["It's", "3.5", "ponies", "she", "said", "naïvely"], tokens.iter().map(|token| token.text).collect::<Vec<_>>());
+        for (position, token) in tokens.iter().enumerate() {
+            assert_eq!(position, token.position);
+            assert_eq!(token.text, &text[token.byte_range.clone()]);
+        }
+        assert_eq!(Token { text: "naïvely", byte_range: 32..40, position: 5 }, tokens[5]);
+    }
+
+    #[test]
+    fn test_tokenize_nothing() {
+        assert_eq!(None, tokenize("").next());
+        assert_eq!(None, tokenize(" -- ... ").next());
+    }
+}
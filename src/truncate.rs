@@ -0,0 +1,69 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! A truncation stemmer.
+//!
+//! Keeping only the first few letters of each word is the usual baseline
+//! stemmer in information retrieval experiments, and with five letters is
+//! often as effective as the real thing. Words no longer than the length are
+//! left alone.
+//!
+//! ```
+//! use porter_stemmer::truncate::{self, Truncate};
+//!
+//! assert_eq!("gener", truncate::stem("generalizations"));
+//! assert_eq!("gen", Truncate::new(3).stem("generalizations"));
+//! ```
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A truncation stemmer keeping words to at most `length` graphemes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Truncate {
+    length: usize,
+}
+
+impl Default for Truncate {
+    /// Truncation to five letters.
+    fn default() -> Truncate {
+        Truncate::new(5)
+    }
+}
+
+impl Truncate {
+    /// The stemmer keeping the first `length` graphemes of each word.
+    pub fn new(length: usize) -> Truncate {
+        Truncate { length }
+    }
+
+    /// The number of graphemes kept.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Given a word, return its first `length` graphemes.
+    pub fn stem(&self, word: &str) -> String {
+        word.graphemes(true).take(self.length).collect()
+    }
+}
+
+/// Given a word, return its first five graphemes.
+pub fn stem(word: &str) -> String {
+    Truncate::default().stem(word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        assert_eq!("hopef", stem("hopefulness"));
+        assert_eq!("hope", stem("hope"));
+        assert_eq!("", stem(""));
+        assert_eq!("naïve", stem("naïvety"));
+        assert_eq!("", Truncate::new(0).stem("word"));
+        assert_eq!(7, Truncate::new(7).length());
+    }
+}
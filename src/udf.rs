@@ -0,0 +1,143 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! `porter_stem`, a DataFusion scalar function, so SQL queries can stem a
+//! column of text: `SELECT porter_stem(word) FROM words`.
+//!
+//! Requires the `datafusion` feature.
+//!
+//! ```
+//! extern crate datafusion;
+//! extern crate porter_stemmer;
+//!
+//! use datafusion::prelude::SessionContext;
+//! use porter_stemmer::udf::porter_stem_udf;
+//!
+//! # fn main() {
+//! let context = SessionContext::new();
+//! context.register_udf(porter_stem_udf());
+//! # }
+//! ```
+
+use std::any::Any;
+use std::sync::Arc;
+
+use datafusion::arrow::array::ArrayRef;
+use datafusion::arrow::datatypes::DataType;
+use datafusion::common::cast::{as_large_string_array, as_string_array};
+use datafusion::common::{exec_err, Result, ScalarValue};
+use datafusion::logical_expr::{ColumnarValue, ScalarUDF, ScalarUDFImpl, Signature, Volatility};
+
+use arrow::stem_array;
+use stem;
+
+/// The `porter_stem` function, which stems each string in a column with
+/// Porter's algorithm, as `stem` does, keeping nulls as they are.
+///
+/// A whole array of strings is stemmed at once, with `stem_array`.
+#[derive(Debug)]
+pub struct PorterStem {
+    signature: Signature,
+}
+
+impl PorterStem {
+    pub fn new() -> PorterStem {
+        PorterStem {
+            signature: Signature::uniform(1, vec![DataType::Utf8, DataType::LargeUtf8], Volatility::Immutable),
+        }
+    }
+}
+
+impl Default for PorterStem {
+    fn default() -> PorterStem {
+        PorterStem::new()
+    }
+}
+
+impl ScalarUDFImpl for PorterStem {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "porter_stem"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        Ok(arg_types[0].clone())
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        match args {
+            [ColumnarValue::Array(array)] => {
+                let stems: ArrayRef = match *array.data_type() {
+                    DataType::Utf8 => Arc::new(stem_array(as_string_array(array.as_ref())?)),
+                    DataType::LargeUtf8 => Arc::new(stem_array(as_large_string_array(array.as_ref())?)),
+                    ref other => return exec_err!("porter_stem expects strings, not {}", other),
+                };
+                Ok(ColumnarValue::Array(stems))
+            },
+            [ColumnarValue::Scalar(ScalarValue::Utf8(word))] => {
+                Ok(ColumnarValue::Scalar(ScalarValue::Utf8(word.as_ref().map(|word| stem(word)))))
+            },
+            [ColumnarValue::Scalar(ScalarValue::LargeUtf8(word))] => {
+                Ok(ColumnarValue::Scalar(ScalarValue::LargeUtf8(word.as_ref().map(|word| stem(word)))))
+            },
+            _ => exec_err!("porter_stem expects a single string argument"),
+        }
+    }
+}
+
+/// `PorterStem`, ready to register with a `SessionContext`.
+pub fn porter_stem_udf() -> ScalarUDF {
+    ScalarUDF::new_from_impl(PorterStem::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use datafusion::arrow::array::{LargeStringArray, StringArray};
+
+    #[test]
+    fn test_porter_stem_arrays() {
+        let words: ArrayRef = Arc::new(StringArray::from(vec![Some("connected"), None, Some("ponies")]));
+        let stems = match PorterStem::new().invoke(&[ColumnarValue::Array(words)]).unwrap() {
+            ColumnarValue::Array(stems) => stems,
+            ColumnarValue::Scalar(_) => panic!("expected an array"),
+        };
+        assert_eq!(&StringArray::from(vec![Some("connect"), None, Some("poni")]), as_string_array(stems.as_ref()).unwrap());
+
+        let words: ArrayRef = Arc::new(LargeStringArray::from(vec!["hopefulness"]));
+        let stems = match PorterStem::new().invoke(&[ColumnarValue::Array(words)]).unwrap() {
+            ColumnarValue::Array(stems) => stems,
+            ColumnarValue::Scalar(_) => panic!("expected an array"),
+        };
+        assert_eq!(&LargeStringArray::from(vec!["hope"]), as_large_string_array(stems.as_ref()).unwrap());
+    }
+
+    #[test]
+    fn test_porter_stem_scalars() {
+        let stem = |word: ScalarValue| match PorterStem::new().invoke(&[ColumnarValue::Scalar(word)]).unwrap() {
+            ColumnarValue::Scalar(stem) => stem,
+            ColumnarValue::Array(_) => panic!("expected a scalar"),
+        };
+
+        assert_eq!(ScalarValue::Utf8(Some("poni".to_string())), stem(ScalarValue::Utf8(Some("ponies".to_string()))));
+        assert_eq!(ScalarValue::LargeUtf8(None), stem(ScalarValue::LargeUtf8(None)));
+        assert!(PorterStem::new().invoke(&[ColumnarValue::Scalar(ScalarValue::Int64(Some(1)))]).is_err());
+    }
+
+    #[test]
+    fn test_porter_stem_udf() {
+        let udf = porter_stem_udf();
+
+        assert_eq!("porter_stem", udf.name());
+        assert_eq!(Ok(DataType::LargeUtf8), udf.inner().return_type(&[DataType::LargeUtf8]).map_err(|_| ()));
+    }
+}
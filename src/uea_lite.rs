@@ -0,0 +1,153 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The UEA-Lite stemmer.
+//!
+//! Jenkins and Smith's conservative stemmer removes little more than plural,
+//! past tense and `ing` endings, repairing the stem so it is usually a word.
+//! Derivational endings are left alone, so distinct technical terms such as
+//! `generation` and `generator` are never merged.
+//!
+//! Rules are tried in order and the first whose ending matches is the only
+//! one applied. Words containing digits are left alone, as are stems that
+//! would be shorter than three letters or contain no vowel.
+//!
+//! ```
+//! use porter_stemmer::uea_lite;
+//!
+//! assert_eq!("relate", uea_lite::stem("related"));
+//! assert_eq!("generation", uea_lite::stem("generations"));
+//! ```
+
+use primitives::ends_with;
+
+/// Rules as `(ending, letters to remove, replacement)`. Rules removing nothing
+/// protect endings that look inflected but aren't.
+const RULES: &[(&str, usize, &str)] = &[
+    ("ss", 0, ""),
+    ("us", 0, ""),
+    ("is", 0, ""),
+    ("eed", 0, ""),
+    ("eeds", 1, ""),
+
+    ("ied", 3, "y"),
+    ("ies", 3, "y"),
+    ("ying", 3, ""),
+    ("ued", 1, ""),
+    ("ues", 1, ""),
+    ("uing", 3, "e"),
+    ("ees", 1, ""),
+    ("oes", 2, ""),
+
+    ("sses", 2, ""),
+    ("zzes", 2, ""),
+    ("ches", 2, ""),
+    ("shes", 2, ""),
+    ("xes", 2, ""),
+    ("es", 1, ""),
+    ("s", 1, ""),
+
+    ("bbed", 3, ""),
+    ("dded", 3, ""),
+    ("gged", 3, ""),
+    ("mmed", 3, ""),
+    ("nned", 3, ""),
+    ("pped", 3, ""),
+    ("rred", 3, ""),
+    ("tted", 3, ""),
+    ("ated", 1, ""),
+    ("ized", 1, ""),
+    ("ised", 1, ""),
+    ("ured", 1, ""),
+    ("bled", 1, ""),
+    ("dled", 1, ""),
+    ("gled", 1, ""),
+    ("kled", 1, ""),
+    ("pled", 1, ""),
+    ("tled", 1, ""),
+    ("ced", 1, ""),
+    ("ged", 1, ""),
+    ("ved", 1, ""),
+    ("ed", 2, ""),
+
+    ("bbing", 4, ""),
+    ("dding", 4, ""),
+    ("gging", 4, ""),
+    ("mming", 4, ""),
+    ("nning", 4, ""),
+    ("pping", 4, ""),
+    ("rring", 4, ""),
+    ("tting", 4, ""),
+    ("nging", 3, ""),
+    ("ating", 3, "e"),
+    ("izing", 3, "e"),
+    ("ising", 3, "e"),
+    ("uring", 3, "e"),
+    ("bling", 3, "e"),
+    ("dling", 3, "e"),
+    ("gling", 3, "e"),
+    ("kling", 3, "e"),
+    ("pling", 3, "e"),
+    ("tling", 3, "e"),
+    ("cing", 3, "e"),
+    ("ging", 3, "e"),
+    ("ving", 3, "e"),
+    ("ing", 3, ""),
+];
+
+/// Given a lowercase word, return its stemmed form using UEA-Lite.
+pub fn stem(word: &str) -> String {
+    if word.chars().any(|c| c.is_ascii_digit()) {
+        return word.to_string();
+    }
+
+    let letters = word.chars().collect::<Vec<char>>();
+
+    let rule = RULES.iter().find(|&&(ending, _, _)| ends_with(&letters, ending));
+
+    if let Some(&(_, remove, replacement)) = rule {
+        let stem = &letters[..letters.len() - remove];
+
+        if remove > 0 && stem.len() >= 3 && stem.iter().any(|&c| "aeiouy".contains(c)) {
+            return stem.iter().cloned().chain(replacement.chars()).collect();
+        }
+    }
+
+    word.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        let pairs = [
+            ("related", "relate"),
+            ("relating", "relate"),
+            ("relations", "relation"),
+            ("running", "run"),
+            ("bringing", "bring"),
+            ("studies", "study"),
+            ("studying", "study"),
+            ("continuing", "continue"),
+            ("boxes", "box"),
+            ("cases", "case"),
+            ("tabled", "table"),
+            ("proceeds", "proceed"),
+            ("genetics", "genetic"),
+        ];
+
+        for &(word, expected) in pairs.iter() {
+            assert_eq!(expected, stem(word), "stemming {}", word);
+        }
+    }
+
+    #[test]
+    fn test_stem_unchanged() {
+        for &word in ["bed", "thing", "need", "class", "thesis", "virus", "proceed", "md5s", "generation"].iter() {
+            assert_eq!(word, stem(word));
+        }
+    }
+}
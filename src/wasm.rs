@@ -0,0 +1,67 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Stemming from JavaScript, through `wasm-bindgen`.
+//!
+//! Built with `wasm-pack`, these functions make up an npm package, so a
+//! browser stems words exactly as a Rust backend indexing them would:
+//!
+//! ```js
+//! import { stem, stemText, stemBatch } from "porter-stemmer";
+//!
+//! stem("hopefulness");               // "hope"
+//! stemText("The ponies ran.");       // "The poni ran."
+//! stemBatch(["connected", "ponies"]); // ["connect", "poni"]
+//! ```
+//!
+//! Requires the `wasm` feature.
+
+use unicode_segmentation::UnicodeSegmentation;
+use wasm_bindgen::prelude::*;
+
+use porter::Porter;
+
+/// Given a word, return its stemmed form, as the crate's `stem` does.
+#[wasm_bindgen]
+pub fn stem(word: &str) -> String {
+    Porter::new().stem(word)
+}
+
+/// Stem each word of `text`, keeping the spaces and punctuation between
+/// them as they are.
+#[wasm_bindgen(js_name = stemText)]
+pub fn stem_text(text: &str) -> String {
+    let porter = Porter::new();
+    let mut stems = String::with_capacity(text.len());
+
+    for segment in text.split_word_bounds() {
+        if segment.chars().any(char::is_alphanumeric) {
+            stems.push_str(&porter.stem(segment));
+        } else {
+            stems.push_str(segment);
+        }
+    }
+
+    stems
+}
+
+/// Stem each of `words`, crossing between JavaScript and WebAssembly once
+/// rather than once a word.
+#[wasm_bindgen(js_name = stemBatch)]
+pub fn stem_batch(words: Vec<String>) -> Vec<String> {
+    let porter = Porter::new();
+    words.iter().map(|word| porter.stem(word)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        assert_eq!(::stem("Hopefulness"), stem("Hopefulness"));
+        assert_eq!("The poni ran, hopefulli.\n", stem_text("The ponies ran, hopefully.\n"));
+        assert_eq!(vec!["connect", "poni", ""], stem_batch(vec!["connected".into(), "ponies".into(), "".into()]));
+    }
+}
@@ -0,0 +1,70 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Runs the `porter-stemmer` binary on files in a scratch directory.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// An empty directory for the test called `name`.
+fn scratch(name: &str) -> PathBuf {
+    let directory = env::temp_dir().join(format!("porter-stemmer-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+#[test]
+fn test_output_dir() {
+    let directory = scratch("output-dir");
+    fs::write(directory.join("a.txt"), "ponies ran\n").unwrap();
+
+    let status = Command::new(env!("CARGO_BIN_EXE_porter-stemmer"))
+        .current_dir(&directory)
+        .args(["-o", "out", "a.txt"])
+        .status()
+        .unwrap();
+
+    assert!(status.success());
+    assert_eq!("poni ran\n", fs::read_to_string(directory.join("out/a.txt")).unwrap());
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_output_dir_of_input() {
+    let directory = scratch("output-dir-of-input");
+    fs::write(directory.join("a.txt"), "ponies ran\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_porter-stemmer"))
+        .current_dir(&directory)
+        .args(["-o", ".", "a.txt"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("would overwrite the input"));
+    assert_eq!("ponies ran\n", fs::read_to_string(directory.join("a.txt")).unwrap());
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_output_dir_collision() {
+    let directory = scratch("output-dir-collision");
+    fs::create_dir(directory.join("sub")).unwrap();
+    fs::write(directory.join("a.txt"), "ponies ran\n").unwrap();
+    fs::write(directory.join("sub/a.txt"), "cats sat\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_porter-stemmer"))
+        .current_dir(directory.join("sub"))
+        .args(["-o", "out", "a.txt", "../a.txt"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("would both be written to"));
+    assert!(!directory.join("sub/out").exists());
+    fs::remove_dir_all(&directory).unwrap();
+}
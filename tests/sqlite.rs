@@ -0,0 +1,96 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Loads the crate's `cdylib` into the `sqlite3` shell and queries a table
+//! using the `porter_rs` tokenizer. The tests are skipped, with a message,
+//! if there is no `sqlite3` on the path.
+
+#![cfg(feature = "sqlite")]
+
+use std::env;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+/// The library built for this test, without its extension, which `.load`
+/// adds. Cargo builds it into `deps`, next to the test itself, with the
+/// features the test was built with.
+fn library() -> PathBuf {
+    let mut directory = env::current_exe().unwrap();
+    directory.pop();
+
+    let library = directory.join(format!("{}porter_stemmer", env::consts::DLL_PREFIX));
+    assert!(
+        library.with_extension(env::consts::DLL_EXTENSION).exists(),
+        "{} hasn't been built", library.display(),
+    );
+    library
+}
+
+/// Run `statements` in an in-memory database with the extension loaded,
+/// returning the shell's output, or `None` if there's no shell to run.
+fn sqlite(statements: &[&str]) -> Option<Output> {
+    let output = Command::new("sqlite3")
+        .arg(":memory:")
+        .arg(format!(".load {}", library().display()))
+        .args(statements)
+        .output();
+
+    match output {
+        Ok(output) => Some(output),
+        Err(ref error) if error.kind() == ErrorKind::NotFound => {
+            eprintln!("skipped: there's no sqlite3 shell on the path");
+            None
+        },
+        Err(error) => panic!("running sqlite3: {}", error),
+    }
+}
+
+/// Run `statements` as `sqlite` does, expecting them to succeed, and return
+/// what the shell printed.
+fn query(statements: &[&str]) -> Option<String> {
+    let output = sqlite(statements)?;
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    Some(String::from_utf8(output.stdout).unwrap())
+}
+
+#[test]
+fn test_match() {
+    let matched = query(&[
+        "CREATE VIRTUAL TABLE documents USING fts5(body, tokenize = 'porter_rs');",
+        "INSERT INTO documents VALUES ('Ponies ran, hopefully.'), ('Connections were made.');",
+        "SELECT body FROM documents WHERE documents MATCH 'connecting';",
+    ]);
+    if let Some(matched) = matched {
+        assert_eq!("Connections were made.\n", matched);
+    }
+}
+
+#[test]
+fn test_match_language() {
+    let matched = query(&[
+        "CREATE VIRTUAL TABLE dokumente USING fts5(body, tokenize = 'porter_rs german');",
+        "INSERT INTO dokumente VALUES ('Die Häuser sind alt.'), ('Der Hund bellt.');",
+        "SELECT body FROM dokumente WHERE dokumente MATCH 'haus';",
+    ]);
+    if let Some(matched) = matched {
+        assert_eq!("Die Häuser sind alt.\n", matched);
+    }
+}
+
+#[test]
+fn test_unknown_stemmer() {
+    // The first table shows the extension loaded, so the error is the
+    // tokenizer's.
+    let output = sqlite(&[
+        "CREATE VIRTUAL TABLE documents USING fts5(body, tokenize = 'porter_rs');",
+        "CREATE VIRTUAL TABLE dokumente USING fts5(body, tokenize = 'porter_rs klingon');",
+    ]);
+
+    if let Some(output) = output {
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("error in tokenizer constructor"));
+    }
+}